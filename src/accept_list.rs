@@ -0,0 +1,82 @@
+//! Abbreviations and ordinals that are correct in every text of a
+//! language, but are split up or rejected by most dictionaries.
+
+use regex::Regex;
+
+use crate::Language;
+
+/// Returns the common abbreviations of `language`, i.e. `etc.` and `e.g.`
+/// for English. It is empty for languages without a list.
+///
+/// ```
+/// use hunspell_rs::accept_list;
+///
+/// let german = "de_DE".parse().unwrap();
+/// assert!(accept_list(&german).contains(&"z.B."));
+/// ```
+pub fn accept_list(language: &Language) -> &'static [&'static str] {
+    match language.primary() {
+        "en" => &[
+            "etc.", "e.g.", "i.e.", "vs.", "approx.", "cf.", "Mr.", "Mrs.", "Ms.", "Dr.", "Prof.",
+            "No.", "St.",
+        ],
+        "de" => &[
+            "z.B.", "d.h.", "u.a.", "usw.", "bzw.", "ca.", "vgl.", "Nr.", "Dr.", "Hr.", "Fr.",
+            "evtl.", "ggf.", "inkl.",
+        ],
+        "fr" => &[
+            "etc.", "p.ex.", "c.-à-d.", "M.", "Mme", "Mlle", "env.", "cf.", "n°",
+        ],
+        "es" => &[
+            "p.ej.", "etc.", "Sr.", "Sra.", "Dr.", "Dra.", "pág.", "aprox.", "Ud.", "Uds.",
+        ],
+        "it" => &["ecc.", "es.", "pag.", "Sig.", "Sig.ra", "Dott.", "ca."],
+        "nl" => &[
+            "bijv.", "d.w.z.", "enz.", "o.a.", "ca.", "blz.", "dhr.", "mevr.",
+        ],
+        "pt" => &["p.ex.", "etc.", "Sr.", "Sra.", "Dr.", "pág.", "aprox."],
+        _ => &[],
+    }
+}
+
+/// Returns the pattern of the ordinal numbers of `language`, i.e. `1st`,
+/// or `XIXe` for centuries in French.
+fn ordinals(language: &Language) -> Option<&'static str> {
+    match language.primary() {
+        "en" => Some(r"\b\d+(?:st|nd|rd|th)\b"),
+        "fr" => Some(r"\b(?:\d+|[IVXLC]+)(?:er|re|e|ème|nd|nde)\b"),
+        "es" | "it" | "pt" => Some(r"\b\d+\.?[ºª]"),
+        "nl" => Some(r"\b\d+(?:e|ste|de)\b"),
+        _ => None,
+    }
+}
+
+/// Returns a regex matching the ordinals of `language`, or `None` when it
+/// has none.
+pub(crate) fn ordinal_regex(language: &Language) -> Option<Regex> {
+    ordinals(language).map(|pattern| Regex::new(pattern).expect("valid regex"))
+}
+
+/// Returns a regex matching the abbreviations and ordinals of `language`,
+/// or `None` when there are none.
+pub(crate) fn accept_regex(language: &Language) -> Option<Regex> {
+    let mut words = accept_list(language).to_vec();
+    // the longest first, so "Sra." is not matched as "Sr"
+    words.sort_by_key(|w| std::cmp::Reverse(w.len()));
+    let mut alternatives: Vec<String> = words
+        .into_iter()
+        .map(|w| {
+            let escaped = regex::escape(w);
+            if w.ends_with(char::is_alphanumeric) {
+                format!(r"\b{escaped}\b")
+            } else {
+                format!(r"\b{escaped}")
+            }
+        })
+        .collect();
+    alternatives.extend(ordinals(language).map(str::to_string));
+    if alternatives.is_empty() {
+        return None;
+    }
+    Some(Regex::new(&alternatives.join("|")).expect("valid regex"))
+}
@@ -0,0 +1,1110 @@
+//! Reading, changing and writing hunspell affix (`.aff`) files.
+//!
+//! An [`AffixFile`] is the list of its [`Directive`]s in the order of the
+//! file. Directives this module knows are parsed into types, like the
+//! [`AffixGroup`]s of `PFX` and `SFX` and the pairs of `REP`; others are
+//! kept as [`Directive::Setting`] or [`Directive::Table`], and comments
+//! and empty lines as [`Directive::Other`], so a file is written back
+//! with nothing lost. Runs of white space within a line are written as a
+//! single space, and the counts of tables and affix groups are updated.
+//!
+//! ```
+//! use hunspell_rs::aff::{AffixFile, AffixRule};
+//!
+//! let mut aff = AffixFile::read("tests/fixtures/reduced.aff").unwrap();
+//! assert_eq!(Some("UTF-8"), aff.encoding());
+//! let plural = aff.suffix_mut("S").unwrap();
+//! assert_eq!("s", plural.rules[0].add);
+//! plural.rules.push(AffixRule {
+//!     strip: "y".to_string(),
+//!     add: "ies".to_string(),
+//!     condition: "[^aeiou]y".to_string(),
+//!     ..Default::default()
+//! });
+//! assert!(aff.to_string().contains("SFX S Y 2\n"));
+//! ```
+
+use std::{borrow::Cow, path::Path};
+
+use crate::{
+    dic::DicEntry, distance::edit_distance_by, encoding::Codec, Error, Operation, Result,
+    DEFAULT_ENCODING,
+};
+
+/// The contents of an affix file.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct AffixFile {
+    pub directives: Vec<Directive>,
+}
+
+/// A line of an affix file, or a table or affix group with its lines.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Directive {
+    /// `SET`, the encoding of the affix and dictionary files.
+    Encoding(String),
+    /// `FLAG`, how flags are written.
+    FlagType(FlagType),
+    /// `TRY`, the characters tried for suggestions, most frequent first.
+    Try(String),
+    /// A flag with a meaning, i.e. `COMPOUNDFLAG X`.
+    Flag(FlagDirective, String),
+    /// `REP`, replacements tried for suggestions. `_` stands for a space.
+    Replacements(Vec<(String, String)>),
+    /// `ICONV`, conversions of the input before checking.
+    InputConversions(Vec<(String, String)>),
+    /// `OCONV`, conversions of the output of suggestions and analyses.
+    OutputConversions(Vec<(String, String)>),
+    /// `PFX` rules with the same flag.
+    Prefix(AffixGroup),
+    /// `SFX` rules with the same flag.
+    Suffix(AffixGroup),
+    /// Another table with a count, like `MAP`, `BREAK` or `AF`, with the
+    /// fields of each row after the name.
+    Table {
+        name: String,
+        rows: Vec<Vec<String>>,
+    },
+    /// Another directive, like `LANG en_US` or `COMPLEXPREFIXES`.
+    Setting { name: String, value: Option<String> },
+    /// A comment, an empty line or a line that is not a directive, as it
+    /// was.
+    Other(String),
+}
+
+/// How the flags of affix rules and dictionary words are written, the
+/// `FLAG` directive.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum FlagType {
+    /// One character per flag, the default.
+    #[default]
+    Char,
+    /// `long`: two characters per flag.
+    Long,
+    /// `num`: numbers separated by commas.
+    Num,
+    /// `UTF-8`: one Unicode character per flag.
+    Utf8,
+}
+
+impl FlagType {
+    /// Splits `flags` as written after a `/` into the separate flags.
+    pub fn split(&self, flags: &str) -> Vec<String> {
+        match self {
+            FlagType::Char | FlagType::Utf8 => flags.chars().map(String::from).collect(),
+            FlagType::Long => {
+                let chars: Vec<char> = flags.chars().collect();
+                chars.chunks(2).map(|c| c.iter().collect()).collect()
+            }
+            FlagType::Num => flags
+                .split(',')
+                .filter(|f| !f.is_empty())
+                .map(str::to_string)
+                .collect(),
+        }
+    }
+
+    /// Joins separate flags as they are written after a `/`.
+    pub fn join<S: AsRef<str>>(&self, flags: &[S]) -> String {
+        let flags: Vec<&str> = flags.iter().map(AsRef::as_ref).collect();
+        match self {
+            FlagType::Num => flags.join(","),
+            _ => flags.concat(),
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            FlagType::Char => "char",
+            FlagType::Long => "long",
+            FlagType::Num => "num",
+            FlagType::Utf8 => "UTF-8",
+        }
+    }
+}
+
+/// The directives that give a flag a meaning.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FlagDirective {
+    CompoundFlag,
+    CompoundBegin,
+    CompoundMiddle,
+    CompoundEnd,
+    CompoundPermitFlag,
+    CompoundForbidFlag,
+    CompoundRoot,
+    OnlyInCompound,
+    NeedAffix,
+    KeepCase,
+    ForbiddenWord,
+    NoSuggest,
+    Circumfix,
+    Substandard,
+    ForceUcase,
+    Warn,
+}
+
+const FLAG_DIRECTIVES: [(FlagDirective, &str); 16] = [
+    (FlagDirective::CompoundFlag, "COMPOUNDFLAG"),
+    (FlagDirective::CompoundBegin, "COMPOUNDBEGIN"),
+    (FlagDirective::CompoundMiddle, "COMPOUNDMIDDLE"),
+    (FlagDirective::CompoundEnd, "COMPOUNDEND"),
+    (FlagDirective::CompoundPermitFlag, "COMPOUNDPERMITFLAG"),
+    (FlagDirective::CompoundForbidFlag, "COMPOUNDFORBIDFLAG"),
+    (FlagDirective::CompoundRoot, "COMPOUNDROOT"),
+    (FlagDirective::OnlyInCompound, "ONLYINCOMPOUND"),
+    (FlagDirective::NeedAffix, "NEEDAFFIX"),
+    (FlagDirective::KeepCase, "KEEPCASE"),
+    (FlagDirective::ForbiddenWord, "FORBIDDENWORD"),
+    (FlagDirective::NoSuggest, "NOSUGGEST"),
+    (FlagDirective::Circumfix, "CIRCUMFIX"),
+    (FlagDirective::Substandard, "SUBSTANDARD"),
+    (FlagDirective::ForceUcase, "FORCEUCASE"),
+    (FlagDirective::Warn, "WARN"),
+];
+
+impl FlagDirective {
+    /// The name of the directive in the affix file, i.e. `COMPOUNDFLAG`.
+    pub fn name(&self) -> &'static str {
+        FLAG_DIRECTIVES
+            .iter()
+            .find(|(d, _)| d == self)
+            .map_or("", |(_, name)| name)
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        FLAG_DIRECTIVES
+            .iter()
+            .find(|(_, n)| *n == name)
+            .map(|(d, _)| *d)
+    }
+}
+
+/// The directives with a value or without one that are kept as
+/// [`Directive::Setting`], for [`AffixFile::setting()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AffixOption {
+    /// The language of the dictionary, i.e. `en_US`.
+    Lang,
+    /// Characters that are part of words besides letters.
+    WordChars,
+    /// Characters removed from words before checking, like vowel marks.
+    Ignore,
+    /// Neighboring keys, for suggestions of typing errors.
+    Key,
+    Name,
+    Version,
+    Home,
+    /// Two prefixes may be stripped instead of two suffixes.
+    ComplexPrefixes,
+    /// A rule may strip a whole word.
+    FullStrip,
+    /// `ß` is written `SS` in uppercase words.
+    CheckSharps,
+    LemmaPresent,
+    /// The minimal length of a part of a compound, 3 by default.
+    CompoundMin,
+    /// The maximal number of words in a compound.
+    CompoundWordMax,
+    CompoundSyllable,
+    SyllableNum,
+    CompoundMoreSuffixes,
+    /// A word may not be repeated in a compound.
+    CheckCompoundDup,
+    /// A compound may not be a `REP` variant of a word.
+    CheckCompoundRep,
+    /// Uppercase letters may not be at the boundaries of compound parts.
+    CheckCompoundCase,
+    /// Three equal letters may not meet at a compound boundary.
+    CheckCompoundTriple,
+    /// Three equal letters at a compound boundary may be written as two.
+    SimplifiedTriple,
+    /// The maximal number of compound suggestions.
+    MaxCpdSugs,
+    /// The maximal number of n-gram suggestions.
+    MaxNgramSugs,
+    /// How different n-gram suggestions may be, 0 to 10.
+    MaxDiff,
+    OnlyMaxDiff,
+    /// Words are not split in two for suggestions.
+    NoSplitSugs,
+    /// Suggestions keep the periods at the end of words.
+    SugsWithDots,
+    /// Words with the `WARN` flag are rejected.
+    ForbidWarn,
+}
+
+const AFFIX_OPTIONS: [(AffixOption, &str); 28] = [
+    (AffixOption::Lang, "LANG"),
+    (AffixOption::WordChars, "WORDCHARS"),
+    (AffixOption::Ignore, "IGNORE"),
+    (AffixOption::Key, "KEY"),
+    (AffixOption::Name, "NAME"),
+    (AffixOption::Version, "VERSION"),
+    (AffixOption::Home, "HOME"),
+    (AffixOption::ComplexPrefixes, "COMPLEXPREFIXES"),
+    (AffixOption::FullStrip, "FULLSTRIP"),
+    (AffixOption::CheckSharps, "CHECKSHARPS"),
+    (AffixOption::LemmaPresent, "LEMMA_PRESENT"),
+    (AffixOption::CompoundMin, "COMPOUNDMIN"),
+    (AffixOption::CompoundWordMax, "COMPOUNDWORDMAX"),
+    (AffixOption::CompoundSyllable, "COMPOUNDSYLLABLE"),
+    (AffixOption::SyllableNum, "SYLLABLENUM"),
+    (AffixOption::CompoundMoreSuffixes, "COMPOUNDMORESUFFIXES"),
+    (AffixOption::CheckCompoundDup, "CHECKCOMPOUNDDUP"),
+    (AffixOption::CheckCompoundRep, "CHECKCOMPOUNDREP"),
+    (AffixOption::CheckCompoundCase, "CHECKCOMPOUNDCASE"),
+    (AffixOption::CheckCompoundTriple, "CHECKCOMPOUNDTRIPLE"),
+    (AffixOption::SimplifiedTriple, "SIMPLIFIEDTRIPLE"),
+    (AffixOption::MaxCpdSugs, "MAXCPDSUGS"),
+    (AffixOption::MaxNgramSugs, "MAXNGRAMSUGS"),
+    (AffixOption::MaxDiff, "MAXDIFF"),
+    (AffixOption::OnlyMaxDiff, "ONLYMAXDIFF"),
+    (AffixOption::NoSplitSugs, "NOSPLITSUGS"),
+    (AffixOption::SugsWithDots, "SUGSWITHDOTS"),
+    (AffixOption::ForbidWarn, "FORBIDWARN"),
+];
+
+impl AffixOption {
+    /// The name of the directive in the affix file, i.e. `COMPOUNDMIN`.
+    pub fn name(&self) -> &'static str {
+        AFFIX_OPTIONS
+            .iter()
+            .find(|(o, _)| o == self)
+            .map_or("", |(_, name)| name)
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        AFFIX_OPTIONS
+            .iter()
+            .find(|(_, n)| *n == name)
+            .map(|(o, _)| *o)
+    }
+}
+
+impl AsRef<str> for AffixOption {
+    fn as_ref(&self) -> &str {
+        self.name()
+    }
+}
+
+/// The tables that are not parsed into a type of their own.
+const TABLES: [&str; 7] = [
+    "MAP",
+    "BREAK",
+    "PHONE",
+    "AF",
+    "AM",
+    "COMPOUNDRULE",
+    "CHECKCOMPOUNDPATTERN",
+];
+
+/// The directive naming the encoding of the affix and dictionary files.
+pub(crate) const ENCODING_DIRECTIVE: &str = "SET";
+
+/// The directives besides the flags, options and tables: the ones parsed
+/// into a [`Directive`] of their own, and the ones hunspell reads but
+/// ignores or knows under an older name.
+const OTHER_DIRECTIVES: [&str; 14] = [
+    ENCODING_DIRECTIVE,
+    "FLAG",
+    "TRY",
+    "REP",
+    "ICONV",
+    "OCONV",
+    "PFX",
+    "SFX",
+    "CHECKNUM",
+    "COMPOUNDFIRST",
+    "COMPOUNDLAST",
+    "NONGRAMSUGGEST",
+    "ONLYROOT",
+    "PSEUDOROOT",
+];
+
+/// Returns true if `name` is a directive of the hunspell affix file format.
+pub(crate) fn is_directive(name: &str) -> bool {
+    FLAG_DIRECTIVES.iter().any(|(_, n)| *n == name)
+        || AFFIX_OPTIONS.iter().any(|(_, n)| *n == name)
+        || TABLES.contains(&name)
+        || OTHER_DIRECTIVES.contains(&name)
+}
+
+/// The `PFX` or `SFX` rules of one flag.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct AffixGroup {
+    pub flag: String,
+    /// Whether the affix combines with affixes of the other kind, `Y` or
+    /// `N` in the header.
+    pub cross_product: bool,
+    pub rules: Vec<AffixRule>,
+}
+
+/// A line of an affix group, i.e. `SFX S y ies [^aeiou]y`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct AffixRule {
+    /// Removed from the word before adding, empty for `0`.
+    pub strip: String,
+    /// Added to the word, empty for `0`.
+    pub add: String,
+    /// The flags after the `/` of the added part, for affixes on affixes.
+    pub continuation: String,
+    /// Which words the rule applies to; `.` for all, or empty.
+    pub condition: String,
+    /// Morphological fields, like `is:plural`.
+    pub morph_fields: Vec<String>,
+}
+
+/// The `MAP` table: groups of similar characters, like `aáàâ`, that
+/// hunspell tries for each other when it makes suggestions. A member can
+/// also be a string, written in parentheses: `(ss)(ß)`.
+///
+/// ```
+/// use hunspell_rs::aff::AffixFile;
+///
+/// let aff = AffixFile::parse("MAP 2\nMAP aáà\nMAP (ss)(ß)\n").unwrap();
+/// let map = aff.char_map();
+/// assert_eq!(vec!["ss", "ß"], map.groups[1]);
+/// assert!(map.are_similar("á", "a"));
+/// assert_eq!(0, map.distance("fàcade", "facade"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct CharMap {
+    pub groups: Vec<Vec<String>>,
+}
+
+impl CharMap {
+    /// Splits a row of the table into its members.
+    pub fn parse_row(row: &str) -> Vec<String> {
+        let mut members = Vec::new();
+        let mut chars = row.chars();
+        while let Some(c) = chars.next() {
+            if c == '(' {
+                let member: String = chars.by_ref().take_while(|&c| c != ')').collect();
+                members.push(member);
+            } else {
+                members.push(c.to_string());
+            }
+        }
+        members
+    }
+
+    /// Returns true if `a` and `b` are the same or in the same group.
+    pub fn are_similar(&self, a: &str, b: &str) -> bool {
+        a == b
+            || self
+                .groups
+                .iter()
+                .any(|g| g.iter().any(|m| m == a) && g.iter().any(|m| m == b))
+    }
+
+    /// The edit distance between `a` and `b` (insertions, deletions,
+    /// substitutions and transpositions), where substituting a similar
+    /// character is free. Members of more than one character are not
+    /// used here.
+    pub fn distance(&self, a: &str, b: &str) -> usize {
+        edit_distance_by(a, b, |x, y| {
+            let (mut x_buffer, mut y_buffer) = ([0; 4], [0; 4]);
+            x == y || self.are_similar(x.encode_utf8(&mut x_buffer), y.encode_utf8(&mut y_buffer))
+        })
+    }
+}
+
+/// An `ICONV` or `OCONV` table: the pairs of patterns and their
+/// replacements.
+///
+/// ```
+/// use hunspell_rs::aff::AffixFile;
+///
+/// let aff = AffixFile::parse("ICONV 3\nICONV ’ '\nICONV ij ĳ\nICONV _ij Ĳ\n").unwrap();
+/// let iconv = aff.input_conversions();
+/// assert_eq!("don't", iconv.apply("don’t"));
+/// assert_eq!("Ĳsbĳl", iconv.apply("ijsbijl"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct ConversionTable {
+    pub pairs: Vec<(String, String)>,
+}
+
+impl ConversionTable {
+    pub fn is_empty(&self) -> bool {
+        self.pairs.is_empty()
+    }
+
+    /// Converts `word` like hunspell does: from the start of the word, the
+    /// longest pattern that matches is replaced. A pattern that starts
+    /// (ends) with `_` only matches at the start (end) of the word, and
+    /// goes before a pattern of the same length that matches anywhere. A
+    /// `_` in a replacement is a space.
+    pub fn apply<'a>(&self, word: &'a str) -> Cow<'a, str> {
+        if self.pairs.is_empty() {
+            return Cow::Borrowed(word);
+        }
+        let mut converted = String::with_capacity(word.len());
+        let mut changed = false;
+        let mut i = 0;
+        while let Some(c) = word[i..].chars().next() {
+            let rest = &word[i..];
+            // the length of the pattern and whether it is anchored
+            let mut longest: Option<((usize, bool), &str)> = None;
+            for (from, to) in &self.pairs {
+                let (initial, pattern) = match from.strip_prefix('_') {
+                    Some(pattern) if !pattern.is_empty() => (true, pattern),
+                    _ => (false, from.as_str()),
+                };
+                let (last, pattern) = match pattern.strip_suffix('_') {
+                    Some(pattern) if !pattern.is_empty() => (true, pattern),
+                    _ => (false, pattern),
+                };
+                let key = (pattern.len(), initial || last);
+                if (initial && i > 0)
+                    || (last && pattern.len() != rest.len())
+                    || !rest.starts_with(pattern)
+                    || longest.is_some_and(|(longest, _)| longest >= key)
+                {
+                    continue;
+                }
+                longest = Some((key, to));
+            }
+            match longest {
+                Some(((len, _), to)) => {
+                    converted.push_str(&to.replace('_', " "));
+                    changed = true;
+                    i += len;
+                }
+                None => {
+                    converted.push(c);
+                    i += c.len_utf8();
+                }
+            }
+        }
+        if changed {
+            Cow::Owned(converted)
+        } else {
+            Cow::Borrowed(word)
+        }
+    }
+}
+
+/// Whether an [`AffixGroup`] comes from `PFX` or `SFX` lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AffixKind {
+    Prefix,
+    Suffix,
+}
+
+impl AffixRule {
+    /// Returns true if the rule applies to `word`: it starts (prefixes)
+    /// or ends (suffixes) with the strip string and matches the
+    /// condition. A condition that cannot be parsed matches nothing.
+    ///
+    /// ```
+    /// use hunspell_rs::aff::{AffixKind, AffixRule};
+    ///
+    /// let rule = AffixRule {
+    ///     strip: "y".to_string(),
+    ///     add: "ies".to_string(),
+    ///     condition: "[^aeiou]y".to_string(),
+    ///     ..Default::default()
+    /// };
+    /// assert!(rule.matches(AffixKind::Suffix, "fly"));
+    /// assert!(!rule.matches(AffixKind::Suffix, "day"));
+    /// assert_eq!(Some("flies".to_string()), rule.apply(AffixKind::Suffix, "fly"));
+    /// ```
+    pub fn matches(&self, kind: AffixKind, word: &str) -> bool {
+        let Some(condition) = parse_condition(&self.condition) else {
+            return false;
+        };
+        let chars: Vec<char> = word.chars().collect();
+        if condition.len() > chars.len() {
+            return false;
+        }
+        let (part, strip) = match kind {
+            AffixKind::Prefix => (&chars[..condition.len()], word.starts_with(&self.strip)),
+            AffixKind::Suffix => (
+                &chars[chars.len() - condition.len()..],
+                word.ends_with(&self.strip),
+            ),
+        };
+        strip
+            && word.len() > self.strip.len()
+            && part
+                .iter()
+                .zip(&condition)
+                .all(|(&c, element)| element.matches(c))
+    }
+
+    /// Returns `word` with the rule applied, `None` when it does not
+    /// apply, see [`matches()`](AffixRule::matches).
+    pub fn apply(&self, kind: AffixKind, word: &str) -> Option<String> {
+        if !self.matches(kind, word) {
+            return None;
+        }
+        Some(match kind {
+            AffixKind::Prefix => format!("{}{}", self.add, &word[self.strip.len()..]),
+            AffixKind::Suffix => format!("{}{}", &word[..word.len() - self.strip.len()], self.add),
+        })
+    }
+}
+
+/// A character of an affix condition.
+enum ConditionChar {
+    Any,
+    Char(char),
+    Set { negated: bool, chars: Vec<char> },
+}
+
+impl ConditionChar {
+    fn matches(&self, c: char) -> bool {
+        match self {
+            ConditionChar::Any => true,
+            ConditionChar::Char(expected) => c == *expected,
+            ConditionChar::Set { negated, chars } => chars.contains(&c) != *negated,
+        }
+    }
+}
+
+/// Parses a condition like `[^aeiou]y`; empty and `.` match any word.
+fn parse_condition(condition: &str) -> Option<Vec<ConditionChar>> {
+    if condition.is_empty() || condition == "." {
+        return Some(Vec::new());
+    }
+    let mut parsed = Vec::new();
+    let mut chars = condition.chars();
+    while let Some(c) = chars.next() {
+        parsed.push(match c {
+            '.' => ConditionChar::Any,
+            '[' => {
+                let mut set: Vec<char> = Vec::new();
+                loop {
+                    match chars.next()? {
+                        ']' => break,
+                        c => set.push(c),
+                    }
+                }
+                let negated = set.first() == Some(&'^');
+                if negated {
+                    set.remove(0);
+                }
+                ConditionChar::Set {
+                    negated,
+                    chars: set,
+                }
+            }
+            c => ConditionChar::Char(c),
+        });
+    }
+    Some(parsed)
+}
+
+impl AffixFile {
+    /// Reads an affix file in the encoding named by its `SET` directive.
+    pub fn read<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let contents = std::fs::read(&path).map_err(Error::io(Operation::Load, &path))?;
+        let codec = Codec::for_name(&encoding_of(&contents));
+        Self::parse(&codec.decode(&contents)?)
+    }
+
+    /// Writes the affix file in the encoding named by its `SET`
+    /// directive. Characters the encoding does not have become `?`.
+    pub fn write<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let codec = Codec::for_name(self.encoding().unwrap_or("UTF-8"));
+        std::fs::write(&path, codec.encode(&self.to_string()))
+            .map_err(Error::io(Operation::Save, &path))?;
+        Ok(())
+    }
+
+    /// Parses the contents of an affix file. Fails with
+    /// `Error::InvalidAffixFile` for rows of tables and affix groups
+    /// without a header, and for incomplete rows.
+    pub fn parse(contents: &str) -> Result<Self> {
+        let mut aff = AffixFile::default();
+        for (i, line) in contents.lines().enumerate() {
+            let invalid =
+                |reason: &str| Error::InvalidAffixFile(format!("line {}: {}", i + 1, reason));
+            let trimmed = line.trim_start_matches('\u{feff}').trim();
+            let fields: Vec<&str> = trimmed.split_whitespace().collect();
+            let Some(&name) = fields.first().filter(|_| !trimmed.starts_with('#')) else {
+                aff.directives.push(Directive::Other(line.to_string()));
+                continue;
+            };
+            let is_header = |n: usize| fields.len() == n && fields[n - 1].parse::<usize>().is_ok();
+            let directive = match name {
+                ENCODING_DIRECTIVE if fields.len() > 1 => {
+                    Directive::Encoding(fields[1].to_string())
+                }
+                "FLAG" if fields.len() > 1 => Directive::FlagType(match fields[1] {
+                    "long" => FlagType::Long,
+                    "num" => FlagType::Num,
+                    "UTF-8" => FlagType::Utf8,
+                    _ => FlagType::Char,
+                }),
+                "TRY" if fields.len() > 1 => Directive::Try(fields[1].to_string()),
+                "REP" | "ICONV" | "OCONV" if is_header(2) => match name {
+                    "REP" => Directive::Replacements(Vec::new()),
+                    "ICONV" => Directive::InputConversions(Vec::new()),
+                    _ => Directive::OutputConversions(Vec::new()),
+                },
+                "REP" | "ICONV" | "OCONV" => {
+                    if fields.len() < 3 {
+                        return Err(invalid(&format!("incomplete {name} row")));
+                    }
+                    let pair = (fields[1].to_string(), fields[2].to_string());
+                    aff.pairs_mut(name)
+                        .ok_or_else(|| invalid(&format!("{name} row without header")))?
+                        .push(pair);
+                    continue;
+                }
+                "PFX" | "SFX" if fields.len() == 4 && matches!(fields[2], "Y" | "N") => {
+                    let group = AffixGroup {
+                        flag: fields[1].to_string(),
+                        cross_product: fields[2] == "Y",
+                        rules: Vec::new(),
+                    };
+                    if name == "PFX" {
+                        Directive::Prefix(group)
+                    } else {
+                        Directive::Suffix(group)
+                    }
+                }
+                "PFX" | "SFX" => {
+                    if fields.len() < 4 {
+                        return Err(invalid(&format!("incomplete {name} rule")));
+                    }
+                    let (add, continuation) = fields[3].split_once('/').unwrap_or((fields[3], ""));
+                    let zero = |s: &str| {
+                        if s == "0" {
+                            String::new()
+                        } else {
+                            s.to_string()
+                        }
+                    };
+                    let rule = AffixRule {
+                        strip: zero(fields[2]),
+                        add: zero(add),
+                        continuation: continuation.to_string(),
+                        condition: fields.get(4).map_or(String::new(), |c| c.to_string()),
+                        morph_fields: fields.iter().skip(5).map(|f| f.to_string()).collect(),
+                    };
+                    aff.group_mut(name == "PFX", fields[1])
+                        .ok_or_else(|| invalid(&format!("{name} rule without header")))?
+                        .rules
+                        .push(rule);
+                    continue;
+                }
+                _ if TABLES.contains(&name) && is_header(2) => Directive::Table {
+                    name: name.to_string(),
+                    rows: Vec::new(),
+                },
+                _ if TABLES.contains(&name) => {
+                    let row = fields[1..].iter().map(|f| f.to_string()).collect();
+                    aff.table_mut(name)
+                        .ok_or_else(|| invalid(&format!("{name} row without header")))?
+                        .push(row);
+                    continue;
+                }
+                _ => match (FlagDirective::from_name(name), fields.get(1)) {
+                    (Some(directive), Some(flag)) => Directive::Flag(directive, flag.to_string()),
+                    _ => {
+                        let value = trimmed[name.len()..].trim();
+                        Directive::Setting {
+                            name: name.to_string(),
+                            value: (!value.is_empty()).then(|| value.to_string()),
+                        }
+                    }
+                },
+            };
+            aff.directives.push(directive);
+        }
+        Ok(aff)
+    }
+
+    /// The `SET` directive.
+    pub fn encoding(&self) -> Option<&str> {
+        self.directives.iter().find_map(|d| match d {
+            Directive::Encoding(encoding) => Some(encoding.as_str()),
+            _ => None,
+        })
+    }
+
+    /// The `FLAG` directive, or the default.
+    pub fn flag_type(&self) -> FlagType {
+        self.directives
+            .iter()
+            .find_map(|d| match d {
+                Directive::FlagType(flag_type) => Some(*flag_type),
+                _ => None,
+            })
+            .unwrap_or_default()
+    }
+
+    /// The `TRY` directive.
+    pub fn try_chars(&self) -> Option<&str> {
+        self.directives.iter().find_map(|d| match d {
+            Directive::Try(chars) => Some(chars.as_str()),
+            _ => None,
+        })
+    }
+
+    /// The flag of `directive`, i.e. of `COMPOUNDFLAG`.
+    pub fn flag(&self, directive: FlagDirective) -> Option<&str> {
+        self.directives.iter().find_map(|d| match d {
+            Directive::Flag(d, flag) if *d == directive => Some(flag.as_str()),
+            _ => None,
+        })
+    }
+
+    /// The value of another directive, by its name or an [`AffixOption`],
+    /// i.e. `LANG`. Directives without a value, like `FULLSTRIP`, have an
+    /// empty one.
+    ///
+    /// ```
+    /// use hunspell_rs::aff::{AffixFile, AffixOption};
+    ///
+    /// let aff = AffixFile::parse("LANG nl_NL\nCOMPOUNDMIN 2\nFULLSTRIP\n").unwrap();
+    /// assert_eq!(Some("2"), aff.setting(AffixOption::CompoundMin));
+    /// assert_eq!(Some(""), aff.setting(AffixOption::FullStrip));
+    /// assert_eq!(Some("nl_NL"), aff.setting("LANG"));
+    /// ```
+    pub fn setting<N: AsRef<str>>(&self, name: N) -> Option<&str> {
+        let name = name.as_ref();
+        self.directives.iter().find_map(|d| match d {
+            Directive::Setting { name: n, value } if n == name => {
+                Some(value.as_deref().unwrap_or(""))
+            }
+            _ => None,
+        })
+    }
+
+    /// The pairs of all `REP` tables.
+    pub fn replacements(&self) -> Vec<&(String, String)> {
+        self.directives
+            .iter()
+            .flat_map(|d| match d {
+                Directive::Replacements(pairs) => pairs.as_slice(),
+                _ => &[],
+            })
+            .collect()
+    }
+
+    /// The pairs of all `ICONV` tables, which hunspell applies to the
+    /// words it is given.
+    pub fn input_conversions(&self) -> ConversionTable {
+        self.conversions(|d| match d {
+            Directive::InputConversions(pairs) => Some(pairs),
+            _ => None,
+        })
+    }
+
+    /// The pairs of all `OCONV` tables, which hunspell applies to its
+    /// suggestions and analyses.
+    pub fn output_conversions(&self) -> ConversionTable {
+        self.conversions(|d| match d {
+            Directive::OutputConversions(pairs) => Some(pairs),
+            _ => None,
+        })
+    }
+
+    fn conversions(
+        &self,
+        table: impl Fn(&Directive) -> Option<&Vec<(String, String)>>,
+    ) -> ConversionTable {
+        ConversionTable {
+            pairs: self
+                .directives
+                .iter()
+                .filter_map(table)
+                .flatten()
+                .cloned()
+                .collect(),
+        }
+    }
+
+    pub fn prefixes(&self) -> impl Iterator<Item = &AffixGroup> {
+        self.directives.iter().filter_map(|d| match d {
+            Directive::Prefix(group) => Some(group),
+            _ => None,
+        })
+    }
+
+    pub fn suffixes(&self) -> impl Iterator<Item = &AffixGroup> {
+        self.directives.iter().filter_map(|d| match d {
+            Directive::Suffix(group) => Some(group),
+            _ => None,
+        })
+    }
+
+    /// The rows of all tables called `name` that have no type of their
+    /// own, like `AF` or `COMPOUNDRULE`, with the fields after the name.
+    pub fn table<N: AsRef<str>>(&self, name: N) -> Vec<&[String]> {
+        self.directives
+            .iter()
+            .flat_map(|d| match d {
+                Directive::Table { name: n, rows } if n == name.as_ref() => rows.as_slice(),
+                _ => &[],
+            })
+            .map(Vec::as_slice)
+            .collect()
+    }
+
+    /// The rows of all `MAP` tables.
+    pub fn char_map(&self) -> CharMap {
+        CharMap {
+            groups: self
+                .directives
+                .iter()
+                .flat_map(|d| match d {
+                    Directive::Table { name, rows } if name == "MAP" => rows.as_slice(),
+                    _ => &[],
+                })
+                .filter_map(|row| row.first())
+                .map(|row| CharMap::parse_row(row))
+                .collect(),
+        }
+    }
+
+    /// The prefix and suffix groups in the order of the file.
+    pub fn affix_groups(&self) -> impl Iterator<Item = (AffixKind, &AffixGroup)> {
+        self.directives.iter().filter_map(|d| match d {
+            Directive::Prefix(group) => Some((AffixKind::Prefix, group)),
+            Directive::Suffix(group) => Some((AffixKind::Suffix, group)),
+            _ => None,
+        })
+    }
+
+    /// The groups of `flag`, i.e. to show what a flag of a word does. A
+    /// flag can have both a prefix and a suffix group.
+    pub fn affix_groups_of<'a>(
+        &'a self,
+        flag: &'a str,
+    ) -> impl Iterator<Item = (AffixKind, &'a AffixGroup)> + 'a {
+        self.affix_groups()
+            .filter(move |(_, group)| group.flag == flag)
+    }
+
+    /// Every form of the dictionary `entry`, like hunspell's `unmunch`:
+    /// the word itself (unless it has the `NEEDAFFIX` flag), the word with
+    /// each of its suffixes and prefixes that apply, with the suffixes of
+    /// the continuation flags of its suffixes, and with a prefix and a
+    /// suffix when both allow the cross product. Forbidden words and
+    /// words only used in compounds have no forms. Compounds are not made.
+    ///
+    /// ```
+    /// use hunspell_rs::{aff::AffixFile, dic::DicEntry};
+    ///
+    /// let aff = AffixFile::parse("PFX U Y 1\nPFX U 0 un .\nSFX S Y 1\nSFX S 0 s .\n").unwrap();
+    /// assert_eq!(
+    ///     vec!["lock", "locks", "unlock", "unlocks"],
+    ///     aff.expand(&DicEntry::parse("lock/SU"))
+    /// );
+    /// ```
+    pub fn expand(&self, entry: &DicEntry) -> Vec<String> {
+        let flag_type = self.flag_type();
+        let has_flag = |flags: &[String], directive| {
+            self.flag(directive)
+                .is_some_and(|flag| flags.iter().any(|f| f == flag))
+        };
+        let word = entry.word.as_str();
+        let flags = flag_type.split(&entry.flags);
+        if has_flag(&flags, FlagDirective::ForbiddenWord)
+            || has_flag(&flags, FlagDirective::OnlyInCompound)
+        {
+            return Vec::new();
+        }
+        let mut forms = Vec::new();
+        if !has_flag(&flags, FlagDirective::NeedAffix) {
+            forms.push(word.to_string());
+        }
+        // the suffixed forms, and whether they take prefixes
+        let mut suffixed: Vec<(String, bool)> = Vec::new();
+        for group in self.suffixes().filter(|g| flags.contains(&g.flag)) {
+            for rule in &group.rules {
+                let Some(form) = rule.apply(AffixKind::Suffix, word) else {
+                    continue;
+                };
+                let continuation = flag_type.split(&rule.continuation);
+                if !has_flag(&continuation, FlagDirective::NeedAffix) {
+                    suffixed.push((form.clone(), group.cross_product));
+                }
+                for outer in self.suffixes().filter(|g| continuation.contains(&g.flag)) {
+                    for outer_rule in &outer.rules {
+                        if let Some(form) = outer_rule.apply(AffixKind::Suffix, &form) {
+                            suffixed.push((form, group.cross_product && outer.cross_product));
+                        }
+                    }
+                }
+            }
+        }
+        let mut prefixed = Vec::new();
+        for group in self.prefixes().filter(|g| flags.contains(&g.flag)) {
+            for rule in group
+                .rules
+                .iter()
+                .filter(|r| r.matches(AffixKind::Prefix, word))
+            {
+                let continuation = flag_type.split(&rule.continuation);
+                if !has_flag(&continuation, FlagDirective::NeedAffix) {
+                    prefixed.push(format!("{}{}", rule.add, &word[rule.strip.len()..]));
+                }
+                if !group.cross_product {
+                    continue;
+                }
+                // the condition of the prefix is on the word, not on the
+                // suffixed form
+                for (form, _) in suffixed
+                    .iter()
+                    .filter(|(f, cross)| *cross && f.starts_with(&rule.strip))
+                {
+                    prefixed.push(format!("{}{}", rule.add, &form[rule.strip.len()..]));
+                }
+            }
+        }
+        for form in suffixed.into_iter().map(|(f, _)| f).chain(prefixed) {
+            if !forms.contains(&form) {
+                forms.push(form);
+            }
+        }
+        forms
+    }
+
+    /// The prefix group of `flag`, to change its rules.
+    pub fn prefix_mut(&mut self, flag: &str) -> Option<&mut AffixGroup> {
+        self.group_mut(true, flag)
+    }
+
+    /// The suffix group of `flag`, to change its rules.
+    pub fn suffix_mut(&mut self, flag: &str) -> Option<&mut AffixGroup> {
+        self.group_mut(false, flag)
+    }
+
+    fn group_mut(&mut self, prefix: bool, flag: &str) -> Option<&mut AffixGroup> {
+        self.directives.iter_mut().rev().find_map(|d| match d {
+            Directive::Prefix(group) if prefix && group.flag == flag => Some(group),
+            Directive::Suffix(group) if !prefix && group.flag == flag => Some(group),
+            _ => None,
+        })
+    }
+
+    fn pairs_mut(&mut self, name: &str) -> Option<&mut Vec<(String, String)>> {
+        self.directives
+            .iter_mut()
+            .rev()
+            .find_map(|d| match (name, d) {
+                ("REP", Directive::Replacements(pairs))
+                | ("ICONV", Directive::InputConversions(pairs))
+                | ("OCONV", Directive::OutputConversions(pairs)) => Some(pairs),
+                _ => None,
+            })
+    }
+
+    fn table_mut(&mut self, name: &str) -> Option<&mut Vec<Vec<String>>> {
+        self.directives.iter_mut().rev().find_map(|d| match d {
+            Directive::Table { name: n, rows } if n == name => Some(rows),
+            _ => None,
+        })
+    }
+}
+
+impl core::fmt::Display for AffixFile {
+    /// Writes the affix file, see the [module documentation](self).
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        for directive in &self.directives {
+            write!(fmt, "{directive}")?;
+        }
+        Ok(())
+    }
+}
+
+impl core::fmt::Display for Directive {
+    /// Writes the lines of the directive, each ending with a newline.
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let pairs = |fmt: &mut core::fmt::Formatter<'_>, name: &str, pairs: &[(String, String)]| {
+            writeln!(fmt, "{name} {}", pairs.len())?;
+            pairs
+                .iter()
+                .try_for_each(|(from, to)| writeln!(fmt, "{name} {from} {to}"))
+        };
+        match self {
+            Directive::Encoding(encoding) => writeln!(fmt, "SET {encoding}"),
+            Directive::FlagType(flag_type) => writeln!(fmt, "FLAG {}", flag_type.name()),
+            Directive::Try(chars) => writeln!(fmt, "TRY {chars}"),
+            Directive::Flag(directive, flag) => writeln!(fmt, "{} {flag}", directive.name()),
+            Directive::Replacements(p) => pairs(fmt, "REP", p),
+            Directive::InputConversions(p) => pairs(fmt, "ICONV", p),
+            Directive::OutputConversions(p) => pairs(fmt, "OCONV", p),
+            Directive::Prefix(group) => write_group(fmt, "PFX", group),
+            Directive::Suffix(group) => write_group(fmt, "SFX", group),
+            Directive::Table { name, rows } => {
+                writeln!(fmt, "{name} {}", rows.len())?;
+                rows.iter()
+                    .try_for_each(|row| writeln!(fmt, "{name} {}", row.join(" ")))
+            }
+            Directive::Setting { name, value: None } => writeln!(fmt, "{name}"),
+            Directive::Setting {
+                name,
+                value: Some(value),
+            } => writeln!(fmt, "{name} {value}"),
+            Directive::Other(line) => writeln!(fmt, "{line}"),
+        }
+    }
+}
+
+fn write_group(
+    fmt: &mut core::fmt::Formatter<'_>,
+    kind: &str,
+    group: &AffixGroup,
+) -> core::fmt::Result {
+    let cross_product = if group.cross_product { 'Y' } else { 'N' };
+    writeln!(
+        fmt,
+        "{kind} {} {cross_product} {}",
+        group.flag,
+        group.rules.len()
+    )?;
+    let zero = |s: &str| {
+        if s.is_empty() {
+            "0".to_string()
+        } else {
+            s.to_string()
+        }
+    };
+    for rule in &group.rules {
+        write!(
+            fmt,
+            "{kind} {} {} {}",
+            group.flag,
+            zero(&rule.strip),
+            zero(&rule.add)
+        )?;
+        if !rule.continuation.is_empty() {
+            write!(fmt, "/{}", rule.continuation)?;
+        }
+        if !rule.condition.is_empty() || !rule.morph_fields.is_empty() {
+            let condition = if rule.condition.is_empty() {
+                "."
+            } else {
+                &rule.condition
+            };
+            write!(fmt, " {condition}")?;
+        }
+        for field in &rule.morph_fields {
+            write!(fmt, " {field}")?;
+        }
+        writeln!(fmt)?;
+    }
+    Ok(())
+}
+
+/// Returns the encoding named by the `SET` directive in the raw contents
+/// of an affix file; the directives are ASCII in every encoding.
+pub(crate) fn encoding_of(contents: &[u8]) -> String {
+    String::from_utf8_lossy(contents)
+        .lines()
+        .find_map(|line| {
+            let mut fields = line.trim_start_matches('\u{feff}').split_whitespace();
+            match (fields.next(), fields.next()) {
+                (Some(ENCODING_DIRECTIVE), Some(encoding)) => Some(encoding.to_string()),
+                _ => None,
+            }
+        })
+        .unwrap_or_else(|| DEFAULT_ENCODING.to_string())
+}
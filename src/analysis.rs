@@ -0,0 +1,158 @@
+//! Memoizing the morphological analyses and stems of words, shared by
+//! `analyze()`, `stem()`, `extended_stem()` and `extended_generate()`.
+
+use crate::{cached::Lru, CacheStats, Result, SpellChecker};
+
+/// The analyses and stems of the most recently used words.
+#[derive(Debug)]
+pub(crate) struct AnalysisCache {
+    analyses: Lru<Vec<String>>,
+    stems: Lru<Vec<String>>,
+    stats: CacheStats,
+}
+
+/// What is cached of a word.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Morphology {
+    Analyses,
+    Stems,
+}
+
+/// The contents of an analysis cache, see
+/// `SpellChecker::analysis_cache_entries()`.
+#[cfg(feature = "snapshot")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct CachedMorphology {
+    pub(crate) capacity: usize,
+    pub(crate) analyses: Vec<(String, Vec<String>)>,
+    pub(crate) stems: Vec<(String, Vec<String>)>,
+}
+
+impl AnalysisCache {
+    fn new(capacity: usize) -> Self {
+        AnalysisCache {
+            analyses: Lru::new(capacity),
+            stems: Lru::new(capacity),
+            stats: CacheStats::default(),
+        }
+    }
+
+    fn lru(&mut self, morphology: Morphology) -> &mut Lru<Vec<String>> {
+        match morphology {
+            Morphology::Analyses => &mut self.analyses,
+            Morphology::Stems => &mut self.stems,
+        }
+    }
+}
+
+impl SpellChecker {
+    /// Keeps the analyses and stems of at most `capacity` words each, so
+    /// that `analyze()`, `stem()`, `extended_stem()` and
+    /// `extended_generate()` of the same word call hunspell once. The
+    /// extended calls use the analyses of `analyze()`. Adding, removing
+    /// and changing how words are passed to hunspell clear the cache.
+    /// Off by default.
+    ///
+    /// ```
+    /// use hunspell_rs::SpellChecker;
+    ///
+    /// let mut spell = SpellChecker::new("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic").unwrap();
+    /// spell.enable_analysis_cache(1000);
+    /// spell.analyze("cats").unwrap();
+    /// spell.extended_stem("cats").unwrap();
+    /// let stats = spell.analysis_cache_stats().unwrap();
+    /// assert_eq!((1, 1), (stats.hits, stats.misses));
+    /// ```
+    pub fn enable_analysis_cache(&mut self, capacity: usize) {
+        *self.analysis_cache.get_mut() = Some(AnalysisCache::new(capacity));
+    }
+
+    /// Stops caching, and forgets the cached results.
+    pub fn disable_analysis_cache(&mut self) {
+        *self.analysis_cache.get_mut() = None;
+    }
+
+    /// The capacity given to `enable_analysis_cache()`, `None` when the
+    /// cache is not enabled.
+    pub fn analysis_cache_capacity(&self) -> Option<usize> {
+        self.analysis_cache
+            .borrow()
+            .as_ref()
+            .map(|cache| cache.analyses.capacity)
+    }
+
+    /// How often the analysis cache could answer, `None` when it is not
+    /// enabled.
+    pub fn analysis_cache_stats(&self) -> Option<CacheStats> {
+        self.analysis_cache
+            .borrow()
+            .as_ref()
+            .map(|cache| cache.stats)
+    }
+
+    /// Returns the cached `morphology` of `word`, or makes it with `make`
+    /// and caches it.
+    pub(crate) fn cached_morphology<F>(
+        &self,
+        morphology: Morphology,
+        word: &str,
+        make: F,
+    ) -> Result<Vec<String>>
+    where
+        F: FnOnce() -> Result<Vec<String>>,
+    {
+        if let Some(cache) = self.analysis_cache.borrow_mut().as_mut() {
+            if let Some(found) = cache.lru(morphology).get(word) {
+                let found = found.clone();
+                cache.stats.hits += 1;
+                return Ok(found);
+            }
+            cache.stats.misses += 1;
+        }
+        let made = make()?;
+        if let Some(cache) = self.analysis_cache.borrow_mut().as_mut() {
+            cache.lru(morphology).insert(word, made.clone());
+        }
+        Ok(made)
+    }
+
+    /// The capacity, analyses and stems of the analysis cache, least
+    /// recently used first, for saving it.
+    #[cfg(feature = "snapshot")]
+    pub(crate) fn analysis_cache_entries(&self) -> Option<CachedMorphology> {
+        self.analysis_cache.borrow().as_ref().map(|cache| {
+            let entries = |lru: &Lru<Vec<String>>| {
+                lru.iter()
+                    .map(|(word, found)| (word.to_string(), found.clone()))
+                    .collect()
+            };
+            CachedMorphology {
+                capacity: cache.analyses.capacity,
+                analyses: entries(&cache.analyses),
+                stems: entries(&cache.stems),
+            }
+        })
+    }
+
+    /// Enables the analysis cache with the `cached` entries.
+    #[cfg(feature = "snapshot")]
+    pub(crate) fn restore_analysis_cache(&mut self, cached: CachedMorphology) {
+        let mut cache = AnalysisCache::new(cached.capacity);
+        for (word, analyses) in cached.analyses {
+            cache.analyses.insert(&word, analyses);
+        }
+        for (word, stems) in cached.stems {
+            cache.stems.insert(&word, stems);
+        }
+        *self.analysis_cache.get_mut() = Some(cache);
+    }
+
+    /// Forgets the cached analyses and stems, after a change to what
+    /// hunspell returns for words.
+    pub(crate) fn clear_analysis_cache(&mut self) {
+        if let Some(cache) = self.analysis_cache.get_mut() {
+            cache.analyses.clear();
+            cache.stems.clear();
+        }
+    }
+}
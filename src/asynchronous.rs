@@ -0,0 +1,151 @@
+//! A `SpellChecker` for async code, that works on its own thread.
+
+use std::{
+    future::Future,
+    panic::AssertUnwindSafe,
+    path::Path,
+    pin::Pin,
+    sync::{mpsc, Arc, Mutex},
+    task::{Context, Poll, Waker},
+};
+
+use crate::{spell_checker::Setup, Error, Result, SpellChecker};
+
+type Job = Box<dyn FnOnce(&SpellChecker) + Send>;
+
+/// A spell checker with its own thread and hunspell handle, so that slow
+/// calls like `suggest()` do not block an async executor.
+///
+/// It is `Send` and `Sync` and works with any executor. Calls are handled
+/// one at a time, in order; for more throughput use several.
+///
+/// ```
+/// use hunspell_rs::AsyncSpellChecker;
+///
+/// # async fn example() -> hunspell_rs::Result<()> {
+/// let spell = AsyncSpellChecker::new("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic")?;
+/// assert!(spell.check("cats").await?);
+/// assert_eq!(vec!["cat"], spell.suggest("catz").await?);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct AsyncSpellChecker {
+    sender: mpsc::Sender<Job>,
+}
+
+impl AsyncSpellChecker {
+    /// Opens a dictionary like [`SpellChecker::new()`], on a new thread.
+    pub fn new<P: AsRef<Path>>(affix: P, dictionary: P) -> Result<Self> {
+        Self::from_setup(SpellChecker::new(affix, dictionary)?.setup())
+    }
+
+    /// Opens the dictionaries of `spell_checker` on a new thread, with its
+    /// key, extra dictionaries and runtime words.
+    pub fn from_spell_checker(spell_checker: &SpellChecker) -> Result<Self> {
+        Self::from_setup(spell_checker.setup())
+    }
+
+    fn from_setup(setup: Setup) -> Result<Self> {
+        let (sender, receiver) = mpsc::channel::<Job>();
+        let (opened, open_result) = mpsc::channel();
+        std::thread::Builder::new()
+            .name("hunspell-rs-async".to_string())
+            .spawn(move || {
+                let spell_checker = match setup.open() {
+                    Ok(spell_checker) => {
+                        let _ = opened.send(Ok(()));
+                        spell_checker
+                    }
+                    Err(e) => {
+                        let _ = opened.send(Err(e));
+                        return;
+                    }
+                };
+                // ends when the AsyncSpellChecker is dropped
+                while let Ok(job) = receiver.recv() {
+                    job(&spell_checker);
+                }
+            })?;
+        open_result.recv().map_err(|_| Error::WorkerStopped)??;
+        Ok(AsyncSpellChecker { sender })
+    }
+
+    /// See [`SpellChecker::check()`].
+    pub fn check<S: AsRef<str>>(&self, word: S) -> Pending<bool> {
+        let word = word.as_ref().to_string();
+        self.run(move |spell_checker| spell_checker.check(word))
+    }
+
+    /// See [`SpellChecker::suggest()`].
+    pub fn suggest<S: AsRef<str>>(&self, word: S) -> Pending<Vec<String>> {
+        let word = word.as_ref().to_string();
+        self.run(move |spell_checker| spell_checker.suggest(word))
+    }
+
+    /// See [`SpellChecker::analyze()`].
+    pub fn analyze<S: AsRef<str>>(&self, word: S) -> Pending<Vec<String>> {
+        let word = word.as_ref().to_string();
+        self.run(move |spell_checker| spell_checker.analyze(word))
+    }
+
+    /// Runs `f` with the spell checker on its thread, for the methods not
+    /// wrapped here.
+    pub fn run<T, F>(&self, f: F) -> Pending<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&SpellChecker) -> Result<T> + Send + 'static,
+    {
+        let slot = Arc::new(Mutex::new(Slot {
+            result: None,
+            waker: None,
+        }));
+        let job_slot = slot.clone();
+        let job: Job = Box::new(move |spell_checker| {
+            // a panic would leave the future waiting forever
+            let result = std::panic::catch_unwind(AssertUnwindSafe(|| f(spell_checker)))
+                .unwrap_or(Err(Error::WorkerStopped));
+            let mut slot = job_slot.lock().unwrap_or_else(|e| e.into_inner());
+            slot.result = Some(result);
+            if let Some(waker) = slot.waker.take() {
+                waker.wake();
+            }
+        });
+        if self.sender.send(job).is_err() {
+            let mut slot = slot.lock().unwrap_or_else(|e| e.into_inner());
+            slot.result = Some(Err(Error::WorkerStopped));
+        }
+        Pending { slot }
+    }
+}
+
+struct Slot<T> {
+    result: Option<Result<T>>,
+    waker: Option<Waker>,
+}
+
+/// The result of an [`AsyncSpellChecker`] call.
+pub struct Pending<T> {
+    slot: Arc<Mutex<Slot<T>>>,
+}
+
+impl<T> std::fmt::Debug for Pending<T> {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fmt.debug_struct("Pending").finish_non_exhaustive()
+    }
+}
+
+impl<T> Future for Pending<T> {
+    type Output = Result<T>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut slot = self.slot.lock().unwrap_or_else(|e| e.into_inner());
+        match slot.result.take() {
+            Some(result) => Poll::Ready(result),
+            None => {
+                slot.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
@@ -0,0 +1,181 @@
+//! Corrections of common English misspellings without asking hunspell,
+//! for correcting as you type.
+
+use crate::{apply_suggestion, Result, SpellChecker};
+
+/// Misspellings that have a single obvious correction, sorted for binary
+/// search. Words that are misspellings of several words, or correct words
+/// in some dictionaries, are left out.
+static COMMON_MISSPELLINGS: &[(&str, &str)] = &[
+    ("abscence", "absence"),
+    ("accidentaly", "accidentally"),
+    ("accomodate", "accommodate"),
+    ("accross", "across"),
+    ("acheive", "achieve"),
+    ("acknowlege", "acknowledge"),
+    ("adress", "address"),
+    ("agressive", "aggressive"),
+    ("alot", "a lot"),
+    ("amature", "amateur"),
+    ("apparantly", "apparently"),
+    ("appearence", "appearance"),
+    ("arguement", "argument"),
+    ("assasination", "assassination"),
+    ("basicly", "basically"),
+    ("becuase", "because"),
+    ("beggining", "beginning"),
+    ("beleive", "believe"),
+    ("belive", "believe"),
+    ("buisness", "business"),
+    ("calender", "calendar"),
+    ("catagory", "category"),
+    ("cemetary", "cemetery"),
+    ("changable", "changeable"),
+    ("cheif", "chief"),
+    ("collegue", "colleague"),
+    ("comming", "coming"),
+    ("commitee", "committee"),
+    ("completly", "completely"),
+    ("concious", "conscious"),
+    ("curiousity", "curiosity"),
+    ("definately", "definitely"),
+    ("definatly", "definitely"),
+    ("dilema", "dilemma"),
+    ("dissapear", "disappear"),
+    ("dissapoint", "disappoint"),
+    ("doesnt", "doesn't"),
+    ("embarass", "embarrass"),
+    ("enviroment", "environment"),
+    ("existance", "existence"),
+    ("experiance", "experience"),
+    ("familar", "familiar"),
+    ("finaly", "finally"),
+    ("foriegn", "foreign"),
+    ("freind", "friend"),
+    ("futher", "further"),
+    ("gaurd", "guard"),
+    ("goverment", "government"),
+    ("happend", "happened"),
+    ("harrass", "harass"),
+    ("hieght", "height"),
+    ("immediatly", "immediately"),
+    ("independant", "independent"),
+    ("infomation", "information"),
+    ("interupt", "interrupt"),
+    ("irrelevent", "irrelevant"),
+    ("knowlege", "knowledge"),
+    ("liason", "liaison"),
+    ("libary", "library"),
+    ("lisence", "license"),
+    ("maintainance", "maintenance"),
+    ("managment", "management"),
+    ("millenium", "millennium"),
+    ("mischievious", "mischievous"),
+    ("mispell", "misspell"),
+    ("neccessary", "necessary"),
+    ("necesary", "necessary"),
+    ("nieghbor", "neighbor"),
+    ("noticable", "noticeable"),
+    ("occassion", "occasion"),
+    ("occured", "occurred"),
+    ("occurence", "occurrence"),
+    ("occuring", "occurring"),
+    ("ocurred", "occurred"),
+    ("oppurtunity", "opportunity"),
+    ("paralel", "parallel"),
+    ("particulary", "particularly"),
+    ("persistant", "persistent"),
+    ("posession", "possession"),
+    ("potatos", "potatoes"),
+    ("preceeding", "preceding"),
+    ("prefered", "preferred"),
+    ("presense", "presence"),
+    ("probaly", "probably"),
+    ("pronounciation", "pronunciation"),
+    ("publically", "publicly"),
+    ("realy", "really"),
+    ("recieve", "receive"),
+    ("recomend", "recommend"),
+    ("refered", "referred"),
+    ("relevent", "relevant"),
+    ("religous", "religious"),
+    ("remeber", "remember"),
+    ("repitition", "repetition"),
+    ("rythm", "rhythm"),
+    ("seige", "siege"),
+    ("seperate", "separate"),
+    ("similiar", "similar"),
+    ("sincerly", "sincerely"),
+    ("speach", "speech"),
+    ("succesful", "successful"),
+    ("supercede", "supersede"),
+    ("suprise", "surprise"),
+    ("teh", "the"),
+    ("tendancy", "tendency"),
+    ("therefor", "therefore"),
+    ("threshhold", "threshold"),
+    ("tommorow", "tomorrow"),
+    ("tomorow", "tomorrow"),
+    ("tounge", "tongue"),
+    ("truely", "truly"),
+    ("twelth", "twelfth"),
+    ("tyrany", "tyranny"),
+    ("untill", "until"),
+    ("useable", "usable"),
+    ("vaccuum", "vacuum"),
+    ("wich", "which"),
+    ("wierd", "weird"),
+    ("withold", "withhold"),
+    ("writting", "writing"),
+];
+
+/// Returns the correction of `word` when it is a common English
+/// misspelling, with the capitalization of `word`.
+///
+/// ```
+/// use hunspell_rs::common_correction;
+///
+/// assert_eq!(Some("receive".to_string()), common_correction("recieve"));
+/// assert_eq!(Some("Definitely".to_string()), common_correction("Definately"));
+/// assert_eq!(None, common_correction("receive"));
+/// ```
+pub fn common_correction(word: &str) -> Option<String> {
+    let lower = word.to_lowercase();
+    COMMON_MISSPELLINGS
+        .binary_search_by(|(from, _)| (*from).cmp(lower.as_str()))
+        .ok()
+        .map(|i| apply_suggestion(word, COMMON_MISSPELLINGS[i].1))
+}
+
+impl SpellChecker {
+    /// Returns the corrections of `word`, trying the fast ways first: the
+    /// autocorrection from `add_replacement()`, the
+    /// [correction table](SpellChecker::set_correction_table), then for English (or
+    /// unknown) dictionaries [`common_correction()`]. Only when both have
+    /// nothing, hunspell makes the suggestions like `suggest()`.
+    ///
+    /// ```
+    /// use hunspell_rs::SpellChecker;
+    ///
+    /// let spell = SpellChecker::new("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic").unwrap();
+    /// assert_eq!(vec!["the"], spell.quick_suggest("teh").unwrap());
+    /// assert_eq!(spell.suggest("catz").unwrap(), spell.quick_suggest("catz").unwrap());
+    /// ```
+    pub fn quick_suggest<S>(&self, word: S) -> Result<Vec<String>>
+    where
+        S: AsRef<str>,
+    {
+        let word = word.as_ref();
+        if let Some(replacement) = self.replacement(word) {
+            return Ok(vec![replacement]);
+        }
+        if let Some(correction) = self.table_correction(word) {
+            return Ok(vec![correction]);
+        }
+        let english = self.language().is_none_or(|l| l.primary() == "en");
+        if let Some(correction) = common_correction(word).filter(|_| english) {
+            return Ok(vec![correction]);
+        }
+        self.suggest(word)
+    }
+}
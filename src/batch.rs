@@ -0,0 +1,94 @@
+//! Stemming and analyzing many words in one call.
+
+use std::{collections::HashMap, ffi::CStr};
+
+use crate::{ffi, ffi_util::HunspellList, Result, SpellChecker};
+
+/// Options for [`SpellChecker::stem_all_with()`] and
+/// [`SpellChecker::analyze_all_with()`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct BatchOptions {
+    /// Looks up every distinct word once and copies the result for its
+    /// repeats. Worth it for running text, where the same words come back
+    /// often; for lists of distinct words it only costs memory.
+    pub deduplicate: bool,
+}
+
+impl SpellChecker {
+    /// Returns the stems of every word, in the order of `words`. The words
+    /// are passed to hunspell through one reused buffer.
+    ///
+    /// ```
+    /// use hunspell_rs::SpellChecker;
+    ///
+    /// let spell = SpellChecker::new("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic").unwrap();
+    /// let stems = spell.stem_all(["cats", "programs", "catz"]).unwrap();
+    /// assert_eq!(vec![vec!["cat"], vec!["program"], vec![]], stems);
+    /// ```
+    pub fn stem_all<'a, I>(&self, words: I) -> Result<Vec<Vec<String>>>
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        self.stem_all_with(words, &BatchOptions::default())
+    }
+
+    /// Like [`stem_all()`](SpellChecker::stem_all), with `options`.
+    pub fn stem_all_with<'a, I>(&self, words: I, options: &BatchOptions) -> Result<Vec<Vec<String>>>
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        self.all(words, options, |word| {
+            HunspellList::new(&self.handle, |handle, list| unsafe {
+                ffi::Hunspell_stem(handle, list, word.as_ptr())
+            })?
+            .to_vec(self.codec)
+        })
+    }
+
+    /// Returns the morphological analyses of every word, in the order of
+    /// `words`, see [`stem_all()`](SpellChecker::stem_all).
+    pub fn analyze_all<'a, I>(&self, words: I) -> Result<Vec<Vec<String>>>
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        self.analyze_all_with(words, &BatchOptions::default())
+    }
+
+    /// Like [`analyze_all()`](SpellChecker::analyze_all), with `options`.
+    pub fn analyze_all_with<'a, I>(
+        &self,
+        words: I,
+        options: &BatchOptions,
+    ) -> Result<Vec<Vec<String>>>
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        self.all(words, options, |word| {
+            HunspellList::new(&self.handle, |handle, list| unsafe {
+                ffi::Hunspell_analyze(handle, list, word.as_ptr())
+            })?
+            .to_vec(self.codec)
+        })
+    }
+
+    fn all<'a, I, F>(&self, words: I, options: &BatchOptions, f: F) -> Result<Vec<Vec<String>>>
+    where
+        I: IntoIterator<Item = &'a str>,
+        F: Fn(&CStr) -> Result<Vec<String>>,
+    {
+        let words = words.into_iter();
+        let mut results: Vec<Vec<String>> = Vec::with_capacity(words.size_hint().0);
+        let mut seen: HashMap<&str, usize> = HashMap::new();
+        for word in words {
+            if options.deduplicate {
+                if let Some(&i) = seen.get(word) {
+                    results.push(results[i].clone());
+                    continue;
+                }
+                seen.insert(word, results.len());
+            }
+            results.push(self.with_c_word(word, &f)??);
+        }
+        Ok(results)
+    }
+}
@@ -0,0 +1,186 @@
+//! A small command line spell checker on top of hunspell-rs.
+
+use std::{
+    io::{self, Write},
+    path::Path,
+    process::ExitCode,
+};
+
+use hunspell_rs::{
+    available_dictionaries, pipe, Config, Diagnostics, Html, Latex, Markdown, Report, SpellChecker,
+    TextChecker,
+};
+
+const CONFIG_FILE: &str = "spellcheck.toml";
+
+const USAGE: &str = "\
+Usage: hunspell-rs [-d DICTIONARY] COMMAND
+
+Commands:
+  check [--stats | --json | --sarif] FILE...
+                    list the misspelled words of the files, with
+                    --stats followed by the word counts per file,
+                    with --json or --sarif as diagnostics with
+                    suggestions
+  suggest WORD...   suggest spellings
+  analyze WORD...   show the morphological analysis
+  list-dicts        list the installed dictionaries
+  -a                ispell pipe mode on stdin and stdout
+
+DICTIONARY is a language tag like en_US or the path of a dictionary
+without extension. Without it, the configuration in spellcheck.toml is
+used, or else SPELLCHECK_DICTIONARY or the language of LANG.";
+
+fn main() -> ExitCode {
+    match run(std::env::args().skip(1).collect()) {
+        Ok(code) => code,
+        Err(message) => {
+            eprintln!("hunspell-rs: {message}");
+            ExitCode::from(2)
+        }
+    }
+}
+
+fn run(mut args: Vec<String>) -> Result<ExitCode, String> {
+    let mut dictionary = None;
+    if args.first().is_some_and(|a| a == "-d") {
+        if args.len() < 2 {
+            return Err("-d needs a dictionary".to_string());
+        }
+        dictionary = Some(args.remove(1));
+        args.remove(0);
+    }
+    let Some(command) = args.first().cloned() else {
+        println!("{USAGE}");
+        return Ok(ExitCode::from(2));
+    };
+    let operands = &args[1..];
+    match command.as_str() {
+        "list-dicts" => {
+            for info in available_dictionaries() {
+                println!(
+                    "{}\t{}\t{}",
+                    info.language_tag,
+                    info.encoding,
+                    info.aff_path.with_extension("").display()
+                );
+            }
+            Ok(ExitCode::SUCCESS)
+        }
+        "check" => match operands.first().map(String::as_str) {
+            Some("--stats") => check(&open(dictionary)?, &operands[1..], true),
+            Some("--json") => diagnose(&open(dictionary)?, &operands[1..], false),
+            Some("--sarif") => diagnose(&open(dictionary)?, &operands[1..], true),
+            _ => check(&open(dictionary)?, operands, false),
+        },
+        "suggest" => {
+            let spell = open(dictionary)?;
+            for word in operands {
+                let suggestions = spell.suggest(word).map_err(|e| e.to_string())?;
+                println!("{word}: {}", suggestions.join(", "));
+            }
+            Ok(ExitCode::SUCCESS)
+        }
+        "analyze" => {
+            let spell = open(dictionary)?;
+            for word in operands {
+                for analysis in spell.analyze(word).map_err(|e| e.to_string())? {
+                    println!("{word}: {analysis}");
+                }
+            }
+            Ok(ExitCode::SUCCESS)
+        }
+        "-a" => {
+            let mut spell = open(dictionary)?;
+            pipe::run(&mut spell, io::stdin().lock(), io::stdout().lock())
+                .map_err(|e| e.to_string())?;
+            Ok(ExitCode::SUCCESS)
+        }
+        "-h" | "--help" | "help" => {
+            println!("{USAGE}");
+            Ok(ExitCode::SUCCESS)
+        }
+        _ => Err(format!("unknown command {command}, see --help")),
+    }
+}
+
+/// Opens `dictionary`, or the dictionary of `spellcheck.toml` or the
+/// environment.
+fn open(dictionary: Option<String>) -> Result<SpellChecker, String> {
+    let config = match dictionary {
+        Some(dictionary) => Config {
+            dictionary,
+            ..Default::default()
+        },
+        None if Path::new(CONFIG_FILE).is_file() => {
+            Config::read(CONFIG_FILE).map_err(|e| format!("{CONFIG_FILE}: {e}"))?
+        }
+        None => Config::from_env()
+            .map_err(|_| "no dictionary given with -d and none found in the environment")?,
+    };
+    SpellChecker::try_from(&config).map_err(|e| e.to_string())
+}
+
+/// Prints the misspellings as `file:line:column: word`, and with `stats`
+/// the [`Report`]. Exits with 1 when there are any misspellings.
+fn check(spell: &SpellChecker, files: &[String], stats: bool) -> Result<ExitCode, String> {
+    let mut report = Report::new();
+    let mut stdout = io::stdout().lock();
+    for file in files {
+        let text = std::fs::read_to_string(file).map_err(|e| format!("{file}: {e}"))?;
+        let checker = text_checker(spell.clone(), Path::new(file));
+        let file_report = checker.report(file, &text).map_err(|e| e.to_string())?;
+        for m in &file_report.misspellings {
+            let before = &text[..m.range.start];
+            let line = before.matches('\n').count() + 1;
+            let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+            let column = before[line_start..].chars().count() + 1;
+            writeln!(stdout, "{file}:{line}:{column}: {}", m.word).map_err(|e| e.to_string())?;
+        }
+        report.push(file_report);
+    }
+    if stats {
+        write!(stdout, "{report}").map_err(|e| e.to_string())?;
+    }
+    Ok(if report.misspellings() > 0 {
+        ExitCode::from(1)
+    } else {
+        ExitCode::SUCCESS
+    })
+}
+
+/// Prints the misspellings as JSON or, with `sarif`, as a SARIF log.
+/// Exits with 1 when there are any misspellings.
+fn diagnose(spell: &SpellChecker, files: &[String], sarif: bool) -> Result<ExitCode, String> {
+    let mut diagnostics = Diagnostics::new();
+    for file in files {
+        let text = std::fs::read_to_string(file).map_err(|e| format!("{file}: {e}"))?;
+        let checker = text_checker(spell.clone(), Path::new(file));
+        diagnostics.extend(
+            checker
+                .diagnostics(file, &text)
+                .map_err(|e| e.to_string())?,
+        );
+    }
+    if sarif {
+        println!("{}", diagnostics.to_sarif());
+    } else {
+        println!("{}", diagnostics.to_json());
+    }
+    Ok(if diagnostics.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::from(1)
+    })
+}
+
+/// Picks the syntax filter by the extension of `path`.
+fn text_checker(spell: SpellChecker, path: &Path) -> TextChecker {
+    let checker = TextChecker::new(spell);
+    match path.extension().and_then(|e| e.to_str()) {
+        Some("tex") | Some("latex") => checker.with_syntax_filter(Latex),
+        Some("md") | Some("markdown") => checker.with_syntax_filter(Markdown),
+        Some("html") | Some("htm") | Some("xhtml") => checker.with_syntax_filter(Html),
+        _ => checker,
+    }
+}
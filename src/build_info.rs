@@ -0,0 +1,73 @@
+//! How the crate was built, for bug reports and diagnostics.
+
+/// The hunspell library and the crate features this build uses, see
+/// [`build_info()`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BuildInfo {
+    /// True if hunspell is compiled in (the `bundled` feature), false if
+    /// the system library is linked.
+    pub bundled: bool,
+    /// The version of the bundled hunspell. The version of a system
+    /// library is not known when the crate is compiled.
+    pub hunspell_version: Option<&'static str>,
+    /// The version of this crate.
+    pub version: &'static str,
+    /// The enabled crate features, in alphabetical order.
+    pub features: Vec<&'static str>,
+}
+
+/// Returns how the crate was built.
+///
+/// ```
+/// let info = hunspell_rs::build_info();
+/// println!("{info}");
+/// assert_eq!(env!("CARGO_PKG_VERSION"), info.version);
+/// ```
+pub fn build_info() -> BuildInfo {
+    let features = [
+        ("async", cfg!(feature = "async")),
+        ("autocorrect", cfg!(feature = "autocorrect")),
+        ("bundled", cfg!(feature = "bundled")),
+        ("cli", cfg!(feature = "cli")),
+        ("dylib", cfg!(feature = "dylib")),
+        ("fetch", cfg!(feature = "fetch")),
+        ("hyphenation", cfg!(feature = "hyphenation")),
+        ("langdetect", cfg!(feature = "langdetect")),
+        ("linked", cfg!(feature = "linked")),
+        ("lsp", cfg!(feature = "lsp")),
+        ("serde", cfg!(feature = "serde")),
+        ("snapshot", cfg!(feature = "snapshot")),
+        ("test-util", cfg!(feature = "test-util")),
+        ("thesaurus", cfg!(feature = "thesaurus")),
+        ("tracing", cfg!(feature = "tracing")),
+        ("watch", cfg!(feature = "watch")),
+        ("zip", cfg!(feature = "zip")),
+    ];
+    BuildInfo {
+        bundled: cfg!(feature = "bundled"),
+        hunspell_version: cfg!(feature = "bundled").then_some("1.7.1"),
+        version: env!("CARGO_PKG_VERSION"),
+        features: features
+            .into_iter()
+            .filter(|(_, enabled)| *enabled)
+            .map(|(name, _)| name)
+            .collect(),
+    }
+}
+
+impl core::fmt::Display for BuildInfo {
+    /// I.e. `hunspell-rs 0.5.0, hunspell 1.7.1 (bundled), features: bundled, serde`.
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(fmt, "hunspell-rs {}, ", self.version)?;
+        match (self.bundled, self.hunspell_version) {
+            (true, Some(version)) => write!(fmt, "hunspell {version} (bundled)")?,
+            (true, None) => fmt.write_str("hunspell (bundled)")?,
+            (false, _) => fmt.write_str("hunspell (system)")?,
+        }
+        if self.features.is_empty() {
+            fmt.write_str(", no features")
+        } else {
+            write!(fmt, ", features: {}", self.features.join(", "))
+        }
+    }
+}
@@ -0,0 +1,204 @@
+//! Making dictionaries from word lists.
+
+use std::{
+    collections::{BTreeSet, HashMap},
+    path::Path,
+};
+
+use crate::{
+    aff::{AffixFile, AffixOption, Directive, FlagDirective},
+    dic::{DicEntry, DicFile},
+    encoding::Codec,
+    Dictionary, Error, Result,
+};
+
+/// The `FORBIDDENWORD` flag of built dictionaries.
+const FORBIDDEN_FLAG: &str = "!";
+
+/// Makes a minimal dictionary from a plain list of words, i.e. a list of
+/// terms of a field. The words are accepted as they are, without affixes.
+///
+/// ```
+/// use hunspell_rs::DictionaryBuilder;
+///
+/// let builder = DictionaryBuilder::from_words(["ibuprofen", "paracetamol"]).with_language("en");
+/// let spell = builder.build().open().unwrap();
+/// assert_eq!(Ok(true), spell.check("ibuprofen"));
+/// assert_eq!(vec!["paracetamol"], spell.suggest("paracetamoll").unwrap());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DictionaryBuilder {
+    words: BTreeSet<String>,
+    language: Option<String>,
+    encoding: Option<String>,
+    forbidden: BTreeSet<String>,
+}
+
+impl DictionaryBuilder {
+    /// Duplicates and empty words are left out, the other words are
+    /// trimmed.
+    pub fn from_words<I, S>(words: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        DictionaryBuilder {
+            words: words
+                .into_iter()
+                .map(|w| w.as_ref().trim().to_string())
+                .filter(|w| !w.is_empty())
+                .collect(),
+            language: None,
+            encoding: None,
+            forbidden: BTreeSet::new(),
+        }
+    }
+
+    /// The `LANG` of the affix file, a language tag like `en_US`.
+    pub fn with_language<S: AsRef<str>>(mut self, language: S) -> Self {
+        self.language = Some(language.as_ref().to_string());
+        self
+    }
+
+    /// Writes the files in `encoding` instead of UTF-8, i.e. `ISO8859-1`
+    /// or `KOI8-R`, for tools that do not read UTF-8 dictionaries. Fails
+    /// with `Error::UnsupportedEncoding` for an encoding hunspell-rs has no
+    /// table for, and with `Error::InvalidWord` when a word has a
+    /// character the encoding cannot write.
+    ///
+    /// ```
+    /// use hunspell_rs::DictionaryBuilder;
+    ///
+    /// let builder = DictionaryBuilder::from_words(["café"]).with_encoding("ISO8859-1").unwrap();
+    /// assert_eq!(Some("ISO8859-1"), builder.encoding());
+    /// assert_eq!(Ok(true), builder.build().open().unwrap().check("café"));
+    /// assert!(DictionaryBuilder::from_words(["кот"]).with_encoding("ISO8859-1").is_err());
+    /// ```
+    pub fn with_encoding<S: AsRef<str>>(mut self, encoding: S) -> Result<Self> {
+        let encoding = encoding.as_ref();
+        let codec = Codec::lookup(encoding)
+            .ok_or_else(|| Error::UnsupportedEncoding(encoding.to_string()))?;
+        for word in self.words.iter().chain(&self.forbidden) {
+            if let Some(c) = word.chars().find(|&c| !codec.can_encode(c)) {
+                return Err(Error::InvalidWord {
+                    word: word.clone(),
+                    character: Some(c),
+                });
+            }
+        }
+        self.encoding = Some(encoding.to_string());
+        Ok(self)
+    }
+
+    /// Marks `words` as wrong with the `FORBIDDENWORD` flag, also when
+    /// they are in the word list or in another dictionary that is loaded
+    /// together with this one. They are left out of the word list.
+    ///
+    /// ```
+    /// use hunspell_rs::DictionaryBuilder;
+    ///
+    /// let builder = DictionaryBuilder::from_words(["regardless", "irregardless"])
+    ///     .with_forbidden_words(["irregardless"]);
+    /// assert_eq!(vec!["regardless"], builder.words().collect::<Vec<_>>());
+    /// assert_eq!("2\nirregardless/!\nregardless\n", builder.dic_file().to_string());
+    /// ```
+    pub fn with_forbidden_words<I, S>(mut self, words: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        for word in words {
+            let word = word.as_ref().trim();
+            if !word.is_empty() {
+                self.words.remove(word);
+                self.forbidden.insert(word.to_string());
+            }
+        }
+        self
+    }
+
+    pub fn forbidden_words(&self) -> impl Iterator<Item = &str> {
+        self.forbidden.iter().map(|w| w.as_str())
+    }
+
+    /// The encoding of the files, `None` for UTF-8.
+    pub fn encoding(&self) -> Option<&str> {
+        self.encoding.as_deref()
+    }
+
+    fn codec(&self) -> Codec {
+        self.encoding
+            .as_deref()
+            .map(Codec::for_name)
+            .unwrap_or_default()
+    }
+
+    pub fn words(&self) -> impl Iterator<Item = &str> {
+        self.words.iter().map(|w| w.as_str())
+    }
+
+    /// Returns the affix file: the encoding, the language, and the
+    /// characters of the words for suggestions (`TRY`), most frequent
+    /// first.
+    pub fn affix_file(&self) -> AffixFile {
+        let mut counts: HashMap<char, usize> = HashMap::new();
+        for c in self.words.iter().flat_map(|w| w.chars()) {
+            *counts.entry(c).or_default() += 1;
+        }
+        let mut chars: Vec<(char, usize)> = counts.into_iter().collect();
+        chars.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        let encoding = self.encoding.as_deref().unwrap_or("UTF-8");
+        let mut directives = vec![Directive::Encoding(encoding.to_string())];
+        if let Some(language) = &self.language {
+            directives.push(Directive::Setting {
+                name: AffixOption::Lang.name().to_string(),
+                value: Some(language.clone()),
+            });
+        }
+        if !chars.is_empty() {
+            directives.push(Directive::Try(chars.into_iter().map(|(c, _)| c).collect()));
+        }
+        if !self.forbidden.is_empty() {
+            directives.push(Directive::Flag(
+                FlagDirective::ForbiddenWord,
+                FORBIDDEN_FLAG.to_string(),
+            ));
+        }
+        AffixFile { directives }
+    }
+
+    /// Returns the dictionary file, the words and forbidden words in
+    /// alphabetical order.
+    pub fn dic_file(&self) -> DicFile {
+        DicFile {
+            entries: self
+                .words
+                .union(&self.forbidden)
+                .map(|word| {
+                    if self.forbidden.contains(word) {
+                        DicEntry::new(word).with_flags(FORBIDDEN_FLAG)
+                    } else {
+                        DicEntry::new(word)
+                    }
+                })
+                .collect(),
+        }
+    }
+
+    /// Writes the affix and dictionary files, to be opened with
+    /// [`SpellChecker::new()`](crate::SpellChecker::new).
+    pub fn write<P: AsRef<Path>>(&self, affix: P, dictionary: P) -> Result<()> {
+        self.affix_file().write(affix)?;
+        self.dic_file()
+            .write(dictionary, self.encoding.as_deref().unwrap_or("UTF-8"))
+    }
+
+    /// Returns the dictionary in memory, see [`Dictionary::from_bytes()`].
+    pub fn build(&self) -> Dictionary {
+        let codec = self.codec();
+        Dictionary::from_bytes(
+            codec.encode(&self.affix_file().to_string()).into_owned(),
+            codec.encode(&self.dic_file().to_string()).into_owned(),
+        )
+    }
+}
@@ -0,0 +1,386 @@
+//! A cache of file reports that is kept between runs, so continuous
+//! integration only checks the files that changed.
+//!
+//! The cache is written by hand as lines of text, like the diagnostics,
+//! so it does not need the `serde` feature.
+
+use std::{
+    collections::HashMap,
+    fmt::Debug,
+    hash::Hasher,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use crate::{Error, FileReport, Misspelling, Operation, Result, RuntimeWord, TextChecker};
+
+/// The first word of a cache, followed by the format version and the
+/// fingerprint of the dictionary.
+const HEADER: &str = "hunspell-rs-cache 4";
+
+/// Where a [`ResultCache`] is kept between runs.
+pub trait CacheStorage: Debug {
+    /// Returns the saved cache, `None` when nothing was saved yet.
+    fn load(&self) -> Result<Option<String>>;
+
+    /// Replaces the saved cache by `contents`.
+    fn save(&self, contents: &str) -> Result<()>;
+}
+
+/// Keeps a cache in a single file, by default `target/cache/hunspell-rs`.
+/// The directories are created when the cache is saved.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FileStorage {
+    path: PathBuf,
+}
+
+impl FileStorage {
+    pub const DEFAULT_PATH: &'static str = "target/cache/hunspell-rs";
+
+    pub fn new<P: AsRef<Path>>(path: P) -> Self {
+        FileStorage {
+            path: path.as_ref().to_path_buf(),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Default for FileStorage {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_PATH)
+    }
+}
+
+impl CacheStorage for FileStorage {
+    fn load(&self) -> Result<Option<String>> {
+        match std::fs::read_to_string(&self.path) {
+            Ok(contents) => Ok(Some(contents)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(Error::io(Operation::Load, &self.path)(e)),
+        }
+    }
+
+    fn save(&self, contents: &str) -> Result<()> {
+        if let Some(dir) = self.path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir).map_err(Error::io(Operation::Save, dir))?;
+        }
+        // written next to the cache and renamed, so an interrupted run
+        // does not leave half a cache
+        let partial = self.path.with_extension("partial");
+        std::fs::write(&partial, contents).map_err(Error::io(Operation::Save, &partial))?;
+        std::fs::rename(&partial, &self.path).map_err(Error::io(Operation::Save, &self.path))?;
+        Ok(())
+    }
+}
+
+/// The reports of files by their path and a hash of their contents, for
+/// one dictionary. See [`TextChecker::report_file_cached()`].
+///
+/// The fingerprint is a hash of the
+/// [`Dictionary::fingerprint()`](crate::Dictionary::fingerprint), the
+/// runtime words, ignored words and replacements of the checker and the
+/// settings of the [`TextChecker`], like its ignore rules, so a cache saved
+/// for other dictionary data or settings is started over. A cache that
+/// cannot be read is started over too.
+///
+/// ```
+/// use hunspell_rs::{FileStorage, ResultCache, SpellChecker, TextChecker};
+///
+/// let spell = SpellChecker::new("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic").unwrap();
+/// let checker = TextChecker::new(spell);
+/// let path = std::env::temp_dir().join(format!("hunspell-rs-doc-cache-{}", std::process::id()));
+/// let mut cache = ResultCache::open(FileStorage::new(&path), &checker).unwrap();
+/// let report = checker.report_file_cached("README.md", &mut cache).unwrap();
+/// cache.save().unwrap();
+///
+/// let mut cache = ResultCache::open(FileStorage::new(&path), &checker).unwrap();
+/// assert_eq!(report, checker.report_file_cached("README.md", &mut cache).unwrap());
+/// assert_eq!((1, 0), (cache.hits(), cache.misses()));
+/// # std::fs::remove_file(path).unwrap();
+/// ```
+#[derive(Debug)]
+pub struct ResultCache<S: CacheStorage = FileStorage> {
+    storage: S,
+    fingerprint: u64,
+    entries: HashMap<PathBuf, Entry>,
+    changed: bool,
+    hits: usize,
+    misses: usize,
+}
+
+/// A cached file report.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Entry {
+    hash: u64,
+    words: usize,
+    misspellings: Vec<Misspelling>,
+}
+
+impl<S: CacheStorage> ResultCache<S> {
+    /// Loads the cache of `storage` for the dictionary and the settings of
+    /// `checker`; a cache saved with other settings starts over. Fails
+    /// when the storage or a file of the dictionary cannot be read.
+    pub fn open(storage: S, checker: &TextChecker) -> Result<Self> {
+        let fingerprint = fingerprint(checker)?;
+        let mut cache = ResultCache {
+            storage,
+            fingerprint,
+            entries: HashMap::new(),
+            changed: false,
+            hits: 0,
+            misses: 0,
+        };
+        match cache.storage.load()?.and_then(|c| parse(&c, fingerprint)) {
+            Some(entries) => cache.entries = entries,
+            None => cache.changed = true,
+        }
+        Ok(cache)
+    }
+
+    pub fn storage(&self) -> &S {
+        &self.storage
+    }
+
+    /// Returns the fingerprint of the dictionary and settings the reports
+    /// are for.
+    pub fn fingerprint(&self) -> u64 {
+        self.fingerprint
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// The number of reports that were found in the cache.
+    pub fn hits(&self) -> usize {
+        self.hits
+    }
+
+    /// The number of files that had to be checked.
+    pub fn misses(&self) -> usize {
+        self.misses
+    }
+
+    /// Forgets all reports.
+    pub fn clear(&mut self) {
+        self.changed |= !self.entries.is_empty();
+        self.entries.clear();
+    }
+
+    /// Forgets the reports of files that do not exist anymore.
+    pub fn prune(&mut self) {
+        let before = self.entries.len();
+        self.entries.retain(|path, _| path.exists());
+        self.changed |= self.entries.len() != before;
+    }
+
+    /// Writes the cache to its storage, if it changed.
+    pub fn save(&mut self) -> Result<()> {
+        if !self.changed {
+            return Ok(());
+        }
+        let mut paths: Vec<&PathBuf> = self.entries.keys().collect();
+        paths.sort();
+        let mut contents = format!("{HEADER} {:016x}\n", self.fingerprint);
+        for path in paths {
+            let entry = &self.entries[path];
+            // only paths that can be written on a line are cached
+            let path = path.to_str().expect("checked when inserted");
+            contents.push_str(&format!("{:016x}\t{}\t{path}\n", entry.hash, entry.words));
+            for m in &entry.misspellings {
+                contents.push_str(&format!(
+                    "\t{}\t{}\t{}\n",
+                    m.range.start, m.range.end, m.word
+                ));
+            }
+        }
+        self.storage.save(&contents)?;
+        self.changed = false;
+        Ok(())
+    }
+
+    fn get(&mut self, path: &Path, hash: u64) -> Option<FileReport> {
+        let entry = self.entries.get(path).filter(|e| e.hash == hash)?;
+        self.hits += 1;
+        Some(FileReport {
+            path: path.to_path_buf(),
+            words: entry.words,
+            misspellings: entry.misspellings.clone(),
+        })
+    }
+
+    fn insert(&mut self, hash: u64, report: &FileReport) {
+        self.misses += 1;
+        let writable = |s: &str| !s.contains(['\n', '\t', '\r']);
+        if !report.path.to_str().is_some_and(writable)
+            || !report.misspellings.iter().all(|m| writable(&m.word))
+        {
+            return;
+        }
+        self.entries.insert(
+            report.path.clone(),
+            Entry {
+                hash,
+                words: report.words,
+                misspellings: report.misspellings.clone(),
+            },
+        );
+        self.changed = true;
+    }
+}
+
+impl TextChecker {
+    /// Like [`report_file()`](TextChecker::report_file), but returns the
+    /// report of `cache` when the file did not change since it was
+    /// checked. Call [`ResultCache::save()`] when all files are checked.
+    pub fn report_file_cached<P, S>(
+        &self,
+        path: P,
+        cache: &mut ResultCache<S>,
+    ) -> Result<FileReport>
+    where
+        P: AsRef<Path>,
+        S: CacheStorage,
+    {
+        let text = std::fs::read_to_string(path.as_ref())
+            .map_err(Error::io(Operation::Load, path.as_ref()))?;
+        let hash = stable_hash(text.as_bytes());
+        if let Some(report) = cache.get(path.as_ref(), hash) {
+            return Ok(report);
+        }
+        let report = self.report(path, &text)?;
+        cache.insert(hash, &report);
+        Ok(report)
+    }
+}
+
+/// Reads the entries of a saved cache, `None` when it is for another
+/// fingerprint or cannot be read.
+fn parse(contents: &str, fingerprint: u64) -> Option<HashMap<PathBuf, Entry>> {
+    let mut lines = contents.lines();
+    if lines.next()? != format!("{HEADER} {fingerprint:016x}") {
+        return None;
+    }
+    let mut entries = HashMap::new();
+    let mut current: Option<(PathBuf, Entry)> = None;
+    for line in lines {
+        if let Some(misspelling) = line.strip_prefix('\t') {
+            let mut fields = misspelling.splitn(3, '\t');
+            let start = fields.next()?.parse().ok()?;
+            let end = fields.next()?.parse().ok()?;
+            let word = fields.next()?.to_string();
+            current.as_mut()?.1.misspellings.push(Misspelling {
+                word,
+                range: start..end,
+            });
+            continue;
+        }
+        let mut fields = line.splitn(3, '\t');
+        let hash = u64::from_str_radix(fields.next()?, 16).ok()?;
+        let words = fields.next()?.parse().ok()?;
+        let path = PathBuf::from(fields.next()?);
+        entries.extend(current.replace((
+            path,
+            Entry {
+                hash,
+                words,
+                misspellings: Vec::new(),
+            },
+        )));
+    }
+    entries.extend(current);
+    Some(entries)
+}
+
+/// Returns a hash of everything of `checker` that decides what it
+/// reports, that stays the same between runs and builds.
+fn fingerprint(checker: &TextChecker) -> Result<u64> {
+    let spell = checker.spell_checker();
+    let mut hasher = StableHasher::default();
+    let mut field = |bytes: &[u8]| hasher.write_field(bytes);
+    field(&spell.state().dictionary().fingerprint()?.to_le_bytes());
+    // the lengths and tags keep the words of different lists and kinds
+    // apart
+    field(&(spell.runtime_words.len() as u64).to_le_bytes());
+    for word in &spell.runtime_words {
+        match word {
+            RuntimeWord::Word(word) => {
+                field(b"w");
+                field(word.as_bytes());
+            }
+            RuntimeWord::WithAffix { word, example } => {
+                field(b"a");
+                field(word.as_bytes());
+                field(example.as_bytes());
+            }
+            RuntimeWord::Removed(word) => {
+                field(b"-");
+                field(word.as_bytes());
+            }
+        }
+    }
+    let mut ignored: Vec<String> = spell.ignored.borrow().iter().cloned().collect();
+    ignored.sort();
+    field(&(ignored.len() as u64).to_le_bytes());
+    for word in ignored {
+        field(word.as_bytes());
+    }
+    let replacements = spell.replacements.borrow();
+    field(&(replacements.len() as u64).to_le_bytes());
+    for (from, to) in replacements.iter() {
+        field(from.as_bytes());
+        field(to.as_bytes());
+    }
+    field(format!("{:?}", spell.normalization).as_bytes());
+    field(format!("{:?}", spell.input_policy).as_bytes());
+    checker.write_settings(&mut field);
+    Ok(hasher.finish())
+}
+
+/// Returns the [`StableHasher`] hash of `bytes`.
+pub(crate) fn stable_hash(bytes: &[u8]) -> u64 {
+    let mut hasher = StableHasher::default();
+    hasher.write(bytes);
+    hasher.finish()
+}
+
+/// 64-bit FNV-1a, a hash that is the same on every run and platform,
+/// unlike the `DefaultHasher` of the standard library. Only for bytes
+/// written with `write()`: the integers of `Hash` impls are written in the
+/// byte order of the platform.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct StableHasher(u64);
+
+impl Default for StableHasher {
+    fn default() -> Self {
+        StableHasher(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl StableHasher {
+    /// Writes the length of `bytes` and then `bytes`, so that fields that
+    /// follow each other cannot be confused.
+    pub(crate) fn write_field(&mut self, bytes: &[u8]) {
+        self.write(&(bytes.len() as u64).to_le_bytes());
+        self.write(bytes);
+    }
+}
+
+impl Hasher for StableHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.0 = (self.0 ^ u64::from(b)).wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
@@ -0,0 +1,275 @@
+//! Memoizing `check()` and `suggest()`, for repetitive texts.
+
+use std::{
+    cell::{Cell, RefCell},
+    collections::{BTreeMap, HashMap},
+};
+
+use crate::{Result, SpellChecker};
+
+/// A [`SpellChecker`] that keeps the results of `check()` and `suggest()`
+/// of the most recently used words.
+///
+/// Words added, removed or ignored through the `CachedSpellChecker` clear
+/// the cache. Changes made through [`spell_checker()`] are not seen, call
+/// [`clear()`] after them.
+///
+/// ```
+/// use hunspell_rs::{CachedSpellChecker, SpellChecker};
+///
+/// let spell = SpellChecker::new("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic").unwrap();
+/// let cached = CachedSpellChecker::new(spell, 1000);
+/// assert_eq!(Ok(true), cached.check("cats"));
+/// assert_eq!(Ok(true), cached.check("cats"));
+/// assert_eq!(1, cached.stats().hits);
+/// ```
+///
+/// [`spell_checker()`]: CachedSpellChecker::spell_checker
+/// [`clear()`]: CachedSpellChecker::clear
+#[derive(Debug)]
+pub struct CachedSpellChecker {
+    spell_checker: SpellChecker,
+    checks: RefCell<Lru<bool>>,
+    suggestions: RefCell<Lru<Vec<String>>>,
+    stats: Cell<CacheStats>,
+}
+
+/// How often a [`CachedSpellChecker`] could answer from its cache.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+impl CacheStats {
+    /// Returns the share of the calls answered from the cache, 0 when
+    /// there were none.
+    pub fn hit_rate(&self) -> f64 {
+        match self.hits + self.misses {
+            0 => 0.0,
+            total => self.hits as f64 / total as f64,
+        }
+    }
+}
+
+impl CachedSpellChecker {
+    /// Keeps the results of at most `capacity` words, for `check()` and
+    /// `suggest()` each.
+    pub fn new(spell_checker: SpellChecker, capacity: usize) -> Self {
+        CachedSpellChecker {
+            spell_checker,
+            checks: RefCell::new(Lru::new(capacity)),
+            suggestions: RefCell::new(Lru::new(capacity)),
+            stats: Cell::new(CacheStats::default()),
+        }
+    }
+
+    pub fn spell_checker(&self) -> &SpellChecker {
+        &self.spell_checker
+    }
+
+    pub fn into_inner(self) -> SpellChecker {
+        self.spell_checker
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.checks.borrow().capacity
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        self.stats.get()
+    }
+
+    pub fn reset_stats(&self) {
+        self.stats.set(CacheStats::default());
+    }
+
+    /// Forgets all cached results, the statistics are kept.
+    pub fn clear(&self) {
+        self.checks.borrow_mut().clear();
+        self.suggestions.borrow_mut().clear();
+    }
+
+    /// See [`SpellChecker::check()`].
+    pub fn check<S: AsRef<str>>(&self, word: S) -> Result<bool> {
+        let word = word.as_ref();
+        if let Some(correct) = self.checks.borrow_mut().get(word) {
+            self.count(true);
+            return Ok(*correct);
+        }
+        self.count(false);
+        let correct = self.spell_checker.check(word)?;
+        self.checks.borrow_mut().insert(word, correct);
+        Ok(correct)
+    }
+
+    /// See [`SpellChecker::suggest()`].
+    pub fn suggest<S: AsRef<str>>(&self, word: S) -> Result<Vec<String>> {
+        let word = word.as_ref();
+        if let Some(suggestions) = self.suggestions.borrow_mut().get(word) {
+            self.count(true);
+            return Ok(suggestions.clone());
+        }
+        self.count(false);
+        let suggestions = self.spell_checker.suggest(word)?;
+        self.suggestions
+            .borrow_mut()
+            .insert(word, suggestions.clone());
+        Ok(suggestions)
+    }
+
+    /// Returns at most `limit` suggestions for `word` from `offset` on,
+    /// for "more suggestions" buttons. The suggestions are made once and
+    /// cached like those of `suggest()`, the next pages come from the
+    /// cache.
+    ///
+    /// ```
+    /// use hunspell_rs::{CachedSpellChecker, SpellChecker};
+    ///
+    /// let spell = SpellChecker::new("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic").unwrap();
+    /// let cached = CachedSpellChecker::new(spell, 100);
+    /// let page = cached.suggest_page("catsz", 0, 1).unwrap();
+    /// assert_eq!(vec!["cats"], page.suggestions);
+    /// assert!(page.has_more());
+    /// let page = cached.suggest_page("catsz", 1, 1).unwrap();
+    /// assert_eq!(vec!["cat"], page.suggestions);
+    /// assert!(!page.has_more());
+    /// assert_eq!(1, cached.stats().hits);
+    /// ```
+    pub fn suggest_page<S: AsRef<str>>(
+        &self,
+        word: S,
+        offset: usize,
+        limit: usize,
+    ) -> Result<SuggestionPage> {
+        let word = word.as_ref();
+        let page = |suggestions: &[String]| SuggestionPage {
+            suggestions: suggestions
+                .iter()
+                .skip(offset)
+                .take(limit)
+                .cloned()
+                .collect(),
+            offset,
+            total: suggestions.len(),
+        };
+        if let Some(suggestions) = self.suggestions.borrow_mut().get(word) {
+            self.count(true);
+            return Ok(page(suggestions));
+        }
+        self.count(false);
+        let suggestions = self.spell_checker.suggest(word)?;
+        let result = page(&suggestions);
+        self.suggestions.borrow_mut().insert(word, suggestions);
+        Ok(result)
+    }
+
+    /// See [`SpellChecker::add()`], clears the cache when the word is new.
+    pub fn add<S: AsRef<str>>(&mut self, word: S) -> Result<()> {
+        if self.spell_checker.contains_added(word.as_ref()) {
+            return Ok(());
+        }
+        self.clear();
+        self.spell_checker.add(word)
+    }
+
+    /// See [`SpellChecker::remove()`], clears the cache.
+    pub fn remove<S: AsRef<str>>(&mut self, word: S) -> Result<()> {
+        self.clear();
+        self.spell_checker.remove(word)
+    }
+
+    /// See [`SpellChecker::ignore()`], clears the cache.
+    pub fn ignore<S: AsRef<str>>(&self, word: S) {
+        self.clear();
+        self.spell_checker.ignore(word)
+    }
+
+    fn count(&self, hit: bool) {
+        let mut stats = self.stats.get();
+        if hit {
+            stats.hits += 1;
+        } else {
+            stats.misses += 1;
+        }
+        self.stats.set(stats);
+    }
+}
+
+/// A part of the suggestions for a word, see
+/// [`CachedSpellChecker::suggest_page()`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct SuggestionPage {
+    pub suggestions: Vec<String>,
+    /// The position of the first suggestion of the page.
+    pub offset: usize,
+    /// The number of suggestions on all pages.
+    pub total: usize,
+}
+
+impl SuggestionPage {
+    /// Returns true if there are suggestions after this page.
+    pub fn has_more(&self) -> bool {
+        self.offset + self.suggestions.len() < self.total
+    }
+}
+
+/// Values by word, the least recently used out first.
+#[derive(Debug)]
+pub(crate) struct Lru<V> {
+    pub(crate) capacity: usize,
+    tick: u64,
+    entries: HashMap<String, (V, u64)>,
+    // the words by the tick of their last use
+    order: BTreeMap<u64, String>,
+}
+
+impl<V> Lru<V> {
+    pub(crate) fn new(capacity: usize) -> Self {
+        Lru {
+            capacity,
+            tick: 0,
+            entries: HashMap::new(),
+            order: BTreeMap::new(),
+        }
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.entries.clear();
+        self.order.clear();
+    }
+
+    /// The entries, least recently used first, so that inserting them in
+    /// this order into another `Lru` keeps the order.
+    #[cfg(feature = "snapshot")]
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (&str, &V)> {
+        self.order
+            .values()
+            .map(|word| (word.as_str(), &self.entries[word].0))
+    }
+
+    pub(crate) fn get(&mut self, word: &str) -> Option<&V> {
+        self.tick += 1;
+        let (value, used) = self.entries.get_mut(word)?;
+        let word = self.order.remove(used).expect("ordered entry");
+        *used = self.tick;
+        self.order.insert(self.tick, word);
+        Some(value)
+    }
+
+    pub(crate) fn insert(&mut self, word: &str, value: V) {
+        if self.capacity == 0 {
+            return;
+        }
+        self.tick += 1;
+        if let Some((_, used)) = self.entries.remove(word) {
+            self.order.remove(&used);
+        } else if self.entries.len() >= self.capacity {
+            if let Some((_, oldest)) = self.order.pop_first() {
+                self.entries.remove(&oldest);
+            }
+        }
+        self.entries.insert(word.to_string(), (value, self.tick));
+        self.order.insert(self.tick, word.to_string());
+    }
+}
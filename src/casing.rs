@@ -0,0 +1,68 @@
+/// The capitalization pattern of a word.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Casing {
+    /// No uppercase letters, i.e. `cat`.
+    Lower,
+    /// Only the first letter is uppercase, i.e. `Cat`.
+    Title,
+    /// All letters are uppercase, i.e. `CAT`. A single uppercase letter
+    /// counts as `Title`.
+    Upper,
+    /// Any other pattern, i.e. `iPhone`.
+    Mixed,
+}
+
+impl Casing {
+    /// Returns the capitalization pattern of `word`, only letters are
+    /// taken into account.
+    pub fn of(word: &str) -> Casing {
+        let mut letters = word.chars().filter(|c| c.is_alphabetic());
+        let Some(first) = letters.next() else {
+            return Casing::Lower;
+        };
+        let (mut upper, mut lower) = (0usize, 0usize);
+        for c in letters {
+            if c.is_uppercase() {
+                upper += 1;
+            } else if c.is_lowercase() {
+                lower += 1;
+            }
+        }
+        match (first.is_uppercase(), upper, lower) {
+            (false, 0, _) => Casing::Lower,
+            (true, 0, _) => Casing::Title,
+            (true, _, 0) => Casing::Upper,
+            _ => Casing::Mixed,
+        }
+    }
+
+    /// Applies the pattern to `word`. `Lower` and `Mixed` leave it as it is,
+    /// since the word might have capitals of its own.
+    pub fn apply(&self, word: &str) -> String {
+        match self {
+            Casing::Upper => word.to_uppercase(),
+            Casing::Title => {
+                let mut chars = word.chars();
+                match chars.next() {
+                    Some(first) => first.to_uppercase().chain(chars).collect(),
+                    None => String::new(),
+                }
+            }
+            Casing::Lower | Casing::Mixed => word.to_string(),
+        }
+    }
+}
+
+/// Returns `suggestion` with the capitalization of the `original` word
+/// restored, for replacing a misspelled word with a suggestion.
+///
+/// ```
+/// use hunspell_rs::apply_suggestion;
+///
+/// assert_eq!("Cats", apply_suggestion("Cast", "cats"));
+/// assert_eq!("NO CATS", apply_suggestion("NOCATS", "no cats"));
+/// assert_eq!("Paris", apply_suggestion("pariss", "Paris"));
+/// ```
+pub fn apply_suggestion(original: &str, suggestion: &str) -> String {
+    Casing::of(original).apply(suggestion)
+}
@@ -0,0 +1,201 @@
+//! Checking words with options for a single call.
+
+use std::borrow::Cow;
+
+use crate::{normalization::typographic, text::identifier_parts, Casing, Result, SpellChecker};
+
+/// Options for [`SpellChecker::check_with()`]. They only apply to one
+/// call, so a shared checker can be used with different options at the
+/// same time. For suggestions see [`SuggestOptions`](crate::SuggestOptions).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct CheckOptions {
+    /// Which capitalizations of a word are accepted.
+    pub case: CasePolicy,
+    /// Replaces typographic apostrophes and hyphens and removes soft
+    /// hyphens and zero width characters before checking, like
+    /// [`Normalization::typographic`](crate::Normalization::typographic)
+    /// for this call only.
+    pub normalize: bool,
+    /// Accepts words with a digit, like `mp3`, `v2` or `10km`, for
+    /// technical text.
+    pub digits: bool,
+    /// Accepts words made of up to this many correct words run together,
+    /// like `catsprogram` for 2, each of at least [`MIN_COMPOUND_PART`]
+    /// characters. 0 and 1 accept no compounds besides those of the
+    /// dictionary.
+    pub compound_parts: usize,
+    /// Accepts a hyphenated word when each of its parts is correct on its
+    /// own, like `cat-program`.
+    pub split_hyphens: bool,
+    /// Accepts a `camelCase`, `PascalCase` or `snake_case` identifier when
+    /// each of its parts is correct on its own, like `catProgram`.
+    pub identifiers: bool,
+}
+
+/// The fewest characters of a part of a compound that
+/// [`CheckOptions::compound_parts`] accepts, like the default
+/// `COMPOUNDMIN` of hunspell.
+pub const MIN_COMPOUND_PART: usize = 3;
+
+/// Which capitalizations of a word [`SpellChecker::check_with()`] accepts.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum CasePolicy {
+    /// As the dictionary says: `Cat` and `CAT` for `cat`, but not `paris`
+    /// for `Paris`.
+    #[default]
+    Dictionary,
+    /// Any capitalization of a correct word, i.e. also `paris`.
+    Insensitive,
+    /// Only the capitalization of the dictionary: `cat`, but not `Cat` or
+    /// `CAT`, for identifiers and other text where case matters.
+    Strict,
+    /// As the dictionary says, and any correct word in all capitals, i.e.
+    /// also `KEEPCASE` words, for headlines and labels of user interfaces.
+    AllowAllCaps,
+}
+
+impl SpellChecker {
+    /// Like `check()`, with `options` for this call only.
+    ///
+    /// ```
+    /// use hunspell_rs::{CheckOptions, SpellChecker};
+    ///
+    /// let spell = SpellChecker::new("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic").unwrap();
+    /// let options = CheckOptions { normalize: true, ..Default::default() };
+    /// assert_eq!(Ok(true), spell.check_with("ca\u{ad}ts", &options));
+    /// assert_eq!(Ok(false), spell.check("ca\u{ad}ts"));
+    ///
+    /// let technical = CheckOptions {
+    ///     digits: true,
+    ///     compound_parts: 3,
+    ///     split_hyphens: true,
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(Ok(true), spell.check_with("mp3", &technical));
+    /// assert_eq!(Ok(true), spell.check_with("catprograms", &technical));
+    /// assert_eq!(Ok(false), spell.check_with("catprogramz", &technical));
+    /// ```
+    pub fn check_with<S>(&self, word: S, options: &CheckOptions) -> Result<bool>
+    where
+        S: AsRef<str>,
+    {
+        let word = if options.normalize {
+            typographic(word.as_ref())
+        } else {
+            Cow::Borrowed(word.as_ref())
+        };
+        if options.digits && word.contains(|c: char| c.is_numeric()) {
+            return Ok(true);
+        }
+        if self.accepts(&word, options)? {
+            return Ok(true);
+        }
+        if options.identifiers {
+            let parts = identifier_parts(&word);
+            if parts.len() > 1 {
+                for part in parts {
+                    if !self.accepts(&word[part], options)? {
+                        return Ok(false);
+                    }
+                }
+                return Ok(true);
+            }
+        }
+        if options.split_hyphens && word.contains('-') {
+            let mut parts = word.split('-').filter(|p| !p.is_empty()).peekable();
+            if parts.peek().is_some() {
+                for part in parts {
+                    if !self.accepts(part, options)? {
+                        return Ok(false);
+                    }
+                }
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    /// Like `check()`, with the capitalizations `case` accepts, see
+    /// [`check_with()`](SpellChecker::check_with).
+    ///
+    /// ```
+    /// use hunspell_rs::{CasePolicy, SpellChecker};
+    ///
+    /// let spell = SpellChecker::new("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic").unwrap();
+    /// assert_eq!(Ok(true), spell.check_cased("CAT", CasePolicy::Dictionary));
+    /// assert_eq!(Ok(false), spell.check_cased("CAT", CasePolicy::Strict));
+    /// assert_eq!(Ok(true), spell.check_cased("cAt", CasePolicy::Insensitive));
+    /// ```
+    pub fn check_cased<S>(&self, word: S, case: CasePolicy) -> Result<bool>
+    where
+        S: AsRef<str>,
+    {
+        self.check_with(
+            word,
+            &CheckOptions {
+                case,
+                ..Default::default()
+            },
+        )
+    }
+
+    /// Checks `word` with the case policy of `options`, and as a compound
+    /// when they allow it.
+    fn accepts(&self, word: &str, options: &CheckOptions) -> Result<bool> {
+        if self.accepts_case(word, options.case)? {
+            return Ok(true);
+        }
+        if options.compound_parts < 2 {
+            return Ok(false);
+        }
+        // the fewest parts the word up to each character splits in
+        let bounds: Vec<usize> = word
+            .char_indices()
+            .map(|(i, _)| i)
+            .chain(std::iter::once(word.len()))
+            .collect();
+        let mut fewest = vec![usize::MAX; bounds.len()];
+        fewest[0] = 0;
+        for end in MIN_COMPOUND_PART..bounds.len() {
+            for start in 0..=end - MIN_COMPOUND_PART {
+                if fewest[start] >= options.compound_parts || fewest[start] + 1 >= fewest[end] {
+                    continue;
+                }
+                if self.accepts_case(&word[bounds[start]..bounds[end]], options.case)? {
+                    fewest[end] = fewest[start] + 1;
+                }
+            }
+        }
+        Ok(fewest[bounds.len() - 1] <= options.compound_parts)
+    }
+
+    fn accepts_case(&self, word: &str, case: CasePolicy) -> Result<bool> {
+        let correct = self.check(word)?;
+        match case {
+            CasePolicy::Dictionary => Ok(correct),
+            CasePolicy::Strict => Ok(correct && !self.is_accepted_by_casing(word)?),
+            CasePolicy::AllowAllCaps if !correct && Casing::of(word) == Casing::Upper => {
+                self.has_correct_casing(word)
+            }
+            CasePolicy::Insensitive if !correct => self.has_correct_casing(word),
+            _ => Ok(correct),
+        }
+    }
+
+    /// Returns true if the lowercase or title case form of `word` is
+    /// correct.
+    fn has_correct_casing(&self, word: &str) -> Result<bool> {
+        let lower = word.to_lowercase();
+        Ok(self.check(&lower)? || self.check(Casing::Title.apply(&lower))?)
+    }
+
+    /// Returns true if hunspell accepts `word` because of a form with
+    /// fewer capitals in the dictionary, like `Cat` and `CAT` for `cat`.
+    fn is_accepted_by_casing(&self, word: &str) -> Result<bool> {
+        match Casing::of(word) {
+            Casing::Title => self.check(word.to_lowercase()),
+            Casing::Upper => self.has_correct_casing(word),
+            Casing::Lower | Casing::Mixed => Ok(false),
+        }
+    }
+}
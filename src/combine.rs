@@ -0,0 +1,178 @@
+//! Spellers made of other spellers, see [`Speller::and()`],
+//! [`Speller::or()`] and [`Speller::not_in()`].
+
+use crate::{Result, Speller};
+
+/// Accepts the words both spellers accept, see [`Speller::and()`].
+///
+/// Suggestions, stems and analyses come from the first speller, leaving
+/// out suggestions the second one rejects. Words are added to and
+/// removed from both spellers.
+#[derive(Debug, Clone)]
+pub struct And<A, B> {
+    first: A,
+    second: B,
+}
+
+/// Accepts the words either speller accepts, see [`Speller::or()`].
+///
+/// Suggestions, stems and analyses of the first speller are followed by
+/// those of the second, leaving out duplicates. Words are added to and
+/// removed from both spellers.
+#[derive(Debug, Clone)]
+pub struct Or<A, B> {
+    first: A,
+    second: B,
+}
+
+/// Accepts the words the first speller accepts and the second one does
+/// not, see [`Speller::not_in()`].
+///
+/// Suggestions, stems and analyses come from the first speller, leaving
+/// out forbidden suggestions. Words are added to and removed from the
+/// first speller only, so a forbidden word stays rejected.
+#[derive(Debug, Clone)]
+pub struct NotIn<A, B> {
+    allowed: A,
+    forbidden: B,
+}
+
+impl<A, B> And<A, B> {
+    pub(crate) fn new(first: A, second: B) -> Self {
+        And { first, second }
+    }
+
+    pub fn into_inner(self) -> (A, B) {
+        (self.first, self.second)
+    }
+}
+
+impl<A, B> Or<A, B> {
+    pub(crate) fn new(first: A, second: B) -> Self {
+        Or { first, second }
+    }
+
+    pub fn into_inner(self) -> (A, B) {
+        (self.first, self.second)
+    }
+}
+
+impl<A, B> NotIn<A, B> {
+    pub(crate) fn new(allowed: A, forbidden: B) -> Self {
+        NotIn { allowed, forbidden }
+    }
+
+    pub fn into_inner(self) -> (A, B) {
+        (self.allowed, self.forbidden)
+    }
+}
+
+impl<A: Speller, B: Speller> Speller for And<A, B> {
+    fn check(&self, word: &str) -> Result<bool> {
+        Ok(self.first.check(word)? && self.second.check(word)?)
+    }
+
+    fn suggest(&self, word: &str) -> Result<Vec<String>> {
+        let mut suggestions = Vec::new();
+        for suggestion in self.first.suggest(word)? {
+            if self.second.check(&suggestion)? {
+                suggestions.push(suggestion);
+            }
+        }
+        Ok(suggestions)
+    }
+
+    fn stem(&self, word: &str) -> Result<Vec<String>> {
+        self.first.stem(word)
+    }
+
+    fn analyze(&self, word: &str) -> Result<Vec<String>> {
+        self.first.analyze(word)
+    }
+
+    fn add(&mut self, word: &str) -> Result<()> {
+        self.first.add(word)?;
+        self.second.add(word)
+    }
+
+    fn remove(&mut self, word: &str) -> Result<()> {
+        self.first.remove(word)?;
+        self.second.remove(word)
+    }
+}
+
+impl<A: Speller, B: Speller> Speller for Or<A, B> {
+    fn check(&self, word: &str) -> Result<bool> {
+        Ok(self.first.check(word)? || self.second.check(word)?)
+    }
+
+    fn suggest(&self, word: &str) -> Result<Vec<String>> {
+        Ok(concat(
+            self.first.suggest(word)?,
+            self.second.suggest(word)?,
+        ))
+    }
+
+    fn stem(&self, word: &str) -> Result<Vec<String>> {
+        Ok(concat(self.first.stem(word)?, self.second.stem(word)?))
+    }
+
+    fn analyze(&self, word: &str) -> Result<Vec<String>> {
+        Ok(concat(
+            self.first.analyze(word)?,
+            self.second.analyze(word)?,
+        ))
+    }
+
+    fn add(&mut self, word: &str) -> Result<()> {
+        self.first.add(word)?;
+        self.second.add(word)
+    }
+
+    fn remove(&mut self, word: &str) -> Result<()> {
+        self.first.remove(word)?;
+        self.second.remove(word)
+    }
+}
+
+impl<A: Speller, B: Speller> Speller for NotIn<A, B> {
+    fn check(&self, word: &str) -> Result<bool> {
+        Ok(self.allowed.check(word)? && !self.forbidden.check(word)?)
+    }
+
+    fn suggest(&self, word: &str) -> Result<Vec<String>> {
+        let mut suggestions = Vec::new();
+        for suggestion in self.allowed.suggest(word)? {
+            if !self.forbidden.check(&suggestion)? {
+                suggestions.push(suggestion);
+            }
+        }
+        Ok(suggestions)
+    }
+
+    fn stem(&self, word: &str) -> Result<Vec<String>> {
+        self.allowed.stem(word)
+    }
+
+    fn analyze(&self, word: &str) -> Result<Vec<String>> {
+        self.allowed.analyze(word)
+    }
+
+    fn add(&mut self, word: &str) -> Result<()> {
+        self.allowed.add(word)
+    }
+
+    fn remove(&mut self, word: &str) -> Result<()> {
+        self.allowed.remove(word)
+    }
+}
+
+/// Appends the words of `second` that are not in `first`.
+fn concat(mut first: Vec<String>, second: Vec<String>) -> Vec<String> {
+    for s in second {
+        if !first.contains(&s) {
+            first.push(s);
+        }
+    }
+    first
+}
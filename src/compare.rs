@@ -0,0 +1,78 @@
+//! Finding where two spellers disagree.
+
+use crate::{Result, Speller};
+
+/// A word for which two spellers gave different results.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Divergence {
+    Check {
+        word: String,
+        left: bool,
+        right: bool,
+    },
+    Suggest {
+        word: String,
+        left: Vec<String>,
+        right: Vec<String>,
+    },
+    Stem {
+        word: String,
+        left: Vec<String>,
+        right: Vec<String>,
+    },
+}
+
+/// Runs `check()`, `suggest()` and `stem()` for every word on both
+/// spellers and returns the differences. Use it to validate a switch to
+/// another [`Speller`] implementation, or another version of a
+/// dictionary, before relying on it.
+///
+/// ```
+/// use hunspell_rs::{compare_spellers, Divergence, SpellChecker};
+///
+/// let old = SpellChecker::new("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic").unwrap();
+/// let mut new = SpellChecker::new("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic").unwrap();
+/// new.add("octonasaurius").unwrap();
+/// let divergences = compare_spellers(&old, &new, ["cats", "octonasaurius"]).unwrap();
+/// assert_eq!(
+///     Divergence::Check { word: "octonasaurius".to_string(), left: false, right: true },
+///     divergences[0]
+/// );
+/// ```
+pub fn compare_spellers<L, R, I, S>(left: &L, right: &R, words: I) -> Result<Vec<Divergence>>
+where
+    L: Speller + ?Sized,
+    R: Speller + ?Sized,
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let mut divergences = Vec::new();
+    for word in words {
+        let word = word.as_ref();
+        let (l, r) = (left.check(word)?, right.check(word)?);
+        if l != r {
+            divergences.push(Divergence::Check {
+                word: word.to_string(),
+                left: l,
+                right: r,
+            });
+        }
+        let (l, r) = (left.suggest(word)?, right.suggest(word)?);
+        if l != r {
+            divergences.push(Divergence::Suggest {
+                word: word.to_string(),
+                left: l,
+                right: r,
+            });
+        }
+        let (l, r) = (left.stem(word)?, right.stem(word)?);
+        if l != r {
+            divergences.push(Divergence::Stem {
+                word: word.to_string(),
+                left: l,
+                right: r,
+            });
+        }
+    }
+    Ok(divergences)
+}
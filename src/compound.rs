@@ -0,0 +1,102 @@
+//! Compound words split into their parts by the morphological analysis.
+
+use crate::{Result, SpellChecker};
+
+/// A part of a compound word, parsed from an analysis of hunspell like
+/// ` pa:foot st:foot po:noun pa:ball st:ball po:noun`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CompoundSegment {
+    /// The part as it is written in the word, `pa:` in the analysis.
+    pub surface: String,
+    /// The stem of the part, `st:` in the analysis, or else the surface.
+    pub stem: String,
+    /// The other fields of the part, i.e. `("po", "noun")`. Fields without
+    /// a name have an empty name.
+    pub tags: Vec<(String, String)>,
+}
+
+impl CompoundSegment {
+    /// Parses one `analysis` of `word` as returned by
+    /// [`SpellChecker::analyze()`]. A word that is not a compound has one
+    /// segment, with `word` as its surface.
+    pub fn parse(word: &str, analysis: &str) -> Vec<CompoundSegment> {
+        let mut segments: Vec<CompoundSegment> = Vec::new();
+        let mut leading = Vec::new();
+        for field in analysis.split_whitespace() {
+            let (name, value) = match field.split_once(':') {
+                Some((name, value)) if name.len() == 2 => (name, value),
+                _ => ("", field),
+            };
+            if name == "pa" {
+                segments.push(CompoundSegment {
+                    surface: value.to_string(),
+                    stem: String::new(),
+                    tags: Vec::new(),
+                });
+                continue;
+            }
+            match segments.last_mut() {
+                None => leading.push((name, value)),
+                Some(segment) if name == "st" && segment.stem.is_empty() => {
+                    segment.stem = value.to_string()
+                }
+                Some(segment) => segment.tags.push((name.to_string(), value.to_string())),
+            }
+        }
+        // fields before the first part belong to it, or to the whole word
+        // when it is not a compound
+        if segments.is_empty() {
+            segments.push(CompoundSegment {
+                surface: word.to_string(),
+                stem: String::new(),
+                tags: Vec::new(),
+            });
+        }
+        let first = &mut segments[0];
+        let mut tags = Vec::new();
+        for (name, value) in leading {
+            if name == "st" && first.stem.is_empty() {
+                first.stem = value.to_string();
+            } else {
+                tags.push((name.to_string(), value.to_string()));
+            }
+        }
+        tags.append(&mut first.tags);
+        first.tags = tags;
+        for segment in &mut segments {
+            if segment.stem.is_empty() {
+                segment.stem.clone_from(&segment.surface);
+            }
+        }
+        segments
+    }
+}
+
+impl SpellChecker {
+    /// Like `analyze()`, with every analysis split into the parts of the
+    /// compound. Needs a dictionary with compounding and morphological
+    /// fields; other words have a single segment.
+    ///
+    /// ```
+    /// use hunspell_rs::SpellChecker;
+    ///
+    /// let spell = SpellChecker::new(
+    ///     "tests/fixtures/compound/compound.aff",
+    ///     "tests/fixtures/compound/compound.dic",
+    /// )
+    /// .unwrap();
+    /// let analyses = spell.analyze_compound("footballs").unwrap();
+    /// let stems: Vec<&str> = analyses[0].iter().map(|s| s.stem.as_str()).collect();
+    /// assert_eq!(vec!["foot", "ball"], stems);
+    /// ```
+    pub fn analyze_compound<S>(&self, word: S) -> Result<Vec<Vec<CompoundSegment>>>
+    where
+        S: AsRef<str>,
+    {
+        Ok(self
+            .analyze(word.as_ref())?
+            .iter()
+            .map(|analysis| CompoundSegment::parse(word.as_ref(), analysis))
+            .collect())
+    }
+}
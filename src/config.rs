@@ -0,0 +1,401 @@
+use std::{
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    discovery::find_dictionary, Error, Html, Latex, Markdown, Operation, Result, SpellChecker,
+    TextChecker,
+};
+
+/// How to open a [`SpellChecker`], for configuration driven setups.
+///
+/// ```
+/// use hunspell_rs::{Config, SpellChecker};
+///
+/// let config = Config {
+///     dictionary: "tests/fixtures/reduced".to_string(),
+///     extra_dictionaries: vec!["tests/fixtures/extra.dic".into()],
+///     ..Default::default()
+/// };
+/// let spell = SpellChecker::try_from(&config).unwrap();
+/// assert_eq!(Ok(true), spell.check("systemdunits"));
+/// ```
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Config {
+    /// Either the base path of the dictionary, the path without the
+    /// `.aff`/`.dic` extension, or the language tag of an installed
+    /// dictionary (see [`available_dictionaries()`](crate::available_dictionaries)).
+    pub dictionary: String,
+    /// Additional dictionaries, see [`SpellChecker::add_dictionary()`].
+    pub extra_dictionaries: Vec<PathBuf>,
+    /// The key of an encrypted dictionary.
+    pub key: Option<String>,
+    /// Words accepted in every text, see [`SpellChecker::ignore()`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub ignore_words: Vec<String>,
+    /// The syntax of the texts for a [`TextChecker`]: `plain` (the
+    /// default), `markdown`, `html` or `latex`.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub syntax: Option<String>,
+}
+
+impl Config {
+    /// Reads a project configuration like `spellcheck.toml`, see
+    /// [`parse()`](Config::parse).
+    pub fn read<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Self::parse(&std::fs::read_to_string(&path).map_err(Error::io(Operation::Load, &path))?)
+    }
+
+    /// Parses a configuration in TOML. Only top level keys with strings
+    /// and arrays of strings are supported, which is all a configuration
+    /// needs:
+    ///
+    /// ```toml
+    /// # spellcheck.toml
+    /// language = "en_US"          # or dictionary = "dicts/en_US"
+    /// extra_dictionaries = ["project.dic"]
+    /// ignore_words = ["hunspell", "rustc"]
+    /// syntax = "markdown"
+    /// ```
+    ///
+    /// Fails with `Error::InvalidConfig` for other TOML, unknown keys and
+    /// when the dictionary is missing. All lines are read, when more than
+    /// one is wrong the error is `Error::Multiple` with all of them.
+    pub fn parse(toml: &str) -> Result<Self> {
+        let mut config = Config::default();
+        let mut problems = Problems::default();
+        let mut lines = toml.lines().enumerate();
+        while let Some((n, line)) = lines.next() {
+            let line_number = format!("line {}", n + 1);
+            let invalid = |reason: &str| Error::InvalidConfig(format!("{line_number}: {reason}"));
+            if let Err(e) = config.parse_line(line, &mut lines, invalid) {
+                problems.push(&line_number, e);
+            }
+        }
+        if config.dictionary.is_empty() {
+            problems.push(
+                "dictionary",
+                Error::InvalidConfig("no dictionary or language".to_string()),
+            );
+        }
+        problems.into_result()?;
+        Ok(config)
+    }
+
+    /// Sets the key of `line`, taking the next of `lines` for arrays that
+    /// span lines.
+    fn parse_line<'a, L, F>(&mut self, line: &str, lines: &mut L, invalid: F) -> Result<()>
+    where
+        L: Iterator<Item = (usize, &'a str)>,
+        F: Fn(&str) -> Error,
+    {
+        let line = strip_comment(line);
+        if line.is_empty() {
+            return Ok(());
+        }
+        let (key, value) = line
+            .split_once('=')
+            .ok_or_else(|| invalid("expected key = value"))?;
+        let mut value = value.trim().to_string();
+        // arrays can span lines
+        while value.starts_with('[') && !value.ends_with(']') {
+            let (_, next) = lines.next().ok_or_else(|| invalid("unclosed array"))?;
+            value.push(' ');
+            value.push_str(strip_comment(next));
+        }
+        let string = || parse_string(&value).ok_or_else(|| invalid("expected a string"));
+        let strings = || parse_array(&value).ok_or_else(|| invalid("expected an array"));
+        match key.trim().trim_matches('"') {
+            "dictionary" | "language" => self.dictionary = string()?,
+            "extra_dictionaries" => {
+                self.extra_dictionaries = strings()?.into_iter().map(PathBuf::from).collect()
+            }
+            "key" => self.key = Some(string()?),
+            "ignore_words" => self.ignore_words = strings()?,
+            "syntax" => self.syntax = Some(string()?),
+            key => return Err(invalid(&format!("unknown key {key}"))),
+        }
+        Ok(())
+    }
+
+    /// Makes a configuration from the environment:
+    ///
+    /// - `SPELLCHECK_DICTIONARY`, or else the language of `LANG`
+    /// - `SPELLCHECK_EXTRA_DICTIONARIES`, paths separated like `PATH`
+    /// - `SPELLCHECK_IGNORE_WORDS`, separated by whitespace or commas
+    /// - `SPELLCHECK_SYNTAX`
+    ///
+    /// Installed dictionaries are also searched in `DICPATH`, see
+    /// [`search_paths()`](crate::search_paths).
+    pub fn from_env() -> Result<Self> {
+        let var = |name| std::env::var(name).ok().filter(|v| !v.is_empty());
+        let dictionary = var("SPELLCHECK_DICTIONARY")
+            .or_else(|| {
+                var("LANG")
+                    .and_then(|lang| lang.split(['.', '@']).next().map(str::to_string))
+                    .filter(|lang| !lang.is_empty() && lang != "C" && lang != "POSIX")
+            })
+            .ok_or_else(|| {
+                Error::InvalidConfig("no dictionary in SPELLCHECK_DICTIONARY or LANG".to_string())
+            })?;
+        Ok(Config {
+            dictionary,
+            extra_dictionaries: std::env::var_os("SPELLCHECK_EXTRA_DICTIONARIES")
+                .map(|paths| std::env::split_paths(&paths).collect())
+                .unwrap_or_default(),
+            key: None,
+            ignore_words: var("SPELLCHECK_IGNORE_WORDS")
+                .map(|words| {
+                    words
+                        .split(|c: char| c == ',' || c.is_whitespace())
+                        .filter(|w| !w.is_empty())
+                        .map(str::to_string)
+                        .collect()
+                })
+                .unwrap_or_default(),
+            syntax: var("SPELLCHECK_SYNTAX"),
+        })
+    }
+
+    /// Returns the affix and dictionary paths for `dictionary`.
+    pub fn resolve(&self) -> Result<(PathBuf, PathBuf)> {
+        let base = PathBuf::from(&self.dictionary);
+        let (affix, dictionary) = base_paths(&base);
+        if affix.is_file() || base.components().count() > 1 {
+            return Ok((affix, dictionary));
+        }
+        find_dictionary(&self.dictionary)
+            .map(|info| (info.aff_path, info.dic_path))
+            .ok_or_else(|| Error::DictionaryNotFound(self.dictionary.clone()))
+    }
+}
+
+/// The problems of a configuration, with what they are about.
+#[derive(Debug, Default)]
+struct Problems(Vec<(String, Error)>);
+
+impl Problems {
+    fn push<S: AsRef<str>>(&mut self, item: S, error: Error) {
+        match error {
+            Error::Multiple(problems) => self.0.extend(problems),
+            error => self.0.push((item.as_ref().to_string(), error)),
+        }
+    }
+
+    /// Returns the only problem, or `Error::Multiple` with all of them.
+    fn into_result(mut self) -> Result<()> {
+        match self.0.len() {
+            0 => Ok(()),
+            1 => Err(self.0.remove(0).1),
+            _ => Err(Error::Multiple(self.0)),
+        }
+    }
+}
+
+/// Returns `line` without a `#` comment and surrounding whitespace.
+fn strip_comment(line: &str) -> &str {
+    let mut quote = None;
+    let mut escaped = false;
+    for (i, c) in line.char_indices() {
+        match (quote, c) {
+            (Some('"'), '\\') if !escaped => {
+                escaped = true;
+                continue;
+            }
+            (Some(q), c) if c == q && !escaped => quote = None,
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '#') => return line[..i].trim(),
+            _ => {}
+        }
+        escaped = false;
+    }
+    line.trim()
+}
+
+/// Parses a basic (`"..."`) or literal (`'...'`) TOML string.
+fn parse_string(value: &str) -> Option<String> {
+    if let Some(literal) = value.strip_prefix('\'') {
+        return literal
+            .strip_suffix('\'')
+            .filter(|s| !s.contains('\''))
+            .map(str::to_string);
+    }
+    let inner = value.strip_prefix('"')?.strip_suffix('"')?;
+    let mut string = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => string.push(match chars.next()? {
+                'n' => '\n',
+                't' => '\t',
+                c @ ('"' | '\\') => c,
+                _ => return None,
+            }),
+            '"' => return None,
+            c => string.push(c),
+        }
+    }
+    Some(string)
+}
+
+/// Parses an array of strings, a trailing comma is allowed.
+fn parse_array(value: &str) -> Option<Vec<String>> {
+    let inner = value.strip_prefix('[')?.strip_suffix(']')?.trim();
+    let mut strings = Vec::new();
+    let mut rest = inner;
+    while !rest.is_empty() {
+        let quote = rest.chars().next()?;
+        if quote != '"' && quote != '\'' {
+            return None;
+        }
+        // the end of the string: the next unescaped quote
+        let mut end = None;
+        let mut escaped = false;
+        for (i, c) in rest.char_indices().skip(1) {
+            if c == quote && !escaped {
+                end = Some(i);
+                break;
+            }
+            escaped = quote == '"' && c == '\\' && !escaped;
+        }
+        let end = end?;
+        strings.push(parse_string(&rest[..=end])?);
+        rest = rest[end + 1..].trim_start();
+        rest = rest.strip_prefix(',').unwrap_or(rest).trim_start();
+    }
+    Some(strings)
+}
+
+/// Returns the affix and dictionary paths for a base path.
+fn base_paths(base: &Path) -> (PathBuf, PathBuf) {
+    let mut affix = base.as_os_str().to_owned();
+    affix.push(".aff");
+    let mut dictionary = base.as_os_str().to_owned();
+    dictionary.push(".dic");
+    (affix.into(), dictionary.into())
+}
+
+impl TryFrom<&Config> for SpellChecker {
+    type Error = Error;
+
+    /// Opens the dictionary with the extra dictionaries of `config`. When
+    /// more than one of them cannot be opened, the error is
+    /// `Error::Multiple` with every dictionary that failed.
+    fn try_from(config: &Config) -> Result<Self> {
+        let mut problems = Problems::default();
+        let opened = config
+            .resolve()
+            .and_then(|(affix, dictionary)| match &config.key {
+                Some(key) => SpellChecker::new_with_key(affix, dictionary, key),
+                None => SpellChecker::new(affix, dictionary),
+            });
+        let mut spell_checker = match opened {
+            Ok(spell_checker) => Some(spell_checker),
+            Err(e) => {
+                problems.push(&config.dictionary, e);
+                None
+            }
+        };
+        for d in &config.extra_dictionaries {
+            let path = d.to_string_lossy();
+            let added = match &mut spell_checker {
+                Some(spell_checker) => spell_checker.add_dictionary(d),
+                // only what can be known without the dictionary
+                None if !d.is_file() => Err(Error::DictionaryFileIsNoFile(path.to_string())),
+                None => Ok(true),
+            };
+            match added {
+                Ok(true) => {}
+                Ok(false) => problems.push(
+                    &path,
+                    Error::DictionaryRejected(format!("hunspell could not add {path}")),
+                ),
+                Err(e) => problems.push(&path, e),
+            }
+        }
+        problems.into_result()?;
+        let spell_checker = spell_checker.expect("no problems, so it was opened");
+        for word in &config.ignore_words {
+            spell_checker.ignore(word);
+        }
+        Ok(spell_checker)
+    }
+}
+
+impl TryFrom<&Config> for TextChecker {
+    type Error = Error;
+
+    /// Opens the spell checker and sets the syntax filter of `config`.
+    /// When more than one thing is wrong, the error is `Error::Multiple`
+    /// with all of them.
+    fn try_from(config: &Config) -> Result<Self> {
+        let mut problems = Problems::default();
+        let syntax = config.syntax.as_deref().unwrap_or("plain");
+        if !["plain", "markdown", "html", "latex"].contains(&syntax) {
+            problems.push(
+                "syntax",
+                Error::InvalidConfig(format!("unknown syntax {syntax}")),
+            );
+        }
+        let spell_checker = SpellChecker::try_from(config);
+        if let Err(e) = &spell_checker {
+            problems.push(&config.dictionary, e.clone());
+        }
+        problems.into_result()?;
+        let checker = TextChecker::new(spell_checker?);
+        Ok(match syntax {
+            "markdown" => checker.with_syntax_filter(Markdown),
+            "html" => checker.with_syntax_filter(Html),
+            "latex" => checker.with_syntax_filter(Latex),
+            _ => checker,
+        })
+    }
+}
+
+impl SpellChecker {
+    /// Opens the spell checker of a project configuration, see
+    /// [`Config::read()`].
+    ///
+    /// ```
+    /// use hunspell_rs::SpellChecker;
+    ///
+    /// let spell = SpellChecker::from_config("tests/fixtures/spellcheck.toml").unwrap();
+    /// assert_eq!(Ok(true), spell.check("systemdunits"));
+    /// assert_eq!(Ok(true), spell.check("rustc"));
+    /// ```
+    pub fn from_config<P: AsRef<Path>>(path: P) -> Result<Self> {
+        SpellChecker::try_from(&Config::read(path)?)
+    }
+
+    /// Opens the spell checker configured by environment variables, see
+    /// [`Config::from_env()`].
+    pub fn from_env() -> Result<Self> {
+        SpellChecker::try_from(&Config::from_env()?)
+    }
+}
+
+impl TryFrom<(&Path, &Path)> for SpellChecker {
+    type Error = Error;
+
+    /// Opens the `(affix, dictionary)` pair.
+    fn try_from((affix, dictionary): (&Path, &Path)) -> Result<Self> {
+        SpellChecker::new(affix, dictionary)
+    }
+}
+
+impl FromStr for SpellChecker {
+    type Err = Error;
+
+    /// Opens the dictionary at a base path, i.e.
+    /// `/usr/share/hunspell/en_US` opens `en_US.aff` and `en_US.dic` in
+    /// `/usr/share/hunspell`.
+    fn from_str(s: &str) -> Result<Self> {
+        let (affix, dictionary) = base_paths(Path::new(s));
+        SpellChecker::new(affix, dictionary)
+    }
+}
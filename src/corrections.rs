@@ -0,0 +1,248 @@
+//! Large tables of known corrections, looked up before hunspell is asked
+//! for suggestions.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Debug,
+    sync::Arc,
+};
+
+use crate::{apply_suggestion, SpellChecker};
+
+/// A table of misspellings and their corrections, set with
+/// [`SpellChecker::set_correction_table()`].
+///
+/// It is implemented for `HashMap` and `BTreeMap`, and by
+/// [`CompactCorrections`] for tables too large to keep in a map.
+pub trait CorrectionTable: Debug + Send + Sync {
+    /// Returns the correction of `word`, as it is spelled.
+    fn correction(&self, word: &str) -> Option<&str>;
+}
+
+impl CorrectionTable for HashMap<String, String> {
+    fn correction(&self, word: &str) -> Option<&str> {
+        self.get(word).map(String::as_str)
+    }
+}
+
+impl CorrectionTable for BTreeMap<String, String> {
+    fn correction(&self, word: &str) -> Option<&str> {
+        self.get(word).map(String::as_str)
+    }
+}
+
+/// A read-only [`CorrectionTable`] for hundreds of thousands of pairs,
+/// looked up with a perfect hash: one probe per word.
+///
+/// All words are kept in a single string, with about 14 bytes per pair
+/// for the index, where a `HashMap<String, String>` needs two allocations
+/// and more than 60 bytes.
+///
+/// ```
+/// use hunspell_rs::{CompactCorrections, CorrectionTable};
+///
+/// let table = CompactCorrections::new([("teh", "the"), ("recieve", "receive")]);
+/// assert_eq!(Some("receive"), table.correction("recieve"));
+/// assert_eq!(None, table.correction("receive"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CompactCorrections {
+    seed: u64,
+    // per bucket of words, what places them in the slots
+    displacements: Vec<u32>,
+    // per slot, the index of a pair or EMPTY
+    slots: Vec<u32>,
+    // the pairs one after the other, misspelling then correction
+    text: String,
+    // the bounds of the words in text, pair i is from bounds[2i] to
+    // bounds[2i + 1] to bounds[2i + 2]
+    bounds: Vec<u32>,
+}
+
+const EMPTY: u32 = u32::MAX;
+/// The average number of words in a bucket.
+const BUCKET_SIZE: usize = 4;
+/// A bucket that cannot be placed with this many displacements makes
+/// the table start over with another seed.
+const MAX_DISPLACEMENT: u32 = 1 << 20;
+
+impl CompactCorrections {
+    /// Builds the table. When a misspelling is given more than once, its
+    /// last correction is kept.
+    ///
+    /// **Panics** if the words take more than 4 GiB.
+    pub fn new<I, S>(pairs: I) -> Self
+    where
+        I: IntoIterator<Item = (S, S)>,
+        S: AsRef<str>,
+    {
+        let mut text = String::new();
+        let mut bounds = vec![0];
+        let offset = |text: &String| u32::try_from(text.len()).expect("table too large");
+        for (from, to) in pairs {
+            text.push_str(from.as_ref());
+            bounds.push(offset(&text));
+            text.push_str(to.as_ref());
+            bounds.push(offset(&text));
+        }
+        let mut table = CompactCorrections {
+            seed: 0,
+            displacements: Vec::new(),
+            slots: Vec::new(),
+            text,
+            bounds,
+        };
+        let mut pairs: Vec<u32> = (0..table.pairs() as u32).collect();
+        // the last one of equal misspellings wins
+        pairs.sort_by(|a, b| table.from(*a).cmp(table.from(*b)).then(b.cmp(a)));
+        pairs.dedup_by(|a, b| table.from(*a) == table.from(*b));
+        while !table.place(&pairs) {
+            table.seed += 1;
+        }
+        table
+    }
+
+    /// Returns the number of pairs, including those of misspellings that
+    /// were given again.
+    fn pairs(&self) -> usize {
+        self.bounds.len() / 2
+    }
+
+    fn from(&self, pair: u32) -> &str {
+        let i = pair as usize * 2;
+        &self.text[self.bounds[i] as usize..self.bounds[i + 1] as usize]
+    }
+
+    fn to(&self, pair: u32) -> &str {
+        let i = pair as usize * 2;
+        &self.text[self.bounds[i + 1] as usize..self.bounds[i + 2] as usize]
+    }
+
+    /// Returns the number of misspellings.
+    pub fn len(&self) -> usize {
+        self.slots.iter().filter(|&&s| s != EMPTY).count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Puts the `pairs` in slots, the largest buckets first. Returns false
+    /// when a bucket could not be placed with the seed.
+    fn place(&mut self, pairs: &[u32]) -> bool {
+        let buckets = pairs.len().div_ceil(BUCKET_SIZE).max(1);
+        self.displacements = vec![0; buckets];
+        // a few free slots keep the last buckets quick to place
+        self.slots = vec![EMPTY; pairs.len() + pairs.len() / 64 + 1];
+        let mut by_bucket: Vec<Vec<(u32, u64)>> = vec![Vec::new(); buckets];
+        for &pair in pairs {
+            let hash = hash(self.from(pair).as_bytes(), self.seed);
+            by_bucket[bucket(hash, buckets)].push((pair, hash));
+        }
+        let mut order: Vec<usize> = (0..buckets).collect();
+        order.sort_by_key(|&b| std::cmp::Reverse(by_bucket[b].len()));
+        let mut positions = Vec::new();
+        for b in order {
+            let words = &by_bucket[b];
+            if words.is_empty() {
+                break;
+            }
+            let placed = (0..MAX_DISPLACEMENT).find(|&d| {
+                positions.clear();
+                for &(_, hash) in words {
+                    let slot = slot(hash, d, self.slots.len());
+                    if self.slots[slot] != EMPTY || positions.contains(&slot) {
+                        return false;
+                    }
+                    positions.push(slot);
+                }
+                true
+            });
+            let Some(d) = placed else {
+                return false;
+            };
+            self.displacements[b] = d;
+            for (&(pair, _), &slot) in words.iter().zip(&positions) {
+                self.slots[slot] = pair;
+            }
+        }
+        true
+    }
+}
+
+impl CorrectionTable for CompactCorrections {
+    fn correction(&self, word: &str) -> Option<&str> {
+        let hash = hash(word.as_bytes(), self.seed);
+        let d = self.displacements[bucket(hash, self.displacements.len())];
+        let pair = self.slots[slot(hash, d, self.slots.len())];
+        (pair != EMPTY && self.from(pair) == word).then(|| self.to(pair))
+    }
+}
+
+/// FNV-1a, finished with the SplitMix64 mixer so all bits depend on all
+/// bytes.
+fn hash(bytes: &[u8], seed: u64) -> u64 {
+    let mut hash = 0xcbf2_9ce4_8422_2325 ^ seed;
+    for &b in bytes {
+        hash = (hash ^ u64::from(b)).wrapping_mul(0x0100_0000_01b3);
+    }
+    mix(hash)
+}
+
+fn mix(mut x: u64) -> u64 {
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    x ^ (x >> 31)
+}
+
+fn bucket(hash: u64, buckets: usize) -> usize {
+    ((hash >> 32) % buckets as u64) as usize
+}
+
+fn slot(hash: u64, displacement: u32, slots: usize) -> usize {
+    (mix(hash ^ u64::from(displacement).wrapping_mul(0x9e37_79b9_7f4a_7c15)) % slots as u64)
+        as usize
+}
+
+impl SpellChecker {
+    /// Looks misspellings up in `table` before hunspell is asked: its
+    /// correction leads the suggestions of `suggest()`, like those of
+    /// [`add_replacement()`](SpellChecker::add_replacement), and
+    /// `quick_suggest()` returns only the correction. Clones share the
+    /// table.
+    ///
+    /// ```
+    /// use hunspell_rs::{CompactCorrections, SpellChecker};
+    ///
+    /// let mut spell = SpellChecker::new("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic").unwrap();
+    /// spell.set_correction_table(CompactCorrections::new([("progarm", "program")]));
+    /// assert_eq!("Program", spell.suggest("Progarm").unwrap()[0]);
+    /// ```
+    pub fn set_correction_table<T: CorrectionTable + 'static>(&mut self, table: T) {
+        self.correction_table = Some(Arc::new(table));
+    }
+
+    /// Removes the table of
+    /// [`set_correction_table()`](SpellChecker::set_correction_table).
+    pub fn clear_correction_table(&mut self) {
+        self.correction_table = None;
+    }
+
+    pub fn correction_table(&self) -> Option<&dyn CorrectionTable> {
+        self.correction_table.as_deref()
+    }
+
+    /// Returns the correction of `word` from the correction table, trying
+    /// its lowercase form too, with the capitalization of `word`.
+    pub(crate) fn table_correction(&self, word: &str) -> Option<String> {
+        let table = self.correction_table.as_deref()?;
+        if let Some(to) = table.correction(word) {
+            return Some(to.to_string());
+        }
+        let lower = word.to_lowercase();
+        table
+            .correction(&lower)
+            .filter(|_| lower != word)
+            .map(|to| apply_suggestion(word, to))
+    }
+}
@@ -0,0 +1,248 @@
+//! Finding out which dictionary accepts a word, or why none does.
+
+use std::path::PathBuf;
+
+use crate::{
+    aff::{AffixFile, AffixOption, FlagDirective},
+    dic::DicFile,
+    Casing, Result, SpellChecker,
+};
+
+/// Where an accepted word was found, see [`SpellChecker::check_detailed()`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum WordSource {
+    /// The dictionary the checker was opened with.
+    Dictionary,
+    /// A dictionary added with `add_dictionary()`.
+    ExtraDictionary(PathBuf),
+    /// Added with `add()` or `add_with_affix()`, and in none of the
+    /// dictionaries.
+    Runtime,
+    /// Ignored with `ignore()`.
+    Ignored,
+}
+
+/// The result of [`SpellChecker::check_detailed()`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CheckDetails {
+    /// The same as `check()`.
+    pub correct: bool,
+    /// Everything that accepts the word, empty when it is not correct.
+    pub sources: Vec<WordSource>,
+}
+
+impl CheckDetails {
+    /// Returns true if the word is correct only because of extra
+    /// dictionaries, i.e. it is project jargon rather than standard
+    /// language.
+    pub fn only_extra(&self) -> bool {
+        self.correct
+            && !self.sources.is_empty()
+            && self
+                .sources
+                .iter()
+                .all(|s| matches!(s, WordSource::ExtraDictionary(_)))
+    }
+}
+
+/// Why a word is rejected, see [`SpellChecker::rejection_kind()`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum RejectionKind {
+    /// The dictionary marks the word as wrong with `FORBIDDENWORD`.
+    Forbidden,
+    /// The word is correct with another capitalization, i.e. `paris`.
+    Case { accepted: String },
+    /// The word is made of correct words that cannot be joined, i.e. a
+    /// missing space.
+    Compound { parts: Vec<String> },
+    /// The dictionary does not know the word.
+    Unknown,
+}
+
+impl SpellChecker {
+    /// Returns why `word` is rejected, or `None` when it is correct, so a
+    /// user interface can explain the problem. Reads the dictionary
+    /// files to find forbidden words, so it is meant for the words shown
+    /// to the user rather than for every word of a text.
+    ///
+    /// ```
+    /// use hunspell_rs::{RejectionKind, SpellChecker};
+    ///
+    /// let spell = SpellChecker::new("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic").unwrap();
+    /// assert_eq!(None, spell.rejection_kind("cats").unwrap());
+    /// assert_eq!(
+    ///     Some(RejectionKind::Case { accepted: "cats".to_string() }),
+    ///     spell.rejection_kind("cAts").unwrap()
+    /// );
+    /// assert_eq!(
+    ///     Some(RejectionKind::Compound { parts: vec!["cat".to_string(), "programs".to_string()] }),
+    ///     spell.rejection_kind("catprograms").unwrap()
+    /// );
+    /// assert_eq!(Some(RejectionKind::Unknown), spell.rejection_kind("catz").unwrap());
+    /// ```
+    pub fn rejection_kind<S>(&self, word: S) -> Result<Option<RejectionKind>>
+    where
+        S: AsRef<str>,
+    {
+        let word = word.as_ref();
+        if self.check(word)? {
+            return Ok(None);
+        }
+        // encrypted dictionaries cannot be read, their words are never
+        // reported as forbidden
+        let affix = AffixFile::read(&self.affix).ok();
+        if let Some(affix) = &affix {
+            if self.has_flagged_form(affix, FlagDirective::ForbiddenWord, word) {
+                return Ok(Some(RejectionKind::Forbidden));
+            }
+        }
+        let lower = word.to_lowercase();
+        for accepted in [
+            lower.clone(),
+            Casing::Title.apply(&lower),
+            word.to_uppercase(),
+        ] {
+            if accepted != word && self.check(&accepted)? {
+                return Ok(Some(RejectionKind::Case { accepted }));
+            }
+        }
+        let min = affix
+            .as_ref()
+            .and_then(|a| a.setting(AffixOption::CompoundMin))
+            .and_then(|m| m.parse().ok())
+            .unwrap_or(3)
+            .max(1);
+        let chars: Vec<(usize, char)> = word.char_indices().collect();
+        for (n, (i, _)) in chars.iter().enumerate().skip(min) {
+            if chars.len() - n < min {
+                break;
+            }
+            let (left, right) = word.split_at(*i);
+            if self.check(left)? && self.check(right)? {
+                return Ok(Some(RejectionKind::Compound {
+                    parts: vec![left.to_string(), right.to_string()],
+                }));
+            }
+        }
+        Ok(Some(RejectionKind::Unknown))
+    }
+
+    /// Returns true if a dictionary has `word` with the `FORBIDDENWORD`
+    /// flag: the dictionary bans it, rather than not knowing it. Reads the
+    /// dictionary files; words of encrypted dictionaries are never
+    /// forbidden.
+    ///
+    /// ```
+    /// use hunspell_rs::SpellChecker;
+    ///
+    /// let spell = SpellChecker::new(
+    ///     "tests/fixtures/forbidden/forbidden.aff",
+    ///     "tests/fixtures/forbidden/forbidden.dic",
+    /// )
+    /// .unwrap();
+    /// assert_eq!(Ok(true), spell.is_forbidden("irregardless"));
+    /// assert_eq!(Ok(false), spell.is_forbidden("regardless"));
+    /// ```
+    pub fn is_forbidden<S>(&self, word: S) -> Result<bool>
+    where
+        S: AsRef<str>,
+    {
+        let Ok(affix) = AffixFile::read(&self.affix) else {
+            return Ok(false);
+        };
+        Ok(self.has_flagged_form(&affix, FlagDirective::ForbiddenWord, word.as_ref()))
+    }
+
+    /// Returns true if `word` is a form of a dictionary word with the
+    /// `WARN` flag: hunspell accepts it, but the dictionary marks it as
+    /// rare or deprecated, i.e. for a style checker. Reads the dictionary
+    /// files; words of encrypted dictionaries never have a warning.
+    ///
+    /// ```
+    /// use hunspell_rs::SpellChecker;
+    ///
+    /// let spell = SpellChecker::new(
+    ///     "tests/fixtures/forbidden/forbidden.aff",
+    ///     "tests/fixtures/forbidden/forbidden.dic",
+    /// )
+    /// .unwrap();
+    /// assert_eq!(Ok(true), spell.check("thru"));
+    /// assert_eq!(Ok(true), spell.is_warn("thru"));
+    /// assert_eq!(Ok(false), spell.is_warn("cats"));
+    /// ```
+    pub fn is_warn<S>(&self, word: S) -> Result<bool>
+    where
+        S: AsRef<str>,
+    {
+        let Ok(affix) = AffixFile::read(&self.affix) else {
+            return Ok(false);
+        };
+        Ok(self.has_flagged_form(&affix, FlagDirective::Warn, word.as_ref()))
+    }
+
+    /// Returns true if one of the dictionaries has an entry with the flag
+    /// of `directive` that is `word`, or makes it with its affixes.
+    fn has_flagged_form(&self, affix: &AffixFile, directive: FlagDirective, word: &str) -> bool {
+        let Some(flag) = affix.flag(directive) else {
+            return false;
+        };
+        let flag_type = affix.flag_type();
+        let encoding = self.encoding();
+        let word = self.input_conversions().apply(word);
+        std::iter::once(&self.dictionary)
+            .chain(&self.additional_dictionaries)
+            .filter_map(|dictionary| DicFile::read(dictionary, &encoding).ok())
+            .flat_map(|dic| dic.entries)
+            .any(|entry| {
+                entry.has_flag(flag, flag_type)
+                    && (entry.word == word || affix.expand(&entry).iter().any(|f| *f == word))
+            })
+    }
+
+    /// Checks `word` and returns which of the dictionaries accept it.
+    ///
+    /// To tell the dictionaries apart, every dictionary is opened once
+    /// more on its own, on first use. This takes as much memory as
+    /// the `SpellChecker` itself.
+    pub fn check_detailed<S>(&self, word: S) -> Result<CheckDetails>
+    where
+        S: AsRef<str>,
+    {
+        let word = word.as_ref();
+        if !self.check(word)? {
+            return Ok(CheckDetails {
+                correct: false,
+                sources: Vec::new(),
+            });
+        }
+        let mut sources = Vec::new();
+        if self.is_ignored(word) {
+            sources.push(WordSource::Ignored);
+        }
+        let mut source_checkers = self.source_checkers.borrow_mut();
+        if source_checkers.len() != self.additional_dictionaries.len() + 1 {
+            *source_checkers = std::iter::once(&self.dictionary)
+                .chain(&self.additional_dictionaries)
+                .map(|dictionary| match &self.key {
+                    Some(key) => SpellChecker::new_with_key(&self.affix, dictionary, key),
+                    None => SpellChecker::new(&self.affix, dictionary),
+                })
+                .collect::<Result<_>>()?;
+        }
+        for (i, checker) in source_checkers.iter().enumerate() {
+            if checker.check(word)? {
+                sources.push(match i {
+                    0 => WordSource::Dictionary,
+                    _ => WordSource::ExtraDictionary(self.additional_dictionaries[i - 1].clone()),
+                });
+            }
+        }
+        if sources.is_empty() {
+            sources.push(WordSource::Runtime);
+        }
+        Ok(CheckDetails {
+            correct: true,
+            sources,
+        })
+    }
+}
@@ -0,0 +1,215 @@
+//! Misspellings as diagnostics for code review tools, in SARIF and a
+//! compact JSON format.
+//!
+//! Both formats are written by hand, they only need strings, numbers and
+//! lists, which does not justify a JSON dependency.
+
+use std::{
+    fmt::Write,
+    ops::Range,
+    path::{Path, PathBuf},
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{Result, TextChecker};
+
+/// A misspelled word of a file with its suggestions, see
+/// [`TextChecker::diagnostics()`].
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Diagnostic {
+    pub path: PathBuf,
+    /// The byte range of the word in the file.
+    pub range: Range<usize>,
+    /// The line of the word, from 1.
+    pub line: usize,
+    /// The column of the word in characters, from 1.
+    pub column: usize,
+    pub word: String,
+    pub suggestions: Vec<String>,
+}
+
+impl Diagnostic {
+    /// I.e. ``unknown word `catz`, did you mean `cat`?``.
+    pub fn message(&self) -> String {
+        let mut message = format!("unknown word `{}`", self.word);
+        if let Some((last, rest)) = self.suggestions.split_last() {
+            message.push_str(", did you mean ");
+            for s in rest {
+                let _ = write!(message, "`{s}`, ");
+            }
+            if !rest.is_empty() {
+                message.truncate(message.len() - 2);
+                message.push_str(" or ");
+            }
+            let _ = write!(message, "`{last}`?");
+        }
+        message
+    }
+}
+
+/// The diagnostics of a set of files.
+///
+/// ```
+/// use hunspell_rs::{Diagnostics, SpellChecker, TextChecker};
+///
+/// let spell = SpellChecker::new("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic").unwrap();
+/// let checker = TextChecker::new(spell);
+/// let mut diagnostics = Diagnostics::new();
+/// diagnostics.extend(checker.diagnostics("a.txt", "cats\ncatz").unwrap());
+/// assert_eq!(
+///     r#"[{"file":"a.txt","range":[5,9],"line":2,"column":1,"message":"unknown word `catz`, did you mean `cat`?","suggestions":["cat"]}]"#,
+///     diagnostics.to_json()
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Diagnostics {
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl Diagnostics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, diagnostic: Diagnostic) {
+        self.diagnostics.push(diagnostic);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.diagnostics.is_empty()
+    }
+
+    /// Returns the diagnostics as a JSON array on one line, with an
+    /// object per misspelling with the `file`, the byte `range` as a pair,
+    /// the `line`, `column`, `message` and `suggestions`.
+    pub fn to_json(&self) -> String {
+        let mut json = String::from("[");
+        for (i, d) in self.diagnostics.iter().enumerate() {
+            if i > 0 {
+                json.push(',');
+            }
+            let _ = write!(
+                json,
+                r#"{{"file":{},"range":[{},{}],"line":{},"column":{},"message":{},"suggestions":{}}}"#,
+                json_string(&d.path.to_string_lossy()),
+                d.range.start,
+                d.range.end,
+                d.line,
+                d.column,
+                json_string(&d.message()),
+                json_strings(&d.suggestions)
+            );
+        }
+        json.push(']');
+        json
+    }
+
+    /// Returns the diagnostics as a SARIF 2.1.0 log with one run, for
+    /// code scanning services. Every misspelling is a `warning` of the rule
+    /// `misspelling`, with its region as line and column (in code points)
+    /// and as byte offset. The suggestions are in the `suggestions`
+    /// property of the result.
+    pub fn to_sarif(&self) -> String {
+        let mut results = String::new();
+        for (i, d) in self.diagnostics.iter().enumerate() {
+            if i > 0 {
+                results.push(',');
+            }
+            let _ = write!(
+                results,
+                r#"{{"ruleId":"misspelling","level":"warning","message":{{"text":{}}},"locations":[{{"physicalLocation":{{"artifactLocation":{{"uri":{}}},"region":{{"startLine":{},"startColumn":{},"endLine":{},"endColumn":{},"byteOffset":{},"byteLength":{}}}}}}}],"properties":{{"suggestions":{}}}}}"#,
+                json_string(&d.message()),
+                json_string(&uri(&d.path)),
+                d.line,
+                d.column,
+                d.line,
+                d.column + d.word.chars().count(),
+                d.range.start,
+                d.range.len(),
+                json_strings(&d.suggestions)
+            );
+        }
+        format!(
+            r#"{{"$schema":"https://json.schemastore.org/sarif-2.1.0.json","version":"2.1.0","runs":[{{"tool":{{"driver":{{"name":"hunspell-rs","version":{},"informationUri":"https://github.com/drahnr/hunspell-rs","rules":[{{"id":"misspelling","shortDescription":{{"text":"Misspelled word"}}}}]}}}},"columnKind":"unicodeCodePoints","results":[{results}]}}]}}"#,
+            json_string(env!("CARGO_PKG_VERSION"))
+        )
+    }
+}
+
+impl Extend<Diagnostic> for Diagnostics {
+    fn extend<I: IntoIterator<Item = Diagnostic>>(&mut self, iter: I) {
+        self.diagnostics.extend(iter);
+    }
+}
+
+impl TextChecker {
+    /// Checks `text`, the contents of `path`, and returns its misspellings
+    /// with their line, column and suggestions.
+    pub fn diagnostics<P: AsRef<Path>>(&self, path: P, text: &str) -> Result<Vec<Diagnostic>> {
+        let mut diagnostics = Vec::new();
+        for m in self.check_text(text)? {
+            let before = &text[..m.range.start];
+            let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+            diagnostics.push(Diagnostic {
+                path: path.as_ref().to_path_buf(),
+                line: before.matches('\n').count() + 1,
+                column: before[line_start..].chars().count() + 1,
+                suggestions: self.spell_checker().suggest(&m.word)?,
+                range: m.range,
+                word: m.word,
+            });
+        }
+        Ok(diagnostics)
+    }
+}
+
+/// Returns `s` as a JSON string, with quotes.
+fn json_string(s: &str) -> String {
+    let mut json = String::with_capacity(s.len() + 2);
+    json.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if c < ' ' => {
+                let _ = write!(json, "\\u{:04x}", c as u32);
+            }
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+/// Returns `strings` as a JSON array.
+fn json_strings(strings: &[String]) -> String {
+    let strings: Vec<String> = strings.iter().map(|s| json_string(s)).collect();
+    format!("[{}]", strings.join(","))
+}
+
+/// Returns `path` as a relative or absolute URI reference, with `/` as
+/// separator and other reserved characters percent encoded.
+fn uri(path: &Path) -> String {
+    let path = path.to_string_lossy().replace('\\', "/");
+    let mut uri = String::with_capacity(path.len());
+    if path.starts_with('/') {
+        uri.push_str("file://");
+    }
+    for b in path.bytes() {
+        match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' => {
+                uri.push(b as char)
+            }
+            b => {
+                let _ = write!(uri, "%{b:02X}");
+            }
+        }
+    }
+    uri
+}
@@ -0,0 +1,216 @@
+//! Reading and writing hunspell dictionary (`.dic`) files.
+//!
+//! Together with the [`aff`](crate::aff) module this makes it possible to
+//! generate dictionaries from Rust:
+//!
+//! ```
+//! use hunspell_rs::{aff::AffixFile, dic::{DicEntry, DicFile}};
+//!
+//! let aff = AffixFile::read("tests/fixtures/reduced.aff").unwrap();
+//! let encoding = aff.encoding().unwrap_or("UTF-8");
+//! let mut dic = DicFile::read("tests/fixtures/reduced.dic", encoding).unwrap();
+//! assert_eq!("cat", dic.entries[0].word);
+//! assert_eq!(vec!["S"], aff.flag_type().split(&dic.entries[0].flags));
+//! dic.entries.push(DicEntry::new("dog").with_flags("S"));
+//! assert!(dic.to_string().starts_with("3\ncat/S\n"));
+//! ```
+
+use std::path::Path;
+
+use crate::{
+    aff::{AffixFile, FlagDirective, FlagType},
+    encoding::Codec,
+    Error, Operation, Result,
+};
+
+/// The words of a dictionary file.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct DicFile {
+    pub entries: Vec<DicEntry>,
+}
+
+/// A line of a dictionary file, i.e. `cat/S po:noun`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct DicEntry {
+    pub word: String,
+    /// The flags after the `/`, split them with
+    /// [`FlagType::split()`](crate::aff::FlagType::split).
+    pub flags: String,
+    /// Morphological fields, like `po:noun`.
+    pub morph_fields: Vec<String>,
+}
+
+impl DicEntry {
+    pub fn new<S: AsRef<str>>(word: S) -> Self {
+        DicEntry {
+            word: word.as_ref().to_string(),
+            ..Default::default()
+        }
+    }
+
+    pub fn with_flags<S: AsRef<str>>(mut self, flags: S) -> Self {
+        self.flags = flags.as_ref().to_string();
+        self
+    }
+
+    pub fn with_morph_field<S: AsRef<str>>(mut self, field: S) -> Self {
+        self.morph_fields.push(field.as_ref().to_string());
+        self
+    }
+
+    /// Returns true if the entry has `flag`, written as `flag_type`.
+    pub fn has_flag(&self, flag: &str, flag_type: FlagType) -> bool {
+        flag_type.split(&self.flags).iter().any(|f| f == flag)
+    }
+
+    /// Parses a line of a dictionary file. A `/` in the word is escaped
+    /// as `\/`. The morphological fields start after a tab, or after a
+    /// space followed by a field like `po:`.
+    pub fn parse(line: &str) -> Self {
+        let (entry, morph) = match line.split_once('\t') {
+            Some((entry, morph)) => (entry, morph),
+            None => {
+                let morph_start = line.match_indices(' ').map(|(i, _)| i).find(|&i| {
+                    let field = &line.as_bytes()[i + 1..];
+                    field.len() > 3 && field[2] == b':'
+                });
+                match morph_start {
+                    Some(i) => (&line[..i], &line[i + 1..]),
+                    None => (line, ""),
+                }
+            }
+        };
+        let mut word = String::with_capacity(entry.len());
+        let mut flags = String::new();
+        let mut chars = entry.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => match chars.next() {
+                    Some('/') => word.push('/'),
+                    Some(c) => {
+                        word.push('\\');
+                        word.push(c);
+                    }
+                    None => word.push('\\'),
+                },
+                '/' => {
+                    flags = chars.collect();
+                    break;
+                }
+                c => word.push(c),
+            }
+        }
+        DicEntry {
+            word: word.trim_end().to_string(),
+            flags: flags.trim_end().to_string(),
+            morph_fields: morph.split_whitespace().map(str::to_string).collect(),
+        }
+    }
+}
+
+impl core::fmt::Display for DicEntry {
+    /// Writes the line of the entry, without a newline.
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        fmt.write_str(&self.word.replace('/', "\\/"))?;
+        if !self.flags.is_empty() {
+            write!(fmt, "/{}", self.flags)?;
+        }
+        for field in &self.morph_fields {
+            write!(fmt, " {field}")?;
+        }
+        Ok(())
+    }
+}
+
+impl DicFile {
+    /// Reads a dictionary file in `encoding`, the `SET` of its affix file.
+    pub fn read<P: AsRef<Path>>(path: P, encoding: &str) -> Result<Self> {
+        let contents = std::fs::read(&path).map_err(Error::io(Operation::Load, &path))?;
+        Self::parse(&Codec::for_name(encoding).decode(&contents)?)
+    }
+
+    /// Writes the dictionary file in `encoding`, the `SET` of its affix
+    /// file. Characters the encoding does not have become `?`.
+    pub fn write<P: AsRef<Path>>(&self, path: P, encoding: &str) -> Result<()> {
+        std::fs::write(&path, Codec::for_name(encoding).encode(&self.to_string()))
+            .map_err(Error::io(Operation::Save, &path))?;
+        Ok(())
+    }
+
+    /// The words that have the `FORBIDDENWORD` flag of `affix`.
+    pub fn forbidden_words<'a>(&'a self, affix: &AffixFile) -> Vec<&'a str> {
+        let Some(forbidden) = affix.flag(FlagDirective::ForbiddenWord) else {
+            return Vec::new();
+        };
+        let flag_type = affix.flag_type();
+        self.entries
+            .iter()
+            .filter(|entry| entry.has_flag(forbidden, flag_type))
+            .map(|entry| entry.word.as_str())
+            .collect()
+    }
+
+    /// Adds the entries of `other` that are not in this file yet, i.e. to
+    /// combine word lists for the same affix file. Like hunspell does
+    /// when it loads several dictionaries, a word that is forbidden in
+    /// either file (the `FORBIDDENWORD` flag of `affix`) is forbidden in
+    /// the result: its other entries are left out.
+    ///
+    /// ```
+    /// use hunspell_rs::{aff::AffixFile, dic::DicFile};
+    ///
+    /// let affix = AffixFile::parse("FORBIDDENWORD !\n").unwrap();
+    /// let mut dic = DicFile::parse("2\nirregardless\nregardless\n").unwrap();
+    /// dic.merge(&DicFile::parse("1\nirregardless/!\n").unwrap(), &affix);
+    /// assert_eq!("2\nregardless\nirregardless/!\n", dic.to_string());
+    /// ```
+    pub fn merge(&mut self, other: &DicFile, affix: &AffixFile) {
+        for entry in &other.entries {
+            if !self.entries.contains(entry) {
+                self.entries.push(entry.clone());
+            }
+        }
+        let Some(forbidden) = affix.flag(FlagDirective::ForbiddenWord) else {
+            return;
+        };
+        let flag_type = affix.flag_type();
+        let forbidden_words: Vec<String> = self
+            .forbidden_words(affix)
+            .into_iter()
+            .map(str::to_string)
+            .collect();
+        self.entries.retain(|entry| {
+            entry.has_flag(forbidden, flag_type) || !forbidden_words.contains(&entry.word)
+        });
+    }
+
+    /// Parses the contents of a dictionary file. The count on the first
+    /// line is not checked, hunspell only uses it to size its tables.
+    /// Fails with `Error::InvalidDictionaryFile` when the count is missing.
+    pub fn parse(contents: &str) -> Result<Self> {
+        let mut lines = contents.lines();
+        let count = lines.next().unwrap_or("").trim_start_matches('\u{feff}');
+        if count.trim().parse::<usize>().is_err() {
+            return Err(Error::InvalidDictionaryFile(
+                "line 1: the count of the words is missing".to_string(),
+            ));
+        }
+        Ok(DicFile {
+            entries: lines
+                .filter(|line| !line.trim().is_empty())
+                .map(DicEntry::parse)
+                .collect(),
+        })
+    }
+}
+
+impl core::fmt::Display for DicFile {
+    /// Writes the dictionary file, starting with the count.
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        writeln!(fmt, "{}", self.entries.len())?;
+        for entry in &self.entries {
+            writeln!(fmt, "{entry}")?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,269 @@
+//! A description of a dictionary that can be opened as a spell checker.
+
+use std::{
+    borrow::Cow,
+    fs::File,
+    hash::Hasher,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    aff::encoding_of, cache::StableHasher, encoding::Codec, memory::memory_file,
+    validate_dictionary, Error, Operation, Result, SpellChecker, ValidationLimits,
+};
+
+/// An affix and dictionary file pair with its key and extra dictionaries,
+/// without a hunspell handle. It is cheap to clone, can be sent to other
+/// threads and (with the `serde` feature) serialized, and is turned into
+/// as many [`SpellChecker`]s as needed with [`open()`](Dictionary::open).
+///
+/// ```
+/// use hunspell_rs::Dictionary;
+///
+/// let dictionary = Dictionary::from_files("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic")
+///     .with_additional_dictionary("tests/fixtures/extra.dic");
+/// dictionary.validate(&Default::default()).unwrap();
+/// let spell = dictionary.open().unwrap();
+/// assert_eq!(Ok(true), spell.check("systemdunits"));
+/// ```
+// Debug is manually implemented to keep the key out of logs.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct Dictionary {
+    source: DictionarySource,
+    key: Option<String>,
+    additional_dictionaries: Vec<PathBuf>,
+}
+
+/// Where the affix and dictionary of a [`Dictionary`] come from.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum DictionarySource {
+    Files {
+        affix: PathBuf,
+        dictionary: PathBuf,
+    },
+    /// The contents of the files, opened like
+    /// [`SpellChecker::from_memory()`].
+    Memory {
+        affix: Arc<[u8]>,
+        dictionary: Arc<[u8]>,
+    },
+}
+
+impl Dictionary {
+    pub fn from_files<P: AsRef<Path>>(affix: P, dictionary: P) -> Self {
+        Self::new(DictionarySource::Files {
+            affix: affix.as_ref().to_path_buf(),
+            dictionary: dictionary.as_ref().to_path_buf(),
+        })
+    }
+
+    /// From the contents of an affix and dictionary file, which are shared
+    /// by the clones.
+    pub fn from_bytes<B: Into<Arc<[u8]>>>(affix: B, dictionary: B) -> Self {
+        Self::new(DictionarySource::Memory {
+            affix: affix.into(),
+            dictionary: dictionary.into(),
+        })
+    }
+
+    fn new(source: DictionarySource) -> Self {
+        Dictionary {
+            source,
+            key: None,
+            additional_dictionaries: Vec::new(),
+        }
+    }
+
+    /// The key of a dictionary encrypted with hzip, see
+    /// [`SpellChecker::new_with_key()`].
+    pub fn with_key<S: AsRef<str>>(mut self, key: S) -> Self {
+        self.key = Some(key.as_ref().to_string());
+        self
+    }
+
+    /// Adds a dictionary file that uses the same affix file, see
+    /// [`SpellChecker::add_dictionary()`].
+    pub fn with_additional_dictionary<P: AsRef<Path>>(mut self, dictionary: P) -> Self {
+        self.additional_dictionaries
+            .push(dictionary.as_ref().to_path_buf());
+        self
+    }
+
+    pub fn source(&self) -> &DictionarySource {
+        &self.source
+    }
+
+    pub fn key(&self) -> Option<&str> {
+        self.key.as_deref()
+    }
+
+    pub fn additional_dictionaries(&self) -> &[PathBuf] {
+        &self.additional_dictionaries
+    }
+
+    /// Returns a hash of the contents of the affix file, the dictionary and
+    /// the additional dictionaries, and of the key. It is the same on every
+    /// run, build and platform, so caches, serialized checkers and workers
+    /// on other machines can tell whether they use the same dictionary
+    /// data, wherever the files are. Fails when a file cannot be read.
+    ///
+    /// ```
+    /// use hunspell_rs::Dictionary;
+    ///
+    /// let files = Dictionary::from_files("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic");
+    /// let bytes = Dictionary::from_bytes(
+    ///     std::fs::read("tests/fixtures/reduced.aff").unwrap(),
+    ///     std::fs::read("tests/fixtures/reduced.dic").unwrap(),
+    /// );
+    /// assert_eq!(files.fingerprint().unwrap(), bytes.fingerprint().unwrap());
+    /// ```
+    pub fn fingerprint(&self) -> Result<u64> {
+        let mut hasher = StableHasher::default();
+        hasher.write_field(&self.affix_contents()?);
+        hasher.write_field(&self.dictionary_contents()?);
+        for d in &self.additional_dictionaries {
+            hasher.write_field(&std::fs::read(d).map_err(Error::io(Operation::Load, d))?);
+        }
+        hasher.write_field(self.key.as_deref().unwrap_or_default().as_bytes());
+        Ok(hasher.finish())
+    }
+
+    /// Fails with `Error::FingerprintMismatch` when the
+    /// [`fingerprint()`](Dictionary::fingerprint) is not `expected`, i.e.
+    /// before trusting results that were made with another copy of the
+    /// dictionary.
+    pub fn check_fingerprint(&self, expected: u64) -> Result<()> {
+        let found = self.fingerprint()?;
+        if found != expected {
+            return Err(Error::FingerprintMismatch { expected, found });
+        }
+        Ok(())
+    }
+
+    /// Returns the `VERSION` of the affix file, `None` when it has none or
+    /// is encrypted.
+    pub fn version(&self) -> Result<Option<String>> {
+        if self.key.is_some() {
+            return Ok(None);
+        }
+        let affix = self.affix_contents()?;
+        let codec = Codec::for_name(&encoding_of(&affix));
+        Ok(codec.decode(&affix)?.lines().find_map(|line| {
+            let line = line.trim_start_matches('\u{feff}').trim();
+            let version = line.strip_prefix("VERSION")?;
+            (version.starts_with(char::is_whitespace)).then(|| version.trim().to_string())
+        }))
+    }
+
+    /// Returns the contents of the affix file.
+    pub(crate) fn affix_contents(&self) -> Result<Cow<'_, [u8]>> {
+        match &self.source {
+            DictionarySource::Files { affix, .. } => Ok(Cow::Owned(
+                std::fs::read(affix).map_err(Error::io(Operation::Load, affix))?,
+            )),
+            DictionarySource::Memory { affix, .. } => Ok(Cow::Borrowed(affix)),
+        }
+    }
+
+    /// Returns the contents of the dictionary file.
+    pub(crate) fn dictionary_contents(&self) -> Result<Cow<'_, [u8]>> {
+        match &self.source {
+            DictionarySource::Files { dictionary, .. } => Ok(Cow::Owned(
+                std::fs::read(dictionary).map_err(Error::io(Operation::Load, dictionary))?,
+            )),
+            DictionarySource::Memory { dictionary, .. } => Ok(Cow::Borrowed(dictionary)),
+        }
+    }
+
+    /// Checks the affix and dictionary with [`validate_dictionary()`],
+    /// without opening them with hunspell. Encrypted dictionaries cannot
+    /// be validated.
+    pub fn validate(&self, limits: &ValidationLimits) -> Result<()> {
+        let (affix, dictionary, _memory_files) = self.paths()?;
+        validate_dictionary(&affix, &dictionary, limits)?;
+        self.additional_dictionaries
+            .iter()
+            .try_for_each(|d| validate_dictionary(&affix, d, limits))
+    }
+
+    /// Opens a new `SpellChecker` with its own handle.
+    pub fn open(&self) -> Result<SpellChecker> {
+        self.open_loaded(&self.load()?)
+    }
+
+    /// Puts the contents of a dictionary from memory in memory files, which
+    /// the spell checkers opened with `open_loaded()` share.
+    pub(crate) fn load(&self) -> Result<LoadedDictionary> {
+        let (affix, dictionary, memory_files) = self.paths()?;
+        Ok(LoadedDictionary {
+            affix,
+            dictionary,
+            memory_files: memory_files.into_iter().map(Arc::new).collect(),
+        })
+    }
+
+    /// Opens a `SpellChecker` from the files of `loaded`, which was made by
+    /// `load()` of this dictionary.
+    pub(crate) fn open_loaded(&self, loaded: &LoadedDictionary) -> Result<SpellChecker> {
+        let LoadedDictionary {
+            affix, dictionary, ..
+        } = loaded;
+        let mut spell_checker = match &self.key {
+            Some(key) => SpellChecker::new_with_key(affix, dictionary, key)?,
+            None => SpellChecker::new(affix, dictionary)?,
+        };
+        spell_checker.memory_files = loaded.memory_files.clone();
+        for d in &self.additional_dictionaries {
+            spell_checker.add_dictionary(d)?;
+        }
+        Ok(spell_checker)
+    }
+
+    /// Returns the paths of the affix and dictionary, with the memory
+    /// files that have to stay open while they are used.
+    fn paths(&self) -> Result<(PathBuf, PathBuf, Vec<File>)> {
+        match &self.source {
+            DictionarySource::Files { affix, dictionary } => {
+                Ok((affix.clone(), dictionary.clone(), Vec::new()))
+            }
+            DictionarySource::Memory { affix, dictionary } => {
+                let (affix_file, affix) = memory_file("hunspell.aff", affix)?;
+                let (dictionary_file, dictionary) = memory_file("hunspell.dic", dictionary)?;
+                Ok((affix, dictionary, vec![affix_file, dictionary_file]))
+            }
+        }
+    }
+}
+
+/// The paths of a [`Dictionary`] that hunspell opens, with the memory
+/// files behind them, see `Dictionary::load()`.
+#[derive(Debug)]
+pub(crate) struct LoadedDictionary {
+    affix: PathBuf,
+    dictionary: PathBuf,
+    memory_files: Vec<Arc<File>>,
+}
+
+impl core::fmt::Debug for Dictionary {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let mut debug = fmt.debug_struct("Dictionary");
+        match &self.source {
+            DictionarySource::Files { affix, dictionary } => {
+                debug.field("affix", affix).field("dictionary", dictionary)
+            }
+            DictionarySource::Memory { affix, dictionary } => debug
+                .field("affix", &format_args!("{} bytes", affix.len()))
+                .field("dictionary", &format_args!("{} bytes", dictionary.len())),
+        };
+        debug
+            .field("additional_dictionaries", &self.additional_dictionaries)
+            .field("encrypted", &self.key.is_some())
+            .finish()
+    }
+}
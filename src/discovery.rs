@@ -0,0 +1,319 @@
+//! Finding the hunspell dictionaries installed on the system.
+//!
+//! Dictionaries are looked up in the directories listed in the `DICPATH`
+//! environment variable followed by the usual system locations, the same
+//! places the hunspell command line tool searches.
+
+use std::{
+    fs::File,
+    io::{BufRead, BufReader},
+    path::{Path, PathBuf},
+};
+
+use crate::{aff::ENCODING_DIRECTIVE, Error, Language, Result, SpellChecker};
+
+/// Hunspell's encoding when the affix file has no `SET` directive.
+pub const DEFAULT_ENCODING: &str = "ISO8859-1";
+
+/// An installed dictionary: a matching pair of affix and dictionary files.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DictionaryInfo {
+    /// The name of the dictionary, normally a language tag like `en_US`.
+    pub language_tag: String,
+    pub aff_path: PathBuf,
+    pub dic_path: PathBuf,
+    /// The encoding declared by the `SET` directive of the affix file.
+    pub encoding: String,
+}
+
+impl DictionaryInfo {
+    /// Looks for a dictionary `<language_tag>.aff` / `<language_tag>.dic`
+    /// in `dir`.
+    pub fn find_in<P, S>(dir: P, language_tag: S) -> Option<DictionaryInfo>
+    where
+        P: AsRef<Path>,
+        S: AsRef<str>,
+    {
+        let aff_path = dir.as_ref().join(format!("{}.aff", language_tag.as_ref()));
+        let dic_path = aff_path.with_extension("dic");
+        if !aff_path.is_file() || !dic_path.is_file() {
+            return None;
+        }
+        Some(DictionaryInfo {
+            language_tag: language_tag.as_ref().to_string(),
+            encoding: read_encoding(&aff_path),
+            aff_path,
+            dic_path,
+        })
+    }
+
+    /// Opens the dictionary with a new `SpellChecker`.
+    pub fn open(&self) -> Result<SpellChecker> {
+        SpellChecker::new(&self.aff_path, &self.dic_path)
+    }
+}
+
+/// Returns the directories searched for dictionaries: the entries of
+/// `DICPATH` followed by the standard system locations.
+pub fn search_paths() -> Vec<PathBuf> {
+    let mut paths = Vec::new();
+    if let Some(dicpath) = std::env::var_os("DICPATH") {
+        paths.extend(std::env::split_paths(&dicpath));
+    }
+    if let Some(home) = std::env::var_os("HOME") {
+        let home = PathBuf::from(home);
+        paths.push(home.join(".local/share/hunspell"));
+        paths.push(home.join("Library/Spelling"));
+    }
+    paths.extend(
+        [
+            "/usr/share/hunspell",
+            "/usr/share/myspell",
+            "/usr/share/myspell/dicts",
+            "/usr/local/share/hunspell",
+            "/usr/local/share/myspell",
+            "/Library/Spelling",
+            "/opt/homebrew/share/hunspell",
+        ]
+        .iter()
+        .map(PathBuf::from),
+    );
+    paths
+}
+
+/// Lists the dictionaries found in the [`search_paths()`].
+pub fn available_dictionaries() -> Vec<DictionaryInfo> {
+    available_dictionaries_in(search_paths())
+}
+
+/// Returns the first dictionary for `language_tag` in the
+/// [`search_paths()`].
+pub fn find_dictionary<S: AsRef<str>>(language_tag: S) -> Option<DictionaryInfo> {
+    search_paths()
+        .iter()
+        .find_map(|dir| DictionaryInfo::find_in(dir, language_tag.as_ref()))
+}
+
+/// Lists the dictionaries found in `paths`, sorted by language tag.
+///
+/// When a language is installed in more than one directory, the one
+/// found first is listed. Directories that do not exist or cannot be
+/// read are skipped.
+pub fn available_dictionaries_in<I, P>(paths: I) -> Vec<DictionaryInfo>
+where
+    I: IntoIterator<Item = P>,
+    P: AsRef<Path>,
+{
+    let mut found: Vec<DictionaryInfo> = Vec::new();
+    for dir in paths {
+        let Ok(entries) = dir.as_ref().read_dir() else {
+            continue;
+        };
+        let mut tags: Vec<String> = entries
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|p| p.extension().is_some_and(|e| e == "aff"))
+            .filter_map(|p| p.file_stem().map(|s| s.to_string_lossy().into_owned()))
+            .collect();
+        tags.sort();
+        for tag in tags {
+            if found.iter().any(|d| d.language_tag == tag) {
+                continue;
+            }
+            if let Some(info) = DictionaryInfo::find_in(dir.as_ref(), &tag) {
+                found.push(info);
+            }
+        }
+    }
+    found.sort_by(|a, b| a.language_tag.cmp(&b.language_tag));
+    found
+}
+
+/// Maps language tags, like the BCP 47 tags of operating systems and
+/// browsers, to installed dictionaries, falling back to related
+/// dictionaries when there is none for the exact tag.
+///
+/// For `pt-BR` the dictionaries tried are, in order:
+///
+/// 1. `pt_BR`, the tag itself; for a tag with a script like `sr-Latn-RS`
+///    also the tag without the script, `sr_RS`,
+/// 2. the aliases of the tag and then those of the language `pt`, see
+///    [`with_alias()`](LanguageResolver::with_alias),
+/// 3. `pt_PT`, the region named like the language,
+/// 4. `pt`, the language alone,
+/// 5. any other dictionary of the language, in alphabetical order.
+///
+/// ```
+/// use hunspell_rs::LanguageResolver;
+///
+/// let resolver = LanguageResolver::new()
+///     .with_search_paths(["tests/fixtures"])
+///     .with_alias("en", "reduced");
+/// assert_eq!("reduced", resolver.resolve("en-GB").unwrap().language_tag);
+/// assert!(resolver.resolve("de-AT").is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LanguageResolver {
+    search_paths: Vec<PathBuf>,
+    aliases: Vec<(String, String)>,
+}
+
+impl Default for LanguageResolver {
+    fn default() -> Self {
+        LanguageResolver {
+            search_paths: search_paths(),
+            aliases: Vec::new(),
+        }
+    }
+}
+
+impl LanguageResolver {
+    /// Looks in the [`search_paths()`], without aliases.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Looks in `paths` instead of the [`search_paths()`].
+    pub fn with_search_paths<I, P>(mut self, paths: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<Path>,
+    {
+        self.search_paths = paths
+            .into_iter()
+            .map(|p| p.as_ref().to_path_buf())
+            .collect();
+        self
+    }
+
+    /// Tries the dictionary `dictionary` for `tag`, a whole tag like
+    /// `en-AU` or a language like `nb`, after the tag itself. Aliases are
+    /// tried in the order they were added.
+    pub fn with_alias<S, D>(mut self, tag: S, dictionary: D) -> Self
+    where
+        S: AsRef<str>,
+        D: Into<String>,
+    {
+        self.aliases
+            .push((normalize_tag(tag.as_ref()), dictionary.into()));
+        self
+    }
+
+    /// Returns the names of the dictionaries tried for `tag` before any
+    /// other dictionary of the language, in order. Fails with
+    /// `Error::InvalidLanguageTag` when `tag` is not a language tag.
+    pub fn candidates(&self, tag: &str) -> Result<Vec<String>> {
+        let language: Language = normalize_tag(tag).parse()?;
+        let primary = language.primary().to_string();
+        let mut names = vec![language.as_str().to_string()];
+        if let Some(region) = language.region() {
+            names.push(format!("{primary}_{region}"));
+        }
+        for key in [language.as_str(), &primary] {
+            names.extend(
+                self.aliases
+                    .iter()
+                    .filter(|(t, _)| t == key)
+                    .map(|(_, dictionary)| dictionary.clone()),
+            );
+        }
+        names.push(format!("{primary}_{}", primary.to_uppercase()));
+        names.push(primary);
+        let mut unique = Vec::with_capacity(names.len());
+        for name in names {
+            if !unique.contains(&name) {
+                unique.push(name);
+            }
+        }
+        Ok(unique)
+    }
+
+    /// Returns the dictionary for `tag`, see [`LanguageResolver`]. Fails
+    /// with `Error::DictionaryNotFound`, listing the dictionaries and
+    /// directories that were searched, when there is none.
+    pub fn resolve(&self, tag: &str) -> Result<DictionaryInfo> {
+        let candidates = self.candidates(tag)?;
+        for name in &candidates {
+            if let Some(info) = self
+                .search_paths
+                .iter()
+                .find_map(|dir| DictionaryInfo::find_in(dir, name))
+            {
+                return Ok(info);
+            }
+        }
+        let primary = normalize_tag(tag)
+            .parse::<Language>()?
+            .primary()
+            .to_string();
+        if let Some(info) = available_dictionaries_in(&self.search_paths)
+            .into_iter()
+            .find(|d| {
+                d.language_tag
+                    .parse::<Language>()
+                    .is_ok_and(|l| l.primary() == primary)
+            })
+        {
+            return Ok(info);
+        }
+        let directories: Vec<String> = self
+            .search_paths
+            .iter()
+            .map(|p| p.display().to_string())
+            .collect();
+        Err(Error::DictionaryNotFound(format!(
+            "{tag}: tried {} and other {primary} dictionaries in {}",
+            candidates.join(", "),
+            directories.join(", ")
+        )))
+    }
+}
+
+/// Returns the installed dictionary for a language tag like `pt-BR`, or a
+/// related one, see [`LanguageResolver`].
+pub fn resolve_language<S: AsRef<str>>(tag: S) -> Result<DictionaryInfo> {
+    LanguageResolver::new().resolve(tag.as_ref())
+}
+
+/// Writes a tag in the hunspell style: `pt-br` becomes `pt_BR` and
+/// `sr-latn-rs` becomes `sr_Latn_RS`.
+fn normalize_tag(tag: &str) -> String {
+    let mut subtags = tag.trim().split(['-', '_']);
+    let mut normalized = subtags.next().unwrap_or_default().to_lowercase();
+    for subtag in subtags {
+        normalized.push('_');
+        match subtag.len() {
+            2 => normalized.push_str(&subtag.to_uppercase()),
+            4 => {
+                let (first, rest) = subtag.split_at(1);
+                normalized.push_str(&first.to_uppercase());
+                normalized.push_str(&rest.to_lowercase());
+            }
+            _ => normalized.push_str(subtag),
+        }
+    }
+    normalized
+}
+
+/// Reads the encoding from the `SET` directive of an affix file.
+pub(crate) fn read_encoding<P: AsRef<Path>>(affix: P) -> String {
+    read_affix_directive(affix, ENCODING_DIRECTIVE).unwrap_or_else(|| DEFAULT_ENCODING.to_string())
+}
+
+/// Returns the value of the first occurrence of a directive like `SET` or
+/// `LANG` in an affix file.
+pub(crate) fn read_affix_directive<P: AsRef<Path>>(affix: P, directive: &str) -> Option<String> {
+    let file = File::open(affix).ok()?;
+    // the affix file can be in any encoding, the directives are ASCII
+    BufReader::new(file)
+        .split(b'\n')
+        .map_while(|l| l.ok())
+        .find_map(|line| {
+            let line = String::from_utf8_lossy(&line);
+            let mut parts = line.trim_start_matches('\u{feff}').split_whitespace();
+            match (parts.next(), parts.next()) {
+                (Some(d), Some(value)) if d == directive => Some(value.to_string()),
+                _ => None,
+            }
+        })
+}
@@ -0,0 +1,96 @@
+/// Returns the optimal string alignment distance between `a` and `b`: the
+/// number of character insertions, deletions, substitutions and
+/// transpositions of adjacent characters needed to turn `a` into `b`.
+pub(crate) fn edit_distance(a: &str, b: &str) -> usize {
+    edit_distance_by(a, b, |x, y| x == y)
+}
+
+/// Like `edit_distance()`, with `same` deciding which characters can be
+/// substituted for free.
+pub(crate) fn edit_distance_by<F>(a: &str, b: &str, same: F) -> usize
+where
+    F: Fn(char, char) -> bool,
+{
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    // rows i-2, i-1 and i of the distance matrix
+    let mut previous2: Vec<usize> = vec![0; b.len() + 1];
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current: Vec<usize> = vec![0; b.len() + 1];
+    for i in 1..=a.len() {
+        current[0] = i;
+        for j in 1..=b.len() {
+            let cost = usize::from(!same(a[i - 1], b[j - 1]));
+            current[j] = (previous[j] + 1)
+                .min(current[j - 1] + 1)
+                .min(previous[j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                current[j] = current[j].min(previous2[j - 2] + 1);
+            }
+        }
+        std::mem::swap(&mut previous2, &mut previous);
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[b.len()]
+}
+
+/// A step of [`alignment()`], on the characters of `a` and `b`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Step {
+    Keep,
+    Insert,
+    Delete,
+    Substitute,
+    Transpose,
+}
+
+/// Returns the steps that turn `a` into `b` with the fewest edits, as
+/// counted by `edit_distance()`, in order. When there are several ways,
+/// the characters at the end of the words are the ones that are kept.
+pub(crate) fn alignment(a: &[char], b: &[char]) -> Vec<Step> {
+    let mut d = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in d.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    d[0] = (0..=b.len()).collect();
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            d[i][j] = (d[i - 1][j] + 1)
+                .min(d[i][j - 1] + 1)
+                .min(d[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                d[i][j] = d[i][j].min(d[i - 2][j - 2] + 1);
+            }
+        }
+    }
+    let mut steps = Vec::with_capacity(a.len().max(b.len()));
+    let (mut i, mut j) = (a.len(), b.len());
+    while i > 0 || j > 0 {
+        let step = if i > 0 && j > 0 && a[i - 1] == b[j - 1] && d[i][j] == d[i - 1][j - 1] {
+            Step::Keep
+        } else if i > 1
+            && j > 1
+            && a[i - 1] == b[j - 2]
+            && a[i - 2] == b[j - 1]
+            && d[i][j] == d[i - 2][j - 2] + 1
+        {
+            Step::Transpose
+        } else if i > 0 && j > 0 && d[i][j] == d[i - 1][j - 1] + 1 {
+            Step::Substitute
+        } else if i > 0 && d[i][j] == d[i - 1][j] + 1 {
+            Step::Delete
+        } else {
+            Step::Insert
+        };
+        match step {
+            Step::Keep | Step::Substitute => (i, j) = (i - 1, j - 1),
+            Step::Transpose => (i, j) = (i - 2, j - 2),
+            Step::Delete => i -= 1,
+            Step::Insert => j -= 1,
+        }
+        steps.push(step);
+    }
+    steps.reverse();
+    steps
+}
@@ -0,0 +1,277 @@
+//! Keeping the misspellings of an edited text up to date line by line.
+
+use std::{ops::Range, rc::Rc};
+
+use crate::{Error, GrammarIssue, GrammarOptions, Misspelling, Result, TextChecker};
+
+/// A text with its misspellings, for editors. The misspellings are kept
+/// per line, and an edit only re-checks the lines it touches.
+///
+/// Every line is checked on its own, so syntax that spans lines, like a
+/// fenced code block in Markdown, is not recognized.
+///
+/// The [`TextChecker`] can be shared by documents as an `Rc<TextChecker>`.
+///
+/// With [`with_grammar()`](Self::with_grammar) the lines are also checked
+/// for doubled words, sentences that start in lowercase and spaces around
+/// punctuation, see [`GrammarOptions`].
+///
+/// ```
+/// use hunspell_rs::{DocumentChecker, SpellChecker, TextChecker};
+///
+/// let spell = SpellChecker::new("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic").unwrap();
+/// let mut document = DocumentChecker::new(TextChecker::new(spell), "cats catz\nprograms").unwrap();
+/// assert_eq!(1, document.misspellings().len());
+/// let changes = document.edit(8..9, "s").unwrap();
+/// assert_eq!("catz", changes.removed[0].word);
+/// assert!(changes.added.is_empty());
+/// assert_eq!("cats cats\nprograms", document.text());
+/// ```
+#[derive(Debug)]
+pub struct DocumentChecker {
+    checker: Rc<TextChecker>,
+    text: String,
+    lines: Vec<Line>,
+    grammar: GrammarOptions,
+}
+
+/// A line of a [`DocumentChecker`].
+#[derive(Debug, Clone)]
+struct Line {
+    /// The length in bytes, without the line feed.
+    len: usize,
+    /// The misspellings, with ranges in the line.
+    misspellings: Vec<Misspelling>,
+    /// The grammar issues, with ranges in the line.
+    issues: Vec<GrammarIssue>,
+}
+
+/// A [`DocumentChecker`] without its checker, see
+/// [`DocumentChecker::detach()`].
+#[cfg(feature = "lsp")]
+#[derive(Debug)]
+pub(crate) struct DetachedDocument {
+    text: String,
+    lines: Vec<Line>,
+    grammar: GrammarOptions,
+}
+
+/// What an edit changed, see [`DocumentChecker::edit()`].
+///
+/// Misspellings after the edited lines move with the text, they are not
+/// in the changes.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct DocumentChanges {
+    /// The new misspellings, with their place in the edited text.
+    pub added: Vec<Misspelling>,
+    /// The misspellings that are gone, with their place in the text before
+    /// the edit.
+    pub removed: Vec<Misspelling>,
+}
+
+impl DocumentChanges {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+}
+
+impl DocumentChecker {
+    /// Checks every line of `text`.
+    pub fn new<C, S>(checker: C, text: S) -> Result<Self>
+    where
+        C: Into<Rc<TextChecker>>,
+        S: Into<String>,
+    {
+        let mut document = DocumentChecker {
+            checker: checker.into(),
+            text: text.into(),
+            lines: Vec::new(),
+            grammar: GrammarOptions::none(),
+        };
+        document.lines = document.check_lines(&document.text)?;
+        Ok(document)
+    }
+
+    /// Checks the lines for the grammar rules of `options` as well.
+    pub fn with_grammar(mut self, options: GrammarOptions) -> Self {
+        self.grammar = options;
+        for (line, text) in self.lines.iter_mut().zip(self.text.split('\n')) {
+            line.issues = options.check(text);
+        }
+        self
+    }
+
+    pub fn grammar(&self) -> GrammarOptions {
+        self.grammar
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn text_checker(&self) -> &TextChecker {
+        &self.checker
+    }
+
+    /// Returns the misspellings of the text, in order.
+    pub fn misspellings(&self) -> Vec<Misspelling> {
+        let mut misspellings = Vec::new();
+        let mut start = 0;
+        for line in &self.lines {
+            misspellings.extend(line.misspellings.iter().map(|m| moved(m, start)));
+            start += line.len + 1;
+        }
+        misspellings
+    }
+
+    /// Returns the grammar issues of the text, in order. There are none
+    /// unless the document was made [`with_grammar()`](Self::with_grammar).
+    pub fn grammar_issues(&self) -> Vec<GrammarIssue> {
+        let mut issues = Vec::new();
+        let mut start = 0;
+        for line in &self.lines {
+            issues.extend(line.issues.iter().map(|i| GrammarIssue {
+                range: i.range.start + start..i.range.end + start,
+                ..i.clone()
+            }));
+            start += line.len + 1;
+        }
+        issues
+    }
+
+    /// Replaces the bytes of `range` by `replacement` and re-checks the
+    /// lines the edit touches. Fails with `Error::InvalidEdit` when
+    /// `range` is not in the text or not on character boundaries.
+    ///
+    /// The changes only have the misspellings, the grammar issues are up
+    /// to date in [`grammar_issues()`](Self::grammar_issues).
+    pub fn edit(&mut self, range: Range<usize>, replacement: &str) -> Result<DocumentChanges> {
+        if range.start > range.end
+            || !self.text.is_char_boundary(range.start)
+            || !self.text.is_char_boundary(range.end)
+        {
+            return Err(Error::InvalidEdit(range));
+        }
+        // the edited lines, with the offset of the first one
+        let mut first = None;
+        let mut last = 0;
+        let mut line_start = 0;
+        let mut first_start = 0;
+        for (i, line) in self.lines.iter().enumerate() {
+            let line_end = line_start + line.len;
+            if first.is_none() && range.start <= line_end {
+                first = Some(i);
+                first_start = line_start;
+            }
+            if range.end <= line_end {
+                last = i;
+                break;
+            }
+            line_start = line_end + 1;
+        }
+        let first = first.unwrap_or(last);
+        let old_end = line_start + self.lines[last].len;
+
+        let removed_lines: Vec<Line> = self.lines.drain(first..=last).collect();
+        self.text.replace_range(range.clone(), replacement);
+        let new_end = old_end + replacement.len() - range.len();
+        let new_lines = self.check_lines(&self.text[first_start..new_end])?;
+
+        // misspellings outside the edit keep their word and move with it
+        let mut new: Vec<Misspelling> = Vec::new();
+        let mut start = first_start;
+        for line in &new_lines {
+            new.extend(line.misspellings.iter().map(|m| moved(m, start)));
+            start += line.len + 1;
+        }
+        let mut changes = DocumentChanges::default();
+        let mut start = first_start;
+        let mut kept = vec![false; new.len()];
+        for line in &removed_lines {
+            for m in line.misspellings.iter().map(|m| moved(m, start)) {
+                let after = if m.range.end <= range.start {
+                    Some(m.range.clone())
+                } else if m.range.start >= range.end {
+                    Some(
+                        m.range.start + replacement.len() - range.len()
+                            ..m.range.end + replacement.len() - range.len(),
+                    )
+                } else {
+                    None
+                };
+                match new
+                    .iter()
+                    .position(|n| Some(&n.range) == after.as_ref() && n.word == m.word)
+                {
+                    Some(i) => kept[i] = true,
+                    None => changes.removed.push(m),
+                }
+            }
+            start += line.len + 1;
+        }
+        changes.added = new
+            .into_iter()
+            .zip(kept)
+            .filter(|(_, kept)| !kept)
+            .map(|(m, _)| m)
+            .collect();
+        self.lines.splice(first..first, new_lines);
+        Ok(changes)
+    }
+
+    /// Checks all lines again, i.e. after a word was added to the
+    /// dictionary.
+    pub fn recheck(&mut self) -> Result<DocumentChanges> {
+        let old = self.misspellings();
+        self.lines = self.check_lines(&self.text)?;
+        let new = self.misspellings();
+        Ok(DocumentChanges {
+            removed: old.iter().filter(|m| !new.contains(m)).cloned().collect(),
+            added: new.iter().filter(|m| !old.contains(m)).cloned().collect(),
+        })
+    }
+
+    /// Lets go of the checker, keeping the text and its results, so the
+    /// checker can be changed while no document shares it.
+    #[cfg(feature = "lsp")]
+    pub(crate) fn detach(self) -> DetachedDocument {
+        DetachedDocument {
+            text: self.text,
+            lines: self.lines,
+            grammar: self.grammar,
+        }
+    }
+
+    /// Undoes [`detach()`](Self::detach) with `checker`, without checking
+    /// again.
+    #[cfg(feature = "lsp")]
+    pub(crate) fn reattach(checker: Rc<TextChecker>, document: DetachedDocument) -> Self {
+        DocumentChecker {
+            checker,
+            text: document.text,
+            lines: document.lines,
+            grammar: document.grammar,
+        }
+    }
+
+    /// Checks the lines of `text`.
+    fn check_lines(&self, text: &str) -> Result<Vec<Line>> {
+        text.split('\n')
+            .map(|line| {
+                Ok(Line {
+                    len: line.len(),
+                    misspellings: self.checker.check_text(line)?,
+                    issues: self.grammar.check(line),
+                })
+            })
+            .collect()
+    }
+}
+
+/// Returns `m` with its range moved by `offset`.
+fn moved(m: &Misspelling, offset: usize) -> Misspelling {
+    Misspelling {
+        word: m.word.clone(),
+        range: m.range.start + offset..m.range.end + offset,
+    }
+}
@@ -0,0 +1,94 @@
+//! The characters a suggestion changes, for editors that highlight them.
+
+use std::ops::Range;
+
+use crate::{
+    distance::{alignment, Step},
+    Result, SpellChecker,
+};
+
+/// What a [`SuggestionEdit`] does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EditKind {
+    /// Inserts a character, the range is empty.
+    Insert,
+    /// Deletes a character, the replacement is empty.
+    Delete,
+    /// Replaces a character by another.
+    Substitute,
+    /// Swaps two adjacent characters.
+    Transpose,
+}
+
+/// A change of a single character, or of two adjacent characters that are
+/// swapped, that turns a misspelled word into a suggestion.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SuggestionEdit {
+    pub kind: EditKind,
+    /// The byte range of the changed characters in the misspelled word.
+    pub range: Range<usize>,
+    /// What is in their place in the suggestion.
+    pub replacement: String,
+}
+
+impl SuggestionEdit {
+    /// Returns the fewest edits that turn `word` into `suggestion`, in
+    /// order, with the ranges in `word`. Empty when they are the same.
+    ///
+    /// ```
+    /// use hunspell_rs::{EditKind, SuggestionEdit};
+    ///
+    /// let edits = SuggestionEdit::between("progam", "program");
+    /// assert_eq!(1, edits.len());
+    /// assert_eq!(EditKind::Insert, edits[0].kind);
+    /// assert_eq!(4..4, edits[0].range);
+    /// assert_eq!("r", edits[0].replacement);
+    /// ```
+    pub fn between(word: &str, suggestion: &str) -> Vec<SuggestionEdit> {
+        let a: Vec<(usize, char)> = word.char_indices().collect();
+        let b: Vec<char> = suggestion.chars().collect();
+        let a_chars: Vec<char> = a.iter().map(|&(_, c)| c).collect();
+        // the byte offset of character i of `word`
+        let offset = |i: usize| a.get(i).map_or(word.len(), |&(o, _)| o);
+        let mut edits = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        for step in alignment(&a_chars, &b) {
+            let (kind, taken, given) = match step {
+                Step::Keep => {
+                    (i, j) = (i + 1, j + 1);
+                    continue;
+                }
+                Step::Insert => (EditKind::Insert, 0, 1),
+                Step::Delete => (EditKind::Delete, 1, 0),
+                Step::Substitute => (EditKind::Substitute, 1, 1),
+                Step::Transpose => (EditKind::Transpose, 2, 2),
+            };
+            edits.push(SuggestionEdit {
+                kind,
+                range: offset(i)..offset(i + taken),
+                replacement: b[j..j + given].iter().collect(),
+            });
+            (i, j) = (i + taken, j + given);
+        }
+        edits
+    }
+}
+
+impl SpellChecker {
+    /// Returns the suggestions for `word`, each with the edits that turn
+    /// `word` into it, see [`SuggestionEdit::between()`].
+    pub fn suggest_with_edits<S>(&self, word: S) -> Result<Vec<(String, Vec<SuggestionEdit>)>>
+    where
+        S: AsRef<str>,
+    {
+        let word = word.as_ref();
+        Ok(self
+            .suggest(word)?
+            .into_iter()
+            .map(|suggestion| {
+                let edits = SuggestionEdit::between(word, &suggestion);
+                (suggestion, edits)
+            })
+            .collect())
+    }
+}
@@ -1,8 +1,12 @@
-use std::path::PathBuf;
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
 pub type Result<T> = core::result::Result<T, Error>;
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
 pub enum Error {
     HunspellLibError(i32),
     NegativeListLength(i32),
@@ -12,12 +16,320 @@ pub enum Error {
     CannotAddMoreDictionaries(PathBuf),
     Utf8Error(core::str::Utf8Error),
     NulError(std::ffi::NulError),
+    IoError(IoError),
+    InMemoryUnsupported,
+    DictionaryRejected(String),
+    InvalidLanguageTag(String),
+    HandleCreationFailed {
+        affix: PathBuf,
+        dictionary: PathBuf,
+    },
+    Timeout(std::time::Duration),
+    WorkerStopped,
+    DictionaryNotFound(String),
+    InvalidSnapshot(String),
+    InvalidPattern(String),
+    WatchFailed(String),
+    PreloadFailed(Vec<(String, Error)>),
+    ReadOnly,
+    InvalidWord {
+        word: String,
+        character: Option<char>,
+    },
+    InvalidAffixFile(String),
+    InvalidDictionaryFile(String),
+    Panicked(String),
+    InvalidExtension(String),
+    FetchFailed(String),
+    ChecksumMismatch(String),
+    InvalidConfig(String),
+    UnsupportedEncoding(String),
+    InvalidFrequencyList(String),
+    InvalidEdit(std::ops::Range<usize>),
+    UnknownDocument(String),
+    UnknownCommand(String),
+    Multiple(Vec<(String, Error)>),
+    LibraryLoading(String),
+    UnsupportedPath(PathBuf),
+    Failed {
+        operation: Operation,
+        subject: String,
+        cause: Box<Error>,
+    },
+    InvalidHyphenationPatterns(String),
+    InvalidThesaurus(String),
+    FingerprintMismatch {
+        expected: u64,
+        found: u64,
+    },
+    InvalidCorrection(String),
+}
+
+/// The operations of a [`SpellChecker`](crate::SpellChecker) that tell
+/// in `Error::Failed` what they were doing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Operation {
+    Check,
+    Suggest,
+    Add,
+    AddWithAffix,
+    Remove,
+    AddDictionary,
+    Open,
+    Load,
+    Save,
+    Fetch,
+    Snapshot,
+}
+
+impl Operation {
+    /// The name of the method, i.e. `add_dictionary`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Operation::Check => "check",
+            Operation::Suggest => "suggest",
+            Operation::Add => "add",
+            Operation::AddWithAffix => "add_with_affix",
+            Operation::Remove => "remove",
+            Operation::AddDictionary => "add_dictionary",
+            Operation::Open => "open",
+            Operation::Load => "load",
+            Operation::Save => "save",
+            Operation::Fetch => "fetch",
+            Operation::Snapshot => "snapshot",
+        }
+    }
+}
+
+impl core::fmt::Display for Operation {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        fmt.write_str(self.name())
+    }
+}
+
+/// An I/O error with the file it happened on, if known.
+#[derive(Debug, Clone)]
+pub struct IoError {
+    path: Option<PathBuf>,
+    error: Arc<std::io::Error>,
+}
+
+impl IoError {
+    /// The file the error happened on.
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// The kind of the underlying error.
+    pub fn kind(&self) -> std::io::ErrorKind {
+        self.error.kind()
+    }
+
+    /// The underlying error.
+    pub fn io_error(&self) -> &std::io::Error {
+        &self.error
+    }
+}
+
+/// Compares the path and the kind, `std::io::Error` has no equality.
+impl PartialEq for IoError {
+    fn eq(&self, other: &Self) -> bool {
+        self.path == other.path && self.kind() == other.kind()
+    }
+}
+
+impl core::fmt::Display for IoError {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match &self.path {
+            Some(path) => write!(fmt, "{}: {}", path.display(), self.error),
+            None => write!(fmt, "{}", self.error),
+        }
+    }
+}
+
+impl core::error::Error for IoError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        Some(self.error.as_ref())
+    }
+}
+
+impl Error {
+    /// Returns a closure for `map_err` that keeps `path` with an I/O error
+    /// and tells the file-level `operation` that failed.
+    pub(crate) fn io(
+        operation: Operation,
+        path: impl AsRef<Path>,
+    ) -> impl FnOnce(std::io::Error) -> Error {
+        let path = path.as_ref().to_path_buf();
+        move |error| Error::Failed {
+            operation,
+            subject: path.to_string_lossy().into_owned(),
+            cause: Box::new(Error::IoError(IoError {
+                path: Some(path),
+                error: Arc::new(error),
+            })),
+        }
+    }
+
+    /// Wraps the errors that do not tell what went wrong by themselves,
+    /// like `HunspellLibError` or `NulError`, in `Error::Failed` with the
+    /// `operation` and the word or path it was given.
+    pub(crate) fn during(self, operation: Operation, subject: &str) -> Error {
+        match self {
+            Error::HunspellLibError(_)
+            | Error::NegativeListLength(_)
+            | Error::NullPtr
+            | Error::Utf8Error(_)
+            | Error::NulError(_)
+            | Error::IoError(_) => Error::Failed {
+                operation,
+                subject: subject.to_string(),
+                cause: Box::new(self),
+            },
+            error => error,
+        }
+    }
+
+    /// Returns the operation that failed, for `Error::Failed`.
+    pub fn operation(&self) -> Option<Operation> {
+        match self {
+            Error::Failed { operation, .. } => Some(*operation),
+            _ => None,
+        }
+    }
+
+    /// Returns the error without the context of `Error::Failed`.
+    ///
+    /// ```
+    /// use hunspell_rs::{Error, Operation, SpellChecker};
+    ///
+    /// let spell = SpellChecker::new("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic").unwrap();
+    /// let error = spell.check("ca\0t").unwrap_err();
+    /// assert_eq!(Some(Operation::Check), error.operation());
+    /// assert!(matches!(error.root_cause(), Error::NulError(_)));
+    /// assert!(error.to_string().starts_with("check \"ca\\0t\": nul byte found"));
+    /// ```
+    pub fn root_cause(&self) -> &Error {
+        match self {
+            Error::Failed { cause, .. } => cause.root_cause(),
+            error => error,
+        }
+    }
 }
 
 impl core::fmt::Display for Error {
     fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        write!(fmt, "{self:?}")
+        match self {
+            Error::Failed {
+                operation,
+                subject,
+                cause,
+            } => match cause.as_ref() {
+                // the subject already names the file
+                Error::IoError(e) if e.path.is_some() => {
+                    write!(fmt, "{operation} {subject:?}: {}", e.error)
+                }
+                cause => write!(fmt, "{operation} {subject:?}: {cause}"),
+            },
+            Error::HunspellLibError(code) => write!(fmt, "hunspell returned error code {code}"),
+            Error::NegativeListLength(length) => {
+                write!(fmt, "hunspell returned a list of length {length}")
+            }
+            Error::NullPtr => write!(fmt, "hunspell returned a null pointer"),
+            Error::AffixFileIsNoFile(path) => write!(fmt, "affix file {path:?} is no file"),
+            Error::DictionaryFileIsNoFile(path) => {
+                write!(fmt, "dictionary file {path:?} is no file")
+            }
+            Error::CannotAddMoreDictionaries(path) => write!(
+                fmt,
+                "cannot add dictionary {}: too many dictionaries",
+                path.display()
+            ),
+            Error::Utf8Error(e) => write!(fmt, "{e}"),
+            Error::NulError(e) => write!(fmt, "{e}"),
+            Error::IoError(e) => write!(fmt, "I/O error: {e}"),
+            Error::InMemoryUnsupported => {
+                write!(
+                    fmt,
+                    "in-memory dictionaries are unsupported on this platform"
+                )
+            }
+            Error::DictionaryRejected(reason) => write!(fmt, "dictionary rejected: {reason}"),
+            Error::InvalidLanguageTag(tag) => write!(fmt, "invalid language tag {tag:?}"),
+            Error::HandleCreationFailed { affix, dictionary } => write!(
+                fmt,
+                "hunspell could not load {} and {}",
+                affix.display(),
+                dictionary.display()
+            ),
+            Error::Timeout(timeout) => write!(fmt, "timed out after {timeout:?}"),
+            Error::WorkerStopped => write!(fmt, "the worker thread stopped"),
+            Error::DictionaryNotFound(name) => write!(fmt, "dictionary {name:?} not found"),
+            Error::InvalidSnapshot(reason) => write!(fmt, "invalid snapshot: {reason}"),
+            Error::InvalidPattern(reason) => write!(fmt, "invalid pattern: {reason}"),
+            Error::WatchFailed(reason) => write!(fmt, "watching failed: {reason}"),
+            Error::PreloadFailed(failed) => {
+                write!(fmt, "preloading failed")?;
+                write_list(fmt, failed)
+            }
+            Error::ReadOnly => write!(fmt, "the spell checker is read-only"),
+            Error::InvalidWord {
+                word,
+                character: Some(character),
+            } => write!(fmt, "invalid word {word:?}: character {character:?}"),
+            Error::InvalidWord {
+                word,
+                character: None,
+            } => write!(fmt, "invalid word {word:?}"),
+            Error::InvalidAffixFile(reason) => write!(fmt, "invalid affix file: {reason}"),
+            Error::InvalidDictionaryFile(reason) => {
+                write!(fmt, "invalid dictionary file: {reason}")
+            }
+            Error::Panicked(message) => write!(fmt, "panicked: {message}"),
+            Error::InvalidExtension(reason) => write!(fmt, "invalid extension: {reason}"),
+            Error::FetchFailed(reason) => write!(fmt, "fetching failed: {reason}"),
+            Error::ChecksumMismatch(file) => write!(fmt, "checksum mismatch for {file}"),
+            Error::InvalidConfig(reason) => write!(fmt, "invalid configuration: {reason}"),
+            Error::UnsupportedEncoding(encoding) => {
+                write!(fmt, "unsupported encoding {encoding:?}")
+            }
+            Error::InvalidFrequencyList(reason) => {
+                write!(fmt, "invalid frequency list: {reason}")
+            }
+            Error::InvalidEdit(range) => write!(fmt, "invalid edit of {range:?}"),
+            Error::UnknownDocument(uri) => write!(fmt, "unknown document {uri}"),
+            Error::UnknownCommand(command) => write!(fmt, "unknown command {command:?}"),
+            Error::Multiple(errors) => {
+                write!(fmt, "{} errors", errors.len())?;
+                write_list(fmt, errors)
+            }
+            Error::LibraryLoading(reason) => {
+                write!(fmt, "loading the hunspell library failed: {reason}")
+            }
+            Error::UnsupportedPath(path) => {
+                write!(fmt, "unsupported path {}", path.display())
+            }
+            Error::InvalidHyphenationPatterns(reason) => {
+                write!(fmt, "invalid hyphenation patterns: {reason}")
+            }
+            Error::InvalidThesaurus(reason) => write!(fmt, "invalid thesaurus: {reason}"),
+            Error::FingerprintMismatch { expected, found } => write!(
+                fmt,
+                "dictionary fingerprint {found:016x} does not match {expected:016x}"
+            ),
+            Error::InvalidCorrection(reason) => write!(fmt, "invalid correction: {reason}"),
+        }
+    }
+}
+
+/// Writes `: name: error; name: error` for the errors of a list.
+fn write_list(fmt: &mut core::fmt::Formatter<'_>, errors: &[(String, Error)]) -> core::fmt::Result {
+    for (i, (name, error)) in errors.iter().enumerate() {
+        let separator = if i == 0 { ": " } else { "; " };
+        write!(fmt, "{separator}{name}: {error}")?;
     }
+    Ok(())
 }
 
 impl From<core::str::Utf8Error> for Error {
@@ -32,4 +344,23 @@ impl From<std::ffi::NulError> for Error {
     }
 }
 
-impl core::error::Error for Error {}
+impl From<std::io::Error> for Error {
+    fn from(value: std::io::Error) -> Self {
+        Self::IoError(IoError {
+            path: None,
+            error: Arc::new(value),
+        })
+    }
+}
+
+impl core::error::Error for Error {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Error::Failed { cause, .. } => Some(cause.as_ref()),
+            Error::Utf8Error(e) => Some(e),
+            Error::NulError(e) => Some(e),
+            Error::IoError(e) => Some(e.io_error()),
+            _ => None,
+        }
+    }
+}
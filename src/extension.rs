@@ -0,0 +1,153 @@
+//! Dictionaries from LibreOffice (`.oxt`) and Mozilla (`.xpi`) extensions.
+//!
+//! Both are zip files with the affix and dictionary files inside. Only the
+//! parts of the zip format these extensions use are read: stored and
+//! deflated entries, without zip64 or encryption.
+
+use std::path::Path;
+
+use crate::{Dictionary, Error, Operation, Result};
+
+impl Dictionary {
+    /// Reads the affix and dictionary files of a LibreOffice (`.oxt`) or
+    /// Mozilla (`.xpi`) dictionary extension. The first spelling dictionary
+    /// in `dictionaries.xcu` is used; without it, the first `.aff` file
+    /// that has a `.dic` file next to it. Fails with
+    /// `Error::InvalidExtension` when the file is no zip file or has no
+    /// dictionary.
+    ///
+    /// ```
+    /// use hunspell_rs::Dictionary;
+    ///
+    /// let spell = Dictionary::from_oxt("tests/fixtures/extension/reduced.oxt")
+    ///     .unwrap()
+    ///     .open()
+    ///     .unwrap();
+    /// assert_eq!(Ok(true), spell.check("cats"));
+    /// ```
+    pub fn from_oxt<P: AsRef<Path>>(path: P) -> Result<Dictionary> {
+        let archive = std::fs::read(&path).map_err(Error::io(Operation::Open, &path))?;
+        let entries = entries(&archive)?;
+        let (affix, dictionary) = match entries.iter().find(|e| e.name == "dictionaries.xcu") {
+            Some(xcu) => {
+                let xcu = extract(&archive, xcu)?;
+                spelling_locations(&String::from_utf8_lossy(&xcu))
+                    .ok_or_else(|| invalid("no spelling dictionary in dictionaries.xcu"))?
+            }
+            None => entries
+                .iter()
+                .filter_map(|e| e.name.strip_suffix(".aff"))
+                .map(|stem| (format!("{stem}.aff"), format!("{stem}.dic")))
+                .find(|(_, dic)| entries.iter().any(|e| &e.name == dic))
+                .ok_or_else(|| invalid("no .aff and .dic files"))?,
+        };
+        let find = |name: &str| {
+            entries
+                .iter()
+                .find(|e| e.name == name)
+                .ok_or_else(|| invalid(&format!("{name} not found")))
+        };
+        Ok(Dictionary::from_bytes(
+            extract(&archive, find(&affix)?)?,
+            extract(&archive, find(&dictionary)?)?,
+        ))
+    }
+}
+
+/// An entry of the central directory of a zip file.
+#[derive(Debug)]
+struct Entry {
+    name: String,
+    method: u16,
+    compressed_size: usize,
+    local_header: usize,
+}
+
+fn invalid(reason: &str) -> Error {
+    Error::InvalidExtension(reason.to_string())
+}
+
+fn u16_at(data: &[u8], at: usize) -> Result<u16> {
+    data.get(at..at + 2)
+        .map(|b| u16::from_le_bytes([b[0], b[1]]))
+        .ok_or_else(|| invalid("truncated zip file"))
+}
+
+fn u32_at(data: &[u8], at: usize) -> Result<u32> {
+    data.get(at..at + 4)
+        .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .ok_or_else(|| invalid("truncated zip file"))
+}
+
+/// Reads the central directory, found through the end of central
+/// directory record at the end of the file.
+fn entries(archive: &[u8]) -> Result<Vec<Entry>> {
+    const END_SIGNATURE: &[u8] = &[0x50, 0x4b, 0x05, 0x06];
+    let end = (0..archive.len().saturating_sub(21))
+        .rev()
+        .find(|&i| archive[i..].starts_with(END_SIGNATURE))
+        .ok_or_else(|| invalid("not a zip file"))?;
+    let count = u16_at(archive, end + 10)? as usize;
+    let mut at = u32_at(archive, end + 16)? as usize;
+    let mut entries = Vec::with_capacity(count);
+    for _ in 0..count {
+        if u32_at(archive, at)? != 0x0201_4b50 {
+            return Err(invalid("corrupt central directory"));
+        }
+        let name_len = u16_at(archive, at + 28)? as usize;
+        let extra_len = u16_at(archive, at + 30)? as usize;
+        let comment_len = u16_at(archive, at + 32)? as usize;
+        let name = archive
+            .get(at + 46..at + 46 + name_len)
+            .ok_or_else(|| invalid("truncated zip file"))?;
+        entries.push(Entry {
+            name: String::from_utf8_lossy(name).into_owned(),
+            method: u16_at(archive, at + 10)?,
+            compressed_size: u32_at(archive, at + 20)? as usize,
+            local_header: u32_at(archive, at + 42)? as usize,
+        });
+        at += 46 + name_len + extra_len + comment_len;
+    }
+    Ok(entries)
+}
+
+/// Returns the uncompressed contents of `entry`.
+fn extract(archive: &[u8], entry: &Entry) -> Result<Vec<u8>> {
+    let at = entry.local_header;
+    if u32_at(archive, at)? != 0x0403_4b50 {
+        return Err(invalid("corrupt local header"));
+    }
+    let start = at + 30 + u16_at(archive, at + 26)? as usize + u16_at(archive, at + 28)? as usize;
+    let data = archive
+        .get(start..start + entry.compressed_size)
+        .ok_or_else(|| invalid("truncated zip file"))?;
+    match entry.method {
+        0 => Ok(data.to_vec()),
+        8 => miniz_oxide::inflate::decompress_to_vec(data)
+            .map_err(|_| invalid(&format!("corrupt entry {}", entry.name))),
+        method => Err(invalid(&format!(
+            "unsupported compression method {method} of {}",
+            entry.name
+        ))),
+    }
+}
+
+/// Returns the affix and dictionary paths of the first `DICT_SPELL` node
+/// of a `dictionaries.xcu`, i.e. from
+/// `<value>%origin%/en_US.aff %origin%/en_US.dic</value>`.
+fn spelling_locations(xcu: &str) -> Option<(String, String)> {
+    xcu.split("<node")
+        .filter(|node| node.contains("DICT_SPELL"))
+        .find_map(|node| {
+            let locations = &node[node.find("\"Locations\"")?..];
+            let value = &locations[locations.find("<value>")? + "<value>".len()..];
+            let value = &value[..value.find("</value>")?];
+            let paths: Vec<&str> = value
+                .split_whitespace()
+                .map(|p| p.trim_start_matches("%origin%").trim_start_matches('/'))
+                .collect();
+            let affix = paths.iter().find(|p| p.ends_with(".aff"))?;
+            let dictionary = paths.iter().find(|p| p.ends_with(".dic"))?;
+            Some((affix.to_string(), dictionary.to_string()))
+        })
+}
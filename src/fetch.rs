@@ -0,0 +1,190 @@
+//! Downloading dictionaries from the wooorm/dictionaries collection.
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use sha2::{Digest, Sha256};
+
+use crate::{Dictionary, Error, Operation, Result};
+
+/// Where [`DictionaryFetcher`] downloads from by default.
+pub const WOOORM_DICTIONARIES: &str =
+    "https://raw.githubusercontent.com/wooorm/dictionaries/main/dictionaries";
+
+type Download = dyn Fn(&str) -> Result<Vec<u8>> + Send + Sync;
+
+/// Downloads dictionaries by language tag and keeps them in a cache
+/// directory, so they are only downloaded once.
+///
+/// The crate has no HTTP client of its own: the application passes a
+/// function that downloads a URL, using the client it already has. Map
+/// its errors to `Error::FetchFailed`.
+///
+/// With [`with_checksums()`](DictionaryFetcher::with_checksums) the
+/// SHA-256 of the files is checked, both when downloading and when using
+/// the cached files.
+///
+/// ```
+/// use hunspell_rs::{DictionaryFetcher, Error};
+///
+/// let cache = std::env::temp_dir().join(format!("hunspell-rs-doc-fetch-{}", std::process::id()));
+/// let fetcher = DictionaryFetcher::new(|url: &str| {
+///     // i.e. ureq::get(url).call()...
+///     match url.rsplit('/').next() {
+///         Some("index.aff") => Ok(std::fs::read("tests/fixtures/reduced.aff")?),
+///         Some("index.dic") => Ok(std::fs::read("tests/fixtures/reduced.dic")?),
+///         _ => Err(Error::FetchFailed(format!("{url}: not found"))),
+///     }
+/// })
+/// .with_cache_dir(&cache);
+/// let spell = fetcher.get("en-US").unwrap().open().unwrap();
+/// assert_eq!(Ok(true), spell.check("cats"));
+/// assert!(cache.join("en-US/index.dic").is_file());
+/// # std::fs::remove_dir_all(cache).unwrap();
+/// ```
+pub struct DictionaryFetcher {
+    download: Box<Download>,
+    base_url: String,
+    cache_dir: PathBuf,
+    // SHA-256 of the affix and dictionary by tag, lowercase hex
+    checksums: HashMap<String, (String, String)>,
+}
+
+impl DictionaryFetcher {
+    /// Downloads from [`WOOORM_DICTIONARIES`] into the [`cache_dir()`]
+    /// of the user.
+    ///
+    /// [`cache_dir()`]: DictionaryFetcher::cache_dir
+    pub fn new<F>(download: F) -> Self
+    where
+        F: Fn(&str) -> Result<Vec<u8>> + Send + Sync + 'static,
+    {
+        DictionaryFetcher {
+            download: Box::new(download),
+            base_url: WOOORM_DICTIONARIES.to_string(),
+            cache_dir: user_cache_dir().join("hunspell-rs").join("dictionaries"),
+            checksums: HashMap::new(),
+        }
+    }
+
+    /// A mirror with the layout of the collection:
+    /// `{base_url}/{tag}/index.aff` and `{base_url}/{tag}/index.dic`.
+    pub fn with_base_url<S: AsRef<str>>(mut self, base_url: S) -> Self {
+        self.base_url = base_url.as_ref().trim_end_matches('/').to_string();
+        self
+    }
+
+    pub fn with_cache_dir<P: AsRef<Path>>(mut self, cache_dir: P) -> Self {
+        self.cache_dir = cache_dir.as_ref().to_path_buf();
+        self
+    }
+
+    /// The SHA-256 of the affix and dictionary file of `tag`, as hex.
+    pub fn with_checksums<S: AsRef<str>>(mut self, tag: S, affix: S, dictionary: S) -> Self {
+        self.checksums.insert(
+            tag.as_ref().to_string(),
+            (
+                affix.as_ref().to_ascii_lowercase(),
+                dictionary.as_ref().to_ascii_lowercase(),
+            ),
+        );
+        self
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// The directory with a subdirectory per downloaded tag. By default
+    /// `hunspell-rs/dictionaries` in `XDG_CACHE_HOME` or `~/.cache`,
+    /// `~/Library/Caches` on macOS and `LOCALAPPDATA` on Windows.
+    pub fn cache_dir(&self) -> &Path {
+        &self.cache_dir
+    }
+
+    /// Returns true if the dictionary of `tag` is in the cache.
+    pub fn is_cached(&self, tag: &str) -> bool {
+        let dir = self.cache_dir.join(tag);
+        dir.join("index.aff").is_file() && dir.join("index.dic").is_file()
+    }
+
+    /// Returns the dictionary of `tag`, a BCP 47 tag as used by the
+    /// collection like `nl` or `en-GB`. It is downloaded when it is not in
+    /// the cache, or when the cached files do not match the checksums.
+    /// Fails with `Error::ChecksumMismatch` when a download does not
+    /// match.
+    pub fn get(&self, tag: &str) -> Result<Dictionary> {
+        if tag.is_empty() || !tag.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+            return Err(Error::InvalidLanguageTag(tag.to_string()));
+        }
+        let dir = self.cache_dir.join(tag);
+        let affix = dir.join("index.aff");
+        let dictionary = dir.join("index.dic");
+        let (affix_sum, dictionary_sum) = match self.checksums.get(tag) {
+            Some((a, d)) => (Some(a.as_str()), Some(d.as_str())),
+            None => (None, None),
+        };
+        let cached = |path: &Path, checksum: Option<&str>| match std::fs::read(path) {
+            Ok(contents) => checksum.is_none_or(|sum| sha256_hex(&contents) == sum),
+            Err(_) => false,
+        };
+        if !(cached(&affix, affix_sum) && cached(&dictionary, dictionary_sum)) {
+            std::fs::create_dir_all(&dir).map_err(Error::io(Operation::Fetch, &dir))?;
+            self.fetch(&format!("{tag}/index.aff"), &affix, affix_sum)?;
+            self.fetch(&format!("{tag}/index.dic"), &dictionary, dictionary_sum)?;
+        }
+        Ok(Dictionary::from_files(affix, dictionary))
+    }
+
+    /// Downloads `file` to `path`, via a temporary file so an interrupted
+    /// download does not leave a partial file in the cache.
+    fn fetch(&self, file: &str, path: &Path, checksum: Option<&str>) -> Result<()> {
+        let url = format!("{}/{file}", self.base_url);
+        let contents = (self.download)(&url)?;
+        if let Some(expected) = checksum {
+            if sha256_hex(&contents) != expected {
+                return Err(Error::ChecksumMismatch(url));
+            }
+        }
+        let partial = path.with_extension("part");
+        std::fs::write(&partial, contents).map_err(Error::io(Operation::Fetch, &partial))?;
+        std::fs::rename(partial, path).map_err(Error::io(Operation::Fetch, path))?;
+        Ok(())
+    }
+}
+
+impl core::fmt::Debug for DictionaryFetcher {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        fmt.debug_struct("DictionaryFetcher")
+            .field("base_url", &self.base_url)
+            .field("cache_dir", &self.cache_dir)
+            .field("checksums", &self.checksums)
+            .finish_non_exhaustive()
+    }
+}
+
+fn user_cache_dir() -> PathBuf {
+    let var = |name| std::env::var_os(name).map(PathBuf::from);
+    if cfg!(windows) {
+        return var("LOCALAPPDATA").unwrap_or_else(std::env::temp_dir);
+    }
+    let home = var("HOME");
+    if cfg!(target_os = "macos") {
+        if let Some(home) = home {
+            return home.join("Library/Caches");
+        }
+    }
+    var("XDG_CACHE_HOME")
+        .or_else(|| home.map(|h| h.join(".cache")))
+        .unwrap_or_else(std::env::temp_dir)
+}
+
+/// Returns the SHA-256 of `data` as lowercase hex.
+fn sha256_hex(data: &[u8]) -> String {
+    Sha256::digest(data)
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
@@ -0,0 +1,235 @@
+//! The functions of the hunspell library: linked with `hunspell-sys`, or
+//! loaded when the first spell checker is opened with the `dylib` feature.
+
+#![allow(non_snake_case)]
+
+#[cfg(not(any(feature = "linked", feature = "dylib")))]
+compile_error!("hunspell-rs needs one of the features `bundled`, `linked` or `dylib`");
+
+#[cfg(feature = "linked")]
+pub use hunspell_sys::Hunhandle;
+#[cfg(not(feature = "dylib"))]
+pub(crate) use hunspell_sys::{
+    Hunspell_add, Hunspell_add_dic, Hunspell_add_with_affix, Hunspell_analyze, Hunspell_create,
+    Hunspell_create_key, Hunspell_destroy, Hunspell_free_list, Hunspell_generate,
+    Hunspell_generate2, Hunspell_get_dic_encoding, Hunspell_remove, Hunspell_spell, Hunspell_stem,
+    Hunspell_stem2, Hunspell_suggest,
+};
+
+#[cfg(feature = "dylib")]
+pub(crate) use dynamic::*;
+#[cfg(feature = "dylib")]
+pub use dynamic::{hunspell_library, load_hunspell_library};
+
+/// A hunspell handle, as returned by `Hunspell_create()`.
+#[cfg(not(feature = "linked"))]
+#[repr(C)]
+pub struct Hunhandle {
+    _unused: [u8; 0],
+}
+
+/// Makes sure there is a hunspell library before a handle is made.
+#[cfg(not(feature = "dylib"))]
+pub(crate) fn load() -> crate::Result<()> {
+    Ok(())
+}
+
+#[cfg(feature = "dylib")]
+mod dynamic {
+    use std::{
+        ffi::{c_char, c_int, OsStr},
+        path::{Path, PathBuf},
+        sync::OnceLock,
+    };
+
+    use libloading::Library;
+
+    use super::Hunhandle;
+    use crate::{Error, Result};
+
+    /// The variable with the path of the library to load, instead of the
+    /// linked or the system library.
+    const LIBRARY_VARIABLE: &str = "HUNSPELL_LIBRARY";
+
+    /// The names the system library is looked for by, newest first.
+    #[cfg(not(feature = "linked"))]
+    const LIBRARY_NAMES: &[&str] = if cfg!(windows) {
+        &["libhunspell-1.7-0.dll", "libhunspell.dll", "hunspell.dll"]
+    } else if cfg!(target_os = "macos") {
+        &[
+            "libhunspell-1.7.0.dylib",
+            "libhunspell-1.7.dylib",
+            "libhunspell-1.6.0.dylib",
+            "libhunspell.dylib",
+        ]
+    } else {
+        &[
+            "libhunspell-1.7.so.0",
+            "libhunspell-1.6.so.0",
+            "libhunspell.so",
+        ]
+    };
+
+    /// Where the functions come from, chosen once: a handle has to be
+    /// passed to the library that made it.
+    static BACKEND: OnceLock<Backend> = OnceLock::new();
+
+    enum Backend {
+        #[cfg(feature = "linked")]
+        Linked,
+        Loaded(Loaded),
+    }
+
+    struct Loaded {
+        path: PathBuf,
+        functions: Functions,
+        // the functions point into it
+        _library: Library,
+    }
+
+    impl Loaded {
+        fn open(path: &OsStr) -> Result<Self> {
+            let failed = |e: libloading::Error| {
+                Error::LibraryLoading(format!("{}: {e}", Path::new(path).display()))
+            };
+            // SAFETY: libhunspell runs no initialization code that makes
+            // assumptions about the loading process
+            let library = unsafe { Library::new(path) }.map_err(failed)?;
+            let functions = unsafe { Functions::of(&library) }.map_err(failed)?;
+            Ok(Loaded {
+                path: PathBuf::from(path),
+                functions,
+                _library: library,
+            })
+        }
+    }
+
+    macro_rules! functions {
+        ($($name:ident($($arg:ident: $ty:ty),*) $(-> $ret:ty)?;)*) => {
+            /// The functions of a loaded library.
+            struct Functions {
+                $($name: unsafe extern "C" fn($($ty),*) $(-> $ret)?,)*
+            }
+
+            impl Functions {
+                /// Looks the functions up in `library`, they have to have
+                /// the signatures of the hunspell C API.
+                unsafe fn of(library: &Library) -> std::result::Result<Self, libloading::Error> {
+                    Ok(Functions {
+                        $($name: *library.get(concat!(stringify!($name), "\0").as_bytes())?,)*
+                    })
+                }
+            }
+
+            $(
+                pub(crate) unsafe fn $name($($arg: $ty),*) $(-> $ret)? {
+                    match backend() {
+                        #[cfg(feature = "linked")]
+                        Backend::Linked => hunspell_sys::$name($($arg),*),
+                        Backend::Loaded(loaded) => (loaded.functions.$name)($($arg),*),
+                    }
+                }
+            )*
+        };
+    }
+
+    functions! {
+        Hunspell_create(affpath: *const c_char, dpath: *const c_char) -> *mut Hunhandle;
+        Hunspell_create_key(affpath: *const c_char, dpath: *const c_char, key: *const c_char) -> *mut Hunhandle;
+        Hunspell_destroy(handle: *mut Hunhandle);
+        Hunspell_add_dic(handle: *mut Hunhandle, dpath: *const c_char) -> c_int;
+        Hunspell_spell(handle: *mut Hunhandle, word: *const c_char) -> c_int;
+        Hunspell_get_dic_encoding(handle: *mut Hunhandle) -> *mut c_char;
+        Hunspell_suggest(handle: *mut Hunhandle, list: *mut *mut *mut c_char, word: *const c_char) -> c_int;
+        Hunspell_analyze(handle: *mut Hunhandle, list: *mut *mut *mut c_char, word: *const c_char) -> c_int;
+        Hunspell_stem(handle: *mut Hunhandle, list: *mut *mut *mut c_char, word: *const c_char) -> c_int;
+        Hunspell_stem2(handle: *mut Hunhandle, list: *mut *mut *mut c_char, desc: *mut *mut c_char, n: c_int) -> c_int;
+        Hunspell_generate(handle: *mut Hunhandle, list: *mut *mut *mut c_char, word: *const c_char, word2: *const c_char) -> c_int;
+        Hunspell_generate2(handle: *mut Hunhandle, list: *mut *mut *mut c_char, word: *const c_char, desc: *mut *mut c_char, n: c_int) -> c_int;
+        Hunspell_add(handle: *mut Hunhandle, word: *const c_char) -> c_int;
+        Hunspell_add_with_affix(handle: *mut Hunhandle, word: *const c_char, example: *const c_char) -> c_int;
+        Hunspell_remove(handle: *mut Hunhandle, word: *const c_char) -> c_int;
+        Hunspell_free_list(handle: *mut Hunhandle, list: *mut *mut *mut c_char, n: c_int);
+    }
+
+    fn backend() -> &'static Backend {
+        // every function but the create ones is passed a handle, and
+        // handles are only made after load()
+        BACKEND.get().expect("hunspell library not loaded")
+    }
+
+    /// Chooses the library when no spell checker was opened yet: the one in
+    /// `HUNSPELL_LIBRARY` if set, else the linked one if there is one, else
+    /// the system library.
+    pub(crate) fn load() -> Result<()> {
+        if BACKEND.get().is_some() {
+            return Ok(());
+        }
+        let backend = match std::env::var_os(LIBRARY_VARIABLE) {
+            Some(path) => Backend::Loaded(Loaded::open(&path)?),
+            None => default_backend()?,
+        };
+        // when another thread was first, its library is used
+        let _ = BACKEND.set(backend);
+        Ok(())
+    }
+
+    #[cfg(feature = "linked")]
+    fn default_backend() -> Result<Backend> {
+        Ok(Backend::Linked)
+    }
+
+    #[cfg(not(feature = "linked"))]
+    fn default_backend() -> Result<Backend> {
+        let mut failures = Vec::new();
+        for name in LIBRARY_NAMES {
+            match Loaded::open(name.as_ref()) {
+                Ok(loaded) => return Ok(Backend::Loaded(loaded)),
+                Err(e) => failures.push(e.to_string()),
+            }
+        }
+        Err(Error::LibraryLoading(format!(
+            "no hunspell library found, set {LIBRARY_VARIABLE} to its path ({})",
+            failures.join("; ")
+        )))
+    }
+
+    /// Loads the hunspell library at `path` for all spell checkers,
+    /// instead of the one chosen when the first spell checker is opened:
+    /// the library in the `HUNSPELL_LIBRARY` environment variable, the
+    /// linked library with the `bundled` or `linked` feature, or else the
+    /// system library.
+    ///
+    /// It has to be called before a spell checker is opened. Fails with
+    /// `Error::LibraryLoading` when the library cannot be loaded, lacks a
+    /// hunspell function, or another library is already in use.
+    pub fn load_hunspell_library<P: AsRef<Path>>(path: P) -> Result<()> {
+        let path = path.as_ref();
+        if let Some(backend) = BACKEND.get() {
+            return match backend {
+                Backend::Loaded(loaded) if loaded.path == path => Ok(()),
+                _ => Err(Error::LibraryLoading(format!(
+                    "{}: another hunspell library is in use",
+                    path.display()
+                ))),
+            };
+        }
+        let loaded = Loaded::open(path.as_os_str())?;
+        BACKEND.set(Backend::Loaded(loaded)).map_err(|_| {
+            Error::LibraryLoading(format!(
+                "{}: another hunspell library is in use",
+                path.display()
+            ))
+        })
+    }
+
+    /// Returns the path of the hunspell library that was loaded, or `None`
+    /// when the linked library is used or no spell checker was opened yet.
+    pub fn hunspell_library() -> Option<&'static Path> {
+        match BACKEND.get()? {
+            Backend::Loaded(loaded) => Some(&loaded.path),
+            #[cfg(feature = "linked")]
+            Backend::Linked => None,
+        }
+    }
+}
@@ -0,0 +1,221 @@
+//! Ownership of the memory that hunspell hands out.
+
+use std::{
+    ffi::{c_char, c_int, CStr, CString},
+    path::Path,
+    ptr::{null_mut, NonNull},
+};
+
+use crate::{encoding::Codec, ffi, Error, Result};
+
+/// A list of strings made by hunspell, freed with `Hunspell_free_list()`
+/// when dropped. It borrows the handle that made it, so it cannot outlive
+/// the handle.
+///
+/// All list-returning hunspell functions go through this type; never take
+/// ownership of a list in any other way, it was not allocated by Rust.
+pub(crate) struct HunspellList<'a> {
+    handle: &'a NonNull<ffi::Hunhandle>,
+    list: *mut *mut c_char,
+    len: usize,
+}
+
+impl<'a> HunspellList<'a> {
+    /// Calls `f` with the handle and the place for the list, `f` returns
+    /// the length of the list like the hunspell functions do.
+    pub(crate) fn new<F>(handle: &'a NonNull<ffi::Hunhandle>, f: F) -> Result<Self>
+    where
+        F: FnOnce(*mut ffi::Hunhandle, &mut *mut *mut c_char) -> c_int,
+    {
+        let mut list = null_mut();
+        let len = f(handle.as_ptr(), &mut list);
+        // frees what was made on the error paths below
+        let mut guard = HunspellList {
+            handle,
+            list,
+            len: 0,
+        };
+        if len < 0 {
+            return Err(Error::NegativeListLength(len));
+        }
+        // hunspell does not allocate a list when there are no results
+        if len > 0 && list.is_null() {
+            return Err(Error::NullPtr);
+        }
+        guard.len = len as usize;
+        Ok(guard)
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns the list for hunspell functions that take one, like
+    /// `Hunspell_stem2()`, with its length.
+    pub(crate) fn as_raw(&self) -> (*mut *mut c_char, c_int) {
+        (self.list, self.len as c_int)
+    }
+
+    /// Returns the string at `index`, `None` when it is out of range or
+    /// hunspell stored a null pointer.
+    pub(crate) fn get(&self, index: usize) -> Option<&CStr> {
+        if index >= self.len {
+            return None;
+        }
+        // SAFETY: hunspell returned a list of len strings
+        let p = unsafe { *self.list.add(index) };
+        if p.is_null() {
+            return None;
+        }
+        // SAFETY: checked for null ptr, hunspell strings end with a nul
+        Some(unsafe { CStr::from_ptr(p) })
+    }
+
+    /// Converts the strings to `String`s with `codec`.
+    pub(crate) fn to_vec(&self, codec: Codec) -> Result<Vec<String>> {
+        (0..self.len)
+            .map(|i| {
+                let s = self.get(i).ok_or(Error::NullPtr)?;
+                Ok(codec.decode(s.to_bytes())?.into_owned())
+            })
+            .collect()
+    }
+}
+
+impl Drop for HunspellList<'_> {
+    fn drop(&mut self) {
+        if !self.list.is_null() {
+            // SAFETY: the list was made by this handle and is freed once
+            unsafe {
+                ffi::Hunspell_free_list(self.handle.as_ptr(), &mut self.list, self.len as c_int)
+            };
+        }
+    }
+}
+
+impl std::fmt::Debug for HunspellList<'_> {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fmt.debug_list()
+            .entries((0..self.len).map(|i| self.get(i)))
+            .finish()
+    }
+}
+
+/// A path as hunspell can open it, for the duration of a call that reads
+/// the file, like `Hunspell_create()` and `Hunspell_add_dic()`.
+///
+/// Hunspell opens files with the narrow C++ functions, which on Windows
+/// take the ANSI code page rather than UTF-8. Paths that are not ASCII
+/// are passed the one way hunspell opens as UTF-8 there: absolute with
+/// the `\\?\` prefix, which only hunspell built with MSVC supports.
+/// With other compilers the file is copied to an ASCII path in the
+/// temporary directory, which is removed when the `HunspellPath` is
+/// dropped. Copies left behind by a process that died are removed by the
+/// next process that makes one.
+pub(crate) struct HunspellPath {
+    path: CString,
+    #[cfg(windows)]
+    copy: Option<std::path::PathBuf>,
+}
+
+impl HunspellPath {
+    #[cfg(not(windows))]
+    pub(crate) fn new(path: &Path) -> Result<Self> {
+        Ok(HunspellPath {
+            path: CString::new(path.as_os_str().as_encoded_bytes())?,
+        })
+    }
+
+    #[cfg(windows)]
+    pub(crate) fn new(path: &Path) -> Result<Self> {
+        let plain = |path: &str| -> Result<Self> {
+            Ok(HunspellPath {
+                path: CString::new(path)?,
+                copy: None,
+            })
+        };
+        match path.to_str() {
+            Some(ascii) if ascii.is_ascii() => plain(ascii),
+            _ if cfg!(target_env = "msvc") => {
+                // canonical paths have the prefix
+                let canonical = path.canonicalize()?;
+                match canonical.to_str() {
+                    Some(utf8) if utf8.starts_with(r"\\?\") => plain(utf8),
+                    _ => Err(Error::UnsupportedPath(path.to_path_buf())),
+                }
+            }
+            _ => Self::copied(path),
+        }
+    }
+
+    /// Copies the file at `path` to an ASCII path.
+    #[cfg(windows)]
+    fn copied(path: &Path) -> Result<Self> {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        static COPIES: AtomicUsize = AtomicUsize::new(0);
+        static SWEEP: std::sync::Once = std::sync::Once::new();
+        SWEEP.call_once(remove_stale_copies);
+        let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+        let copy = std::env::temp_dir().join(format!(
+            "{COPY_PREFIX}{}-{}.{extension}",
+            std::process::id(),
+            COPIES.fetch_add(1, Ordering::Relaxed)
+        ));
+        let ascii = match copy.to_str() {
+            Some(ascii) if ascii.is_ascii() => CString::new(ascii)?,
+            _ => return Err(Error::UnsupportedPath(path.to_path_buf())),
+        };
+        std::fs::copy(path, &copy).map_err(Error::io(Operation::Open, path))?;
+        Ok(HunspellPath {
+            path: ascii,
+            copy: Some(copy),
+        })
+    }
+
+    pub(crate) fn as_ptr(&self) -> *const c_char {
+        self.path.as_ptr()
+    }
+}
+
+/// The start of the names of the copies of `HunspellPath::copied()`.
+#[cfg(windows)]
+const COPY_PREFIX: &str = "hunspell-rs-copy-";
+
+/// Removes the copies in the temporary directory that are older than a
+/// handle takes to create, which were left by a process that died.
+#[cfg(windows)]
+fn remove_stale_copies() {
+    const STALE: std::time::Duration = std::time::Duration::from_secs(60 * 60);
+
+    let Ok(entries) = std::fs::read_dir(std::env::temp_dir()) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let is_copy = entry
+            .file_name()
+            .to_str()
+            .is_some_and(|name| name.starts_with(COPY_PREFIX));
+        // copying keeps the modification time, not the creation time
+        let is_stale = || {
+            entry
+                .metadata()
+                .and_then(|m| m.created())
+                .ok()
+                .and_then(|created| created.elapsed().ok())
+                .is_some_and(|age| age > STALE)
+        };
+        if is_copy && is_stale() {
+            let _ = std::fs::remove_file(entry.path());
+        }
+    }
+}
+
+#[cfg(windows)]
+impl Drop for HunspellPath {
+    fn drop(&mut self) {
+        if let Some(copy) = &self.copy {
+            let _ = std::fs::remove_file(copy);
+        }
+    }
+}
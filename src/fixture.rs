@@ -0,0 +1,251 @@
+//! Throwaway dictionaries for tests, with the `test-util` feature.
+
+use std::{
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use crate::{
+    aff::{AffixFile, AffixGroup, AffixRule, Directive},
+    dic::{DicEntry, DicFile},
+    encoding::Codec,
+    Dictionary, Error, Result, SpellChecker,
+};
+
+/// Numbers the directories of the fixtures of this process.
+static NEXT_FIXTURE: AtomicUsize = AtomicUsize::new(0);
+
+/// Writes an affix and dictionary file pair for a test, from words, affix
+/// rules and other directives, instead of copying fixture files around.
+/// The files are written to a new directory in the temporary directory,
+/// which is removed when the [`Fixture`] is dropped.
+///
+/// ```
+/// use hunspell_rs::FixtureBuilder;
+///
+/// let fixture = FixtureBuilder::new()
+///     .with_suffix("S", [("0", "s", "[^sxzhy]"), ("y", "ies", "[^aeiou]y")])
+///     .with_word("cat/S")
+///     .with_word("city/S")
+///     .with_setting("KEY", "qwertyuiop|asdfghjkl|zxcvbnm")
+///     .write()
+///     .unwrap();
+/// let spell = fixture.open().unwrap();
+/// assert_eq!(Ok(true), spell.check("cities"));
+/// assert_eq!(Ok(false), spell.check("citys"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FixtureBuilder {
+    encoding: Option<String>,
+    directives: Vec<Directive>,
+    entries: Vec<DicEntry>,
+}
+
+impl FixtureBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a line of the dictionary, a word with optional flags and
+    /// morphological fields like `cat/S po:noun`.
+    pub fn with_word<S: AsRef<str>>(mut self, line: S) -> Self {
+        self.entries.push(DicEntry::parse(line.as_ref()));
+        self
+    }
+
+    /// Adds lines of the dictionary, see
+    /// [`with_word()`](FixtureBuilder::with_word).
+    pub fn with_words<I, S>(self, lines: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        lines.into_iter().fold(self, Self::with_word)
+    }
+
+    /// Adds `count` made up lowercase words of 2 to 10 ASCII letters, the
+    /// same ones for the same `seed`, for property tests and fuzzing.
+    pub fn with_random_words(mut self, count: usize, seed: u64) -> Self {
+        let mut state = seed;
+        let mut next = move || {
+            // SplitMix64
+            state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+            let mut x = state;
+            x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+            x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+            x ^ (x >> 31)
+        };
+        for _ in 0..count {
+            let len = 2 + (next() % 9) as usize;
+            let word: String = (0..len)
+                .map(|_| (b'a' + (next() % 26) as u8) as char)
+                .collect();
+            self.entries.push(DicEntry::new(word));
+        }
+        self
+    }
+
+    /// Adds the suffix rules of `flag`, as `(strip, add, condition)` like
+    /// they are written in an affix file, with `0` for nothing.
+    pub fn with_suffix<S, I, R>(mut self, flag: S, rules: I) -> Self
+    where
+        S: AsRef<str>,
+        I: IntoIterator<Item = (R, R, R)>,
+        R: AsRef<str>,
+    {
+        self.directives
+            .push(Directive::Suffix(affix_group(flag.as_ref(), rules)));
+        self
+    }
+
+    /// Adds the prefix rules of `flag`, see
+    /// [`with_suffix()`](FixtureBuilder::with_suffix).
+    pub fn with_prefix<S, I, R>(mut self, flag: S, rules: I) -> Self
+    where
+        S: AsRef<str>,
+        I: IntoIterator<Item = (R, R, R)>,
+        R: AsRef<str>,
+    {
+        self.directives
+            .push(Directive::Prefix(affix_group(flag.as_ref(), rules)));
+        self
+    }
+
+    /// Adds another directive to the affix file, like `COMPOUNDFLAG X`
+    /// or `FLAG long`. An empty `value` writes only the name.
+    pub fn with_setting<N: AsRef<str>, V: AsRef<str>>(mut self, name: N, value: V) -> Self {
+        let value = value.as_ref();
+        self.directives.push(Directive::Setting {
+            name: name.as_ref().to_string(),
+            value: (!value.is_empty()).then(|| value.to_string()),
+        });
+        self
+    }
+
+    /// Writes the files in `encoding` instead of UTF-8, i.e. `ISO8859-1`.
+    /// Fails with `Error::UnsupportedEncoding` for an encoding hunspell-rs
+    /// has no table for.
+    pub fn with_encoding<S: AsRef<str>>(mut self, encoding: S) -> Result<Self> {
+        let encoding = encoding.as_ref();
+        Codec::lookup(encoding).ok_or_else(|| Error::UnsupportedEncoding(encoding.to_string()))?;
+        self.encoding = Some(encoding.to_string());
+        Ok(self)
+    }
+
+    pub fn encoding(&self) -> &str {
+        self.encoding.as_deref().unwrap_or("UTF-8")
+    }
+
+    /// Returns the affix file, starting with the encoding.
+    pub fn affix_file(&self) -> AffixFile {
+        let mut directives = vec![Directive::Encoding(self.encoding().to_string())];
+        directives.extend(self.directives.iter().cloned());
+        AffixFile { directives }
+    }
+
+    pub fn dic_file(&self) -> DicFile {
+        DicFile {
+            entries: self.entries.clone(),
+        }
+    }
+
+    /// Writes the files to a new directory in the temporary directory.
+    /// Fails with `Error::InvalidWord` when a word has a character the
+    /// encoding cannot write.
+    pub fn write(&self) -> Result<Fixture> {
+        let dir = std::env::temp_dir().join(format!(
+            "hunspell-rs-fixture-{}-{}",
+            std::process::id(),
+            NEXT_FIXTURE.fetch_add(1, Ordering::Relaxed)
+        ));
+        let codec = Codec::for_name(self.encoding());
+        for entry in &self.entries {
+            if let Some(c) = entry.word.chars().find(|&c| !codec.can_encode(c)) {
+                return Err(Error::InvalidWord {
+                    word: entry.word.clone(),
+                    character: Some(c),
+                });
+            }
+        }
+        std::fs::create_dir_all(&dir)?;
+        let fixture = Fixture {
+            affix: dir.join("fixture.aff"),
+            dictionary: dir.join("fixture.dic"),
+            dir,
+        };
+        self.affix_file().write(&fixture.affix)?;
+        self.dic_file()
+            .write(&fixture.dictionary, self.encoding())?;
+        Ok(fixture)
+    }
+}
+
+/// The files written by [`FixtureBuilder::write()`], removed with their
+/// directory when it is dropped.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Fixture {
+    dir: PathBuf,
+    affix: PathBuf,
+    dictionary: PathBuf,
+}
+
+impl Fixture {
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn affix(&self) -> &Path {
+        &self.affix
+    }
+
+    pub fn dictionary(&self) -> &Path {
+        &self.dictionary
+    }
+
+    /// Opens a spell checker of the files.
+    pub fn open(&self) -> Result<SpellChecker> {
+        SpellChecker::new(&self.affix, &self.dictionary)
+    }
+
+    /// Returns a [`Dictionary`] of the files. It cannot be opened after
+    /// the fixture is dropped.
+    pub fn to_dictionary(&self) -> Dictionary {
+        Dictionary::from_files(&self.affix, &self.dictionary)
+    }
+}
+
+impl Drop for Fixture {
+    fn drop(&mut self) {
+        // a throwaway directory, left behind when it cannot be removed
+        let _ = std::fs::remove_dir_all(&self.dir);
+    }
+}
+
+/// Returns the affix group of `flag` with `rules`, combining with affixes
+/// of the other kind.
+fn affix_group<I, R>(flag: &str, rules: I) -> AffixGroup
+where
+    I: IntoIterator<Item = (R, R, R)>,
+    R: AsRef<str>,
+{
+    let field = |s: &str| {
+        if s == "0" {
+            String::new()
+        } else {
+            s.to_string()
+        }
+    };
+    AffixGroup {
+        flag: flag.to_string(),
+        cross_product: true,
+        rules: rules
+            .into_iter()
+            .map(|(strip, add, condition)| AffixRule {
+                strip: field(strip.as_ref()),
+                add: field(add.as_ref()),
+                condition: condition.as_ref().to_string(),
+                ..AffixRule::default()
+            })
+            .collect(),
+    }
+}
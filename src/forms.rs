@@ -0,0 +1,40 @@
+//! The forms of the words of a dictionary, made from its affix rules.
+
+use crate::{aff::AffixFile, dic::DicFile, Result, SpellChecker};
+
+impl SpellChecker {
+    /// Every form of `stem` that the entries of the dictionary files for
+    /// it make, see [`AffixFile::expand()`], converted with the `ICONV`
+    /// and `OCONV` tables. Words added at runtime are not included. Empty
+    /// when no dictionary has `stem`; fails when the files cannot be read,
+    /// i.e. when they are encrypted.
+    ///
+    /// ```
+    /// use hunspell_rs::SpellChecker;
+    ///
+    /// let spell = SpellChecker::new("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic").unwrap();
+    /// assert_eq!(vec!["cat", "cats"], spell.all_forms("cat").unwrap());
+    /// ```
+    pub fn all_forms<S>(&self, stem: S) -> Result<Vec<String>>
+    where
+        S: AsRef<str>,
+    {
+        let affix = AffixFile::read(&self.affix)?;
+        let encoding = self.encoding();
+        let stem = self.input_conversions().apply(stem.as_ref());
+        let oconv = self.output_conversions();
+        let mut forms: Vec<String> = Vec::new();
+        for dictionary in std::iter::once(&self.dictionary).chain(&self.additional_dictionaries) {
+            let dic = DicFile::read(dictionary, &encoding)?;
+            for entry in dic.entries.iter().filter(|e| e.word == stem) {
+                for form in affix.expand(entry) {
+                    let form = oconv.apply(&form).into_owned();
+                    if !forms.contains(&form) {
+                        forms.push(form);
+                    }
+                }
+            }
+        }
+        Ok(forms)
+    }
+}
@@ -0,0 +1,88 @@
+//! Ordering suggestions by how common the words are.
+
+use std::path::Path;
+
+use crate::{Error, Operation, Result, SpellChecker};
+
+impl SpellChecker {
+    /// Adds the counts of words in a corpus, for
+    /// [`suggest_by_frequency()`](SpellChecker::suggest_by_frequency).
+    /// Counts of a word that is already known are added up.
+    pub fn add_frequencies<I, S>(&self, frequencies: I)
+    where
+        I: IntoIterator<Item = (S, u64)>,
+        S: AsRef<str>,
+    {
+        let mut known = self.frequencies.borrow_mut();
+        for (word, count) in frequencies {
+            *known.entry(word.as_ref().to_string()).or_default() += count;
+        }
+    }
+
+    /// Reads a frequency list: a word and its count on every line,
+    /// separated by white space. Empty lines and lines starting with `#`
+    /// are skipped. Fails with `Error::InvalidFrequencyList` for other
+    /// lines.
+    pub fn read_frequencies<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let contents = std::fs::read_to_string(&path).map_err(Error::io(Operation::Load, &path))?;
+        let mut frequencies = Vec::new();
+        for (n, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let invalid = || Error::InvalidFrequencyList(format!("line {}: {line}", n + 1));
+            let mut fields = line.split_whitespace();
+            let (Some(word), Some(count), None) = (fields.next(), fields.next(), fields.next())
+            else {
+                return Err(invalid());
+            };
+            frequencies.push((word, count.parse().map_err(|_| invalid())?));
+        }
+        self.add_frequencies(frequencies);
+        Ok(())
+    }
+
+    /// Forgets all frequencies.
+    pub fn clear_frequencies(&self) {
+        self.frequencies.borrow_mut().clear();
+    }
+
+    /// Returns the count of `word`, or of its lowercase form when `word`
+    /// has none, and 0 for unknown words.
+    pub fn frequency<S: AsRef<str>>(&self, word: S) -> u64 {
+        let word = word.as_ref();
+        let frequencies = self.frequencies.borrow();
+        frequencies
+            .get(word)
+            .or_else(|| frequencies.get(&word.to_lowercase()))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Returns the suggestions of hunspell, the most frequent words first.
+    /// Words with the same count, i.e. those without one, keep the order
+    /// of hunspell.
+    ///
+    /// ```
+    /// use hunspell_rs::SpellChecker;
+    ///
+    /// let spell = SpellChecker::new(
+    ///     "tests/fixtures/frequency/frequency.aff",
+    ///     "tests/fixtures/frequency/frequency.dic",
+    /// )
+    /// .unwrap();
+    /// assert_eq!(vec!["the", "he", "hate", "hoe", "hue"], spell.suggest("hte").unwrap());
+    /// spell.add_frequencies([("hue", 20), ("hoe", 5)]);
+    /// assert_eq!(
+    ///     vec!["hue", "hoe", "the", "he", "hate"],
+    ///     spell.suggest_by_frequency("hte").unwrap()
+    /// );
+    /// ```
+    pub fn suggest_by_frequency<S: AsRef<str>>(&self, word: S) -> Result<Vec<String>> {
+        let mut suggestions = self.suggest(word)?;
+        // stable, so equal counts keep the order of hunspell
+        suggestions.sort_by_cached_key(|s| std::cmp::Reverse(self.frequency(s)));
+        Ok(suggestions)
+    }
+}
@@ -0,0 +1,138 @@
+//! A read-only spell checker that can be shared between threads.
+
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+    sync::{Mutex, MutexGuard},
+};
+
+use crate::{Language, Result, SpellChecker};
+
+/// A [`SpellChecker`] that can no longer be changed, made with
+/// [`SpellChecker::freeze()`]. It is `Send` and `Sync`, so it can be shared
+/// behind an `Arc` for read-mostly workloads.
+///
+/// Ignored words, the paths, the language and the session hash are read
+/// without a lock. Hunspell itself is not thread safe, so the calls into
+/// it take turns; use one `SpellChecker` per thread when those calls are
+/// the bottleneck.
+///
+/// ```
+/// use std::sync::Arc;
+/// use hunspell_rs::SpellChecker;
+///
+/// let spell = SpellChecker::new("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic").unwrap();
+/// let frozen = Arc::new(spell.freeze());
+/// let shared = frozen.clone();
+/// let found = std::thread::spawn(move || shared.check("cats")).join().unwrap();
+/// assert_eq!(Ok(true), found);
+/// ```
+#[derive(Debug)]
+pub struct FrozenSpellChecker {
+    spell_checker: Mutex<Locked>,
+    ignored: HashSet<String>,
+    affix: PathBuf,
+    dictionary: PathBuf,
+    language: Option<Language>,
+    session_hash: u64,
+}
+
+/// The spell checker behind the lock.
+#[derive(Debug)]
+struct Locked(SpellChecker);
+
+// SAFETY: hunspell handles are not tied to the thread that made them, and
+// the mutex makes sure only one thread at a time uses the handle and the
+// `RefCell`s of the `SpellChecker`. It is never handed out by reference.
+unsafe impl Send for Locked {}
+
+impl SpellChecker {
+    /// Returns a read-only version of this checker that can be shared
+    /// between threads, see [`FrozenSpellChecker`].
+    pub fn freeze(self) -> FrozenSpellChecker {
+        let ignored = self.ignored.borrow().clone();
+        FrozenSpellChecker {
+            ignored,
+            affix: self.affix.clone(),
+            dictionary: self.dictionary.clone(),
+            language: self.language.clone(),
+            session_hash: self.session_hash(),
+            spell_checker: Mutex::new(Locked(self)),
+        }
+    }
+}
+
+impl FrozenSpellChecker {
+    /// Returns the mutable `SpellChecker` again.
+    pub fn thaw(self) -> SpellChecker {
+        let locked = self
+            .spell_checker
+            .into_inner()
+            .unwrap_or_else(|e| e.into_inner());
+        locked.0
+    }
+
+    pub fn affix(&self) -> &Path {
+        &self.affix
+    }
+
+    pub fn dictionary(&self) -> &Path {
+        &self.dictionary
+    }
+
+    pub fn language(&self) -> Option<&Language> {
+        self.language.as_ref()
+    }
+
+    /// See [`SpellChecker::session_hash()`], it never changes.
+    pub fn session_hash(&self) -> u64 {
+        self.session_hash
+    }
+
+    /// See [`SpellChecker::is_ignored()`].
+    pub fn is_ignored<S: AsRef<str>>(&self, word: S) -> bool {
+        self.ignored.contains(word.as_ref())
+    }
+
+    /// See [`SpellChecker::check()`].
+    pub fn check<S: AsRef<str>>(&self, word: S) -> Result<bool> {
+        if self.is_ignored(word.as_ref()) {
+            return Ok(true);
+        }
+        self.lock().0.check(word)
+    }
+
+    /// See [`SpellChecker::suggest()`].
+    pub fn suggest<S: AsRef<str>>(&self, word: S) -> Result<Vec<String>> {
+        self.lock().0.suggest(word)
+    }
+
+    /// See [`SpellChecker::analyze()`].
+    pub fn analyze<S: AsRef<str>>(&self, word: S) -> Result<Vec<String>> {
+        self.lock().0.analyze(word)
+    }
+
+    /// See [`SpellChecker::stem()`].
+    pub fn stem<S: AsRef<str>>(&self, word: S) -> Result<Vec<String>> {
+        self.lock().0.stem(word)
+    }
+
+    /// See [`SpellChecker::generate()`].
+    pub fn generate<S: AsRef<str>>(&self, word1: S, word2: S) -> Result<Vec<String>> {
+        self.lock().0.generate(word1, word2)
+    }
+
+    /// Returns a frozen checker of `swapped` with the session of this one,
+    /// see [`SpellChecker::swap_dictionary()`]. The lock is only held while
+    /// the session is handed over, `swapped` is opened already.
+    pub(crate) fn hand_over(&self, mut swapped: SpellChecker) -> Result<FrozenSpellChecker> {
+        self.lock().0.hand_over(&mut swapped)?;
+        Ok(swapped.freeze())
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Locked> {
+        // only read methods are called, a panic cannot leave the spell
+        // checker half changed
+        self.spell_checker.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
@@ -0,0 +1,246 @@
+//! Cheap checks of a text besides its spelling: doubled words, sentences
+//! that start in lowercase and spaces around punctuation.
+
+use std::ops::Range;
+
+/// The rule a [`GrammarIssue`] breaks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GrammarRule {
+    /// The same word twice in a row, like `the the`.
+    DoubledWord,
+    /// A sentence that starts with a lowercase letter, like `end. then`.
+    LowercaseSentenceStart,
+    /// A space before punctuation, no space after a comma or semicolon, or
+    /// more than one space between words.
+    PunctuationSpacing,
+}
+
+/// A problem found by [`GrammarOptions::check()`], with the text that
+/// fixes it.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GrammarIssue {
+    pub rule: GrammarRule,
+    /// The byte range of the problem in the text.
+    pub range: Range<usize>,
+    /// The text of the range.
+    pub text: String,
+    /// What to replace the range by, i.e. nothing for a doubled word.
+    pub replacement: String,
+}
+
+impl GrammarIssue {
+    /// I.e. ``doubled word `the` ``.
+    pub fn message(&self) -> String {
+        match self.rule {
+            GrammarRule::DoubledWord => format!("doubled word `{}`", self.text.trim()),
+            GrammarRule::LowercaseSentenceStart => {
+                format!("sentence starts with lowercase `{}`", self.text)
+            }
+            GrammarRule::PunctuationSpacing if self.replacement.is_empty() => {
+                "space before punctuation".to_string()
+            }
+            GrammarRule::PunctuationSpacing if self.text.trim().is_empty() => {
+                "repeated spaces".to_string()
+            }
+            GrammarRule::PunctuationSpacing => format!("missing space after `{}`", self.text),
+        }
+    }
+}
+
+/// Which of the [`GrammarRule`]s are checked, all of them by default.
+///
+/// The rules only look at characters, not at the meaning of the text, so
+/// a lowercase word after an abbreviation like `etc.` is reported, but
+/// not after `e.g.` or a single letter. French spacing before `!`, `?`,
+/// `:` and `;` is reported as well.
+///
+/// ```
+/// use hunspell_rs::{GrammarOptions, GrammarRule};
+///
+/// let issues = GrammarOptions::default().check("It is the the end. then ,more");
+/// assert_eq!(GrammarRule::DoubledWord, issues[0].rule);
+/// assert_eq!(9..13, issues[0].range);
+/// assert_eq!("T", issues[1].replacement);
+/// assert_eq!("space before punctuation", issues[2].message());
+/// assert_eq!("missing space after `,`", issues[3].message());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GrammarOptions {
+    pub doubled_words: bool,
+    pub sentence_start: bool,
+    pub punctuation_spacing: bool,
+}
+
+impl Default for GrammarOptions {
+    fn default() -> Self {
+        GrammarOptions {
+            doubled_words: true,
+            sentence_start: true,
+            punctuation_spacing: true,
+        }
+    }
+}
+
+impl GrammarOptions {
+    /// Checks none of the rules.
+    pub fn none() -> Self {
+        GrammarOptions {
+            doubled_words: false,
+            sentence_start: false,
+            punctuation_spacing: false,
+        }
+    }
+
+    pub fn is_none(&self) -> bool {
+        *self == Self::none()
+    }
+
+    /// Returns the issues of `text`, in order.
+    pub fn check(&self, text: &str) -> Vec<GrammarIssue> {
+        let mut issues = Vec::new();
+        if self.doubled_words {
+            doubled_words(text, &mut issues);
+        }
+        if self.sentence_start {
+            sentence_starts(text, &mut issues);
+        }
+        if self.punctuation_spacing {
+            punctuation_spacing(text, &mut issues);
+        }
+        issues.sort_by_key(|i| (i.range.start, i.range.end));
+        issues
+    }
+}
+
+fn issue(text: &str, rule: GrammarRule, range: Range<usize>, replacement: String) -> GrammarIssue {
+    GrammarIssue {
+        rule,
+        text: text[range.clone()].to_string(),
+        range,
+        replacement,
+    }
+}
+
+/// Returns the ranges of the words of `text`: letters, digits and
+/// apostrophes.
+fn words(text: &str) -> Vec<Range<usize>> {
+    let mut words = Vec::new();
+    let mut start = None;
+    for (i, c) in text
+        .char_indices()
+        .chain(std::iter::once((text.len(), ' ')))
+    {
+        match (start, c.is_alphanumeric() || c == '\'' || c == '’') {
+            (None, true) => start = Some(i),
+            (Some(s), false) => {
+                words.push(s..i);
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    words
+}
+
+fn doubled_words(text: &str, issues: &mut Vec<GrammarIssue>) {
+    for pair in words(text).windows(2) {
+        let (first, second) = (&text[pair[0].clone()], &text[pair[1].clone()]);
+        let between = &text[pair[0].end..pair[1].start];
+        if between.chars().all(|c| c == ' ' || c == '\t')
+            && first.chars().any(char::is_alphabetic)
+            && first.to_lowercase() == second.to_lowercase()
+        {
+            issues.push(issue(
+                text,
+                GrammarRule::DoubledWord,
+                pair[0].end..pair[1].end,
+                String::new(),
+            ));
+        }
+    }
+}
+
+fn sentence_starts(text: &str, issues: &mut Vec<GrammarIssue>) {
+    let chars: Vec<(usize, char)> = text.char_indices().collect();
+    for (n, &(i, c)) in chars.iter().enumerate() {
+        if !matches!(c, '.' | '!' | '?') {
+            continue;
+        }
+        if c == '.' {
+            // an ellipsis, or an abbreviation like `e.g.` or `J.`
+            let word = text[..i]
+                .rsplit(|c: char| !c.is_alphanumeric() && c != '.')
+                .next()
+                .unwrap_or("");
+            if word.is_empty() || word.contains('.') || word.chars().count() == 1 {
+                continue;
+            }
+        }
+        let mut rest = chars[n + 1..]
+            .iter()
+            .skip_while(|(_, c)| matches!(c, '"' | '\'' | ')' | ']' | '”' | '’'));
+        let mut spaced = false;
+        for &(j, next) in rest.by_ref() {
+            if next.is_whitespace() {
+                spaced = true;
+                continue;
+            }
+            if spaced && next.is_lowercase() {
+                issues.push(issue(
+                    text,
+                    GrammarRule::LowercaseSentenceStart,
+                    j..j + next.len_utf8(),
+                    next.to_uppercase().collect(),
+                ));
+            }
+            break;
+        }
+    }
+}
+
+fn punctuation_spacing(text: &str, issues: &mut Vec<GrammarIssue>) {
+    let chars: Vec<(usize, char)> = text.char_indices().collect();
+    let mut n = 0;
+    while n < chars.len() {
+        let (i, c) = chars[n];
+        if c == ' ' || c == '\t' {
+            let end = chars[n..]
+                .iter()
+                .position(|&(_, c)| c != ' ' && c != '\t')
+                .map_or(chars.len(), |len| n + len);
+            let end_byte = chars.get(end).map_or(text.len(), |&(j, _)| j);
+            let next = chars.get(end).map(|&(_, c)| c);
+            let next_next = chars.get(end + 1).map(|&(_, c)| c);
+            if n > 0 {
+                if matches!(next, Some(',' | '.' | ';' | ':' | '!' | '?'))
+                    && !(next == Some('.') && next_next == Some('.'))
+                {
+                    issues.push(issue(
+                        text,
+                        GrammarRule::PunctuationSpacing,
+                        i..end_byte,
+                        String::new(),
+                    ));
+                } else if end - n > 1 && next.is_some() {
+                    issues.push(issue(
+                        text,
+                        GrammarRule::PunctuationSpacing,
+                        i..end_byte,
+                        " ".to_string(),
+                    ));
+                }
+            }
+            n = end;
+            continue;
+        }
+        if matches!(c, ',' | ';') && chars.get(n + 1).is_some_and(|(_, c)| c.is_alphabetic()) {
+            issues.push(issue(
+                text,
+                GrammarRule::PunctuationSpacing,
+                i..i + 1,
+                format!("{c} "),
+            ));
+        }
+        n += 1;
+    }
+}
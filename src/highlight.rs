@@ -0,0 +1,137 @@
+//! Highlighting misspelled words in text as it is written.
+
+use std::io::{self, Write};
+
+use crate::{text::check_plain_text, Speller};
+
+/// Pending text is checked anyway when it grows beyond this without white
+/// space, to bound the memory.
+const MAX_PENDING: usize = 64 * 1024;
+
+/// Passes text through to another writer, marking the misspelled words
+/// with ANSI escape codes, by default underlined in red. Like
+/// [`SpellChecker::check_text()`](crate::SpellChecker::check_text) URLs,
+/// email addresses and hex literals are skipped.
+///
+/// Text is held back until white space follows it, so a word written in
+/// pieces is checked as a whole. The rest is written by `flush()`,
+/// `into_inner()` and when the writer is dropped. Bytes that are not
+/// UTF-8 are passed through unchanged.
+///
+/// ```
+/// use std::io::Write;
+/// use hunspell_rs::{HighlightWriter, SpellChecker};
+///
+/// let spell = SpellChecker::new("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic").unwrap();
+/// let mut writer = HighlightWriter::new(&spell, Vec::new());
+/// write!(writer, "cats and pro").unwrap();
+/// write!(writer, "grams").unwrap();
+/// let output = writer.into_inner().unwrap();
+/// assert_eq!(
+///     "cats \x1b[4;31mand\x1b[0m programs",
+///     String::from_utf8(output).unwrap()
+/// );
+/// ```
+#[derive(Debug)]
+pub struct HighlightWriter<S: Speller, W: Write> {
+    speller: S,
+    // None only after into_inner()
+    inner: Option<W>,
+    style: String,
+    pending: Vec<u8>,
+}
+
+impl<S: Speller, W: Write> HighlightWriter<S, W> {
+    pub fn new(speller: S, inner: W) -> Self {
+        HighlightWriter {
+            speller,
+            inner: Some(inner),
+            style: "\x1b[4;31m".to_string(),
+            pending: Vec::new(),
+        }
+    }
+
+    /// Sets the escape code written before a misspelled word, i.e.
+    /// `"\x1b[1;33m"` for bold yellow. The word is followed by a reset.
+    pub fn with_style<T: Into<String>>(mut self, style: T) -> Self {
+        self.style = style.into();
+        self
+    }
+
+    pub fn style(&self) -> &str {
+        &self.style
+    }
+
+    pub fn speller(&self) -> &S {
+        &self.speller
+    }
+
+    pub fn get_ref(&self) -> &W {
+        self.inner.as_ref().expect("inner writer taken")
+    }
+
+    /// Writes the held back text and returns the inner writer.
+    pub fn into_inner(mut self) -> io::Result<W> {
+        self.write_pending(self.pending.len())?;
+        Ok(self.inner.take().expect("inner writer taken"))
+    }
+
+    /// Checks and writes the first `end` bytes of the pending text.
+    fn write_pending(&mut self, end: usize) -> io::Result<()> {
+        let pending: Vec<u8> = self.pending.drain(..end).collect();
+        let inner = self.inner.as_mut().expect("inner writer taken");
+        let mut rest = &pending[..];
+        while !rest.is_empty() {
+            let (valid, invalid) = match std::str::from_utf8(rest) {
+                Ok(text) => (text, 0),
+                Err(e) => (
+                    std::str::from_utf8(&rest[..e.valid_up_to()]).unwrap_or_default(),
+                    // an incomplete character at the end is passed as well
+                    e.error_len().unwrap_or(rest.len() - e.valid_up_to()),
+                ),
+            };
+            let misspellings = check_plain_text(|word| self.speller.check(word), valid)
+                .map_err(io::Error::other)?
+                .0;
+            let mut written = 0;
+            for m in misspellings {
+                inner.write_all(&rest[written..m.range.start])?;
+                write!(inner, "{}{}\x1b[0m", self.style, m.word)?;
+                written = m.range.end;
+            }
+            inner.write_all(&rest[written..valid.len() + invalid])?;
+            rest = &rest[valid.len() + invalid..];
+        }
+        Ok(())
+    }
+}
+
+impl<S: Speller, W: Write> Write for HighlightWriter<S, W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.pending.extend_from_slice(buf);
+        let end = self
+            .pending
+            .iter()
+            .rposition(u8::is_ascii_whitespace)
+            .map(|i| i + 1);
+        match end {
+            Some(end) => self.write_pending(end)?,
+            None if self.pending.len() > MAX_PENDING => self.write_pending(self.pending.len())?,
+            None => {}
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.write_pending(self.pending.len())?;
+        self.inner.as_mut().expect("inner writer taken").flush()
+    }
+}
+
+impl<S: Speller, W: Write> Drop for HighlightWriter<S, W> {
+    fn drop(&mut self) {
+        if self.inner.is_some() {
+            let _ = self.write_pending(self.pending.len());
+        }
+    }
+}
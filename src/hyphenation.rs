@@ -0,0 +1,178 @@
+//! Hyphenation with the pattern files of libhyphen, the hyphenation
+//! library of LibreOffice that comes with the hunspell dictionaries.
+
+use std::{collections::HashMap, path::Path};
+
+use crate::{encoding::Codec, Error, Operation, Result};
+
+/// Finds the places where words can be hyphenated, with the patterns of a
+/// libhyphen `hyph_*.dic` file and Liang's algorithm, like TeX.
+///
+/// The file starts with its encoding, followed by a pattern on every line:
+/// letters with digits between them, where an odd digit allows a hyphen
+/// and an even one forbids it, the highest digit of all matching patterns
+/// winning. A `.` matches the start or end of the word. The
+/// `LEFTHYPHENMIN` and `RIGHTHYPHENMIN` lines set how many characters are
+/// kept together at the start and the end, 2 by default.
+///
+/// The patterns of compound word boundaries before a `NEXTLEVEL` line are
+/// used like the others, and non-standard patterns, with a `/`, are left
+/// out.
+///
+/// ```
+/// use hunspell_rs::Hyphenator;
+///
+/// let hyphenator = Hyphenator::from_file("tests/fixtures/hyphen/hyph_en.dic").unwrap();
+/// assert_eq!(vec!["in", "for", "ma", "tion"], hyphenator.hyphenate("information"));
+/// assert_eq!(vec![2, 5, 7], hyphenator.hyphenation_points("information"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hyphenator {
+    // the letters of a pattern and its digits, one more than letters
+    patterns: HashMap<String, Vec<u8>>,
+    // the most characters of a pattern
+    longest: usize,
+    left_min: usize,
+    right_min: usize,
+}
+
+impl Hyphenator {
+    /// Reads a libhyphen pattern file.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Self::from_bytes(&std::fs::read(&path).map_err(Error::io(Operation::Load, &path))?)
+    }
+
+    /// Reads the contents of a libhyphen pattern file. Fails with
+    /// `Error::InvalidHyphenationPatterns` when the encoding is unknown or
+    /// a pattern has no letters.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let (first, rest) = match bytes.iter().position(|&b| b == b'\n') {
+            Some(i) => (&bytes[..i], &bytes[i + 1..]),
+            None => (bytes, &[][..]),
+        };
+        let encoding = String::from_utf8_lossy(first).trim().to_string();
+        let codec = Codec::lookup(&encoding).ok_or_else(|| {
+            Error::InvalidHyphenationPatterns(format!("unknown encoding {encoding}"))
+        })?;
+        let contents = codec.decode(rest)?;
+        let mut hyphenator = Hyphenator {
+            patterns: HashMap::new(),
+            longest: 0,
+            left_min: 2,
+            right_min: 2,
+        };
+        for (n, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            let invalid = || Error::InvalidHyphenationPatterns(format!("line {}: {line}", n + 2));
+            let mut fields = line.split_whitespace();
+            match (fields.next(), fields.next()) {
+                (None, _) => {}
+                (Some(comment), _) if comment.starts_with('%') => {}
+                (Some("LEFTHYPHENMIN"), Some(n)) => {
+                    hyphenator.left_min = n.parse().map_err(|_| invalid())?
+                }
+                (Some("RIGHTHYPHENMIN"), Some(n)) => {
+                    hyphenator.right_min = n.parse().map_err(|_| invalid())?
+                }
+                (
+                    Some(
+                        "COMPOUNDLEFTHYPHENMIN"
+                        | "COMPOUNDRIGHTHYPHENMIN"
+                        | "NOHYPHEN"
+                        | "NEXTLEVEL",
+                    ),
+                    _,
+                ) => {}
+                (Some(pattern), _) if pattern.contains('/') => {}
+                (Some(pattern), _) => {
+                    let (letters, digits) = parse_pattern(pattern).ok_or_else(invalid)?;
+                    hyphenator.longest = hyphenator.longest.max(digits.len() - 1);
+                    let known = hyphenator.patterns.entry(letters).or_default();
+                    if known.is_empty() {
+                        *known = digits;
+                    } else {
+                        known
+                            .iter_mut()
+                            .zip(digits)
+                            .for_each(|(k, d)| *k = (*k).max(d));
+                    }
+                }
+            }
+        }
+        Ok(hyphenator)
+    }
+
+    /// Keeps at least `left` characters at the start of a word and `right`
+    /// at the end together, instead of the minimums of the file.
+    pub fn with_min(mut self, left: usize, right: usize) -> Self {
+        self.left_min = left;
+        self.right_min = right;
+        self
+    }
+
+    pub fn left_min(&self) -> usize {
+        self.left_min
+    }
+
+    pub fn right_min(&self) -> usize {
+        self.right_min
+    }
+
+    /// Returns the byte offsets in `word` where it can be hyphenated.
+    pub fn hyphenation_points(&self, word: &str) -> Vec<usize> {
+        let offsets: Vec<usize> = word.char_indices().map(|(i, _)| i).collect();
+        // the word in lowercase, a character for a character, between dots
+        let dotted: Vec<char> = std::iter::once('.')
+            .chain(word.chars().map(|c| c.to_lowercase().next().unwrap_or(c)))
+            .chain(std::iter::once('.'))
+            .collect();
+        let mut levels = vec![0u8; dotted.len() + 1];
+        let mut key = String::new();
+        for start in 0..dotted.len() {
+            key.clear();
+            for (length, &c) in dotted[start..].iter().take(self.longest).enumerate() {
+                key.push(c);
+                if let Some(digits) = self.patterns.get(&key) {
+                    for (level, &digit) in levels[start..=start + length + 1].iter_mut().zip(digits)
+                    {
+                        *level = (*level).max(digit);
+                    }
+                }
+            }
+        }
+        // a hyphen before character i of the word is at level i + 1
+        (self.left_min.max(1)..offsets.len())
+            .filter(|&i| offsets.len() - i >= self.right_min.max(1) && levels[i + 1] % 2 == 1)
+            .map(|i| offsets[i])
+            .collect()
+    }
+
+    /// Returns the parts of `word` between its hyphenation points.
+    pub fn hyphenate<'a>(&self, word: &'a str) -> Vec<&'a str> {
+        let mut parts = Vec::new();
+        let mut start = 0;
+        for point in self.hyphenation_points(word) {
+            parts.push(&word[start..point]);
+            start = point;
+        }
+        parts.push(&word[start..]);
+        parts
+    }
+}
+
+/// Splits a pattern like `.ex1` in its letters and the digits before,
+/// between and after them, `None` when it has no letters.
+fn parse_pattern(pattern: &str) -> Option<(String, Vec<u8>)> {
+    let mut letters = String::new();
+    let mut digits = vec![0];
+    for c in pattern.chars() {
+        match c.to_digit(10) {
+            Some(d) => *digits.last_mut().expect("digits is never empty") = d as u8,
+            None => {
+                letters.extend(c.to_lowercase().next());
+                digits.push(0);
+            }
+        }
+    }
+    (!letters.is_empty()).then_some((letters, digits))
+}
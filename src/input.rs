@@ -0,0 +1,131 @@
+//! Deciding what to do with words hunspell should not be given, for
+//! services that check untrusted input.
+
+use std::borrow::Cow;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::SpellChecker;
+
+/// What a [`SpellChecker`] does with NUL characters in words, which
+/// cannot be passed to hunspell.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum NulHandling {
+    /// Fails with `Error::NulError`, the default.
+    #[default]
+    Fail,
+    /// Removes them: `ca\0t` is checked as `cat`.
+    Strip,
+    /// Replaces them by the character.
+    Replace(char),
+}
+
+/// How a [`SpellChecker`] guards hunspell against input it cannot or
+/// should not handle, set with
+/// [`set_input_policy()`](SpellChecker::set_input_policy).
+///
+/// A word that is rejected never reaches hunspell: `check()` returns
+/// false, and `suggest()`, `analyze()` and `stem()` return no results.
+/// Calls that change the dictionary, like `add()`, fail with
+/// `Error::InvalidWord` instead.
+///
+/// ```
+/// use hunspell_rs::{InputPolicy, SpellChecker};
+///
+/// let mut spell = SpellChecker::new("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic").unwrap();
+/// assert!(spell.check("ca\0t").is_err());
+/// spell.set_input_policy(InputPolicy::untrusted());
+/// assert_eq!(Ok(true), spell.check("ca\0t"));
+/// assert_eq!(Ok(false), spell.check("ca\u{7}t"));
+/// assert_eq!(Ok(false), spell.check("a".repeat(10_000)));
+/// ```
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct InputPolicy {
+    /// What is done with NUL characters.
+    pub nul: NulHandling,
+    /// Words with more characters than this are rejected. No limit by
+    /// default.
+    pub max_word_length: Option<usize>,
+    /// Rejects words with control characters, other than NUL when it is
+    /// stripped or replaced. Off by default.
+    pub reject_control_chars: bool,
+}
+
+impl InputPolicy {
+    /// The length limit of [`untrusted()`](InputPolicy::untrusted), well
+    /// above the longest words of natural languages.
+    pub const UNTRUSTED_MAX_WORD_LENGTH: usize = 100;
+
+    /// Strips NUL characters, and rejects words with control characters
+    /// or with more than
+    /// [`UNTRUSTED_MAX_WORD_LENGTH`](InputPolicy::UNTRUSTED_MAX_WORD_LENGTH)
+    /// characters.
+    pub fn untrusted() -> Self {
+        InputPolicy {
+            nul: NulHandling::Strip,
+            max_word_length: Some(Self::UNTRUSTED_MAX_WORD_LENGTH),
+            reject_control_chars: true,
+        }
+    }
+
+    /// Returns `word` with its NUL characters handled, or `None` if it is
+    /// rejected.
+    pub fn apply<'a>(&self, word: &'a str) -> Option<Cow<'a, str>> {
+        let word = match self.nul {
+            NulHandling::Strip if word.contains('\0') => Cow::Owned(word.replace('\0', "")),
+            NulHandling::Replace(c) if word.contains('\0') => {
+                Cow::Owned(word.replace('\0', c.encode_utf8(&mut [0; 4])))
+            }
+            _ => Cow::Borrowed(word),
+        };
+        if self.rejected_character(&word).is_some() || self.is_too_long(&word) {
+            None
+        } else {
+            Some(word)
+        }
+    }
+
+    /// The first character for which `word` is rejected.
+    fn rejected_character(&self, word: &str) -> Option<char> {
+        if !self.reject_control_chars {
+            return None;
+        }
+        word.chars()
+            .find(|&c| c.is_control() && !(c == '\0' && self.nul == NulHandling::Fail))
+    }
+
+    fn is_too_long(&self, word: &str) -> bool {
+        self.max_word_length
+            .is_some_and(|max| word.len() > max && word.chars().count() > max)
+    }
+
+    /// The error for a word that [`apply()`](InputPolicy::apply) rejects.
+    pub(crate) fn rejection(&self, word: &str) -> crate::Error {
+        crate::Error::InvalidWord {
+            word: word.to_string(),
+            character: self.rejected_character(word),
+        }
+    }
+}
+
+impl SpellChecker {
+    /// Handles NUL characters, and rejects words that are too long or
+    /// have control characters, as `policy` says, in all calls. By default
+    /// all words are passed on and NUL characters are an error.
+    pub fn set_input_policy(&mut self, policy: InputPolicy) {
+        self.input_policy = policy;
+        self.clear_analysis_cache();
+    }
+
+    pub fn input_policy(&self) -> InputPolicy {
+        self.input_policy
+    }
+
+    /// Returns true if the input policy keeps `word` from hunspell.
+    pub(crate) fn is_rejected_input(&self, word: &str) -> bool {
+        self.input_policy.apply(word).is_none()
+    }
+}
@@ -0,0 +1,99 @@
+//! Spans and counters around the calls to hunspell.
+
+use std::{path::Path, time::Instant};
+
+use crate::stats::StatsCounters;
+
+/// What a call to hunspell does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum FfiOperation {
+    Create,
+    Check,
+    Suggest,
+    Analyze,
+}
+
+impl FfiOperation {
+    #[cfg(feature = "tracing")]
+    fn name(&self) -> &'static str {
+        match self {
+            FfiOperation::Create => "create",
+            FfiOperation::Check => "check",
+            FfiOperation::Suggest => "suggest",
+            FfiOperation::Analyze => "analyze",
+        }
+    }
+}
+
+/// A call to hunspell, counted in the [`Stats`](crate::Stats) of the
+/// checker when they are enabled.
+///
+/// With the `tracing` feature the call is also recorded as a `hunspell`
+/// span at debug level with the `operation`, the `word_len` in bytes, the
+/// number of `results` and the `duration_us` in microseconds. Handles are
+/// created in a span with the `affix` file instead of the word length.
+pub(crate) struct FfiCall<'a> {
+    operation: FfiOperation,
+    stats: Option<&'a StatsCounters>,
+    start: Instant,
+    #[cfg(feature = "tracing")]
+    span: tracing::span::EnteredSpan,
+}
+
+impl<'a> FfiCall<'a> {
+    /// Starts `operation` on a word of `word_len` bytes.
+    #[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+    pub(crate) fn start(
+        operation: FfiOperation,
+        word_len: usize,
+        stats: Option<&'a StatsCounters>,
+    ) -> Self {
+        FfiCall {
+            operation,
+            stats,
+            #[cfg(feature = "tracing")]
+            span: tracing::debug_span!(
+                "hunspell",
+                operation = operation.name(),
+                word_len,
+                results = tracing::field::Empty,
+                duration_us = tracing::field::Empty,
+            )
+            .entered(),
+            start: Instant::now(),
+        }
+    }
+
+    /// Starts creating a handle for `affix`.
+    #[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+    pub(crate) fn create(affix: &Path) -> Self {
+        FfiCall {
+            operation: FfiOperation::Create,
+            stats: None,
+            #[cfg(feature = "tracing")]
+            span: tracing::debug_span!(
+                "hunspell",
+                operation = FfiOperation::Create.name(),
+                affix = %affix.display(),
+                results = tracing::field::Empty,
+                duration_us = tracing::field::Empty,
+            )
+            .entered(),
+            start: Instant::now(),
+        }
+    }
+
+    /// Records the number of results and the duration, and leaves the
+    /// span.
+    pub(crate) fn finish(self, results: usize) {
+        let elapsed = self.start.elapsed();
+        if let Some(stats) = self.stats {
+            stats.record(self.operation, results, elapsed);
+        }
+        #[cfg(feature = "tracing")]
+        {
+            self.span.record("results", results);
+            self.span.record("duration_us", elapsed.as_micros() as u64);
+        }
+    }
+}
@@ -0,0 +1,169 @@
+//! Going through the misspellings of a text one by one, as the spell check
+//! dialog of an editor does.
+
+use std::{collections::HashMap, ops::Range};
+
+use crate::{Error, Misspelling, Result, SpellChecker};
+
+/// What to do with the current misspelling of a [`CorrectionSession`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CorrectionAction {
+    /// Replaces the word by the suggestion with this index.
+    Replace(usize),
+    /// Replaces the word and its later occurrences by the suggestion with
+    /// this index.
+    ReplaceAll(usize),
+    /// Leaves the word as it is.
+    Ignore,
+    /// Leaves the word and its later occurrences as they are, and accepts
+    /// the word in the spell checker for the rest of its session, see
+    /// [`SpellChecker::ignore()`].
+    IgnoreAll,
+    /// Adds the word to the runtime dictionary, see
+    /// [`SpellChecker::add()`].
+    AddToDictionary,
+}
+
+/// Walks the misspellings of a text one at a time, applying a
+/// [`CorrectionAction`] to each, and keeps the text and the places of the
+/// misspellings that are left up to date with the replacements.
+///
+/// The text is checked like [`SpellChecker::check_text()`] does.
+///
+/// ```
+/// use hunspell_rs::{CorrectionAction, CorrectionSession, SpellChecker};
+///
+/// let mut spell = SpellChecker::new("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic").unwrap();
+/// let mut session = CorrectionSession::new(&mut spell, "catz, progam, catz").unwrap();
+/// assert_eq!("catz", session.current().unwrap().word);
+/// assert_eq!("cat", session.suggestions()[0]);
+/// session.apply(CorrectionAction::ReplaceAll(0)).unwrap();
+/// assert_eq!("progam", session.current().unwrap().word);
+/// assert_eq!(5..11, session.current().unwrap().range);
+/// session.apply(CorrectionAction::AddToDictionary).unwrap();
+/// assert!(session.is_done());
+/// assert_eq!("cat, progam, cat", session.into_text());
+/// assert_eq!(Ok(true), spell.check("progam"));
+/// ```
+#[derive(Debug)]
+pub struct CorrectionSession<'a> {
+    spell_checker: &'a mut SpellChecker,
+    text: String,
+    // The misspellings after the current one, with their place in the
+    // text before the replacements, which move them by `shift`.
+    remaining: std::vec::IntoIter<Misspelling>,
+    shift: isize,
+    current: Option<Misspelling>,
+    suggestions: Vec<String>,
+    // The replacements of `ReplaceAll`, by word.
+    replace_all: HashMap<String, String>,
+}
+
+impl<'a> CorrectionSession<'a> {
+    /// Checks `text` and goes to its first misspelling.
+    pub fn new<S: Into<String>>(spell_checker: &'a mut SpellChecker, text: S) -> Result<Self> {
+        let text = text.into();
+        let misspellings = spell_checker.check_text(&text)?;
+        let mut session = CorrectionSession {
+            spell_checker,
+            text,
+            remaining: misspellings.into_iter(),
+            shift: 0,
+            current: None,
+            suggestions: Vec::new(),
+            replace_all: HashMap::new(),
+        };
+        session.advance()?;
+        Ok(session)
+    }
+
+    /// The misspelling to decide on, with its place in the current
+    /// [`text()`](CorrectionSession::text). `None` when all are done.
+    pub fn current(&self) -> Option<&Misspelling> {
+        self.current.as_ref()
+    }
+
+    /// The suggestions for the current misspelling, which the indices of
+    /// `Replace` and `ReplaceAll` refer to.
+    pub fn suggestions(&self) -> &[String] {
+        &self.suggestions
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.current.is_none()
+    }
+
+    /// The text with the replacements made so far.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Returns the corrected text. The misspellings that are left are
+    /// kept as they are.
+    pub fn into_text(self) -> String {
+        self.text
+    }
+
+    /// Applies `action` to the current misspelling and goes to the next
+    /// one, skipping the words that were replaced all, ignored all or
+    /// added before. Fails with `Error::InvalidCorrection` when all
+    /// misspellings are done or there is no suggestion with the index.
+    pub fn apply(&mut self, action: CorrectionAction) -> Result<()> {
+        let current = self
+            .current
+            .as_ref()
+            .ok_or_else(|| Error::InvalidCorrection("there is no misspelling left".to_string()))?;
+        let replacement = match action {
+            CorrectionAction::Replace(index) | CorrectionAction::ReplaceAll(index) => {
+                Some(self.suggestions.get(index).cloned().ok_or_else(|| {
+                    Error::InvalidCorrection(format!(
+                        "`{}` has no suggestion {index}",
+                        current.word
+                    ))
+                })?)
+            }
+            _ => None,
+        };
+        let Misspelling { word, range } = current.clone();
+        match action {
+            CorrectionAction::IgnoreAll => self.spell_checker.ignore(&word),
+            CorrectionAction::AddToDictionary => self.spell_checker.add(&word)?,
+            _ => {}
+        }
+        if let Some(replacement) = replacement {
+            self.replace(range, &replacement);
+            if let CorrectionAction::ReplaceAll(_) = action {
+                self.replace_all.insert(word, replacement);
+            }
+        }
+        self.current = None;
+        self.advance()
+    }
+
+    /// Replaces `range` of the text and moves the misspellings after it.
+    fn replace(&mut self, range: Range<usize>, replacement: &str) {
+        self.shift += replacement.len() as isize - range.len() as isize;
+        self.text.replace_range(range, replacement);
+    }
+
+    /// Makes the next misspelling that needs a decision current.
+    fn advance(&mut self) -> Result<()> {
+        self.suggestions.clear();
+        while let Some(mut misspelling) = self.remaining.next() {
+            let start = misspelling.range.start.saturating_add_signed(self.shift);
+            misspelling.range = start..start + misspelling.range.len();
+            if let Some(replacement) = self.replace_all.get(&misspelling.word) {
+                let replacement = replacement.clone();
+                self.replace(misspelling.range, &replacement);
+                continue;
+            }
+            if self.spell_checker.check(&misspelling.word)? {
+                continue;
+            }
+            self.suggestions = self.spell_checker.suggest(&misspelling.word)?;
+            self.current = Some(misspelling);
+            break;
+        }
+        Ok(())
+    }
+}
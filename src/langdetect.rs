@@ -0,0 +1,101 @@
+//! Routing the sentences of a text to the dictionary of their language.
+
+use std::{borrow::Cow, cell::RefCell, ops::Range};
+
+use crate::{
+    text::{check_plain_text, strip_invisible, words},
+    Misspelling, MultiLanguageChecker, Result, SpellChecker, SuggestionArbiter,
+};
+
+impl<A: SuggestionArbiter> MultiLanguageChecker<A> {
+    /// Makes `check_text()` check every sentence with the dictionaries of
+    /// its language only, see [`detect_language()`](Self::detect_language).
+    /// A word of one language in a sentence of another is then reported,
+    /// instead of being accepted by the wrong dictionary.
+    pub fn with_language_detection(mut self) -> Self {
+        self.detect_language = true;
+        self
+    }
+
+    pub fn language_detection(&self) -> bool {
+        self.detect_language
+    }
+
+    /// Returns the checkers that accept the most words of `text`, in
+    /// order. The dictionaries themselves are the language models, so
+    /// every installed language can be detected. When no checker accepts
+    /// any word, all checkers are returned.
+    pub fn detect_language(&self, text: &str) -> Result<Vec<&SpellChecker>> {
+        let words: Vec<Cow<str>> = words(text)
+            .into_iter()
+            .map(|range| strip_invisible(&text[range]))
+            .filter(|word| word.chars().any(char::is_alphabetic))
+            .collect();
+        let mut scores = Vec::with_capacity(self.checkers().len());
+        for checker in self.checkers() {
+            let mut score = 0;
+            for word in &words {
+                if checker.check(word)? {
+                    score += 1;
+                }
+            }
+            scores.push(score);
+        }
+        let best = scores.iter().copied().max().unwrap_or(0);
+        Ok(self
+            .checkers()
+            .iter()
+            .zip(scores)
+            .filter(|(_, score)| best == 0 || *score == best)
+            .map(|(checker, _)| checker)
+            .collect())
+    }
+
+    /// Returns the misspellings and the number of checked words, see
+    /// `check_text_counted()`.
+    pub(crate) fn check_text_by_sentence(
+        &self,
+        text: &str,
+        accepted: &RefCell<Vec<usize>>,
+    ) -> Result<(Vec<Misspelling>, usize)> {
+        let mut misspellings = Vec::new();
+        let mut checked = 0;
+        for sentence in sentences(text) {
+            let checkers = self.detect_language(&text[sentence.clone()])?;
+            let check = |word: &str| self.check_counted(&checkers, word, accepted);
+            let (found, words) = check_plain_text(check, &text[sentence.clone()])?;
+            checked += words;
+            misspellings.extend(found.into_iter().map(|m| Misspelling {
+                word: m.word,
+                range: m.range.start + sentence.start..m.range.end + sentence.start,
+            }));
+        }
+        Ok((misspellings, checked))
+    }
+}
+
+/// Returns the byte ranges of the sentences of `text`: they end after
+/// `.`, `!` or `?` followed by white space, and at blank lines.
+fn sentences(text: &str) -> Vec<Range<usize>> {
+    let mut sentences = Vec::new();
+    let mut start = 0;
+    let mut chars = text.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        let next = chars.peek().map(|&(_, next)| next);
+        let end = match c {
+            '.' | '!' | '?' => next.is_some_and(char::is_whitespace),
+            '\n' => text[i + 1..]
+                .trim_start_matches([' ', '\t'])
+                .starts_with('\n'),
+            _ => false,
+        };
+        if end {
+            sentences.push(start..i + c.len_utf8());
+            start = i + c.len_utf8();
+        }
+    }
+    if start < text.len() {
+        sentences.push(start..text.len());
+    }
+    sentences
+}
@@ -0,0 +1,87 @@
+use std::{path::Path, str::FromStr};
+
+use crate::{aff::AffixOption, discovery::read_affix_directive, Error};
+
+/// The language of a dictionary, a tag like `en_US` or `de`.
+///
+/// Tags are kept in the hunspell style with an underscore; BCP 47 tags
+/// like `en-US` are converted when parsing.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Language {
+    tag: String,
+}
+
+impl Language {
+    /// Returns the hunspell style tag, i.e. `en_US`.
+    pub fn as_str(&self) -> &str {
+        &self.tag
+    }
+
+    /// Returns the primary language subtag, i.e. `en` for `en_US`.
+    pub fn primary(&self) -> &str {
+        self.tag.split('_').next().unwrap_or(&self.tag)
+    }
+
+    /// Returns the region subtag if present, i.e. `US` for `en_US`.
+    pub fn region(&self) -> Option<&str> {
+        self.tag
+            .split('_')
+            .skip(1)
+            .find(|s| s.len() == 2 || s.chars().all(|c| c.is_ascii_digit()))
+    }
+
+    /// Returns the tag in BCP 47 style, i.e. `en-US`.
+    pub fn to_bcp47(&self) -> String {
+        self.tag.replace('_', "-")
+    }
+
+    /// Derives the language from the `LANG` directive of the affix file,
+    /// or else from the name of the dictionary file.
+    pub(crate) fn from_files<P: AsRef<Path>>(affix: P, dictionary: P) -> Option<Language> {
+        read_affix_directive(affix, AffixOption::Lang.name())
+            .and_then(|l| l.parse().ok())
+            .or_else(|| {
+                dictionary
+                    .as_ref()
+                    .file_stem()
+                    .and_then(|s| s.to_str())
+                    .and_then(|s| s.parse().ok())
+            })
+    }
+}
+
+impl FromStr for Language {
+    type Err = Error;
+
+    /// Parses a language tag, accepting both `en_US` and `en-US`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tag = s.trim().replace('-', "_");
+        let primary = tag.split('_').next().unwrap_or_default();
+        if (2..=3).contains(&primary.len()) && primary.chars().all(|c| c.is_ascii_alphabetic()) {
+            Ok(Language { tag })
+        } else {
+            Err(Error::InvalidLanguageTag(s.to_string()))
+        }
+    }
+}
+
+impl core::fmt::Display for Language {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        fmt.write_str(&self.tag)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Language {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.tag)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Language {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let tag = String::deserialize(deserializer)?;
+        tag.parse().map_err(serde::de::Error::custom)
+    }
+}
@@ -22,23 +22,247 @@
 //! - Libre office: git://anongit.freedesktop.org/libreoffice/dictionaries
 //! - [Collection of normalized and installable hunspell dictionaries](https://github.com/wooorm/dictionaries)
 //!
+//! Installed dictionaries can be listed with [`available_dictionaries()`],
+//! which searches the directories in `DICPATH` and the usual system
+//! locations.
+//!
+//! # Read-only file systems
+//!
+//! The crate never writes to the file system on its own, with one
+//! exception on Windows: hunspell built with another compiler than MSVC
+//! cannot open files with non-ASCII paths, so those are copied to the
+//! temporary directory while hunspell reads them and removed right after,
+//! or, when the process dies, by the next process that copies one.
+//!
+//! Dictionaries can be loaded without any files using
+//! [`SpellChecker::from_memory()`], which keeps the dictionary in anonymous
+//! memory files (Linux and Android).
+//!
 //! # Features
 //!
 //! - **bundeled** The bundled code of hunspell can be compiled with the `cc`
 //!   crate and will be linked `static`ally when the `bundled` feature is
 //!   present (default).
-//! - **serde** Serialize/deserialize the hunspell [`Dictionary`].
+//! - **linked** Link the system hunspell library when the program is
+//!   built, without compiling hunspell. `bundled` includes it.
+//! - **dylib** Load the system hunspell library with `libloading` when the
+//!   first spell checker is opened, or the one in the `HUNSPELL_LIBRARY`
+//!   environment variable or given to `load_hunspell_library()`. Opening
+//!   fails with `Error::LibraryLoading` when there is none.
+//! - **serde** Serialize/deserialize the hunspell [`SpellChecker`] and
+//!   [`Dictionary`], or a `PortableSpellChecker` that includes the
+//!   dictionary files. A `SpellChecker` can be deserialized as a
+//!   [`SpellCheckerState`] without opening its files. Only self-describing
+//!   formats like JSON can be read by later versions, bincode blobs cannot.
+//! - **snapshot** Save a [`SpellChecker`] to a single compact file with
+//!   `SpellChecker::save_snapshot()`.
+//! - **watch** Re-check the files of a directory when they change with
+//!   `TextChecker::watch_dir()`, and reload a `ReloadingSpellChecker` when
+//!   its dictionary files change.
+//! - **async** `AsyncSpellChecker`, which works on its own thread so slow
+//!   calls do not block an async executor.
+//! - **langdetect** Check every sentence with the dictionary of its
+//!   language in `MultiLanguageChecker::check_text()`.
+//! - **lsp** `SpellingServer`, which keeps the documents of a language
+//!   server checked and offers fixes as `lsp-types` code actions.
+//! - **zip** Read dictionaries from LibreOffice and Mozilla extensions
+//!   with `Dictionary::from_oxt()`.
+//! - **fetch** Download dictionaries from the wooorm/dictionaries
+//!   collection into a cache with `DictionaryFetcher`.
+//! - **autocorrect** A table of common English misspellings, tried before
+//!   hunspell by `SpellChecker::quick_suggest()`.
+//! - **hyphenation** `Hyphenator`, which hyphenates words with the
+//!   libhyphen `hyph_*.dic` patterns that come with the dictionaries.
+//! - **thesaurus** `Thesaurus`, the synonyms of the MyThes `th_*.idx` and
+//!   `th_*.dat` files that come with the dictionaries.
+//! - **test-util** `FixtureBuilder`, which writes throwaway affix and
+//!   dictionary files for tests.
+//! - **tracing** A debug span named `hunspell` around the calls to hunspell
+//!   that check, suggest, analyze and create handles, with the word length,
+//!   number of results and duration.
+//! - **cli** Build the `hunspell-rs` command line tool, with `check`,
+//!   `suggest`, `analyze`, `list-dicts` and the `-a` pipe mode.
 //!
 //! [Hunspell library]: https://hunspell.github.io/
 //! [hunspell-sys]: https://crates.io/crates/hunspell-sys
+mod accept_list;
+pub mod aff;
+mod analysis;
+#[cfg(feature = "async")]
+mod asynchronous;
+#[cfg(feature = "autocorrect")]
+mod autocorrect;
+mod batch;
+mod build_info;
+mod builder;
+mod cache;
+mod cached;
+mod casing;
+mod check;
+mod combine;
+mod compare;
+mod compound;
+mod config;
+mod corrections;
+mod detailed;
+mod diagnostics;
+pub mod dic;
+mod dictionary;
+mod discovery;
+mod distance;
+mod document;
+mod edits;
+mod encoding;
 mod error;
+#[cfg(feature = "zip")]
+mod extension;
+#[cfg(feature = "fetch")]
+mod fetch;
+mod ffi;
+mod ffi_util;
+#[cfg(feature = "test-util")]
+mod fixture;
+mod forms;
+mod frequency;
+mod frozen;
+mod grammar;
+mod highlight;
+#[cfg(feature = "hyphenation")]
+mod hyphenation;
+mod input;
+mod instrument;
+mod interactive;
+#[cfg(feature = "langdetect")]
+mod langdetect;
+mod language;
+mod lint;
+#[cfg(feature = "lsp")]
+mod lsp;
+mod memory;
+mod multi;
+mod normalization;
+mod null;
+mod personal;
+pub mod pipe;
+mod pool;
+mod profile;
+mod rank;
+mod registry;
+mod regression;
+mod report;
 mod spell_checker;
+mod speller;
+mod split;
+mod state;
+mod stats;
+mod stem;
+mod stream;
+mod suggest;
+mod syntax;
+mod text;
+#[cfg(feature = "thesaurus")]
+mod thesaurus;
+mod token;
+mod tokenizer;
+mod unwind;
+mod validate;
 
+#[cfg(feature = "serde")]
+mod portable;
 #[cfg(feature = "serde")]
 mod serde;
+#[cfg(feature = "snapshot")]
+mod snapshot;
+#[cfg(feature = "watch")]
+mod watch;
 
-pub use error::{Error, Result};
-pub use spell_checker::SpellChecker;
+pub use accept_list::accept_list;
+#[cfg(feature = "async")]
+pub use asynchronous::{AsyncSpellChecker, Pending};
+#[cfg(feature = "autocorrect")]
+pub use autocorrect::common_correction;
+pub use batch::BatchOptions;
+pub use build_info::{build_info, BuildInfo};
+pub use builder::DictionaryBuilder;
+pub use cache::{CacheStorage, FileStorage, ResultCache};
+pub use cached::{CacheStats, CachedSpellChecker, SuggestionPage};
+pub use casing::{apply_suggestion, Casing};
+pub use check::{CasePolicy, CheckOptions, MIN_COMPOUND_PART};
+pub use combine::{And, NotIn, Or};
+pub use compare::{compare_spellers, Divergence};
+pub use compound::CompoundSegment;
+pub use config::Config;
+pub use corrections::{CompactCorrections, CorrectionTable};
+pub use detailed::{CheckDetails, RejectionKind, WordSource};
+pub use diagnostics::{Diagnostic, Diagnostics};
+pub use dictionary::{Dictionary, DictionarySource};
+pub use discovery::{
+    available_dictionaries, available_dictionaries_in, find_dictionary, resolve_language,
+    search_paths, DictionaryInfo, LanguageResolver, DEFAULT_ENCODING,
+};
+pub use document::{DocumentChanges, DocumentChecker};
+pub use edits::{EditKind, SuggestionEdit};
+pub use error::{Error, IoError, Operation, Result};
+#[cfg(feature = "fetch")]
+pub use fetch::{DictionaryFetcher, WOOORM_DICTIONARIES};
+pub use ffi::Hunhandle;
+#[cfg(feature = "dylib")]
+pub use ffi::{hunspell_library, load_hunspell_library};
+#[cfg(feature = "test-util")]
+pub use fixture::{Fixture, FixtureBuilder};
+pub use frozen::FrozenSpellChecker;
+pub use grammar::{GrammarIssue, GrammarOptions, GrammarRule};
+pub use highlight::HighlightWriter;
+#[cfg(feature = "linked")]
+pub use hunspell_sys;
+#[cfg(feature = "hyphenation")]
+pub use hyphenation::Hyphenator;
+pub use input::{InputPolicy, NulHandling};
+pub use interactive::{CorrectionAction, CorrectionSession};
+pub use language::Language;
+pub use lint::{DictionaryStats, LintIssue, LintKind};
+#[cfg(feature = "lsp")]
+pub use lsp::{SpellingServer, ADD_TO_DICTIONARY};
+pub use multi::{
+    ChainSuggestion, CheckerChain, FallbackChecker, FirstWins, Interleave, MultiLanguageChecker,
+    ScoreBased, SuggestionArbiter,
+};
+pub use normalization::Normalization;
+pub use null::NullSpellChecker;
+pub use personal::PersonalDictionary;
+pub use pool::{PooledSpellChecker, SpellCheckerPool};
+#[cfg(feature = "serde")]
+pub use portable::PortableSpellChecker;
+pub use profile::UserProfile;
+pub use rank::{KeyboardLayout, RankingOptions};
+pub use registry::CheckerRegistry;
+pub use regression::{SuggestionCase, SuggestionMismatch, SuggestionReport};
+pub use report::{
+    Estimate, FileReport, LanguageCount, MisspelledWord, Report, ReportDiff, SampleOptions,
+    SampleUnit, SpellReport,
+};
+pub use spell_checker::{FileStamp, RuntimeWord, SpellChecker};
+pub use speller::Speller;
+pub use state::SpellCheckerState;
+pub use stats::Stats;
+pub use stem::StemDetails;
+pub use stream::MisspellingStream;
+pub use suggest::{
+    ShortWordPolicy, SuggestIter, SuggestOptions, Suggestion, SuggestionKind, SuggestionSource,
+};
+pub use syntax::{Html, Latex, Markdown, PlainText, SyntaxFilter};
+pub use text::{
+    BudgetedCheck, ExtraWordPolicy, IgnoreRules, InvisibleChars, Misspelling, TextChecker,
+    WordForms,
+};
+#[cfg(feature = "thesaurus")]
+pub use thesaurus::{SynonymEntry, Thesaurus};
+pub use token::{classify_tokens, Token, TokenKind};
+pub use tokenizer::TokenizerProfile;
+pub use unwind::{catch_panic, PanicBoundary};
+pub use validate::{validate_dictionary, ValidationLimits};
+#[cfg(feature = "watch")]
+pub use watch::{ReloadingSpellChecker, WatchEvent};
 
 #[cfg(test)]
 mod tests;
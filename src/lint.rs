@@ -0,0 +1,331 @@
+//! Statistics and checks of the word lists of a [`Dictionary`], for the
+//! people who maintain them.
+
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    path::PathBuf,
+};
+
+use crate::{
+    aff::{encoding_of, AffixFile, Directive, FlagType},
+    dic::DicEntry,
+    encoding::Codec,
+    Dictionary, Error, Operation, Result,
+};
+
+/// Numbers about the words of a dictionary and its additional
+/// dictionaries, made by [`Dictionary::stats()`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DictionaryStats {
+    /// The number of words, not counting the lines that are malformed.
+    pub entries: usize,
+    /// The `SET` of the affix file.
+    pub encoding: String,
+    pub flag_type: FlagType,
+    /// How many words have each flag, with the aliases of `AF` resolved.
+    pub flag_usage: BTreeMap<String, usize>,
+    /// The longest words, longest first, at most
+    /// [`LONGEST_ENTRIES`](DictionaryStats::LONGEST_ENTRIES).
+    pub longest_entries: Vec<String>,
+}
+
+impl DictionaryStats {
+    pub const LONGEST_ENTRIES: usize = 10;
+}
+
+/// A problem in a dictionary file, found by [`Dictionary::lint()`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LintIssue {
+    /// The additional dictionary the line is in, `None` for the
+    /// dictionary itself.
+    pub additional_dictionary: Option<PathBuf>,
+    /// The number of the line, starting at 1.
+    pub line: usize,
+    pub kind: LintKind,
+}
+
+/// What is wrong with a line of a dictionary file.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum LintKind {
+    /// The word is in the file before, with the same flags.
+    Duplicate { word: String, first_line: usize },
+    /// The word has a flag that the affix file does not define.
+    UndefinedFlag { word: String, flag: String },
+    /// The line cannot be read, with the reason.
+    Malformed(String),
+}
+
+impl LintIssue {
+    /// I.e. ``line 3: duplicate of `cat` on line 1``.
+    pub fn message(&self) -> String {
+        let problem = match &self.kind {
+            LintKind::Duplicate { word, first_line } => {
+                format!("duplicate of `{word}` on line {first_line}")
+            }
+            LintKind::UndefinedFlag { word, flag } => {
+                format!("`{word}` has flag `{flag}`, which is not defined")
+            }
+            LintKind::Malformed(reason) => reason.clone(),
+        };
+        match &self.additional_dictionary {
+            Some(path) => format!("{}: line {}: {problem}", path.display(), self.line),
+            None => format!("line {}: {problem}", self.line),
+        }
+    }
+}
+
+/// The flags of the affix file and the words of a dictionary file, line
+/// by line.
+struct WordLists {
+    affix: AffixFile,
+    encoding: String,
+    files: Vec<(Option<PathBuf>, Vec<Line>)>,
+}
+
+/// A line of a dictionary file with its number, and the word with its
+/// flags, or why it is malformed.
+type Line = (usize, std::result::Result<(DicEntry, Vec<String>), String>);
+
+impl Dictionary {
+    /// Counts the words of the dictionary and its additional dictionaries
+    /// and the use of their flags. Fails when a file cannot be read; an
+    /// encrypted dictionary cannot be read.
+    ///
+    /// ```
+    /// use hunspell_rs::Dictionary;
+    ///
+    /// let dictionary = Dictionary::from_files("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic");
+    /// let stats = dictionary.stats().unwrap();
+    /// assert_eq!(2, stats.entries);
+    /// assert_eq!(Some(&2), stats.flag_usage.get("S"));
+    /// assert_eq!("program", stats.longest_entries[0]);
+    /// ```
+    pub fn stats(&self) -> Result<DictionaryStats> {
+        let word_lists = self.word_lists()?;
+        let mut stats = DictionaryStats {
+            encoding: word_lists.encoding,
+            flag_type: word_lists.affix.flag_type(),
+            ..Default::default()
+        };
+        let mut words: Vec<&str> = Vec::new();
+        for (_, lines) in &word_lists.files {
+            for (entry, flags) in lines.iter().filter_map(|(_, line)| line.as_ref().ok()) {
+                stats.entries += 1;
+                for flag in flags {
+                    *stats.flag_usage.entry(flag.clone()).or_default() += 1;
+                }
+                words.push(&entry.word);
+            }
+        }
+        // stable, so words of the same length stay in the order of the files
+        words.sort_by_key(|w| std::cmp::Reverse(w.chars().count()));
+        stats.longest_entries = words
+            .into_iter()
+            .take(DictionaryStats::LONGEST_ENTRIES)
+            .map(str::to_string)
+            .collect();
+        Ok(stats)
+    }
+
+    /// Finds words that are in a dictionary file twice with the same
+    /// flags, words with flags the affix file does not define, and lines
+    /// that cannot be read, in the dictionary and its additional
+    /// dictionaries. Fails when a file cannot be read; an encrypted
+    /// dictionary cannot be read.
+    ///
+    /// ```
+    /// use hunspell_rs::{Dictionary, LintKind};
+    ///
+    /// let dictionary = Dictionary::from_bytes(
+    ///     b"SFX S Y 1\nSFX S 0 s .\n".to_vec(),
+    ///     b"3\ncat/S\ndog/X\ncat/S\n".to_vec(),
+    /// );
+    /// let issues = dictionary.lint().unwrap();
+    /// assert_eq!("line 3: `dog` has flag `X`, which is not defined", issues[0].message());
+    /// assert!(matches!(issues[1].kind, LintKind::Duplicate { first_line: 2, .. }));
+    /// ```
+    pub fn lint(&self) -> Result<Vec<LintIssue>> {
+        let word_lists = self.word_lists()?;
+        let defined = defined_flags(&word_lists.affix);
+        let mut issues = Vec::new();
+        for (additional_dictionary, lines) in &word_lists.files {
+            let mut seen: HashMap<(&str, Vec<&str>), usize> = HashMap::new();
+            for (n, line) in lines {
+                let issue = |kind| LintIssue {
+                    additional_dictionary: additional_dictionary.clone(),
+                    line: *n,
+                    kind,
+                };
+                let (entry, flags) = match line {
+                    Ok(line) => line,
+                    Err(reason) => {
+                        issues.push(issue(LintKind::Malformed(reason.clone())));
+                        continue;
+                    }
+                };
+                for flag in flags.iter().filter(|f| !defined.contains(*f)) {
+                    issues.push(issue(LintKind::UndefinedFlag {
+                        word: entry.word.clone(),
+                        flag: flag.clone(),
+                    }));
+                }
+                let mut sorted: Vec<&str> = flags.iter().map(String::as_str).collect();
+                sorted.sort_unstable();
+                match seen.get(&(entry.word.as_str(), sorted.clone())) {
+                    Some(&first_line) => issues.push(issue(LintKind::Duplicate {
+                        word: entry.word.clone(),
+                        first_line,
+                    })),
+                    None => {
+                        seen.insert((&entry.word, sorted), *n);
+                    }
+                }
+            }
+        }
+        Ok(issues)
+    }
+
+    fn word_lists(&self) -> Result<WordLists> {
+        if self.key().is_some() {
+            return Err(Error::InvalidDictionaryFile(
+                "an encrypted dictionary cannot be read".to_string(),
+            ));
+        }
+        let affix = self.affix_contents()?;
+        let encoding = encoding_of(&affix);
+        let codec = Codec::for_name(&encoding);
+        let affix = AffixFile::parse(&codec.decode(&affix)?)?;
+        let aliases = affix.table("AF");
+        let mut files = vec![(None, self.dictionary_contents()?.into_owned())];
+        for d in self.additional_dictionaries() {
+            files.push((
+                Some(d.clone()),
+                std::fs::read(d).map_err(Error::io(Operation::Load, d))?,
+            ));
+        }
+        let files = files
+            .into_iter()
+            .map(|(path, contents)| {
+                let lines = read_lines(&contents, codec, &encoding, |entry| {
+                    entry_flags(&affix, &aliases, entry)
+                });
+                (path, lines)
+            })
+            .collect();
+        Ok(WordLists {
+            encoding,
+            files,
+            affix,
+        })
+    }
+}
+
+/// Reads the lines of a dictionary file, with `flags` splitting the flags
+/// of a word.
+fn read_lines(
+    contents: &[u8],
+    codec: Codec,
+    encoding: &str,
+    flags: impl Fn(&DicEntry) -> std::result::Result<Vec<String>, String>,
+) -> Vec<Line> {
+    let mut lines = Vec::new();
+    for (i, line) in contents.split(|&b| b == b'\n').enumerate() {
+        let line = line.strip_suffix(b"\r").unwrap_or(line);
+        let line = match codec.decode(line) {
+            Ok(line) => line,
+            Err(_) => {
+                lines.push((i + 1, Err(format!("not in the encoding {encoding}"))));
+                continue;
+            }
+        };
+        if i == 0 {
+            let count = line.trim_start_matches('\u{feff}').trim();
+            if count.parse::<usize>().is_err() {
+                lines.push((1, Err("the count of the words is missing".to_string())));
+            }
+            continue;
+        }
+        if line.trim().is_empty() {
+            continue;
+        }
+        let entry = DicEntry::parse(&line);
+        let result = if entry.word.is_empty() {
+            Err("the word is missing".to_string())
+        } else {
+            flags(&entry).map(|flags| (entry, flags))
+        };
+        lines.push((i + 1, result));
+    }
+    lines
+}
+
+/// The flags of `entry`, with an alias of the `AF` table resolved.
+fn entry_flags(
+    affix: &AffixFile,
+    aliases: &[&[String]],
+    entry: &DicEntry,
+) -> std::result::Result<Vec<String>, String> {
+    let flag_type = affix.flag_type();
+    let flags = if aliases.is_empty() || entry.flags.is_empty() {
+        entry.flags.as_str()
+    } else {
+        entry
+            .flags
+            .parse::<usize>()
+            .ok()
+            .and_then(|n| aliases.get(n.checked_sub(1)?))
+            .and_then(|row| row.first())
+            .ok_or_else(|| format!("flag alias `{}` is not in the AF table", entry.flags))?
+    };
+    match flag_type {
+        FlagType::Long if flags.chars().count() % 2 == 1 => Err(format!(
+            "`{flags}` has an odd number of characters for long flags"
+        )),
+        FlagType::Num => {
+            let flags = flag_type.split(flags);
+            match flags.iter().find(|f| f.parse::<u16>().is_err()) {
+                Some(flag) => Err(format!("flag `{flag}` is not a number")),
+                None => Ok(flags),
+            }
+        }
+        _ => Ok(flag_type.split(flags)),
+    }
+}
+
+/// The flags of the affix groups, of directives like `NEEDAFFIX` and of
+/// the `COMPOUNDRULE` patterns.
+fn defined_flags(affix: &AffixFile) -> HashSet<String> {
+    let mut defined: HashSet<String> = affix
+        .affix_groups()
+        .map(|(_, group)| group.flag.clone())
+        .collect();
+    for directive in &affix.directives {
+        if let Directive::Flag(_, flag) = directive {
+            defined.insert(flag.clone());
+        }
+    }
+    for pattern in affix
+        .table("COMPOUNDRULE")
+        .iter()
+        .filter_map(|row| row.first())
+    {
+        if pattern.contains('(') {
+            // long and numeric flags are written in parentheses
+            defined.extend(
+                pattern
+                    .split(['(', ')'])
+                    .skip(1)
+                    .step_by(2)
+                    .map(str::to_string),
+            );
+        } else {
+            defined.extend(
+                pattern
+                    .chars()
+                    .filter(|c| !matches!(c, '*' | '?'))
+                    .map(String::from),
+            );
+        }
+    }
+    defined
+}
@@ -0,0 +1,333 @@
+//! The parts of a spelling language server: diagnostics for the open
+//! documents and code actions to fix them, as `lsp-types` messages.
+
+use std::{collections::HashMap, rc::Rc};
+
+use lsp_types::{
+    CodeAction, CodeActionKind, CodeActionOrCommand, CodeActionParams,
+    CodeActionProviderCapability, Command, Diagnostic, DiagnosticSeverity,
+    DidChangeTextDocumentParams, DidCloseTextDocumentParams, DidOpenTextDocumentParams,
+    ExecuteCommandOptions, ExecuteCommandParams, NumberOrString, Position,
+    PublishDiagnosticsParams, Range, ServerCapabilities, TextDocumentSyncCapability,
+    TextDocumentSyncKind, TextEdit, Uri, WorkspaceEdit,
+};
+
+use crate::{
+    DocumentChecker, Error, Misspelling, PersonalDictionary, Result, SpellChecker, TextChecker,
+};
+
+/// The command of the "add to dictionary" code actions, with the word as
+/// its argument.
+pub const ADD_TO_DICTIONARY: &str = "hunspell-rs.addToDictionary";
+
+/// Keeps the documents a client has open checked and answers its requests,
+/// the transport is left to the application.
+///
+/// Every document has a [`DocumentChecker`], so a change only re-checks
+/// the lines it touches. Positions are in UTF-16 code units, the default
+/// encoding of the protocol.
+///
+/// ```
+/// use hunspell_rs::{SpellChecker, SpellingServer, TextChecker};
+/// use lsp_types::{DidOpenTextDocumentParams, TextDocumentItem};
+///
+/// let spell = SpellChecker::new("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic").unwrap();
+/// let mut server = SpellingServer::new(TextChecker::new(spell));
+/// let published = server
+///     .did_open(DidOpenTextDocumentParams {
+///         text_document: TextDocumentItem {
+///             uri: "file:///notes.txt".parse().unwrap(),
+///             language_id: "plaintext".to_string(),
+///             version: 1,
+///             text: "cats catz".to_string(),
+///         },
+///     })
+///     .unwrap();
+/// assert_eq!(1, published.diagnostics.len());
+/// assert_eq!(5, published.diagnostics[0].range.start.character);
+/// ```
+#[derive(Debug)]
+pub struct SpellingServer {
+    checker: Rc<TextChecker>,
+    personal: Option<PersonalDictionary>,
+    documents: HashMap<Uri, DocumentChecker>,
+}
+
+impl SpellingServer {
+    pub fn new(checker: TextChecker) -> Self {
+        SpellingServer {
+            checker: Rc::new(checker),
+            personal: None,
+            documents: HashMap::new(),
+        }
+    }
+
+    /// Adds the words of `personal` to the spell checker, and saves the
+    /// words added with [`ADD_TO_DICTIONARY`] to it.
+    pub fn with_personal_dictionary(mut self, personal: PersonalDictionary) -> Result<Self> {
+        self.change_spell_checker(|spell_checker| {
+            spell_checker.add_personal_dictionary(&personal)
+        })?;
+        Ok(SpellingServer {
+            personal: Some(personal),
+            ..self
+        })
+    }
+
+    pub fn personal_dictionary(&self) -> Option<&PersonalDictionary> {
+        self.personal.as_ref()
+    }
+
+    pub fn text_checker(&self) -> &TextChecker {
+        &self.checker
+    }
+
+    /// Returns the text of an open document.
+    pub fn text(&self, uri: &Uri) -> Option<&str> {
+        self.documents.get(uri).map(DocumentChecker::text)
+    }
+
+    /// What the server handles, for the `initialize` response: incremental
+    /// changes, code actions and the [`ADD_TO_DICTIONARY`] command.
+    pub fn capabilities() -> ServerCapabilities {
+        ServerCapabilities {
+            text_document_sync: Some(TextDocumentSyncCapability::Kind(
+                TextDocumentSyncKind::INCREMENTAL,
+            )),
+            code_action_provider: Some(CodeActionProviderCapability::Simple(true)),
+            execute_command_provider: Some(ExecuteCommandOptions {
+                commands: vec![ADD_TO_DICTIONARY.to_string()],
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    /// Checks a document that was opened, and returns its diagnostics.
+    pub fn did_open(
+        &mut self,
+        params: DidOpenTextDocumentParams,
+    ) -> Result<PublishDiagnosticsParams> {
+        let document = params.text_document;
+        let checker = DocumentChecker::new(Rc::clone(&self.checker), document.text)?;
+        let diagnostics = diagnostics(checker.text(), &checker.misspellings());
+        self.documents.insert(document.uri.clone(), checker);
+        Ok(PublishDiagnosticsParams::new(
+            document.uri,
+            diagnostics,
+            Some(document.version),
+        ))
+    }
+
+    /// Applies the changes to a document and returns its diagnostics. Fails
+    /// with `Error::UnknownDocument` when the document is not open.
+    pub fn did_change(
+        &mut self,
+        params: DidChangeTextDocumentParams,
+    ) -> Result<PublishDiagnosticsParams> {
+        let uri = params.text_document.uri;
+        let checker = self
+            .documents
+            .get_mut(&uri)
+            .ok_or_else(|| Error::UnknownDocument(uri.to_string()))?;
+        for change in params.content_changes {
+            let range = match change.range {
+                Some(range) => {
+                    offset(checker.text(), range.start)..offset(checker.text(), range.end)
+                }
+                None => 0..checker.text().len(),
+            };
+            checker.edit(range, &change.text)?;
+        }
+        Ok(PublishDiagnosticsParams::new(
+            uri,
+            diagnostics(checker.text(), &checker.misspellings()),
+            Some(params.text_document.version),
+        ))
+    }
+
+    /// Forgets a document, the returned empty diagnostics clear the ones
+    /// the client shows.
+    pub fn did_close(&mut self, params: DidCloseTextDocumentParams) -> PublishDiagnosticsParams {
+        let uri = params.text_document.uri;
+        self.documents.remove(&uri);
+        PublishDiagnosticsParams::new(uri, Vec::new(), None)
+    }
+
+    /// Returns the current diagnostics of an open document.
+    pub fn diagnostics(&self, uri: &Uri) -> Option<Vec<Diagnostic>> {
+        self.documents
+            .get(uri)
+            .map(|checker| diagnostics(checker.text(), &checker.misspellings()))
+    }
+
+    /// Returns the code actions for the misspellings in the range of
+    /// `params`: a quick fix per suggestion, the first one preferred, and
+    /// one to add the word to the dictionary.
+    pub fn code_actions(&self, params: &CodeActionParams) -> Result<Vec<CodeActionOrCommand>> {
+        let uri = &params.text_document.uri;
+        let checker = self
+            .documents
+            .get(uri)
+            .ok_or_else(|| Error::UnknownDocument(uri.to_string()))?;
+        let text = checker.text();
+        let start = offset(text, params.range.start);
+        let end = offset(text, params.range.end);
+        let mut actions = Vec::new();
+        for m in checker.misspellings() {
+            // an empty range is the cursor, which may be at the end of a word
+            if m.range.end < start || m.range.start > end {
+                continue;
+            }
+            let diagnostic = diagnostic(text, &m);
+            for (i, suggestion) in self
+                .checker
+                .spell_checker()
+                .suggest(&m.word)?
+                .into_iter()
+                .enumerate()
+            {
+                let edit = TextEdit::new(diagnostic.range, suggestion.clone());
+                actions.push(CodeActionOrCommand::CodeAction(CodeAction {
+                    title: format!("Replace with `{suggestion}`"),
+                    kind: Some(CodeActionKind::QUICKFIX),
+                    diagnostics: Some(vec![diagnostic.clone()]),
+                    edit: Some(WorkspaceEdit {
+                        changes: Some(HashMap::from([(uri.clone(), vec![edit])])),
+                        ..Default::default()
+                    }),
+                    is_preferred: Some(i == 0),
+                    ..Default::default()
+                }));
+            }
+            let title = format!("Add `{}` to the dictionary", m.word);
+            actions.push(CodeActionOrCommand::CodeAction(CodeAction {
+                title: title.clone(),
+                kind: Some(CodeActionKind::QUICKFIX),
+                diagnostics: Some(vec![diagnostic]),
+                command: Some(Command::new(
+                    title,
+                    ADD_TO_DICTIONARY.to_string(),
+                    Some(vec![m.word.into()]),
+                )),
+                ..Default::default()
+            }));
+        }
+        Ok(actions)
+    }
+
+    /// Runs [`ADD_TO_DICTIONARY`]: adds the word to the spell checker and
+    /// saves it in the personal dictionary, if there is one. Returns the
+    /// new diagnostics of the documents in which misspellings are gone.
+    /// Fails with `Error::UnknownCommand` for other commands or arguments.
+    pub fn execute_command(
+        &mut self,
+        params: ExecuteCommandParams,
+    ) -> Result<Vec<PublishDiagnosticsParams>> {
+        let word = match (params.command.as_str(), params.arguments.as_slice()) {
+            (ADD_TO_DICTIONARY, [serde_json::Value::String(word)]) => word,
+            _ => return Err(Error::UnknownCommand(params.command)),
+        };
+        let published = self.change_spell_checker(|spell_checker| spell_checker.add(word))?;
+        if let Some(personal) = &mut self.personal {
+            if personal.add(word) {
+                personal.save()?;
+            }
+        }
+        Ok(published)
+    }
+
+    /// Changes the spell checker the documents share, and checks them
+    /// again. Returns the new diagnostics of the documents whose
+    /// misspellings changed. When `change` fails the documents are kept as
+    /// they were; when checking one again fails it keeps its old
+    /// diagnostics, the others are checked and the first error is returned.
+    fn change_spell_checker<F>(&mut self, change: F) -> Result<Vec<PublishDiagnosticsParams>>
+    where
+        F: FnOnce(&mut SpellChecker) -> Result<()>,
+    {
+        // the documents let go of the checker while it changes
+        let documents: Vec<_> = self
+            .documents
+            .drain()
+            .map(|(uri, checker)| (uri, checker.detach()))
+            .collect();
+        let changed = change(
+            Rc::get_mut(&mut self.checker)
+                .expect("only the documents share the checker, and they let go")
+                .spell_checker_mut(),
+        );
+        let mut published = Vec::new();
+        let mut failed = None;
+        for (uri, document) in documents {
+            let mut checker = DocumentChecker::reattach(Rc::clone(&self.checker), document);
+            if changed.is_ok() {
+                match checker.recheck() {
+                    Ok(changes) if !changes.is_empty() => {
+                        published.push(PublishDiagnosticsParams::new(
+                            uri.clone(),
+                            diagnostics(checker.text(), &checker.misspellings()),
+                            None,
+                        ))
+                    }
+                    Ok(_) => {}
+                    Err(e) => {
+                        failed.get_or_insert(e);
+                    }
+                }
+            }
+            self.documents.insert(uri, checker);
+        }
+        changed?;
+        failed.map_or(Ok(published), Err)
+    }
+}
+
+/// Returns the diagnostics of the misspellings of `text`, which are in
+/// order.
+fn diagnostics(text: &str, misspellings: &[Misspelling]) -> Vec<Diagnostic> {
+    misspellings.iter().map(|m| diagnostic(text, m)).collect()
+}
+
+fn diagnostic(text: &str, m: &Misspelling) -> Diagnostic {
+    Diagnostic {
+        range: Range::new(position(text, m.range.start), position(text, m.range.end)),
+        severity: Some(DiagnosticSeverity::INFORMATION),
+        code: Some(NumberOrString::String("misspelling".to_string())),
+        source: Some("hunspell-rs".to_string()),
+        message: format!("unknown word `{}`", m.word),
+        ..Default::default()
+    }
+}
+
+/// Returns the position of the byte `offset` of `text`.
+fn position(text: &str, offset: usize) -> Position {
+    let before = &text[..offset];
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    Position::new(
+        before.matches('\n').count() as u32,
+        before[line_start..].encode_utf16().count() as u32,
+    )
+}
+
+/// Returns the byte offset of `position` in `text`. Like the protocol
+/// says, a character beyond the end of the line is the end of the line,
+/// and a line beyond the end of the text is the end of the text.
+fn offset(text: &str, position: Position) -> usize {
+    let mut line_start = 0;
+    for _ in 0..position.line {
+        match text[line_start..].find('\n') {
+            Some(i) => line_start += i + 1,
+            None => return text.len(),
+        }
+    }
+    let line = text[line_start..].split('\n').next().unwrap_or_default();
+    let mut units = 0;
+    for (i, c) in line.char_indices() {
+        if units >= position.character as usize {
+            return line_start + i;
+        }
+        units += c.len_utf16();
+    }
+    line_start + line.len()
+}
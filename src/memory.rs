@@ -0,0 +1,36 @@
+//! Dictionaries loaded from memory.
+//!
+//! Hunspell can only open dictionaries by path. To load a dictionary held
+//! in memory without writing to the file system, the contents are put in
+//! an anonymous memory file (`memfd_create`) and hunspell is given its
+//! `/proc/self/fd` path. This works on read-only file systems and in
+//! sandboxes that forbid creating temporary files.
+
+use std::{fs::File, path::PathBuf};
+
+use crate::Result;
+
+/// Creates an anonymous memory file containing `contents`.
+///
+/// Returns the file, which has to be kept open for as long as the path
+/// is used, and its path.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub(crate) fn memory_file(name: &str, contents: &[u8]) -> Result<(File, PathBuf)> {
+    use std::{ffi::CString, io::Write, os::fd::FromRawFd};
+
+    let cname = CString::new(name)?;
+    // SAFETY: cname is a valid nul terminated string
+    let fd = unsafe { libc::memfd_create(cname.as_ptr(), libc::MFD_CLOEXEC) };
+    if fd < 0 {
+        return Err(std::io::Error::last_os_error().into());
+    }
+    // SAFETY: fd was just created and is not owned by anything else
+    let mut file = unsafe { File::from_raw_fd(fd) };
+    file.write_all(contents)?;
+    Ok((file, PathBuf::from(format!("/proc/self/fd/{fd}"))))
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+pub(crate) fn memory_file(_name: &str, _contents: &[u8]) -> Result<(File, PathBuf)> {
+    Err(crate::Error::InMemoryUnsupported)
+}
@@ -0,0 +1,465 @@
+//! Checking with more than one dictionary at the same time.
+
+use std::cell::RefCell;
+
+use crate::{
+    distance::edit_distance, text::check_plain_text, Misspelling, Result, SpellChecker, Speller,
+    Suggestion,
+};
+
+/// Merges the suggestions of several checkers into one list.
+///
+/// `suggestions` has one list per checker, in the order of the checkers.
+/// Implement it for application specific policies:
+///
+/// ```
+/// use hunspell_rs::SuggestionArbiter;
+///
+/// /// Only the shortest suggestions.
+/// struct Shortest;
+///
+/// impl SuggestionArbiter for Shortest {
+///     fn arbitrate(&self, _word: &str, suggestions: Vec<Vec<String>>) -> Vec<String> {
+///         let mut all: Vec<String> = suggestions.into_iter().flatten().collect();
+///         all.sort_by_key(|s| s.len());
+///         all.truncate(3);
+///         all
+///     }
+/// }
+/// ```
+pub trait SuggestionArbiter {
+    fn arbitrate(&self, word: &str, suggestions: Vec<Vec<String>>) -> Vec<String>;
+}
+
+/// Takes the suggestions of the first checker that has any.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FirstWins;
+
+impl SuggestionArbiter for FirstWins {
+    fn arbitrate(&self, _word: &str, suggestions: Vec<Vec<String>>) -> Vec<String> {
+        suggestions
+            .into_iter()
+            .find(|s| !s.is_empty())
+            .unwrap_or_default()
+    }
+}
+
+/// Takes the first suggestion of every checker, then the second, etc.
+/// Duplicates are left out.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Interleave;
+
+impl SuggestionArbiter for Interleave {
+    fn arbitrate(&self, _word: &str, suggestions: Vec<Vec<String>>) -> Vec<String> {
+        let mut merged: Vec<String> = Vec::new();
+        let mut iters: Vec<_> = suggestions.into_iter().map(|s| s.into_iter()).collect();
+        loop {
+            let mut any = false;
+            for suggestion in iters.iter_mut().filter_map(|i| i.next()) {
+                any = true;
+                if !merged.contains(&suggestion) {
+                    merged.push(suggestion);
+                }
+            }
+            if !any {
+                return merged;
+            }
+        }
+    }
+}
+
+/// Orders all suggestions by their edit distance to the misspelled word,
+/// keeping the order of the checkers for equal distances. Duplicates are
+/// left out.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ScoreBased;
+
+impl SuggestionArbiter for ScoreBased {
+    fn arbitrate(&self, word: &str, suggestions: Vec<Vec<String>>) -> Vec<String> {
+        let mut merged: Vec<String> = Vec::new();
+        for suggestion in suggestions.into_iter().flatten() {
+            if !merged.contains(&suggestion) {
+                merged.push(suggestion);
+            }
+        }
+        merged.sort_by_cached_key(|s| edit_distance(word, s));
+        merged
+    }
+}
+
+/// Accepts words from any of its checkers, i.e. for texts that mix
+/// languages. Suggestions are merged with `A`, by default [`Interleave`];
+/// [`suggest_classified()`](MultiLanguageChecker::suggest_classified)
+/// tells the language of each suggestion.
+///
+/// ```
+/// use hunspell_rs::{MultiLanguageChecker, SpellChecker};
+///
+/// let reduced = SpellChecker::new("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic").unwrap();
+/// let extra = SpellChecker::new("tests/fixtures/reduced.aff", "tests/fixtures/extra.dic").unwrap();
+/// let multi = MultiLanguageChecker::new(vec![reduced, extra]);
+/// let misspelled = multi.check_text("cats and systemdunits").unwrap();
+/// assert_eq!(vec!["and"], misspelled.iter().map(|m| &m.word).collect::<Vec<_>>());
+/// ```
+#[doc(alias = "MultiSpellChecker")]
+#[derive(Debug)]
+pub struct MultiLanguageChecker<A = Interleave> {
+    checkers: Vec<SpellChecker>,
+    arbiter: A,
+    #[cfg(feature = "langdetect")]
+    pub(crate) detect_language: bool,
+}
+
+impl MultiLanguageChecker {
+    pub fn new(checkers: Vec<SpellChecker>) -> Self {
+        Self::with_arbiter(checkers, Interleave)
+    }
+}
+
+impl<A: SuggestionArbiter> MultiLanguageChecker<A> {
+    pub fn with_arbiter(checkers: Vec<SpellChecker>, arbiter: A) -> Self {
+        MultiLanguageChecker {
+            checkers,
+            arbiter,
+            #[cfg(feature = "langdetect")]
+            detect_language: false,
+        }
+    }
+
+    pub fn checkers(&self) -> &[SpellChecker] {
+        &self.checkers
+    }
+
+    /// For adding or removing words in some of the checkers.
+    pub fn checkers_mut(&mut self) -> &mut [SpellChecker] {
+        &mut self.checkers
+    }
+
+    /// Returns true when any of the checkers accepts `word`.
+    pub fn check<S: AsRef<str>>(&self, word: S) -> Result<bool> {
+        for checker in &self.checkers {
+            if checker.check(word.as_ref())? {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    /// Returns the checkers that accept `word`, in order.
+    pub fn accepted_by<S: AsRef<str>>(&self, word: S) -> Result<Vec<&SpellChecker>> {
+        let mut accepting = Vec::new();
+        for checker in &self.checkers {
+            if checker.check(word.as_ref())? {
+                accepting.push(checker);
+            }
+        }
+        Ok(accepting)
+    }
+
+    /// Returns the words of `text` that none of the checkers accept, like
+    /// [`SpellChecker::check_text()`].
+    ///
+    /// With the `langdetect` feature and
+    /// `with_language_detection()` every sentence is only checked with the
+    /// checkers of its language.
+    pub fn check_text(&self, text: &str) -> Result<Vec<Misspelling>> {
+        self.check_text_counted(text)
+            .map(|(misspellings, _, _)| misspellings)
+    }
+
+    /// Returns the misspellings of `text`, the number of words that were
+    /// checked and, per checker, the number of words it was the first to
+    /// accept.
+    pub(crate) fn check_text_counted(
+        &self,
+        text: &str,
+    ) -> Result<(Vec<Misspelling>, usize, Vec<usize>)> {
+        let accepted = RefCell::new(vec![0; self.checkers.len()]);
+        #[cfg(feature = "langdetect")]
+        if self.detect_language {
+            let (misspellings, words) = self.check_text_by_sentence(text, &accepted)?;
+            return Ok((misspellings, words, accepted.into_inner()));
+        }
+        let checkers: Vec<&SpellChecker> = self.checkers.iter().collect();
+        let (misspellings, words) =
+            check_plain_text(|word| self.check_counted(&checkers, word, &accepted), text)?;
+        Ok((misspellings, words, accepted.into_inner()))
+    }
+
+    /// Returns true if one of `checkers` accepts `word`, and counts the
+    /// word for the first one that does.
+    pub(crate) fn check_counted(
+        &self,
+        checkers: &[&SpellChecker],
+        word: &str,
+        accepted: &RefCell<Vec<usize>>,
+    ) -> Result<bool> {
+        for checker in checkers {
+            if checker.check(word)? {
+                if let Some(i) = self.checkers.iter().position(|c| std::ptr::eq(c, *checker)) {
+                    accepted.borrow_mut()[i] += 1;
+                }
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    /// Returns the suggestions of all checkers, merged by the arbiter.
+    pub fn suggest<S: AsRef<str>>(&self, word: S) -> Result<Vec<String>> {
+        let suggestions = self
+            .checkers
+            .iter()
+            .map(|c| c.suggest(word.as_ref()))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(self.arbiter.arbitrate(word.as_ref(), suggestions))
+    }
+
+    /// Like `suggest()`, with each suggestion classified and tagged with
+    /// the language of the checker that made it.
+    pub fn suggest_classified<S: AsRef<str>>(&self, word: S) -> Result<Vec<Suggestion>> {
+        let suggestions = self
+            .checkers
+            .iter()
+            .map(|c| c.suggest_classified(word.as_ref()))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(arbitrate_classified(
+            &self.arbiter,
+            word.as_ref(),
+            suggestions,
+        ))
+    }
+}
+
+/// Merges classified suggestions by their words, keeping the first
+/// suggestion of a word.
+fn arbitrate_classified<A: SuggestionArbiter>(
+    arbiter: &A,
+    word: &str,
+    suggestions: Vec<Vec<Suggestion>>,
+) -> Vec<Suggestion> {
+    let words = suggestions
+        .iter()
+        .map(|s| s.iter().map(|s| s.word.clone()).collect())
+        .collect();
+    let mut all: Vec<Suggestion> = suggestions.into_iter().flatten().collect();
+    arbiter
+        .arbitrate(word, words)
+        .into_iter()
+        .filter_map(|w| {
+            let i = all.iter().position(|s| s.word == w)?;
+            Some(all.remove(i))
+        })
+        .collect()
+}
+
+/// A checker with a fallback, i.e. a regional dictionary backed by the
+/// general one. Suggestions are merged with `A`, by default
+/// [`FirstWins`], so the fallback only suggests when the primary checker
+/// has nothing.
+#[derive(Debug)]
+pub struct FallbackChecker<A = FirstWins> {
+    primary: SpellChecker,
+    fallback: SpellChecker,
+    arbiter: A,
+}
+
+impl FallbackChecker {
+    pub fn new(primary: SpellChecker, fallback: SpellChecker) -> Self {
+        Self::with_arbiter(primary, fallback, FirstWins)
+    }
+}
+
+impl<A: SuggestionArbiter> FallbackChecker<A> {
+    pub fn with_arbiter(primary: SpellChecker, fallback: SpellChecker, arbiter: A) -> Self {
+        FallbackChecker {
+            primary,
+            fallback,
+            arbiter,
+        }
+    }
+
+    pub fn primary(&self) -> &SpellChecker {
+        &self.primary
+    }
+
+    pub fn fallback(&self) -> &SpellChecker {
+        &self.fallback
+    }
+
+    pub fn primary_mut(&mut self) -> &mut SpellChecker {
+        &mut self.primary
+    }
+
+    pub fn fallback_mut(&mut self) -> &mut SpellChecker {
+        &mut self.fallback
+    }
+
+    /// Checks with the primary checker, and with the fallback when the
+    /// primary rejects `word`.
+    pub fn check<S: AsRef<str>>(&self, word: S) -> Result<bool> {
+        Ok(self.primary.check(word.as_ref())? || self.fallback.check(word.as_ref())?)
+    }
+
+    /// Returns the suggestions of both checkers, merged by the arbiter.
+    pub fn suggest<S: AsRef<str>>(&self, word: S) -> Result<Vec<String>> {
+        let suggestions = vec![
+            self.primary.suggest(word.as_ref())?,
+            self.fallback.suggest(word.as_ref())?,
+        ];
+        Ok(self.arbiter.arbitrate(word.as_ref(), suggestions))
+    }
+
+    /// Like `suggest()`, with each suggestion classified and tagged with
+    /// the language of the checker that made it.
+    pub fn suggest_classified<S: AsRef<str>>(&self, word: S) -> Result<Vec<Suggestion>> {
+        let suggestions = vec![
+            self.primary.suggest_classified(word.as_ref())?,
+            self.fallback.suggest_classified(word.as_ref())?,
+        ];
+        Ok(arbitrate_classified(
+            &self.arbiter,
+            word.as_ref(),
+            suggestions,
+        ))
+    }
+}
+
+/// Spellers consulted in order, like a project dictionary, a personal
+/// dictionary and a language dictionary, each a named layer with a weight
+/// for its suggestions. A word is correct when a layer accepts it, and
+/// [`accepted_by()`](CheckerChain::accepted_by) tells which one.
+///
+/// ```
+/// use hunspell_rs::{CheckerChain, SpellChecker};
+///
+/// let language = SpellChecker::new("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic").unwrap();
+/// let project = SpellChecker::new("tests/fixtures/reduced.aff", "tests/fixtures/extra.dic").unwrap();
+/// let chain = CheckerChain::new()
+///     .with_layer("project", project, 2.0)
+///     .with_layer("language", language, 1.0);
+/// assert_eq!(Some("project"), chain.accepted_by("systemdunits").unwrap());
+/// assert_eq!(Some("language"), chain.accepted_by("cats").unwrap());
+/// assert_eq!(None, chain.accepted_by("catz").unwrap());
+/// ```
+#[derive(Default)]
+pub struct CheckerChain {
+    pub(crate) layers: Vec<ChainLayer>,
+}
+
+pub(crate) struct ChainLayer {
+    name: String,
+    pub(crate) speller: Box<dyn Speller>,
+    weight: f64,
+}
+
+/// A suggestion of a [`CheckerChain`] with its score, see
+/// [`suggest_scored()`](CheckerChain::suggest_scored).
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChainSuggestion {
+    pub word: String,
+    /// The layers that suggested the word, in order.
+    pub layers: Vec<String>,
+    /// The sum of the weight of each of these layers divided by the
+    /// position of the word in its suggestions, starting at 1.
+    pub score: f64,
+}
+
+impl CheckerChain {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a layer after the others. Its suggestions count `weight`
+    /// times as much as those of a layer with weight 1.
+    pub fn with_layer<N, S>(mut self, name: N, speller: S, weight: f64) -> Self
+    where
+        N: AsRef<str>,
+        S: Speller + 'static,
+    {
+        self.layers.push(ChainLayer {
+            name: name.as_ref().to_string(),
+            speller: Box::new(speller),
+            weight,
+        });
+        self
+    }
+
+    /// The names of the layers, in order.
+    pub fn layers(&self) -> impl Iterator<Item = &str> {
+        self.layers.iter().map(|l| l.name.as_str())
+    }
+
+    /// The speller of the layer called `name`, i.e. to add words to a
+    /// personal dictionary.
+    pub fn layer_mut(&mut self, name: &str) -> Option<&mut dyn Speller> {
+        self.layers
+            .iter_mut()
+            .find(|l| l.name == name)
+            .map(|l| l.speller.as_mut() as &mut dyn Speller)
+    }
+
+    /// Returns true when a layer accepts `word`.
+    pub fn check<S: AsRef<str>>(&self, word: S) -> Result<bool> {
+        Ok(self.accepted_by(word)?.is_some())
+    }
+
+    /// Returns the name of the first layer that accepts `word`, the
+    /// layers after it are not consulted.
+    pub fn accepted_by<S: AsRef<str>>(&self, word: S) -> Result<Option<&str>> {
+        for layer in &self.layers {
+            if layer.speller.check(word.as_ref())? {
+                return Ok(Some(&layer.name));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Returns the suggestions of all layers, best first, see
+    /// [`suggest_scored()`](CheckerChain::suggest_scored).
+    pub fn suggest<S: AsRef<str>>(&self, word: S) -> Result<Vec<String>> {
+        Ok(self
+            .suggest_scored(word)?
+            .into_iter()
+            .map(|s| s.word)
+            .collect())
+    }
+
+    /// Returns the suggestions of all layers with their scores, highest
+    /// first. A word suggested by several layers gets the sum of their
+    /// scores; equal scores keep the order of the layers.
+    pub fn suggest_scored<S: AsRef<str>>(&self, word: S) -> Result<Vec<ChainSuggestion>> {
+        let mut merged: Vec<ChainSuggestion> = Vec::new();
+        for layer in &self.layers {
+            for (i, suggestion) in layer
+                .speller
+                .suggest(word.as_ref())?
+                .into_iter()
+                .enumerate()
+            {
+                let score = layer.weight / (i + 1) as f64;
+                match merged.iter_mut().find(|s| s.word == suggestion) {
+                    Some(merged) => {
+                        merged.score += score;
+                        merged.layers.push(layer.name.clone());
+                    }
+                    None => merged.push(ChainSuggestion {
+                        word: suggestion,
+                        layers: vec![layer.name.clone()],
+                        score,
+                    }),
+                }
+            }
+        }
+        merged.sort_by(|a, b| b.score.total_cmp(&a.score));
+        Ok(merged)
+    }
+}
+
+impl core::fmt::Debug for CheckerChain {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        fmt.debug_map()
+            .entries(self.layers.iter().map(|l| (&l.name, l.weight)))
+            .finish()
+    }
+}
@@ -0,0 +1,103 @@
+//! Changing words to the form of the dictionaries before hunspell sees
+//! them.
+
+use std::borrow::Cow;
+
+use unicode_normalization::{is_nfc_quick, IsNormalized, UnicodeNormalization};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{text::is_invisible, SpellChecker};
+
+/// How a [`SpellChecker`] changes words before they are passed to
+/// hunspell, set with
+/// [`set_normalization()`](SpellChecker::set_normalization).
+///
+/// ```
+/// use hunspell_rs::{Normalization, SpellChecker};
+///
+/// let mut spell = SpellChecker::new("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic").unwrap();
+/// spell.add("don't").unwrap();
+/// assert_eq!(Ok(false), spell.check("don’t"));
+/// spell.set_normalization(Normalization { typographic: true, ..Default::default() });
+/// assert_eq!(Ok(true), spell.check("don’t"));
+/// ```
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Normalization {
+    /// Composes words to Unicode NFC, the form dictionaries are written
+    /// in: `e` followed by a combining acute accent becomes `é`. On by
+    /// default.
+    pub nfc: bool,
+    /// Replaces typographic apostrophes (`’`, `‘`, `ʼ`) by `'` and
+    /// (non-breaking) hyphens (U+2010, U+2011) by `-`, and removes soft
+    /// hyphens and zero width characters, for text copied from word
+    /// processors and web pages. Off by default.
+    pub typographic: bool,
+}
+
+impl Default for Normalization {
+    fn default() -> Self {
+        Normalization {
+            nfc: true,
+            typographic: false,
+        }
+    }
+}
+
+impl Normalization {
+    /// No changes at all.
+    pub fn none() -> Self {
+        Normalization {
+            nfc: false,
+            typographic: false,
+        }
+    }
+
+    /// Returns `word` as hunspell is given it.
+    pub fn apply<'a>(&self, word: &'a str) -> Cow<'a, str> {
+        let word = if self.typographic {
+            typographic(word)
+        } else {
+            Cow::Borrowed(word)
+        };
+        if self.nfc && is_nfc_quick(word.chars()) != IsNormalized::Yes {
+            Cow::Owned(word.nfc().collect())
+        } else {
+            word
+        }
+    }
+}
+
+impl SpellChecker {
+    /// Changes words with `normalization` before they are passed to
+    /// hunspell, in all calls. By default they are composed to NFC.
+    pub fn set_normalization(&mut self, normalization: Normalization) {
+        self.normalization = normalization;
+        self.clear_analysis_cache();
+    }
+
+    pub fn normalization(&self) -> Normalization {
+        self.normalization
+    }
+}
+
+/// Returns `word` with typographic apostrophes and hyphens replaced and
+/// invisible characters removed.
+pub(crate) fn typographic(word: &str) -> Cow<'_, str> {
+    let replacement = |c: char| match c {
+        '’' | '‘' | 'ʼ' => Some('\''),
+        '\u{2010}' | '\u{2011}' => Some('-'),
+        _ => None,
+    };
+    if !word.contains(|c| is_invisible(c) || replacement(c).is_some()) {
+        return Cow::Borrowed(word);
+    }
+    Cow::Owned(
+        word.chars()
+            .filter(|&c| !is_invisible(c))
+            .map(|c| replacement(c).unwrap_or(c))
+            .collect(),
+    )
+}
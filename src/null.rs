@@ -0,0 +1,39 @@
+//! A spell checker for when there is no dictionary.
+
+use crate::{resolve_language, Result, SpellChecker, Speller};
+
+/// A [`Speller`] that accepts every word and has no suggestions, stems or
+/// analyses. Adding and removing words does nothing.
+///
+/// It stands in for a [`SpellChecker`] when the dictionary of the user's
+/// language is not installed, so that spell checking is best effort and
+/// the rest of a program works as usual, see
+/// [`SpellChecker::for_language_or_null()`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct NullSpellChecker;
+
+impl SpellChecker {
+    /// Opens the installed dictionary for a language tag like `pt-BR`, or
+    /// a related one, see [`LanguageResolver`](crate::LanguageResolver).
+    pub fn for_language<S: AsRef<str>>(tag: S) -> Result<SpellChecker> {
+        resolve_language(tag)?.open()
+    }
+
+    /// Like [`for_language()`](SpellChecker::for_language), but returns a
+    /// [`NullSpellChecker`] when there is no dictionary for the language
+    /// or it cannot be opened.
+    ///
+    /// ```
+    /// use hunspell_rs::SpellChecker;
+    ///
+    /// let spell = SpellChecker::for_language_or_null("xx-NOT-INSTALLED");
+    /// assert_eq!(Ok(true), spell.check("anything"));
+    /// assert_eq!(Ok(Vec::new()), spell.suggest("anything"));
+    /// ```
+    pub fn for_language_or_null<S: AsRef<str>>(tag: S) -> Box<dyn Speller> {
+        match Self::for_language(tag) {
+            Ok(spell_checker) => Box::new(spell_checker),
+            Err(_) => Box::new(NullSpellChecker),
+        }
+    }
+}
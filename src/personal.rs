@@ -0,0 +1,128 @@
+use std::{
+    collections::BTreeSet,
+    path::{Path, PathBuf},
+};
+
+use crate::{dic::DicEntry, Error, Operation, Result, SpellChecker};
+
+/// A user's word list that persists between sessions.
+///
+/// Words added with [`SpellChecker::add()`] are lost when the
+/// `SpellChecker` is dropped. A `PersonalDictionary` keeps the words in a
+/// file in the hunspell dictionary format: a line with the number of
+/// words followed by one word per line. Such a file can also be loaded
+/// with [`SpellChecker::add_dictionary()`].
+///
+/// ```
+/// use hunspell_rs::{PersonalDictionary, SpellChecker};
+///
+/// let mut spell = SpellChecker::new("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic").unwrap();
+/// let mut personal = PersonalDictionary::new(std::env::temp_dir().join("doctest-personal.dic"));
+/// personal.add("octonasaurius");
+/// spell.add_personal_dictionary(&personal).unwrap();
+/// assert_eq!(Ok(true), spell.check("octonasaurius"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PersonalDictionary {
+    path: PathBuf,
+    words: BTreeSet<String>,
+}
+
+impl PersonalDictionary {
+    /// Creates an empty personal dictionary that will be saved to `path`.
+    pub fn new<P: AsRef<Path>>(path: P) -> PersonalDictionary {
+        PersonalDictionary {
+            path: path.as_ref().to_path_buf(),
+            words: BTreeSet::new(),
+        }
+    }
+
+    /// Reads a personal dictionary from `path`, when the file does not
+    /// exist yet the dictionary is empty.
+    ///
+    /// Affix flags (after a `/`) are ignored, the runtime dictionary has no
+    /// way to use them.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<PersonalDictionary> {
+        let mut personal = Self::new(path);
+        if !personal.path.exists() {
+            return Ok(personal);
+        }
+        let contents = std::fs::read_to_string(&personal.path)
+            .map_err(Error::io(Operation::Open, &personal.path))?;
+        let mut lines = contents.lines().peekable();
+        // the word count header is optional
+        if lines
+            .peek()
+            .is_some_and(|l| l.trim().chars().all(|c| c.is_ascii_digit()))
+        {
+            lines.next();
+        }
+        personal.words = lines
+            .map(|l| DicEntry::parse(l.trim()).word)
+            .filter(|w| !w.is_empty())
+            .collect();
+        Ok(personal)
+    }
+
+    /// Returns the `Path` the dictionary is saved to.
+    pub fn path(&self) -> &Path {
+        self.path.as_path()
+    }
+
+    /// Returns the words in alphabetical order.
+    pub fn words(&self) -> impl Iterator<Item = &str> {
+        self.words.iter().map(|w| w.as_str())
+    }
+
+    /// Returns the number of words.
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    /// Returns true if the dictionary has no words.
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// Returns true if the word is in the dictionary.
+    pub fn contains<S: AsRef<str>>(&self, word: S) -> bool {
+        self.words.contains(word.as_ref())
+    }
+
+    /// Adds a word, returns false if it was already present.
+    ///
+    /// To make the word available for spell checking right away, also
+    /// add it to the `SpellChecker` with `add()`.
+    pub fn add<S: AsRef<str>>(&mut self, word: S) -> bool {
+        self.words.insert(word.as_ref().to_string())
+    }
+
+    /// Removes a word, returns false if it was not present.
+    pub fn remove<S: AsRef<str>>(&mut self, word: S) -> bool {
+        self.words.remove(word.as_ref())
+    }
+
+    /// Writes the dictionary to its `path()`.
+    pub fn save(&self) -> Result<()> {
+        self.save_as(&self.path)
+    }
+
+    /// Writes the dictionary to `path` in the hunspell dictionary format.
+    pub fn save_as<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let mut contents = format!("{}\n", self.words.len());
+        for word in &self.words {
+            contents.push_str(&DicEntry::new(word).to_string());
+            contents.push('\n');
+        }
+        std::fs::write(&path, contents).map_err(Error::io(Operation::Save, &path))?;
+        Ok(())
+    }
+}
+
+impl SpellChecker {
+    /// Adds all the words of a personal dictionary to the runtime
+    /// dictionary.
+    pub fn add_personal_dictionary(&mut self, personal: &PersonalDictionary) -> Result<()> {
+        personal.words().try_for_each(|w| self.add(w))
+    }
+}
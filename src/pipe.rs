@@ -0,0 +1,133 @@
+//! The `ispell -a` pipe protocol, spoken by editors like vim and emacs.
+//!
+//! Every input line is checked and answered with one line per word,
+//! followed by an empty line:
+//!
+//! - `*` the word is correct,
+//! - `& <word> <count> <offset>: <suggestion>, <suggestion>` the word is
+//!   misspelled, with suggestions,
+//! - `# <word> <offset>` the word is misspelled, without suggestions.
+//!
+//! The offset is the character offset of the word in the input line. Lines
+//! starting with one of these characters are commands:
+//!
+//! - `*<word>` adds the word to the runtime dictionary,
+//! - `&<word>` adds the word in lowercase,
+//! - `@<word>` accepts the word for the session (see
+//!   [`SpellChecker::ignore()`]),
+//! - `#` saves the personal dictionary, there is none so it does nothing,
+//! - `!` and `%` switch terse mode on and off (no `*` lines),
+//! - `+` and `-` switch TeX mode on and off (see [`Latex`]),
+//! - `~` is accepted and ignored,
+//! - `^` checks the rest of the line, for lines starting with a command
+//!   character.
+//!
+//! ```
+//! use hunspell_rs::{pipe, SpellChecker};
+//!
+//! let mut spell = SpellChecker::new("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic").unwrap();
+//! let mut output = Vec::new();
+//! pipe::run(&mut spell, "cats catz\n".as_bytes(), &mut output).unwrap();
+//! let output = String::from_utf8(output).unwrap();
+//! assert!(output.ends_with("*\n& catz 1 5: cat\n\n"));
+//! ```
+
+use std::io::{BufRead, Write};
+
+use crate::{
+    text::{strip_invisible, words},
+    Error, Latex, PlainText, Result, SpellChecker, SyntaxFilter,
+};
+
+/// The first line written by [`run()`].
+pub const BANNER: &str = concat!(
+    "@(#) International Ispell Version 3.2.06 (but really hunspell-rs ",
+    env!("CARGO_PKG_VERSION"),
+    ")"
+);
+
+/// Answers the lines of `input` on `output` until the input ends. The
+/// output is flushed after every line.
+pub fn run<R, W>(spell_checker: &mut SpellChecker, input: R, mut output: W) -> Result<()>
+where
+    R: BufRead,
+    W: Write,
+{
+    writeln!(output, "{BANNER}")?;
+    output.flush()?;
+    let mut terse = false;
+    let mut tex = false;
+    for line in input.lines() {
+        let line = line?;
+        // the commands are ASCII, so the word starts at byte 1
+        let word = || line[1..].trim();
+        match line.chars().next() {
+            Some('*') => skip_invalid(spell_checker.add(word()))?,
+            Some('&') => skip_invalid(spell_checker.add(word().to_lowercase()))?,
+            Some('@') => spell_checker.ignore(word()),
+            Some('#') | Some('~') => {}
+            Some('!') => terse = true,
+            Some('%') => terse = false,
+            Some('+') => tex = true,
+            Some('-') => tex = false,
+            Some('^') => check_line(spell_checker, &line, 1, terse, tex, &mut output)?,
+            _ => check_line(spell_checker, &line, 0, terse, tex, &mut output)?,
+        }
+        output.flush()?;
+    }
+    Ok(())
+}
+
+/// Like ispell, words that cannot be added are left out silently.
+fn skip_invalid(result: Result<()>) -> Result<()> {
+    match result {
+        Err(Error::InvalidWord { .. }) => Ok(()),
+        result => result,
+    }
+}
+
+/// Answers the words in `line` after `start`, followed by an empty line.
+fn check_line<W: Write>(
+    spell_checker: &SpellChecker,
+    line: &str,
+    start: usize,
+    terse: bool,
+    tex: bool,
+    output: &mut W,
+) -> Result<()> {
+    let text = &line[start..];
+    let prose = if tex {
+        Latex.prose_regions(text)
+    } else {
+        PlainText.prose_regions(text)
+    };
+    for range in words(text) {
+        if !prose
+            .iter()
+            .any(|p| p.start <= range.start && range.end <= p.end)
+        {
+            continue;
+        }
+        let word = &text[range.clone()];
+        if spell_checker.check(strip_invisible(word))? {
+            if !terse {
+                writeln!(output, "*")?;
+            }
+            continue;
+        }
+        let offset = line[..start + range.start].chars().count();
+        let suggestions = spell_checker.suggest(strip_invisible(word))?;
+        if suggestions.is_empty() {
+            writeln!(output, "# {word} {offset}")?;
+        } else {
+            writeln!(
+                output,
+                "& {word} {} {offset}: {}",
+                suggestions.len(),
+                suggestions.join(", ")
+            )?;
+        }
+    }
+    writeln!(output)?;
+    Ok(())
+}
@@ -0,0 +1,276 @@
+//! A fixed set of spell checkers for servers that check concurrently.
+
+use std::{
+    ops::{Deref, DerefMut},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Condvar, Mutex, MutexGuard,
+    },
+    time::{Duration, Instant},
+};
+
+use crate::{
+    text::check_plain_text, Dictionary, Error, FileReport, Operation, Report, Result, SpellChecker,
+};
+
+/// Spell checkers opened from the same [`Dictionary`] up front, handed
+/// out to one thread at a time with [`get()`](SpellCheckerPool::get).
+///
+/// Opening a large dictionary takes long, and a single shared checker,
+/// like a [`FrozenSpellChecker`](crate::FrozenSpellChecker), lets one
+/// thread at a time call hunspell. With a pool as many requests as it
+/// has checkers run at once, and more wait for one to come back. The pool
+/// is `Send` and `Sync`, so it can be shared behind an `Arc`.
+///
+/// Words added or ignored through a pooled checker stay with that checker
+/// only.
+///
+/// # Memory
+///
+/// Every checker costs about as much memory as a `SpellChecker` of its
+/// own: hunspell reads the dictionary into hash tables per handle, and its
+/// C API has no way to share them between handles. What can be shared is
+/// shared: the contents of a dictionary made with
+/// [`Dictionary::from_bytes()`] are put in memory files once, which all
+/// checkers of the pool read.
+///
+/// ```
+/// use std::sync::Arc;
+/// use hunspell_rs::{Dictionary, SpellCheckerPool};
+///
+/// let dictionary = Dictionary::from_files("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic");
+/// let pool = Arc::new(SpellCheckerPool::new(&dictionary, 2).unwrap());
+/// let threads: Vec<_> = ["cats", "catz"]
+///     .into_iter()
+///     .map(|word| {
+///         let pool = pool.clone();
+///         std::thread::spawn(move || pool.get().check(word))
+///     })
+///     .collect();
+/// let found: Vec<_> = threads.into_iter().map(|t| t.join().unwrap()).collect();
+/// assert_eq!(vec![Ok(true), Ok(false)], found);
+/// ```
+#[derive(Debug)]
+pub struct SpellCheckerPool {
+    dictionary: Dictionary,
+    idle: Mutex<Vec<Pooled>>,
+    returned: Condvar,
+    size: usize,
+    deterministic: bool,
+}
+
+/// A spell checker of a pool.
+#[derive(Debug)]
+struct Pooled(SpellChecker);
+
+// SAFETY: hunspell handles are not tied to the thread that made them. A
+// pooled checker is owned by the pool or by one guard, never shared, so
+// only one thread at a time uses its handle and `RefCell`s.
+unsafe impl Send for Pooled {}
+
+/// A spell checker taken from a [`SpellCheckerPool`], it goes back to the
+/// pool when dropped.
+#[derive(Debug)]
+pub struct PooledSpellChecker<'a> {
+    pool: &'a SpellCheckerPool,
+    // None only while dropping
+    spell_checker: Option<Pooled>,
+}
+
+impl SpellCheckerPool {
+    /// Opens `size` spell checkers from `dictionary`, at least one, on
+    /// as many threads.
+    pub fn new(dictionary: &Dictionary, size: usize) -> Result<Self> {
+        let size = size.max(1);
+        let loaded = dictionary.load()?;
+        let idle = std::thread::scope(|scope| {
+            let threads: Vec<_> = (0..size)
+                .map(|_| scope.spawn(|| dictionary.open_loaded(&loaded).map(Pooled)))
+                .collect();
+            threads
+                .into_iter()
+                .map(|t| {
+                    t.join()
+                        .unwrap_or_else(|_| Err(Error::Panicked("opening a dictionary".into())))
+                })
+                .collect::<Result<Vec<Pooled>>>()
+        })?;
+        Ok(SpellCheckerPool {
+            dictionary: dictionary.clone(),
+            idle: Mutex::new(idle),
+            returned: Condvar::new(),
+            size,
+            deterministic: true,
+        })
+    }
+
+    /// Whether [`report_files()`](SpellCheckerPool::report_files) keeps the
+    /// files in the order they are given, true by default. Then the same
+    /// files give the same report however the threads run, i.e. for
+    /// comparing reports in continuous integration. Without it, files
+    /// are in the order they were checked in, and an error is the first
+    /// one that occurred rather than the one of the first file.
+    pub fn with_deterministic(mut self, deterministic: bool) -> Self {
+        self.deterministic = deterministic;
+        self
+    }
+
+    pub fn is_deterministic(&self) -> bool {
+        self.deterministic
+    }
+
+    pub fn dictionary(&self) -> &Dictionary {
+        &self.dictionary
+    }
+
+    /// Returns the number of spell checkers.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Returns the number of spell checkers that are not in use.
+    pub fn available(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Takes a spell checker, waiting for one to come back when all are in
+    /// use.
+    pub fn get(&self) -> PooledSpellChecker<'_> {
+        let mut idle = self.lock();
+        loop {
+            if let Some(spell_checker) = idle.pop() {
+                return self.guard(spell_checker);
+            }
+            idle = self.returned.wait(idle).unwrap_or_else(|e| e.into_inner());
+        }
+    }
+
+    /// Takes a spell checker if one is not in use.
+    pub fn try_get(&self) -> Option<PooledSpellChecker<'_>> {
+        self.lock().pop().map(|s| self.guard(s))
+    }
+
+    /// Like [`get()`](SpellCheckerPool::get), but fails with
+    /// `Error::Timeout` when no spell checker came back within `timeout`.
+    pub fn get_timeout(&self, timeout: Duration) -> Result<PooledSpellChecker<'_>> {
+        let deadline = Instant::now() + timeout;
+        let mut idle = self.lock();
+        loop {
+            if let Some(spell_checker) = idle.pop() {
+                return Ok(self.guard(spell_checker));
+            }
+            let left = deadline.saturating_duration_since(Instant::now());
+            if left.is_zero() {
+                return Err(Error::Timeout(timeout));
+            }
+            idle = self
+                .returned
+                .wait_timeout(idle, left)
+                .unwrap_or_else(|e| e.into_inner())
+                .0;
+        }
+    }
+
+    /// Checks the files at `paths` as plain text, like
+    /// [`SpellChecker::check_text()`], with all spell checkers of the pool
+    /// at once.
+    ///
+    /// ```
+    /// use hunspell_rs::{Dictionary, SpellCheckerPool};
+    ///
+    /// let dictionary = Dictionary::from_files("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic");
+    /// let pool = SpellCheckerPool::new(&dictionary, 2).unwrap();
+    /// let report = pool.report_files(["README.md", "CHANGELOG.md"]).unwrap();
+    /// assert_eq!("README.md", report.files[0].path.to_str().unwrap());
+    /// ```
+    pub fn report_files<I, P>(&self, paths: I) -> Result<Report>
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<Path>,
+    {
+        let paths: Vec<PathBuf> = paths
+            .into_iter()
+            .map(|p| p.as_ref().to_path_buf())
+            .collect();
+        let next = AtomicUsize::new(0);
+        let done = Mutex::new(Vec::with_capacity(paths.len()));
+        std::thread::scope(|scope| {
+            for _ in 0..self.size.min(paths.len()) {
+                scope.spawn(|| {
+                    let spell_checker = self.get();
+                    loop {
+                        let i = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(path) = paths.get(i) else {
+                            break;
+                        };
+                        let report = report_file(&spell_checker, path);
+                        let failed = report.is_err();
+                        done.lock()
+                            .unwrap_or_else(|e| e.into_inner())
+                            .push((i, report));
+                        if failed {
+                            break;
+                        }
+                    }
+                });
+            }
+        });
+        let mut done = done.into_inner().unwrap_or_else(|e| e.into_inner());
+        if self.deterministic {
+            done.sort_by_key(|(i, _)| *i);
+        }
+        let files = done
+            .into_iter()
+            .map(|(_, report)| report)
+            .collect::<Result<_>>()?;
+        Ok(Report { files })
+    }
+
+    fn guard(&self, spell_checker: Pooled) -> PooledSpellChecker<'_> {
+        PooledSpellChecker {
+            pool: self,
+            spell_checker: Some(spell_checker),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Vec<Pooled>> {
+        // the list is only pushed to and popped from, a panic cannot
+        // leave it half changed
+        self.idle.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Deref for PooledSpellChecker<'_> {
+    type Target = SpellChecker;
+
+    fn deref(&self) -> &SpellChecker {
+        &self.spell_checker.as_ref().expect("spell checker taken").0
+    }
+}
+
+impl DerefMut for PooledSpellChecker<'_> {
+    fn deref_mut(&mut self) -> &mut SpellChecker {
+        &mut self.spell_checker.as_mut().expect("spell checker taken").0
+    }
+}
+
+impl Drop for PooledSpellChecker<'_> {
+    fn drop(&mut self) {
+        if let Some(spell_checker) = self.spell_checker.take() {
+            self.pool.lock().push(spell_checker);
+            self.pool.returned.notify_one();
+        }
+    }
+}
+
+/// Reads and checks the file at `path` as plain text.
+fn report_file(spell_checker: &SpellChecker, path: &Path) -> Result<FileReport> {
+    let text = std::fs::read_to_string(path).map_err(Error::io(Operation::Load, path))?;
+    let (misspellings, words) = check_plain_text(|word| spell_checker.check(word), &text)?;
+    Ok(FileReport {
+        path: path.to_path_buf(),
+        words,
+        misspellings,
+    })
+}
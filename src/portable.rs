@@ -0,0 +1,106 @@
+//! A serializable `SpellChecker` that includes its dictionary files.
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+use crate::{
+    memory::memory_file,
+    spell_checker::{RuntimeWord, Setup},
+    Error, Language, Operation, Result, SpellChecker,
+};
+
+/// A [`SpellChecker`] with the contents of its affix, dictionary and extra
+/// dictionary files, made with
+/// [`to_portable()`](SpellChecker::to_portable). A serialized
+/// `SpellChecker` only refers to the files by path, a serialized
+/// `PortableSpellChecker` can be opened on another machine.
+///
+/// ```
+/// use hunspell_rs::{PortableSpellChecker, SpellChecker};
+///
+/// let mut spell = SpellChecker::new("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic").unwrap();
+/// spell.add("octonasaurius").unwrap();
+/// let bytes = bincode::serialize(&spell.to_portable().unwrap()).unwrap();
+///
+/// let portable: PortableSpellChecker = bincode::deserialize(&bytes).unwrap();
+/// let spell = portable.open().unwrap();
+/// assert_eq!(Ok(true), spell.check("octonasaurius"));
+/// ```
+// Debug is manually implemented to keep the key and contents out of logs.
+#[derive(Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct PortableSpellChecker {
+    affix: Vec<u8>,
+    dictionary: Vec<u8>,
+    additional_dictionaries: Vec<Vec<u8>>,
+    key: Option<String>,
+    language: Option<Language>,
+    runtime_words: Vec<RuntimeWord>,
+}
+
+impl PortableSpellChecker {
+    /// Opens the checker with the files loaded from memory, see
+    /// [`SpellChecker::from_memory()`].
+    pub fn open(&self) -> Result<SpellChecker> {
+        let (affix_file, affix) = memory_file("hunspell.aff", &self.affix)?;
+        let (dictionary_file, dictionary) = memory_file("hunspell.dic", &self.dictionary)?;
+        let mut setup = Setup {
+            affix,
+            dictionary,
+            additional_dictionaries: Vec::new(),
+            key: self.key.clone(),
+            language: self.language.clone(),
+            runtime_words: self.runtime_words.clone(),
+            memory_files: vec![affix_file.into(), dictionary_file.into()],
+        };
+        for data in &self.additional_dictionaries {
+            let (file, path) = memory_file("hunspell.dic", data)?;
+            setup.additional_dictionaries.push(path);
+            setup.memory_files.push(file.into());
+        }
+        setup.open()
+    }
+}
+
+impl core::fmt::Debug for PortableSpellChecker {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        fmt.debug_struct("PortableSpellChecker")
+            .field("affix", &format_args!("{} bytes", self.affix.len()))
+            .field(
+                "dictionary",
+                &format_args!("{} bytes", self.dictionary.len()),
+            )
+            .field(
+                "additional_dictionaries",
+                &self.additional_dictionaries.len(),
+            )
+            .field("encrypted", &self.key.is_some())
+            .field("language", &self.language)
+            .field("runtime_words", &self.runtime_words.len())
+            .finish()
+    }
+}
+
+impl SpellChecker {
+    /// Returns the checker with the contents of its files, to be
+    /// serialized where the files may not exist when it is deserialized.
+    /// Unlike with a serialized `SpellChecker`, the ignored words, the
+    /// autocorrect pairs and the options are not included.
+    pub fn to_portable(&self) -> Result<PortableSpellChecker> {
+        Ok(PortableSpellChecker {
+            affix: read(&self.affix)?,
+            dictionary: read(&self.dictionary)?,
+            additional_dictionaries: self
+                .additional_dictionaries
+                .iter()
+                .map(read)
+                .collect::<Result<_>>()?,
+            key: self.key.clone(),
+            language: self.language.clone(),
+            runtime_words: self.runtime_words.clone(),
+        })
+    }
+}
+
+fn read(path: &PathBuf) -> Result<Vec<u8>> {
+    std::fs::read(path).map_err(Error::io(Operation::Load, path))
+}
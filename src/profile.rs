@@ -0,0 +1,130 @@
+//! A user's words, ignores and autocorrections as one portable value.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{apply_suggestion, Result, SpellChecker};
+
+/// Everything a user taught a [`SpellChecker`]: the added and ignored
+/// words and the autocorrect pairs. Unlike the `SpellChecker` it does not
+/// refer to dictionary files, so (with the `serde` feature) it can be
+/// synced between devices that have the dictionary in different places.
+///
+/// ```
+/// use hunspell_rs::SpellChecker;
+///
+/// let mut laptop = SpellChecker::new("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic").unwrap();
+/// laptop.add("octonasaurius").unwrap();
+/// laptop.add_replacement("teh", "the");
+/// let profile = laptop.extract_profile();
+///
+/// let mut phone = SpellChecker::new("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic").unwrap();
+/// phone.apply_profile(&profile).unwrap();
+/// assert_eq!(Ok(true), phone.check("octonasaurius"));
+/// assert_eq!(Some("the".to_string()), phone.replacement("teh"));
+/// ```
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UserProfile {
+    /// Words added with `add()` or `add_with_affix()`.
+    pub words: BTreeSet<String>,
+    /// Words accepted with `ignore()`.
+    pub ignored: BTreeSet<String>,
+    /// Autocorrect pairs from `add_replacement()`.
+    pub replacements: BTreeMap<String, String>,
+}
+
+impl SpellChecker {
+    /// Returns the added and ignored words and the autocorrect pairs.
+    pub fn extract_profile(&self) -> UserProfile {
+        UserProfile {
+            words: self.added.iter().cloned().collect(),
+            ignored: self.ignored.borrow().iter().cloned().collect(),
+            replacements: self.replacements.borrow().clone(),
+        }
+    }
+
+    /// Adds the words, ignores and autocorrect pairs of `profile` to those
+    /// already there.
+    pub fn apply_profile(&mut self, profile: &UserProfile) -> Result<()> {
+        profile.words.iter().try_for_each(|w| self.add(w))?;
+        profile.ignored.iter().for_each(|w| self.ignore(w));
+        self.replacements
+            .borrow_mut()
+            .extend(profile.replacements.clone());
+        Ok(())
+    }
+
+    /// Replaces `from` by `to` when autocorrecting, see `replacement()`.
+    ///
+    /// The pairs also lead the suggestions, like the `REP` table of the
+    /// affix file that hunspell cannot change at runtime: a word that is
+    /// `from` gets `to` as its first suggestion, with the capitalization
+    /// of the word. A word that contains `from` gets the word with `to` in
+    /// its place, when that is correct.
+    ///
+    /// ```
+    /// use hunspell_rs::SpellChecker;
+    ///
+    /// let spell = SpellChecker::new("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic").unwrap();
+    /// spell.add_replacement("progarm", "program");
+    /// assert_eq!("program", spell.suggest("progarm").unwrap()[0]);
+    /// assert_eq!("Programs", spell.suggest("Progarms").unwrap()[0]);
+    /// ```
+    pub fn add_replacement<S: AsRef<str>>(&self, from: S, to: S) {
+        self.replacements
+            .borrow_mut()
+            .insert(from.as_ref().to_string(), to.as_ref().to_string());
+    }
+
+    /// Forgets the autocorrection of `from`.
+    pub fn remove_replacement<S: AsRef<str>>(&self, from: S) {
+        self.replacements.borrow_mut().remove(from.as_ref());
+    }
+
+    /// Returns the autocorrection of `word` added with
+    /// `add_replacement()`.
+    pub fn replacement<S: AsRef<str>>(&self, word: S) -> Option<String> {
+        self.replacements.borrow().get(word.as_ref()).cloned()
+    }
+
+    /// Moves the suggestions from the replacements of `word` to the front
+    /// of `suggestions`, see `add_replacement()`. Returns the number of
+    /// suggestions.
+    pub(crate) fn prepend_replacements(
+        &self,
+        word: &str,
+        suggestions: &mut Vec<String>,
+    ) -> Result<usize> {
+        let mut found: Vec<String> = Vec::new();
+        {
+            let replacements = self.replacements.borrow();
+            let lower = word.to_lowercase();
+            if let Some(to) = replacements.get(word) {
+                found.push(to.clone());
+            } else if let Some(to) = replacements.get(&lower) {
+                found.push(apply_suggestion(word, to));
+            } else if let Some(to) = self.table_correction(word) {
+                found.push(to);
+            }
+            for (from, to) in replacements.iter().filter(|(from, _)| **from != lower) {
+                for (i, _) in lower.match_indices(from.as_str()) {
+                    let candidate = apply_suggestion(
+                        word,
+                        &format!("{}{to}{}", &lower[..i], &lower[i + from.len()..]),
+                    );
+                    if !found.contains(&candidate) && self.check(&candidate)? {
+                        found.push(candidate);
+                    }
+                }
+            }
+        }
+        if !found.is_empty() {
+            suggestions.retain(|s| !found.contains(s));
+            suggestions.splice(0..0, found);
+        }
+        Ok(suggestions.len())
+    }
+}
@@ -0,0 +1,147 @@
+//! Ordering suggestions by how likely they are as typing errors.
+
+use crate::{Result, SpellChecker};
+
+/// The keyboard a text was typed on, for [`RankingOptions`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum KeyboardLayout {
+    #[default]
+    Qwerty,
+    Azerty,
+    Dvorak,
+}
+
+impl KeyboardLayout {
+    /// The rows of keys from the digits down, without shift.
+    fn rows(&self) -> [&'static str; 4] {
+        match self {
+            KeyboardLayout::Qwerty => ["1234567890-=", "qwertyuiop[]", "asdfghjkl;'", "zxcvbnm,./"],
+            KeyboardLayout::Azerty => {
+                ["&é\"'(-è_çà)=", "azertyuiop^$", "qsdfghjklmù", "wxcvbn,;:!"]
+            }
+            KeyboardLayout::Dvorak => ["1234567890[]", "',.pyfgcrl/=", "aoeuidhtns-", ";qjkxbmwvz"],
+        }
+    }
+
+    /// The place of the key of `c` in key widths, with the rows shifted
+    /// like on a real keyboard.
+    fn position(&self, c: char) -> Option<(f32, f32)> {
+        const ROW_OFFSETS: [f32; 4] = [0.0, 0.5, 0.75, 1.25];
+        let c = c.to_lowercase().next()?;
+        self.rows()
+            .iter()
+            .zip(ROW_OFFSETS)
+            .enumerate()
+            .find_map(|(row, (keys, offset))| {
+                let column = keys.chars().position(|k| k == c)?;
+                Some((row as f32, column as f32 + offset))
+            })
+    }
+
+    /// Returns true if the keys of `a` and `b` touch. Characters that are
+    /// not on the layout are never neighbours.
+    pub fn are_neighbours(&self, a: char, b: char) -> bool {
+        match (self.position(a), self.position(b)) {
+            (Some((row_a, col_a)), Some((row_b, col_b))) => {
+                a != b && (row_a - row_b).abs() <= 1.0 && (col_a - col_b).abs() <= 1.0
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Options for [`SpellChecker::suggest_ranked()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RankingOptions {
+    pub layout: KeyboardLayout,
+    /// Maximal number of suggestions returned.
+    pub max: usize,
+}
+
+impl Default for RankingOptions {
+    fn default() -> Self {
+        RankingOptions {
+            layout: KeyboardLayout::default(),
+            max: usize::MAX,
+        }
+    }
+}
+
+impl SpellChecker {
+    /// Returns the suggestions of hunspell ordered by a typing distance:
+    /// the edit distance, where hitting a neighbouring key costs half an
+    /// edit and a character of the same `MAP` group nothing. Suggestions
+    /// at the same distance keep the order of hunspell.
+    ///
+    /// ```
+    /// use hunspell_rs::{KeyboardLayout, RankingOptions, SpellChecker};
+    ///
+    /// let spell = SpellChecker::new("tests/fixtures/rank/rank.aff", "tests/fixtures/rank/rank.dic").unwrap();
+    /// assert_eq!(vec!["cat", "cot", "cut"], spell.suggest("cet").unwrap());
+    /// let options = RankingOptions {
+    ///     layout: KeyboardLayout::Dvorak,
+    ///     ..Default::default()
+    /// };
+    /// // on dvorak e is between o and u
+    /// assert_eq!(vec!["cot", "cut", "cat"], spell.suggest_ranked("cet", &options).unwrap());
+    /// ```
+    pub fn suggest_ranked<S>(&self, word: S, options: &RankingOptions) -> Result<Vec<String>>
+    where
+        S: AsRef<str>,
+    {
+        let word = word.as_ref();
+        let char_map = self.char_map();
+        let mut buffers = ([0; 4], [0; 4]);
+        let mut similar = |a: char, b: char| {
+            char_map.are_similar(a.encode_utf8(&mut buffers.0), b.encode_utf8(&mut buffers.1))
+        };
+        let mut suggestions: Vec<(u32, String)> = self
+            .suggest(word)?
+            .into_iter()
+            .map(|s| (typing_distance(word, &s, options.layout, &mut similar), s))
+            .collect();
+        // stable, so equal distances keep the order of hunspell
+        suggestions.sort_by_key(|(distance, _)| *distance);
+        Ok(suggestions
+            .into_iter()
+            .map(|(_, s)| s)
+            .take(options.max)
+            .collect())
+    }
+}
+
+/// The optimal string alignment distance between `a` and `b` in half
+/// edits: substituting a neighbouring key costs 1, one in the same group
+/// of `similar` 0, other edits 2. Case is ignored.
+fn typing_distance<F>(a: &str, b: &str, layout: KeyboardLayout, similar: &mut F) -> u32
+where
+    F: FnMut(char, char) -> bool,
+{
+    let a: Vec<char> = a.to_lowercase().chars().collect();
+    let b: Vec<char> = b.to_lowercase().chars().collect();
+    let mut previous2: Vec<u32> = vec![0; b.len() + 1];
+    let mut previous: Vec<u32> = (0..=b.len() as u32).map(|j| j * 2).collect();
+    let mut current: Vec<u32> = vec![0; b.len() + 1];
+    for i in 1..=a.len() {
+        current[0] = i as u32 * 2;
+        for j in 1..=b.len() {
+            let (x, y) = (a[i - 1], b[j - 1]);
+            let cost = if x == y || similar(x, y) {
+                0
+            } else if layout.are_neighbours(x, y) {
+                1
+            } else {
+                2
+            };
+            current[j] = (previous[j] + 2)
+                .min(current[j - 1] + 2)
+                .min(previous[j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                current[j] = current[j].min(previous2[j - 2] + 2);
+            }
+        }
+        std::mem::swap(&mut previous2, &mut previous);
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[b.len()]
+}
@@ -0,0 +1,214 @@
+//! Spell checkers by language, shared by the threads of a service.
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, MutexGuard},
+    time::{Duration, Instant},
+};
+
+use crate::{catch_panic, Config, Dictionary, Error, FrozenSpellChecker, Result, SpellChecker};
+
+/// Frozen spell checkers by dictionary, opened on first use or up front
+/// with [`preload()`](CheckerRegistry::preload).
+///
+/// The dictionaries are named like [`Config::dictionary`]: a language tag
+/// of an installed dictionary or a path without extension.
+///
+/// With [`with_idle_timeout()`](CheckerRegistry::with_idle_timeout)
+/// dictionaries that were not used for a while are dropped, and opened
+/// again when they are asked for. A checker is only freed when the
+/// callers dropped their `Arc`s as well.
+///
+/// ```
+/// use hunspell_rs::CheckerRegistry;
+///
+/// let registry = CheckerRegistry::new();
+/// registry.preload(&["tests/fixtures/reduced"]).unwrap();
+/// let spell = registry.get("tests/fixtures/reduced").unwrap();
+/// assert_eq!(Ok(true), spell.check("cats"));
+/// ```
+#[derive(Debug, Default)]
+pub struct CheckerRegistry {
+    checkers: Mutex<HashMap<String, Entry>>,
+    idle_timeout: Option<Duration>,
+}
+
+#[derive(Debug)]
+struct Entry {
+    checker: Arc<FrozenSpellChecker>,
+    last_used: Instant,
+}
+
+impl Entry {
+    fn new(checker: Arc<FrozenSpellChecker>) -> Self {
+        Entry {
+            checker,
+            last_used: Instant::now(),
+        }
+    }
+}
+
+impl CheckerRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Drops the dictionaries that were not used for `timeout`, checked
+    /// on every `get()` and by `evict_idle()`.
+    pub fn with_idle_timeout(mut self, timeout: Duration) -> Self {
+        self.idle_timeout = Some(timeout);
+        self
+    }
+
+    pub fn idle_timeout(&self) -> Option<Duration> {
+        self.idle_timeout
+    }
+
+    /// Drops the dictionaries that were not used for the idle timeout,
+    /// returns how many. Call it from a timer to free memory when no
+    /// `get()` calls come in.
+    pub fn evict_idle(&self) -> usize {
+        Self::evict(&mut self.lock(), self.idle_timeout)
+    }
+
+    fn evict(checkers: &mut HashMap<String, Entry>, idle_timeout: Option<Duration>) -> usize {
+        let Some(timeout) = idle_timeout else {
+            return 0;
+        };
+        let before = checkers.len();
+        checkers.retain(|_, entry| entry.last_used.elapsed() < timeout);
+        before - checkers.len()
+    }
+
+    /// Opens the `dictionaries` that are not open yet, each on its own
+    /// thread. Fails with `Error::PreloadFailed` listing every dictionary
+    /// that could not be opened, or whose thread panicked; the others are
+    /// opened anyway.
+    pub fn preload<S: AsRef<str>>(&self, dictionaries: &[S]) -> Result<()> {
+        let missing: Vec<String> = {
+            let checkers = self.lock();
+            let mut missing: Vec<String> = dictionaries
+                .iter()
+                .map(|d| d.as_ref().to_string())
+                .filter(|d| !checkers.contains_key(d))
+                .collect();
+            missing.sort();
+            missing.dedup();
+            missing
+        };
+        let opened: Vec<(String, Result<FrozenSpellChecker>)> = std::thread::scope(|scope| {
+            let threads: Vec<_> = missing
+                .into_iter()
+                .map(|dictionary| {
+                    scope.spawn(move || {
+                        let result = catch_panic(|| open(&dictionary));
+                        (dictionary, result)
+                    })
+                })
+                .collect();
+            threads
+                .into_iter()
+                .map(|t| t.join().unwrap_or_else(|e| std::panic::resume_unwind(e)))
+                .collect()
+        });
+        let mut failed = Vec::new();
+        let mut checkers = self.lock();
+        for (dictionary, result) in opened {
+            match result {
+                Ok(checker) => {
+                    checkers
+                        .entry(dictionary)
+                        .or_insert_with(|| Entry::new(Arc::new(checker)));
+                }
+                Err(e) => failed.push((dictionary, e)),
+            }
+        }
+        if failed.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::PreloadFailed(failed))
+        }
+    }
+
+    /// Returns the spell checker of `dictionary`, opening it when it is not
+    /// open yet.
+    pub fn get(&self, dictionary: &str) -> Result<Arc<FrozenSpellChecker>> {
+        {
+            let mut checkers = self.lock();
+            Self::evict(&mut checkers, self.idle_timeout);
+            if let Some(entry) = checkers.get_mut(dictionary) {
+                entry.last_used = Instant::now();
+                return Ok(entry.checker.clone());
+            }
+        }
+        // opened without the lock, so other dictionaries can be used
+        // in the meantime
+        let checker = Arc::new(open(dictionary)?);
+        let mut checkers = self.lock();
+        let entry = checkers
+            .entry(dictionary.to_string())
+            .or_insert_with(|| Entry::new(checker));
+        entry.last_used = Instant::now();
+        Ok(entry.checker.clone())
+    }
+
+    /// Replaces the checker of `dictionary` by one of `upgrade`, keeping
+    /// its runtime words and session like
+    /// [`SpellChecker::swap_dictionary()`]. `upgrade` is opened without
+    /// blocking the registry: until it is ready `get()` returns the old
+    /// checker, after that the new one, and callers holding the old one
+    /// can finish with it. When it fails the old checker stays. When
+    /// `dictionary` is not open, `upgrade` is opened under its name.
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    /// use hunspell_rs::{CheckerRegistry, Dictionary};
+    ///
+    /// let registry = Arc::new(CheckerRegistry::new());
+    /// let old = registry.get("tests/fixtures/reduced").unwrap();
+    /// let upgrade = Dictionary::from_files("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic")
+    ///     .with_additional_dictionary("tests/fixtures/extra.dic");
+    /// let shared = registry.clone();
+    /// let swap = std::thread::spawn(move || shared.swap_dictionary("tests/fixtures/reduced", upgrade));
+    /// // the old checker keeps answering in the meantime
+    /// assert_eq!(Ok(false), old.check("systemdunits"));
+    /// swap.join().unwrap().unwrap();
+    /// let new = registry.get("tests/fixtures/reduced").unwrap();
+    /// assert_eq!(Ok(true), new.check("systemdunits"));
+    /// ```
+    pub fn swap_dictionary(&self, dictionary: &str, upgrade: Dictionary) -> Result<()> {
+        let old = self.lock().get(dictionary).map(|e| e.checker.clone());
+        let opened = upgrade.open()?;
+        let checker = match old {
+            Some(old) => old.hand_over(opened)?,
+            None => opened.freeze(),
+        };
+        self.lock()
+            .insert(dictionary.to_string(), Entry::new(Arc::new(checker)));
+        Ok(())
+    }
+
+    /// Returns true if `dictionary` is open.
+    pub fn contains(&self, dictionary: &str) -> bool {
+        self.lock().contains_key(dictionary)
+    }
+
+    /// Returns the names of the open dictionaries, sorted.
+    pub fn dictionaries(&self) -> Vec<String> {
+        let mut dictionaries: Vec<String> = self.lock().keys().cloned().collect();
+        dictionaries.sort();
+        dictionaries
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashMap<String, Entry>> {
+        self.checkers.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+fn open(dictionary: &str) -> Result<FrozenSpellChecker> {
+    let config = Config {
+        dictionary: dictionary.to_string(),
+        ..Default::default()
+    };
+    SpellChecker::try_from(&config).map(SpellChecker::freeze)
+}
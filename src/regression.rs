@@ -0,0 +1,123 @@
+//! Checking that a dictionary still makes the expected suggestions.
+
+use std::path::Path;
+
+use crate::{Error, Operation, Result, SpellChecker};
+
+/// A misspelled word with the suggestions it should get, in order.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct SuggestionCase {
+    pub word: String,
+    pub expected: Vec<String>,
+}
+
+impl SuggestionCase {
+    pub fn new<S: AsRef<str>>(word: S, expected: &[S]) -> Self {
+        SuggestionCase {
+            word: word.as_ref().to_string(),
+            expected: expected.iter().map(|s| s.as_ref().to_string()).collect(),
+        }
+    }
+
+    /// Reads cases in the format of the hunspell test suite: a `.wrong`
+    /// file with a misspelled word per line and a `.sug` file with the
+    /// comma separated suggestions for the word on the same line.
+    pub fn read<P: AsRef<Path>>(wrong: P, sug: P) -> Result<Vec<SuggestionCase>> {
+        let wrong = std::fs::read_to_string(&wrong).map_err(Error::io(Operation::Load, &wrong))?;
+        let sug = std::fs::read_to_string(&sug).map_err(Error::io(Operation::Load, &sug))?;
+        let mut suggestions = sug.lines();
+        Ok(wrong
+            .lines()
+            .map(str::trim)
+            .filter(|w| !w.is_empty())
+            .map(|word| SuggestionCase {
+                word: word.to_string(),
+                expected: suggestions
+                    .next()
+                    .unwrap_or("")
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|s| !s.is_empty())
+                    .map(str::to_string)
+                    .collect(),
+            })
+            .collect())
+    }
+}
+
+/// A case whose suggestions were not as expected.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SuggestionMismatch {
+    pub word: String,
+    pub expected: Vec<String>,
+    pub actual: Vec<String>,
+}
+
+/// The outcome of [`SpellChecker::test_suggestions()`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SuggestionReport {
+    /// The number of cases with the expected suggestions.
+    pub passed: usize,
+    pub mismatches: Vec<SuggestionMismatch>,
+}
+
+impl SuggestionReport {
+    /// Returns true if all cases had the expected suggestions.
+    pub fn is_ok(&self) -> bool {
+        self.mismatches.is_empty()
+    }
+}
+
+impl core::fmt::Display for SuggestionReport {
+    /// Shows the number of passed cases and a line per mismatch, i.e.
+    /// `catz: expected cats, cat; got cat`.
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            fmt,
+            "{} passed, {} failed",
+            self.passed,
+            self.mismatches.len()
+        )?;
+        for m in &self.mismatches {
+            write!(
+                fmt,
+                "\n{}: expected {}; got {}",
+                m.word,
+                m.expected.join(", "),
+                m.actual.join(", ")
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl SpellChecker {
+    /// Compares the suggestions for the words of `cases` with the
+    /// expected ones, including their order. Run it in the tests of an
+    /// application to notice when a new dictionary or hunspell version
+    /// changes the suggestions.
+    ///
+    /// ```
+    /// use hunspell_rs::{SpellChecker, SuggestionCase};
+    ///
+    /// let spell = SpellChecker::new("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic").unwrap();
+    /// let report = spell.test_suggestions(&[SuggestionCase::new("progam", &["program"])]).unwrap();
+    /// assert!(report.is_ok(), "{report}");
+    /// ```
+    pub fn test_suggestions(&self, cases: &[SuggestionCase]) -> Result<SuggestionReport> {
+        let mut report = SuggestionReport::default();
+        for case in cases {
+            let actual = self.suggest(&case.word)?;
+            if actual == case.expected {
+                report.passed += 1;
+            } else {
+                report.mismatches.push(SuggestionMismatch {
+                    word: case.word.clone(),
+                    expected: case.expected.clone(),
+                    actual,
+                });
+            }
+        }
+        Ok(report)
+    }
+}
@@ -0,0 +1,487 @@
+//! Spelling statistics of a set of files, to follow them over time.
+
+use std::{
+    cell::Cell,
+    collections::HashMap,
+    ops::Range,
+    path::{Path, PathBuf},
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    Error, Language, Misspelling, MultiLanguageChecker, Operation, Result, SuggestionArbiter,
+    TextChecker,
+};
+
+/// The misspellings of one file, and how many words were checked.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileReport {
+    pub path: PathBuf,
+    /// The number of words that were checked, without the ignored parts
+    /// of the text.
+    pub words: usize,
+    pub misspellings: Vec<Misspelling>,
+}
+
+impl FileReport {
+    /// Misspellings per 1000 checked words, 0 for a file without words.
+    pub fn per_thousand(&self) -> f64 {
+        per_thousand(self.misspellings.len(), self.words)
+    }
+}
+
+/// The statistics of one text, see [`TextChecker::spell_report()`] and
+/// [`MultiLanguageChecker::spell_report()`]. With the `serde` feature it
+/// can be written as JSON, i.e. for continuous integration tools.
+///
+/// ```
+/// use hunspell_rs::{SpellChecker, TextChecker};
+///
+/// let spell = SpellChecker::new("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic").unwrap();
+/// let checker = TextChecker::new(spell);
+/// let report = checker.spell_report("catz cats catz").unwrap();
+/// assert!(!report.is_clean());
+/// assert_eq!(3, report.words);
+/// assert_eq!(1, report.misspellings.len());
+/// assert_eq!(2, report.occurrences());
+/// assert_eq!("catz", report.misspellings[0].word);
+/// assert_eq!(vec![0..4, 10..14], report.misspellings[0].ranges);
+/// assert_eq!(1, report.languages[0].words);
+/// ```
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SpellReport {
+    /// The number of words that were checked, without the ignored parts
+    /// of the text.
+    pub words: usize,
+    /// Every misspelled word once, in the order of their first
+    /// occurrence.
+    pub misspellings: Vec<MisspelledWord>,
+    /// The number of correct words per dictionary, in the order of the
+    /// checkers.
+    pub languages: Vec<LanguageCount>,
+}
+
+/// A misspelled word of a [`SpellReport`] and where it occurs.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MisspelledWord {
+    pub word: String,
+    /// The byte ranges of the occurrences, in order.
+    pub ranges: Vec<Range<usize>>,
+}
+
+/// The number of words of a text a dictionary accepts, see
+/// [`SpellReport`]. A word is counted for the first dictionary that
+/// accepts it only.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LanguageCount {
+    pub language: Option<Language>,
+    pub words: usize,
+}
+
+impl SpellReport {
+    fn new(words: usize, found: Vec<Misspelling>, languages: Vec<LanguageCount>) -> Self {
+        let mut misspellings: Vec<MisspelledWord> = Vec::new();
+        let mut index: HashMap<String, usize> = HashMap::new();
+        for m in found {
+            match index.get(&m.word) {
+                Some(&i) => misspellings[i].ranges.push(m.range),
+                None => {
+                    index.insert(m.word.clone(), misspellings.len());
+                    misspellings.push(MisspelledWord {
+                        word: m.word,
+                        ranges: vec![m.range],
+                    });
+                }
+            }
+        }
+        SpellReport {
+            words,
+            misspellings,
+            languages,
+        }
+    }
+
+    /// Returns true when nothing is misspelled, for the exit status of
+    /// tools.
+    pub fn is_clean(&self) -> bool {
+        self.misspellings.is_empty()
+    }
+
+    /// The number of misspellings, counting every occurrence.
+    pub fn occurrences(&self) -> usize {
+        self.misspellings.iter().map(|m| m.ranges.len()).sum()
+    }
+
+    /// Misspellings per 1000 checked words, counting every occurrence.
+    pub fn per_thousand(&self) -> f64 {
+        per_thousand(self.occurrences(), self.words)
+    }
+}
+
+/// The reports of a set of files, with totals.
+///
+/// It is displayed as a line per file and a line with the totals:
+///
+/// ```
+/// use hunspell_rs::{Report, SpellChecker, TextChecker};
+///
+/// let spell = SpellChecker::new("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic").unwrap();
+/// let checker = TextChecker::new(spell);
+/// let mut report = Report::new();
+/// report.push(checker.report("a.txt", "cats see cats").unwrap());
+/// report.push(checker.report("b.txt", "programs").unwrap());
+/// assert_eq!(4, report.words());
+/// assert_eq!(1, report.misspellings());
+/// assert_eq!(
+///     "a.txt: 3 words, 1 misspelled (333.3 per 1000)\n\
+///      b.txt: 1 words, 0 misspelled (0.0 per 1000)\n\
+///      total: 4 words, 1 misspelled (250.0 per 1000)\n",
+///     report.to_string()
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Report {
+    pub files: Vec<FileReport>,
+}
+
+impl Report {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, file: FileReport) {
+        self.files.push(file);
+    }
+
+    /// The number of checked words in all files.
+    pub fn words(&self) -> usize {
+        self.files.iter().map(|f| f.words).sum()
+    }
+
+    /// The number of misspellings in all files.
+    pub fn misspellings(&self) -> usize {
+        self.files.iter().map(|f| f.misspellings.len()).sum()
+    }
+
+    /// Misspellings per 1000 checked words over all files.
+    pub fn per_thousand(&self) -> f64 {
+        per_thousand(self.misspellings(), self.words())
+    }
+
+    /// Compares this report with an `older` one of the same files. The
+    /// misspellings are matched by file and word, not by position, so
+    /// edits elsewhere in a file do not make them new. A word that is
+    /// misspelled more often than before counts as new that many times.
+    ///
+    /// ```
+    /// use hunspell_rs::{Report, SpellChecker, TextChecker};
+    ///
+    /// let spell = SpellChecker::new("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic").unwrap();
+    /// let checker = TextChecker::new(spell);
+    /// let mut older = Report::new();
+    /// older.push(checker.report("a.txt", "cats see programz").unwrap());
+    /// let mut newer = Report::new();
+    /// newer.push(checker.report("a.txt", "see cats see programs").unwrap());
+    /// let diff = newer.compare(&older);
+    /// assert_eq!(vec!["see"], diff.new.iter().map(|(_, m)| &m.word).collect::<Vec<_>>());
+    /// assert_eq!(vec!["programz"], diff.fixed.iter().map(|(_, m)| &m.word).collect::<Vec<_>>());
+    /// assert_eq!(1, diff.persisting.len());
+    /// assert_eq!("1 new, 1 fixed, 1 persisting misspellings", diff.summary());
+    /// ```
+    pub fn compare(&self, older: &Report) -> ReportDiff {
+        let mut diff = ReportDiff::default();
+        let mut remaining: HashMap<(&Path, &str), Vec<&Misspelling>> = HashMap::new();
+        for file in &older.files {
+            for m in &file.misspellings {
+                remaining.entry((&file.path, &m.word)).or_default().push(m);
+            }
+        }
+        for file in &self.files {
+            for m in &file.misspellings {
+                let matched = remaining
+                    .get_mut(&(file.path.as_path(), m.word.as_str()))
+                    .filter(|older| !older.is_empty())
+                    .map(|older| older.remove(0));
+                let list = match matched {
+                    Some(_) => &mut diff.persisting,
+                    None => &mut diff.new,
+                };
+                list.push((file.path.clone(), m.clone()));
+            }
+        }
+        // in the order of the older report
+        for file in &older.files {
+            for m in &file.misspellings {
+                let key = (file.path.as_path(), m.word.as_str());
+                if let Some(left) = remaining.get_mut(&key) {
+                    if let Some(i) = left.iter().position(|l| std::ptr::eq(*l, m)) {
+                        left.remove(i);
+                        diff.fixed.push((file.path.clone(), m.clone()));
+                    }
+                }
+            }
+        }
+        diff
+    }
+}
+
+/// The difference between two reports, made by [`Report::compare()`].
+/// The misspellings are paired with their file; new and persisting ones
+/// have their place in the newer report, fixed ones in the older.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReportDiff {
+    pub new: Vec<(PathBuf, Misspelling)>,
+    pub fixed: Vec<(PathBuf, Misspelling)>,
+    pub persisting: Vec<(PathBuf, Misspelling)>,
+}
+
+impl ReportDiff {
+    /// I.e. `3 new, 1 fixed, 12 persisting misspellings`.
+    pub fn summary(&self) -> String {
+        format!(
+            "{} new, {} fixed, {} persisting misspellings",
+            self.new.len(),
+            self.fixed.len(),
+            self.persisting.len()
+        )
+    }
+}
+
+impl core::fmt::Display for ReportDiff {
+    /// The summary, followed by the new and fixed misspellings as
+    /// `new: path: word` and `fixed: path: word`.
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        writeln!(f, "{}", self.summary())?;
+        for (label, list) in [("new", &self.new), ("fixed", &self.fixed)] {
+            for (path, m) in list {
+                writeln!(f, "{label}: {}: {}", path.display(), m.word)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl core::fmt::Display for Report {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        for file in &self.files {
+            write_line(f, &file.path.display(), file.misspellings.len(), file.words)?;
+        }
+        write_line(f, &"total", self.misspellings(), self.words())
+    }
+}
+
+fn write_line(
+    f: &mut core::fmt::Formatter<'_>,
+    name: &dyn core::fmt::Display,
+    misspellings: usize,
+    words: usize,
+) -> core::fmt::Result {
+    writeln!(
+        f,
+        "{name}: {words} words, {misspellings} misspelled ({:.1} per 1000)",
+        per_thousand(misspellings, words)
+    )
+}
+
+fn per_thousand(misspellings: usize, words: usize) -> f64 {
+    if words == 0 {
+        0.0
+    } else {
+        misspellings as f64 * 1000.0 / words as f64
+    }
+}
+
+/// What [`TextChecker::estimate_files()`] checks of a set of files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SampleOptions {
+    /// Check one in this many files or words, at least 1.
+    pub every: usize,
+    /// Chooses the first file or word that is checked, the same seed
+    /// checks the same sample.
+    pub seed: u64,
+    pub unit: SampleUnit,
+}
+
+impl Default for SampleOptions {
+    fn default() -> Self {
+        SampleOptions {
+            every: 10,
+            seed: 0,
+            unit: SampleUnit::Words,
+        }
+    }
+}
+
+/// What is sampled, see [`SampleOptions`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SampleUnit {
+    /// Whole files, which saves reading the others.
+    Files,
+    /// Words of every file, which gives a closer estimate for the same
+    /// number of checked words when files differ a lot.
+    Words,
+}
+
+/// The misspelling rate of a set of files estimated from a sample, see
+/// [`TextChecker::estimate_files()`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Estimate {
+    /// The number of files that were read.
+    pub files: usize,
+    /// The number of words that were checked.
+    pub words: usize,
+    /// The number of misspellings among them.
+    pub misspellings: usize,
+    /// The estimated misspellings per 1000 words.
+    pub per_thousand: f64,
+    /// The bounds of the 95% confidence interval of `per_thousand`, from
+    /// the Wilson score interval. Without words they are 0 and 1000.
+    pub lower: f64,
+    pub upper: f64,
+}
+
+impl Estimate {
+    fn new(files: usize, words: usize, misspellings: usize) -> Self {
+        // the 97.5% quantile of the normal distribution
+        const Z: f64 = 1.959_964;
+        let (lower, upper) = if words == 0 {
+            (0.0, 1.0)
+        } else {
+            let n = words as f64;
+            let p = misspellings as f64 / n;
+            let z2 = Z * Z;
+            let denominator = 1.0 + z2 / n;
+            let center = (p + z2 / (2.0 * n)) / denominator;
+            let half = Z * (p * (1.0 - p) / n + z2 / (4.0 * n * n)).sqrt() / denominator;
+            ((center - half).max(0.0), (center + half).min(1.0))
+        };
+        Estimate {
+            files,
+            words,
+            misspellings,
+            per_thousand: per_thousand(misspellings, words),
+            lower: lower * 1000.0,
+            upper: upper * 1000.0,
+        }
+    }
+}
+
+impl core::fmt::Display for Estimate {
+    /// I.e. `12.5 per 1000 (95%: 8.1 to 19.2), from 2000 words in 8 files`.
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "{:.1} per 1000 (95%: {:.1} to {:.1}), from {} words in {} files",
+            self.per_thousand, self.lower, self.upper, self.words, self.files
+        )
+    }
+}
+
+impl TextChecker {
+    /// Estimates the misspelling rate of the files at `paths` by checking
+    /// one in [`every`](SampleOptions::every) files or words only, to
+    /// find out quickly whether checking all of them is worth it.
+    ///
+    /// ```
+    /// use hunspell_rs::{SampleOptions, SampleUnit, SpellChecker, TextChecker};
+    ///
+    /// let spell = SpellChecker::new("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic").unwrap();
+    /// let checker = TextChecker::new(spell);
+    /// let options = SampleOptions { every: 2, seed: 7, unit: SampleUnit::Words };
+    /// let estimate = checker.estimate_files(["README.md"], &options).unwrap();
+    /// assert_eq!(1, estimate.files);
+    /// assert!(estimate.lower <= estimate.per_thousand && estimate.per_thousand <= estimate.upper);
+    /// ```
+    pub fn estimate_files<I, P>(&self, paths: I, options: &SampleOptions) -> Result<Estimate>
+    where
+        I: IntoIterator<Item = P>,
+        P: AsRef<Path>,
+    {
+        let every = options.every.max(1);
+        let start = (splitmix(options.seed) % every as u64) as usize;
+        let (mut files, mut words, mut misspellings) = (0, 0, 0);
+        // counts the words of all files, so the sample does not start over
+        let seen = Cell::new(0usize);
+        for (i, path) in paths.into_iter().enumerate() {
+            if options.unit == SampleUnit::Files && i % every != start {
+                continue;
+            }
+            let text = std::fs::read_to_string(path.as_ref())
+                .map_err(Error::io(Operation::Load, path.as_ref()))?;
+            let (found, checked) = match options.unit {
+                SampleUnit::Files => self.check_uncached(&text)?,
+                SampleUnit::Words => self.check_selected(&text, |_| {
+                    let n = seen.get();
+                    seen.set(n + 1);
+                    n % every == start
+                })?,
+            };
+            files += 1;
+            words += checked;
+            misspellings += found.len();
+        }
+        Ok(Estimate::new(files, words, misspellings))
+    }
+
+    /// Checks `text`, the contents of `path`, and counts its words. The
+    /// cache of [`check_text()`](TextChecker::check_text) is not used.
+    pub fn report<P: AsRef<Path>>(&self, path: P, text: &str) -> Result<FileReport> {
+        let (misspellings, words) = self.check_uncached(text)?;
+        Ok(FileReport {
+            path: path.as_ref().to_path_buf(),
+            words,
+            misspellings,
+        })
+    }
+
+    /// Reads and checks the file at `path`, see
+    /// [`report()`](TextChecker::report).
+    pub fn report_file<P: AsRef<Path>>(&self, path: P) -> Result<FileReport> {
+        let text = std::fs::read_to_string(path.as_ref())
+            .map_err(Error::io(Operation::Load, path.as_ref()))?;
+        self.report(path, &text)
+    }
+
+    /// Checks `text` and returns its statistics. The cache of
+    /// [`check_text()`](TextChecker::check_text) is not used.
+    pub fn spell_report(&self, text: &str) -> Result<SpellReport> {
+        let (misspellings, words) = self.check_uncached(text)?;
+        let languages = vec![LanguageCount {
+            language: self.spell_checker().language().cloned(),
+            words: words - misspellings.len(),
+        }];
+        Ok(SpellReport::new(words, misspellings, languages))
+    }
+}
+
+impl<A: SuggestionArbiter> MultiLanguageChecker<A> {
+    /// Checks `text` like [`check_text()`](MultiLanguageChecker::check_text)
+    /// and returns its statistics, with the words every dictionary
+    /// accepts.
+    pub fn spell_report(&self, text: &str) -> Result<SpellReport> {
+        let (misspellings, words, accepted) = self.check_text_counted(text)?;
+        let languages = self
+            .checkers()
+            .iter()
+            .zip(accepted)
+            .map(|(checker, words)| LanguageCount {
+                language: checker.language().cloned(),
+                words,
+            })
+            .collect();
+        Ok(SpellReport::new(words, misspellings, languages))
+    }
+}
+
+/// The SplitMix64 mixer, spreads seeds that differ little.
+fn splitmix(seed: u64) -> u64 {
+    let mut x = seed.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    x ^ (x >> 31)
+}
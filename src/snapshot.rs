@@ -0,0 +1,476 @@
+//! A single file snapshot of a `SpellChecker`.
+//!
+//! The format is an 8 byte magic `HSRSSNAP`, a little endian `u16` version
+//! and a sequence of sections. Each section starts with a tag byte, a byte
+//! that is 1 when the payload is deflate compressed, and the payload length
+//! as little endian `u64`. Since version 2 a compressed payload is preceded
+//! by its decompressed length, also a little endian `u64`, which bounds the
+//! decompression. Unknown sections are skipped when loading, so later
+//! versions can add sections.
+//!
+//! The sections are the configuration (the paths, key and language), the
+//! runtime words, the rest of the [`SpellCheckerState`] (the ignored words,
+//! autocorrect pairs and options), the analysis cache when it is enabled,
+//! and either the contents of the dictionary files or their
+//! [`Dictionary::fingerprint()`](crate::Dictionary::fingerprint).
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    io::{Read, Write},
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    analysis::CachedMorphology, memory::memory_file, spell_checker::RuntimeWord, Error,
+    InputPolicy, Normalization, NulHandling, Operation, Result, SpellChecker, SpellCheckerState,
+};
+
+const MAGIC: &[u8; 8] = b"HSRSSNAP";
+const VERSION: u16 = 2;
+
+const CONFIG: u8 = 1;
+const RUNTIME_WORDS: u8 = 2;
+const AFFIX_DATA: u8 = 3;
+const DICTIONARY_DATA: u8 = 4;
+const EXTRA_DICTIONARY_DATA: u8 = 5;
+const STATE: u8 = 6;
+const CACHES: u8 = 7;
+const FINGERPRINT: u8 = 8;
+
+/// Sections smaller than this are not worth compressing.
+const COMPRESS_FROM: usize = 64;
+
+/// The most deflate can compress, for the decompressed length of sections
+/// of version 1 snapshots, which do not store it.
+const MAX_DEFLATE_RATIO: u64 = 1032;
+
+impl SpellChecker {
+    /// Writes a snapshot of the checker: everything of its
+    /// [`state()`](SpellChecker::state), and the analysis cache when it is
+    /// enabled. With `embed_dictionaries` the contents of the affix,
+    /// dictionary and extra dictionary files are included, so the snapshot
+    /// can be loaded where those files do not exist; otherwise the
+    /// fingerprint of the files is included, so loading notices when they
+    /// changed. Fails with `Error::UnsupportedPath` when a path is not
+    /// valid UTF-8.
+    pub fn save_snapshot<W: Write>(&self, mut writer: W, embed_dictionaries: bool) -> Result<()> {
+        let state = self.state();
+        let mut config = Vec::new();
+        put_path(&mut config, &state.affix)?;
+        put_path(&mut config, &state.dictionary)?;
+        put_len(&mut config, state.additional_dictionaries.len());
+        for d in &state.additional_dictionaries {
+            put_path(&mut config, d)?;
+        }
+        put_option(&mut config, state.key.as_deref());
+        put_option(&mut config, state.language.as_ref().map(|l| l.as_str()));
+
+        writer.write_all(MAGIC)?;
+        writer.write_all(&VERSION.to_le_bytes())?;
+        write_section(&mut writer, CONFIG, &config)?;
+
+        let mut words = Vec::new();
+        put_len(&mut words, state.runtime_words.len());
+        for word in &state.runtime_words {
+            match word {
+                RuntimeWord::Word(w) => {
+                    words.push(0);
+                    put_str(&mut words, w);
+                }
+                RuntimeWord::WithAffix { word, example } => {
+                    words.push(1);
+                    put_str(&mut words, word);
+                    put_str(&mut words, example);
+                }
+                RuntimeWord::Removed(w) => {
+                    words.push(2);
+                    put_str(&mut words, w);
+                }
+            }
+        }
+        write_section(&mut writer, RUNTIME_WORDS, &words)?;
+        write_section(&mut writer, STATE, &state_section(&state))?;
+        if let Some(cached) = self.analysis_cache_entries() {
+            write_section(&mut writer, CACHES, &caches_section(&cached))?;
+        }
+
+        if embed_dictionaries {
+            let read =
+                |path: &PathBuf| std::fs::read(path).map_err(Error::io(Operation::Snapshot, path));
+            write_section(&mut writer, AFFIX_DATA, &read(&self.affix)?)?;
+            write_section(&mut writer, DICTIONARY_DATA, &read(&self.dictionary)?)?;
+            for d in &self.additional_dictionaries {
+                write_section(&mut writer, EXTRA_DICTIONARY_DATA, &read(d)?)?;
+            }
+        } else {
+            let fingerprint = state.dictionary().fingerprint()?;
+            write_section(&mut writer, FINGERPRINT, &fingerprint.to_le_bytes())?;
+        }
+        Ok(())
+    }
+
+    /// Opens a checker from a snapshot written by `save_snapshot()`.
+    ///
+    /// Embedded dictionaries are loaded from memory (see
+    /// [`SpellChecker::from_memory()`]), otherwise the files are opened
+    /// from the paths in the snapshot. Those fail with
+    /// `Error::FingerprintMismatch` when their contents changed since the
+    /// snapshot was written. The analysis cache is enabled again when it
+    /// was, with its entries unless the files could not be checked, as in
+    /// snapshots of version 1.
+    pub fn load_snapshot<R: Read>(mut reader: R) -> Result<SpellChecker> {
+        let mut header = [0u8; 10];
+        reader.read_exact(&mut header)?;
+        if &header[..8] != MAGIC {
+            return Err(invalid("not a snapshot"));
+        }
+        let version = u16::from_le_bytes([header[8], header[9]]);
+        if version > VERSION {
+            return Err(invalid(&format!("unsupported version {version}")));
+        }
+
+        let mut state: Option<SpellCheckerState> = None;
+        let mut runtime_words = Vec::new();
+        // snapshots of older versions have no state section
+        let mut settings = Settings::default();
+        let mut cached = None;
+        let mut affix_data = None;
+        let mut dictionary_data = None;
+        let mut extra_data = Vec::new();
+        let mut fingerprint = None;
+        while let Some((tag, payload)) = read_section(&mut reader, version)? {
+            let mut input = payload.as_slice();
+            match tag {
+                CONFIG => {
+                    let affix = PathBuf::from(get_str(&mut input)?);
+                    let dictionary = PathBuf::from(get_str(&mut input)?);
+                    let mut additional_dictionaries = Vec::new();
+                    for _ in 0..get_len(&mut input)? {
+                        additional_dictionaries.push(PathBuf::from(get_str(&mut input)?));
+                    }
+                    let key = get_option(&mut input)?;
+                    let language = get_option(&mut input)?.map(|l| l.parse()).transpose()?;
+                    state = Some(SpellCheckerState {
+                        affix,
+                        dictionary,
+                        additional_dictionaries,
+                        key,
+                        language,
+                        runtime_words: Vec::new(),
+                        version: SpellChecker::SERDE_VERSION,
+                        ignored: BTreeSet::new(),
+                        replacements: BTreeMap::new(),
+                        normalization: Normalization::default(),
+                        max_suggest_length: None,
+                        input_policy: InputPolicy::default(),
+                    });
+                }
+                RUNTIME_WORDS => {
+                    for _ in 0..get_len(&mut input)? {
+                        let word = match take(&mut input, 1)?[0] {
+                            0 => RuntimeWord::Word(get_str(&mut input)?),
+                            1 => RuntimeWord::WithAffix {
+                                word: get_str(&mut input)?,
+                                example: get_str(&mut input)?,
+                            },
+                            2 => RuntimeWord::Removed(get_str(&mut input)?),
+                            kind => return Err(invalid(&format!("unknown word kind {kind}"))),
+                        };
+                        runtime_words.push(word);
+                    }
+                }
+                STATE => settings = Settings::read(&mut input)?,
+                CACHES => cached = Some(read_caches(&mut input)?),
+                AFFIX_DATA => affix_data = Some(payload),
+                DICTIONARY_DATA => dictionary_data = Some(payload),
+                EXTRA_DICTIONARY_DATA => extra_data.push(payload),
+                FINGERPRINT => {
+                    let bytes = take(&mut input, 8)?.try_into().expect("8 bytes");
+                    fingerprint = Some(u64::from_le_bytes(bytes))
+                }
+                _ => {}
+            }
+        }
+
+        let mut state = state.ok_or_else(|| invalid("no configuration section"))?;
+        state.runtime_words = runtime_words;
+        settings.apply(&mut state);
+        let mut memory_files = Vec::new();
+        let embedded = affix_data.is_some() && dictionary_data.is_some();
+        if let Some(expected) = fingerprint.filter(|_| !embedded) {
+            state.dictionary().check_fingerprint(expected)?;
+        }
+        let unchanged = embedded || fingerprint.is_some();
+        if let (Some(affix), Some(dictionary)) = (affix_data, dictionary_data) {
+            let (affix_file, affix_path) = memory_file("hunspell.aff", &affix)?;
+            let (dictionary_file, dictionary_path) = memory_file("hunspell.dic", &dictionary)?;
+            state.affix = affix_path;
+            state.dictionary = dictionary_path;
+            memory_files = vec![affix_file.into(), dictionary_file.into()];
+            state.additional_dictionaries.clear();
+            for data in extra_data {
+                let (file, path) = memory_file("hunspell.dic", &data)?;
+                state.additional_dictionaries.push(path);
+                memory_files.push(file.into());
+            }
+        }
+        let mut spell_checker = state.open_with_memory_files(memory_files)?;
+        match cached {
+            Some(cached) if unchanged => spell_checker.restore_analysis_cache(cached),
+            Some(cached) => spell_checker.enable_analysis_cache(cached.capacity),
+            None => {}
+        }
+        Ok(spell_checker)
+    }
+}
+
+/// The part of a [`SpellCheckerState`] in the state section.
+#[derive(Debug, Default)]
+struct Settings {
+    ignored: BTreeSet<String>,
+    replacements: BTreeMap<String, String>,
+    normalization: Normalization,
+    max_suggest_length: Option<usize>,
+    input_policy: InputPolicy,
+}
+
+impl Settings {
+    fn read(input: &mut &[u8]) -> Result<Settings> {
+        let mut settings = Settings::default();
+        for _ in 0..get_len(input)? {
+            settings.ignored.insert(get_str(input)?);
+        }
+        for _ in 0..get_len(input)? {
+            let from = get_str(input)?;
+            settings.replacements.insert(from, get_str(input)?);
+        }
+        let flags = take(input, 2)?;
+        settings.normalization = Normalization {
+            nfc: flags[0] != 0,
+            typographic: flags[1] != 0,
+        };
+        settings.max_suggest_length = get_option_len(input)?;
+        let nul = match take(input, 1)?[0] {
+            0 => NulHandling::Fail,
+            1 => NulHandling::Strip,
+            2 => {
+                let c = u32::from_le_bytes(take(input, 4)?.try_into().expect("4 bytes"));
+                NulHandling::Replace(
+                    char::from_u32(c).ok_or_else(|| invalid("invalid NUL replacement"))?,
+                )
+            }
+            kind => return Err(invalid(&format!("unknown NUL handling {kind}"))),
+        };
+        settings.input_policy = InputPolicy {
+            nul,
+            max_word_length: get_option_len(input)?,
+            reject_control_chars: take(input, 1)?[0] != 0,
+        };
+        Ok(settings)
+    }
+
+    fn apply(self, state: &mut SpellCheckerState) {
+        state.ignored = self.ignored;
+        state.replacements = self.replacements;
+        state.normalization = self.normalization;
+        state.max_suggest_length = self.max_suggest_length;
+        state.input_policy = self.input_policy;
+    }
+}
+
+fn state_section(state: &SpellCheckerState) -> Vec<u8> {
+    let mut buffer = Vec::new();
+    put_len(&mut buffer, state.ignored.len());
+    for word in &state.ignored {
+        put_str(&mut buffer, word);
+    }
+    put_len(&mut buffer, state.replacements.len());
+    for (from, to) in &state.replacements {
+        put_str(&mut buffer, from);
+        put_str(&mut buffer, to);
+    }
+    buffer.push(u8::from(state.normalization.nfc));
+    buffer.push(u8::from(state.normalization.typographic));
+    put_option_len(&mut buffer, state.max_suggest_length);
+    match state.input_policy.nul {
+        NulHandling::Fail => buffer.push(0),
+        NulHandling::Strip => buffer.push(1),
+        NulHandling::Replace(c) => {
+            buffer.push(2);
+            buffer.extend_from_slice(&u32::from(c).to_le_bytes());
+        }
+    }
+    put_option_len(&mut buffer, state.input_policy.max_word_length);
+    buffer.push(u8::from(state.input_policy.reject_control_chars));
+    buffer
+}
+
+fn caches_section(cached: &CachedMorphology) -> Vec<u8> {
+    let mut buffer = Vec::new();
+    buffer.extend_from_slice(&(cached.capacity as u64).to_le_bytes());
+    for entries in [&cached.analyses, &cached.stems] {
+        put_len(&mut buffer, entries.len());
+        for (word, found) in entries {
+            put_str(&mut buffer, word);
+            put_len(&mut buffer, found.len());
+            for f in found {
+                put_str(&mut buffer, f);
+            }
+        }
+    }
+    buffer
+}
+
+fn read_caches(input: &mut &[u8]) -> Result<CachedMorphology> {
+    let capacity = u64::from_le_bytes(take(input, 8)?.try_into().expect("8 bytes"));
+    let mut read_entries = || -> Result<Vec<(String, Vec<String>)>> {
+        let mut entries = Vec::new();
+        for _ in 0..get_len(input)? {
+            let word = get_str(input)?;
+            let found = (0..get_len(input)?)
+                .map(|_| get_str(input))
+                .collect::<Result<Vec<String>>>()?;
+            entries.push((word, found));
+        }
+        Ok(entries)
+    };
+    let analyses = read_entries()?;
+    let stems = read_entries()?;
+    Ok(CachedMorphology {
+        capacity: usize::try_from(capacity).unwrap_or(usize::MAX),
+        analyses,
+        stems,
+    })
+}
+
+fn invalid(reason: &str) -> Error {
+    Error::InvalidSnapshot(reason.to_string())
+}
+
+fn write_section<W: Write>(writer: &mut W, tag: u8, payload: &[u8]) -> Result<()> {
+    let compressed = payload.len() >= COMPRESS_FROM;
+    let data = if compressed {
+        miniz_oxide::deflate::compress_to_vec(payload, 6)
+    } else {
+        payload.to_vec()
+    };
+    writer.write_all(&[tag, u8::from(compressed)])?;
+    writer.write_all(&(data.len() as u64).to_le_bytes())?;
+    if compressed {
+        writer.write_all(&(payload.len() as u64).to_le_bytes())?;
+    }
+    writer.write_all(&data)?;
+    Ok(())
+}
+
+/// Returns the next section of a snapshot of `version`, or `None` at the
+/// end of the snapshot.
+fn read_section<R: Read>(reader: &mut R, version: u16) -> Result<Option<(u8, Vec<u8>)>> {
+    let mut header = [0u8; 10];
+    match reader.read_exact(&mut header[..1]) {
+        Ok(()) => {}
+        Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(e) => return Err(e.into()),
+    }
+    reader.read_exact(&mut header[1..])?;
+    let length = u64::from_le_bytes(header[2..].try_into().expect("8 bytes"));
+    let compressed = header[1] == 1;
+    let decompressed_length = match (compressed, version) {
+        (false, _) => None,
+        (true, 1) => Some(length.saturating_mul(MAX_DEFLATE_RATIO)),
+        (true, _) => {
+            let mut bytes = [0u8; 8];
+            reader.read_exact(&mut bytes)?;
+            Some(u64::from_le_bytes(bytes))
+        }
+    };
+    let mut data = Vec::new();
+    reader.take(length).read_to_end(&mut data)?;
+    if data.len() as u64 != length {
+        return Err(invalid("truncated section"));
+    }
+    if let Some(limit) = decompressed_length {
+        let limit = usize::try_from(limit).unwrap_or(usize::MAX);
+        data = miniz_oxide::inflate::decompress_to_vec_with_limit(&data, limit)
+            .map_err(|_| invalid("corrupt compressed section"))?;
+        if version > 1 && data.len() != limit {
+            return Err(invalid("corrupt compressed section"));
+        }
+    }
+    Ok(Some((header[0], data)))
+}
+
+fn put_len(buffer: &mut Vec<u8>, len: usize) {
+    buffer.extend_from_slice(&(len as u32).to_le_bytes());
+}
+
+fn put_str(buffer: &mut Vec<u8>, s: &str) {
+    put_len(buffer, s.len());
+    buffer.extend_from_slice(s.as_bytes());
+}
+
+/// Writes `path` as a string, paths that are not UTF-8 cannot be read
+/// back the same on every platform.
+fn put_path(buffer: &mut Vec<u8>, path: &Path) -> Result<()> {
+    let path = path
+        .to_str()
+        .ok_or_else(|| Error::UnsupportedPath(path.to_path_buf()))?;
+    put_str(buffer, path);
+    Ok(())
+}
+
+fn put_option_len(buffer: &mut Vec<u8>, len: Option<usize>) {
+    match len {
+        Some(len) => {
+            buffer.push(1);
+            buffer.extend_from_slice(&(len as u64).to_le_bytes());
+        }
+        None => buffer.push(0),
+    }
+}
+
+fn put_option(buffer: &mut Vec<u8>, s: Option<&str>) {
+    match s {
+        Some(s) => {
+            buffer.push(1);
+            put_str(buffer, s);
+        }
+        None => buffer.push(0),
+    }
+}
+
+fn take<'a>(input: &mut &'a [u8], n: usize) -> Result<&'a [u8]> {
+    if input.len() < n {
+        return Err(invalid("section too short"));
+    }
+    let (head, tail) = input.split_at(n);
+    *input = tail;
+    Ok(head)
+}
+
+fn get_len(input: &mut &[u8]) -> Result<usize> {
+    let bytes = take(input, 4)?;
+    Ok(u32::from_le_bytes(bytes.try_into().expect("4 bytes")) as usize)
+}
+
+fn get_str(input: &mut &[u8]) -> Result<String> {
+    let len = get_len(input)?;
+    Ok(std::str::from_utf8(take(input, len)?)?.to_string())
+}
+
+fn get_option_len(input: &mut &[u8]) -> Result<Option<usize>> {
+    match take(input, 1)?[0] {
+        0 => Ok(None),
+        _ => {
+            let len = u64::from_le_bytes(take(input, 8)?.try_into().expect("8 bytes"));
+            Ok(Some(usize::try_from(len).unwrap_or(usize::MAX)))
+        }
+    }
+}
+
+fn get_option(input: &mut &[u8]) -> Result<Option<String>> {
+    match take(input, 1)?[0] {
+        0 => Ok(None),
+        _ => get_str(input).map(Some),
+    }
+}
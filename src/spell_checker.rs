@@ -15,33 +15,104 @@
 //   See the License for the specific language governing permissions and
 //   limitations under the License.
 
-use hunspell_sys as ffi;
 use std::{
-    ffi::{CStr, CString},
+    cell::{OnceCell, RefCell},
+    collections::{BTreeMap, HashMap, HashSet},
+    ffi::{c_char, c_int, CStr, CString},
+    fs::File,
     path::{Path, PathBuf},
-    ptr::null_mut,
+    ptr::NonNull,
+    sync::Arc,
+    time::SystemTime,
 };
 
 #[cfg(feature = "serde")]
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-use crate::{Error, Result};
+use crate::{
+    aff::{AffixFile, AffixOption, CharMap, ConversionTable},
+    analysis::{AnalysisCache, Morphology},
+    dic::{DicEntry, DicFile},
+    discovery::read_affix_directive,
+    encoding::Codec,
+    ffi,
+    ffi_util::{HunspellList, HunspellPath},
+    instrument::{FfiCall, FfiOperation},
+    memory::memory_file,
+    stats::StatsCounters,
+    suggest::{SuggestWorker, SuggestionFilter},
+    CorrectionTable, Dictionary, Error, InputPolicy, Language, Normalization, Operation, Result,
+};
 
 /// Hunspell spelk checker.
 ///
 ///
 // Should not derive Clone because when the struct is dropped
 // the handle is destroyed, see manual impl Clone below.
-// Deserialize is manually implemented.
-#[cfg_attr(feature = "serde", derive(Serialize))]
-#[derive(Debug)]
+// Serialize and Deserialize are manually implemented, see serde.rs.
+// Debug is manually implemented to keep the key and handle out of logs.
 pub struct SpellChecker {
     pub(crate) affix: PathBuf,
     pub(crate) dictionary: PathBuf,
     pub(crate) additional_dictionaries: Vec<PathBuf>,
     pub(crate) key: Option<String>,
-    #[cfg_attr(feature = "serde", serde(skip))]
-    pub(crate) handle: *mut ffi::Hunhandle,
+    pub(crate) language: Option<Language>,
+    // Words added or removed at runtime, replayed by clone() and serde.
+    pub(crate) runtime_words: Vec<RuntimeWord>,
+    // The words of runtime_words that are added, see contains_added().
+    pub(crate) added: HashSet<String>,
+    // Words accepted by check() for this session only, see ignore().
+    pub(crate) ignored: RefCell<HashSet<String>>,
+    // Autocorrect pairs, see add_replacement().
+    pub(crate) replacements: RefCell<BTreeMap<String, String>>,
+    // Keeps the memory files of dictionaries loaded from memory open.
+    pub(crate) memory_files: Vec<Arc<File>>,
+    // Makes suggestions for suggest_with_options() when it has a timeout.
+    pub(crate) suggest_worker: RefCell<Option<SuggestWorker>>,
+    // The dictionaries opened one by one, for check_detailed().
+    pub(crate) source_checkers: RefCell<Vec<SpellChecker>>,
+    // Reused for the words passed to hunspell, see with_c_word().
+    pub(crate) scratch: RefCell<Vec<u8>>,
+    // Converts words for dictionaries that are not UTF-8.
+    pub(crate) codec: Codec,
+    // The WORDCHARS of the affix file, for validate_word().
+    pub(crate) word_chars: String,
+    // The files as they were when they were loaded, see is_stale().
+    pub(crate) file_stamps: Vec<FileStamp>,
+    // The MAP table of the affix file, read on first use.
+    pub(crate) char_map: OnceCell<CharMap>,
+    // The ICONV and OCONV tables of the affix file, read on first use.
+    pub(crate) conversions: OnceCell<(ConversionTable, ConversionTable)>,
+    // Corpus counts of words, see suggest_by_frequency().
+    pub(crate) frequencies: RefCell<HashMap<String, u64>>,
+    // Applied to words before hunspell sees them, see set_normalization().
+    pub(crate) normalization: Normalization,
+    // Guards hunspell against untrusted words, see set_input_policy().
+    pub(crate) input_policy: InputPolicy,
+    // Longer words get no suggestions, see set_max_suggest_length().
+    pub(crate) max_suggest_length: Option<usize>,
+    // Looked up before hunspell, see set_correction_table().
+    pub(crate) correction_table: Option<Arc<dyn CorrectionTable>>,
+    // Applied to every list of suggestions, see set_suggestion_filter().
+    pub(crate) suggestion_filter: Option<Arc<SuggestionFilter>>,
+    // Analyses and stems by word, see enable_analysis_cache().
+    pub(crate) analysis_cache: RefCell<Option<AnalysisCache>>,
+    // Counts the calls to hunspell, see enable_stats().
+    pub(crate) stats: Option<Arc<StatsCounters>>,
+    pub(crate) handle: NonNull<ffi::Hunhandle>,
+}
+
+/// A change to the runtime dictionary, see
+/// [`runtime_words()`](SpellChecker::runtime_words).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum RuntimeWord {
+    /// Added with `add()`.
+    Word(String),
+    /// Added with `add_with_affix()`.
+    WithAffix { word: String, example: String },
+    /// Removed with `remove()` without having been added.
+    Removed(String),
 }
 
 impl SpellChecker {
@@ -55,18 +126,17 @@ impl SpellChecker {
         P: AsRef<Path>,
     {
         let (affix, dictionary) = check_paths(affix, dictionary)?;
-        Ok(unsafe {
-            SpellChecker {
-                handle: ffi::Hunspell_create(
-                    CString::new(affix.as_os_str().as_encoded_bytes())?.as_ptr(),
-                    CString::new(dictionary.as_os_str().as_encoded_bytes())?.as_ptr(), // affix_cstring.as_ptr(), dicpath.as_ptr()
-                ),
-                affix,
-                dictionary,
-                additional_dictionaries: Vec::new(),
-                key: None,
-            }
-        })
+        ffi::load()?;
+        let call = FfiCall::create(&affix);
+        let handle = unsafe {
+            ffi::Hunspell_create(
+                HunspellPath::new(&affix)?.as_ptr(),
+                HunspellPath::new(&dictionary)?.as_ptr(),
+            )
+        };
+        call.finish(usize::from(!handle.is_null()));
+        let handle = checked_handle(handle, &affix, &dictionary)?;
+        Ok(Self::with_handle(handle, affix, dictionary, None))
     }
 
     /// Opens an encrypted spell checking dictionary, which consist of a hunspell affix
@@ -81,19 +151,138 @@ impl SpellChecker {
         S: AsRef<str>,
     {
         let (affix, dictionary) = check_paths(affix, dictionary)?;
-        Ok(unsafe {
-            SpellChecker {
-                handle: ffi::Hunspell_create_key(
-                    CString::new(affix.as_os_str().as_encoded_bytes())?.as_ptr(),
-                    CString::new(dictionary.as_os_str().as_encoded_bytes())?.as_ptr(),
-                    CString::new(key.as_ref())?.as_ptr(),
-                ),
-                affix,
-                dictionary,
-                additional_dictionaries: Vec::new(),
-                key: Some(key.as_ref().to_string()),
-            }
-        })
+        ffi::load()?;
+        let call = FfiCall::create(&affix);
+        let handle = unsafe {
+            ffi::Hunspell_create_key(
+                HunspellPath::new(&affix)?.as_ptr(),
+                HunspellPath::new(&dictionary)?.as_ptr(),
+                CString::new(key.as_ref())?.as_ptr(),
+            )
+        };
+        call.finish(usize::from(!handle.is_null()));
+        let handle = checked_handle(handle, &affix, &dictionary)?;
+        Ok(Self::with_handle(
+            handle,
+            affix,
+            dictionary,
+            Some(key.as_ref().to_string()),
+        ))
+    }
+
+    /// Wraps a handle made with `Hunspell_create()` from `affix` and
+    /// `dictionary`, for handles made outside of this crate. Fails with
+    /// `Error::HandleCreationFailed` when `handle` is null. With the `dylib`
+    /// feature the library is loaded first when no spell checker was opened
+    /// yet, failing with `Error::LibraryLoading` when there is none.
+    ///
+    /// The `SpellChecker` owns the handle from then on: it is destroyed
+    /// with `Hunspell_destroy()` when the `SpellChecker` is dropped, and
+    /// not before. Clones open their own handle from the files, so they
+    /// need to exist.
+    ///
+    /// # Safety
+    ///
+    /// `handle` must be null or a live handle of the hunspell library this
+    /// crate uses, made from `affix` and `dictionary`, that nothing else
+    /// destroys or uses after this call.
+    pub unsafe fn from_raw<P>(
+        handle: *mut ffi::Hunhandle,
+        affix: P,
+        dictionary: P,
+    ) -> Result<SpellChecker>
+    where
+        P: AsRef<Path>,
+    {
+        let affix = affix.as_ref().to_path_buf();
+        let dictionary = dictionary.as_ref().to_path_buf();
+        ffi::load()?;
+        let handle = checked_handle(handle, &affix, &dictionary)?;
+        Ok(Self::with_handle(handle, affix, dictionary, None))
+    }
+
+    /// Returns the hunspell handle, for calling `hunspell_sys` functions
+    /// this crate does not wrap. It is valid as long as the `SpellChecker`
+    /// lives, it is freed when the `SpellChecker` is dropped.
+    ///
+    /// ```
+    /// use std::ffi::CString;
+    /// use hunspell_rs::{hunspell_sys, SpellChecker};
+    ///
+    /// let spell = SpellChecker::new("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic").unwrap();
+    /// let word = CString::new("cats").unwrap();
+    /// let found = unsafe { hunspell_sys::Hunspell_spell(spell.as_raw(), word.as_ptr()) };
+    /// assert_ne!(0, found);
+    /// ```
+    ///
+    /// # Safety
+    ///
+    /// The handle must not be destroyed, or used after the `SpellChecker`
+    /// is dropped. Words added or removed through it are not known to
+    /// the `SpellChecker`, so they are lost by clones and serialization.
+    /// Like the `SpellChecker`, the handle must not be used by two threads
+    /// at once.
+    pub unsafe fn as_raw(&self) -> *mut ffi::Hunhandle {
+        self.handle.as_ptr()
+    }
+
+    /// Opens a spell checking dictionary from the contents of a hunspell
+    /// affix file and dictionary file.
+    ///
+    /// Nothing is written to the file system: the contents are kept in
+    /// anonymous memory files, so this also works on read-only file systems.
+    /// The paths returned by `affix()` and `dictionary()` are only valid
+    /// within the running process, so a `SpellChecker` created this way
+    /// cannot be deserialized elsewhere.
+    ///
+    /// Only supported on Linux and Android, on other platforms
+    /// `Error::InMemoryUnsupported` is returned.
+    pub fn from_memory(affix: &[u8], dictionary: &[u8]) -> Result<SpellChecker> {
+        let (affix_file, affix_path) = memory_file("hunspell.aff", affix)?;
+        let (dictionary_file, dictionary_path) = memory_file("hunspell.dic", dictionary)?;
+        let mut spell_checker = Self::new(affix_path, dictionary_path)?;
+        spell_checker.memory_files = vec![Arc::new(affix_file), Arc::new(dictionary_file)];
+        Ok(spell_checker)
+    }
+
+    /// A `SpellChecker` that owns `handle`, made from `affix` and
+    /// `dictionary`.
+    fn with_handle(
+        handle: NonNull<ffi::Hunhandle>,
+        affix: PathBuf,
+        dictionary: PathBuf,
+        key: Option<String>,
+    ) -> SpellChecker {
+        SpellChecker {
+            codec: Codec::for_name(&dic_encoding(handle)),
+            handle,
+            language: Language::from_files(&affix, &dictionary),
+            word_chars: read_affix_directive(&affix, AffixOption::WordChars.name())
+                .unwrap_or_default(),
+            file_stamps: vec![FileStamp::of(&affix), FileStamp::of(&dictionary)],
+            affix,
+            dictionary,
+            additional_dictionaries: Vec::new(),
+            key,
+            runtime_words: Vec::new(),
+            added: HashSet::new(),
+            ignored: RefCell::new(HashSet::new()),
+            replacements: RefCell::new(BTreeMap::new()),
+            memory_files: Vec::new(),
+            suggest_worker: RefCell::new(None),
+            source_checkers: RefCell::new(Vec::new()),
+            scratch: RefCell::new(Vec::new()),
+            char_map: OnceCell::new(),
+            conversions: OnceCell::new(),
+            frequencies: RefCell::new(HashMap::new()),
+            normalization: Normalization::default(),
+            input_policy: InputPolicy::default(),
+            max_suggest_length: None,
+            correction_table: None,
+            suggestion_filter: None,
+            analysis_cache: RefCell::new(None),
+            stats: None,
+        }
     }
 
     /// Returns the `Path` if the affix file.
@@ -106,6 +295,90 @@ impl SpellChecker {
         self.dictionary.as_path()
     }
 
+    /// Returns the encoding of the dictionary as hunspell names it, i.e.
+    /// `UTF-8` or `ISO8859-1`. Words and results are converted from and to
+    /// `&str` for all 8-bit encodings that hunspell supports, except ISCII.
+    pub fn encoding(&self) -> String {
+        dic_encoding(self.handle)
+    }
+
+    /// Returns the language of the dictionary, taken from the `LANG`
+    /// directive of the affix file or else the name of the dictionary file.
+    pub fn language(&self) -> Option<&Language> {
+        self.language.as_ref()
+    }
+
+    /// Sets the language, for dictionaries whose language cannot be derived
+    /// from the files.
+    pub fn set_language(&mut self, language: Language) {
+        self.language = Some(language);
+    }
+
+    /// Makes `suggest()` return no suggestions for words of more than
+    /// `max` characters without asking hunspell, whose suggestions take
+    /// very long for long tokens like hashes or base64 data. `None`, the
+    /// default, has no limit.
+    ///
+    /// ```
+    /// use hunspell_rs::SpellChecker;
+    ///
+    /// let mut spell = SpellChecker::new("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic").unwrap();
+    /// spell.set_max_suggest_length(Some(4));
+    /// assert_eq!(Ok(vec!["cat".to_string()]), spell.suggest("catz"));
+    /// assert_eq!(Ok(Vec::new()), spell.suggest("programz"));
+    /// ```
+    pub fn set_max_suggest_length(&mut self, max: Option<usize>) {
+        self.max_suggest_length = max;
+    }
+
+    pub fn max_suggest_length(&self) -> Option<usize> {
+        self.max_suggest_length
+    }
+
+    /// Returns true if `word` is too long for suggestions, see
+    /// `set_max_suggest_length()`.
+    pub(crate) fn is_too_long_to_suggest(&self, word: &str) -> bool {
+        self.max_suggest_length
+            .is_some_and(|max| word.len() > max && word.chars().count() > max)
+    }
+
+    /// The `MAP` table of the affix file, empty when the file has none or
+    /// cannot be read. Suggestions are filtered and classified with its
+    /// [`distance()`](CharMap::distance), so `facade` is as close to
+    /// `façade` as hunspell thinks it is.
+    pub fn char_map(&self) -> &CharMap {
+        self.char_map.get_or_init(|| {
+            AffixFile::read(&self.affix)
+                .map(|aff| aff.char_map())
+                .unwrap_or_default()
+        })
+    }
+
+    /// The `ICONV` table of the affix file, empty when the file has none
+    /// or cannot be read. Hunspell converts the words given to `check()`,
+    /// `suggest()` and `analyze()` with it; hunspell-rs converts the words
+    /// of `add()` and `remove()` as well, which hunspell does not, and the
+    /// words it looks up in the dictionary files itself.
+    pub fn input_conversions(&self) -> &ConversionTable {
+        &self.conversions().0
+    }
+
+    /// The `OCONV` table of the affix file, empty when the file has none
+    /// or cannot be read. Hunspell converts its suggestions and analyses
+    /// with it; hunspell-rs converts the forms of
+    /// [`all_forms()`](SpellChecker::all_forms).
+    pub fn output_conversions(&self) -> &ConversionTable {
+        &self.conversions().1
+    }
+
+    fn conversions(&self) -> &(ConversionTable, ConversionTable) {
+        self.conversions.get_or_init(|| {
+            AffixFile::read(&self.affix)
+                .map(|aff| (aff.input_conversions(), aff.output_conversions()))
+                .unwrap_or_default()
+        })
+    }
+
     /// Add an additional dictonary for lookup usage for i.e. `check()`.
     ///
     /// The extra dictionaries use the affix file of `SpellChecker`.
@@ -125,9 +398,24 @@ impl SpellChecker {
                 dictionary.to_string_lossy().into_owned(),
             ));
         }
-        let dictionary_cstring = CString::new(dictionary.as_os_str().as_encoded_bytes())?;
+        let dictionary_path = HunspellPath::new(&dictionary)
+            .map_err(|e| e.during(Operation::AddDictionary, &dictionary.to_string_lossy()))?;
+        self.file_stamps.push(FileStamp::of(&dictionary));
         self.additional_dictionaries.push(dictionary);
-        Ok(unsafe { ffi::Hunspell_add_dic(self.handle, dictionary_cstring.as_ptr()) == 0 })
+        self.clear_analysis_cache();
+        Ok(unsafe { ffi::Hunspell_add_dic(self.handle.as_ptr(), dictionary_path.as_ptr()) == 0 })
+    }
+
+    /// Add an additional dictonary from the contents of a dictionary file,
+    /// like `add_dictionary()` without using the file system.
+    ///
+    /// Only supported on Linux and Android, on other platforms
+    /// `Error::InMemoryUnsupported` is returned.
+    pub fn add_dictionary_from_memory(&mut self, dictionary: &[u8]) -> Result<bool> {
+        let (file, path) = memory_file("hunspell.dic", dictionary)?;
+        let added = self.add_dictionary(path)?;
+        self.memory_files.push(Arc::new(file));
+        Ok(added)
     }
 
     /// Add a word to the runtime dictionary.
@@ -135,17 +423,36 @@ impl SpellChecker {
     /// When `SpellChecker` is dropped, the added words are as well.
     /// For a more permanent addition, create a dictionary file
     /// and load it with `add_dictionary()`.
-    pub fn add<S>(&self, word: S) -> Result<()>
+    ///
+    /// Hunspell accepts any string, but words it could never match are
+    /// refused with `Error::InvalidWord`: empty words, words with white
+    /// space or control characters, characters that the encoding of the
+    /// dictionary cannot write, and ASCII punctuation other than `'`, `-`,
+    /// `.` and `/` that is not in the `WORDCHARS` of the affix file.
+    ///
+    /// Adding a word that was already added does nothing, see
+    /// `contains_added()`.
+    pub fn add<S>(&mut self, word: S) -> Result<()>
     where
         S: AsRef<str>,
     {
-        let cword = CString::new(word.as_ref())?;
+        if self.contains_added(word.as_ref()) {
+            return Ok(());
+        }
+        self.validate_word(word.as_ref())?;
+        let cword = self
+            .c_string(&self.input_conversions().apply(word.as_ref()))
+            .map_err(|e| e.during(Operation::Add, word.as_ref()))?;
 
-        let result = unsafe { ffi::Hunspell_add(self.handle, cword.as_ptr()) };
+        let result = unsafe { ffi::Hunspell_add(self.handle.as_ptr(), cword.as_ptr()) };
         if result == 0 {
+            self.runtime_words
+                .push(RuntimeWord::Word(word.as_ref().to_string()));
+            self.added.insert(word.as_ref().to_string());
+            self.clear_analysis_cache();
             Ok(())
         } else {
-            Err(Error::HunspellLibError(result))
+            Err(Error::HunspellLibError(result).during(Operation::Add, word.as_ref()))
         }
     }
 
@@ -156,46 +463,344 @@ impl SpellChecker {
     /// When `SpellChecker` is dropped, the added words are as well.
     /// For a more permanent addition, create a dictionary file
     /// and load it with `add_dictionary()`.
-    pub fn add_with_affix<S>(&self, word: S, example: S) -> Result<()>
+    ///
+    /// `word` is validated like in `add()`. Adding a word with the same
+    /// example again does nothing.
+    pub fn add_with_affix<S>(&mut self, word: S, example: S) -> Result<()>
     where
         S: AsRef<str>,
     {
-        let cword = CString::new(word.as_ref())?;
-        let cexample = CString::new(example.as_ref())?;
-        let result =
-            unsafe { ffi::Hunspell_add_with_affix(self.handle, cword.as_ptr(), cexample.as_ptr()) };
+        let runtime_word = RuntimeWord::WithAffix {
+            word: word.as_ref().to_string(),
+            example: example.as_ref().to_string(),
+        };
+        if self.runtime_words.contains(&runtime_word) {
+            return Ok(());
+        }
+        self.validate_word(word.as_ref())?;
+        let during = |e: Error| e.during(Operation::AddWithAffix, word.as_ref());
+        let iconv = self.input_conversions();
+        let cword = self.c_string(&iconv.apply(word.as_ref())).map_err(during)?;
+        let cexample = self
+            .c_string(&iconv.apply(example.as_ref()))
+            .map_err(during)?;
+        let result = unsafe {
+            ffi::Hunspell_add_with_affix(self.handle.as_ptr(), cword.as_ptr(), cexample.as_ptr())
+        };
         if result == 0 {
+            self.runtime_words.push(runtime_word);
+            self.added.insert(word.as_ref().to_string());
+            self.clear_analysis_cache();
             Ok(())
         } else {
-            Err(Error::HunspellLibError(result))
+            Err(during(Error::HunspellLibError(result)))
         }
     }
 
+    /// Fails with `Error::InvalidWord` when hunspell could never match
+    /// `word`, see `add()`.
+    fn validate_word(&self, word: &str) -> Result<()> {
+        let invalid = |character| {
+            Err(Error::InvalidWord {
+                word: word.to_string(),
+                character,
+            })
+        };
+        if word.is_empty() {
+            return invalid(None);
+        }
+        let bad = word.chars().find(|&c| {
+            c.is_whitespace()
+                || c.is_control()
+                || !self.codec.can_encode(c)
+                || (c.is_ascii_punctuation()
+                    && !matches!(c, '\'' | '-' | '.' | '/')
+                    && !self.word_chars.contains(c))
+        });
+        match bad {
+            Some(c) => invalid(Some(c)),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns true if `word` was added with `add()` or `add_with_affix()`
+    /// and not removed since.
+    pub fn contains_added<S>(&self, word: S) -> bool
+    where
+        S: AsRef<str>,
+    {
+        self.added.contains(word.as_ref())
+    }
+
+    /// Returns the changes to the runtime dictionary in the order they
+    /// were made: the words added with `add()` and `add_with_affix()`, and
+    /// those removed with `remove()` that were not added. A word that is
+    /// added and then removed is left out. The same changes are made again
+    /// by `clone()`, and kept by serde.
+    ///
+    /// ```
+    /// use hunspell_rs::{RuntimeWord, SpellChecker};
+    ///
+    /// let mut spell = SpellChecker::new("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic").unwrap();
+    /// spell.add("octonasaurius").unwrap();
+    /// spell.remove("cats").unwrap();
+    /// assert_eq!(
+    ///     &[
+    ///         RuntimeWord::Word("octonasaurius".to_string()),
+    ///         RuntimeWord::Removed("cats".to_string())
+    ///     ],
+    ///     spell.runtime_words()
+    /// );
+    /// ```
+    pub fn runtime_words(&self) -> &[RuntimeWord] {
+        &self.runtime_words
+    }
+
+    /// Writes the words added with `add()` and `add_with_affix()` as a
+    /// dictionary file, to be loaded with `add_dictionary()` in a later
+    /// session. A word added with `add_with_affix()` gets the flags of its
+    /// example, looked up in the dictionary files; when the example is
+    /// not found there (i.e. in an encrypted dictionary) the word is
+    /// written without flags.
+    ///
+    /// ```
+    /// use hunspell_rs::SpellChecker;
+    ///
+    /// let mut spell = SpellChecker::new("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic").unwrap();
+    /// spell.add_with_affix("dog", "cat").unwrap();
+    /// let path = std::env::temp_dir().join(format!("hunspell-rs-doc-export-{}.dic", std::process::id()));
+    /// spell.export_runtime_words(&path).unwrap();
+    ///
+    /// let mut later = SpellChecker::new("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic").unwrap();
+    /// later.add_dictionary(&path).unwrap();
+    /// assert_eq!(Ok(true), later.check("dogs"));
+    /// # std::fs::remove_file(path).unwrap();
+    /// ```
+    pub fn export_runtime_words<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let encoding = self.encoding();
+        let mut flags: HashMap<String, String> = HashMap::new();
+        for dictionary in std::iter::once(&self.dictionary).chain(&self.additional_dictionaries) {
+            if let Ok(dic) = DicFile::read(dictionary, &encoding) {
+                for entry in dic.entries {
+                    flags.entry(entry.word).or_insert(entry.flags);
+                }
+            }
+        }
+        let mut dic = DicFile::default();
+        for runtime_word in self.runtime_words.iter() {
+            match runtime_word {
+                RuntimeWord::Word(word) => dic.entries.push(DicEntry::new(word)),
+                RuntimeWord::WithAffix { word, example } => {
+                    let example_flags = flags.get(example).cloned().unwrap_or_default();
+                    flags.entry(word.clone()).or_insert(example_flags.clone());
+                    dic.entries
+                        .push(DicEntry::new(word).with_flags(example_flags));
+                }
+                RuntimeWord::Removed(_) => (),
+            }
+        }
+        dic.write(path, &encoding)
+    }
+
     /// Remove a word added with `add()` or `add_with_affix()`.
-    pub fn remove<S>(&self, word: S) -> Result<()>
+    pub fn remove<S>(&mut self, word: S) -> Result<()>
     where
         S: AsRef<str>,
     {
-        let cword = CString::new(word.as_ref())?;
-        let result = unsafe { ffi::Hunspell_remove(self.handle, cword.as_ptr()) };
+        let during = |e: Error| e.during(Operation::Remove, word.as_ref());
+        let cword = self
+            .c_string(&self.input_conversions().apply(word.as_ref()))
+            .map_err(during)?;
+        let result = unsafe { ffi::Hunspell_remove(self.handle.as_ptr(), cword.as_ptr()) };
         if result == 0 {
+            let runtime_words = &mut self.runtime_words;
+            let before = runtime_words.len();
+            runtime_words.retain(|w| match w {
+                RuntimeWord::Word(added) | RuntimeWord::WithAffix { word: added, .. } => {
+                    added != word.as_ref()
+                }
+                RuntimeWord::Removed(_) => true,
+            });
+            if runtime_words.len() == before {
+                runtime_words.push(RuntimeWord::Removed(word.as_ref().to_string()));
+            }
+            self.added.remove(word.as_ref());
+            self.clear_analysis_cache();
             Ok(())
         } else {
-            Err(Error::HunspellLibError(result))
+            Err(during(Error::HunspellLibError(result)))
         }
     }
 
-    /// Returns true if the word is spelled correctly.
+    /// Accepts `word` in `check()` from now on, without adding it to the
+    /// runtime dictionary: an ignored word is not suggested and not used
+    /// in compounds. Ignored words are kept by `clone()` and serde.
+    pub fn ignore<S>(&self, word: S)
+    where
+        S: AsRef<str>,
+    {
+        self.ignored.borrow_mut().insert(word.as_ref().to_string());
+    }
+
+    /// Returns true if `word` was ignored with `ignore()`.
+    pub fn is_ignored<S>(&self, word: S) -> bool
+    where
+        S: AsRef<str>,
+    {
+        self.ignored.borrow().contains(word.as_ref())
+    }
+
+    /// Forgets all words passed to `ignore()`.
+    pub fn clear_ignored(&self) {
+        self.ignored.borrow_mut().clear();
+    }
+
+    /// Returns a hash of everything that decides what `check()` accepts
+    /// and `suggest()` returns: the files with their modification time and
+    /// size when they were loaded, the key, the runtime words, the ignored
+    /// words, the replacements, the options, the correction table and the
+    /// suggestion filter. It changes when words are added, removed or
+    /// ignored, when options are set and when the files are reloaded, so
+    /// it can be used to invalidate cached results. Session hashes are only
+    /// comparable within one run of the program; use
+    /// [`Dictionary::fingerprint()`](crate::Dictionary::fingerprint) for a
+    /// hash of the dictionary data that is stable between runs.
+    pub fn session_hash(&self) -> u64 {
+        use std::hash::{Hash, Hasher};
+
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        self.hash(&mut hasher);
+        self.file_stamps.hash(&mut hasher);
+        self.runtime_words.hash(&mut hasher);
+        let ignored = self.ignored.borrow();
+        let mut ignored: Vec<&String> = ignored.iter().collect();
+        ignored.sort();
+        ignored.hash(&mut hasher);
+        self.replacements.borrow().hash(&mut hasher);
+        self.normalization.hash(&mut hasher);
+        self.input_policy.hash(&mut hasher);
+        self.max_suggest_length.hash(&mut hasher);
+        // the functions cannot be hashed, clones share them
+        let table = self.correction_table.as_ref();
+        table
+            .map(|t| Arc::as_ptr(t) as *const () as usize)
+            .hash(&mut hasher);
+        let filter = self.suggestion_filter.as_ref();
+        filter
+            .map(|f| Arc::as_ptr(f) as *const () as usize)
+            .hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// Returns the modification time and size of the affix file, the
+    /// dictionary and the extra dictionaries when they were loaded.
+    pub fn file_stamps(&self) -> &[FileStamp] {
+        &self.file_stamps
+    }
+
+    /// Returns true if one of the files was changed, replaced or removed
+    /// since it was loaded. Poll it to decide when to call `reload()`.
+    pub fn is_stale(&self) -> bool {
+        self.file_stamps.iter().any(|stamp| !stamp.is_current())
+    }
+
+    /// Loads the files again, keeping the runtime words, the ignored
+    /// words and the autocorrect pairs. When loading fails the
+    /// `SpellChecker` is left as it was.
+    pub fn reload(&mut self) -> Result<()> {
+        *self = self.try_clone()?;
+        Ok(())
+    }
+
+    /// Replaces the dictionaries by `dictionary`, i.e. to upgrade them in
+    /// a long running service, keeping the runtime words, the ignored
+    /// words, the autocorrect pairs, the frequencies and the options. The
+    /// language becomes that of the new files, unless it was set with
+    /// `set_language()`. The new handle is opened and the runtime words
+    /// are added to it before it takes the place of the old one, which is
+    /// left as it was when that fails.
+    ///
+    /// The checker cannot be used while the new dictionary is opened. To
+    /// keep answering requests from other threads during the swap, share
+    /// the checker with a [`CheckerRegistry`](crate::CheckerRegistry) and
+    /// use its [`swap_dictionary()`](crate::CheckerRegistry::swap_dictionary).
+    ///
+    /// ```
+    /// use hunspell_rs::{Dictionary, SpellChecker};
+    ///
+    /// let mut spell = SpellChecker::new("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic").unwrap();
+    /// spell.add("progam").unwrap();
+    /// let upgrade = Dictionary::from_files("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic")
+    ///     .with_additional_dictionary("tests/fixtures/extra.dic");
+    /// spell.swap_dictionary(upgrade).unwrap();
+    /// assert_eq!(Ok(true), spell.check("systemdunits"));
+    /// assert_eq!(Ok(true), spell.check("progam"));
+    /// ```
+    pub fn swap_dictionary(&mut self, dictionary: Dictionary) -> Result<()> {
+        let mut swapped = dictionary.open()?;
+        self.hand_over(&mut swapped)?;
+        *self = swapped;
+        Ok(())
+    }
+
+    /// Gives `swapped`, a checker of other dictionaries, the language set
+    /// with `set_language()`, the runtime words and the session of this
+    /// checker, for [`swap_dictionary()`](SpellChecker::swap_dictionary).
+    pub(crate) fn hand_over(&self, swapped: &mut SpellChecker) -> Result<()> {
+        // a language that differs from the one of the files was set
+        if self.language != Language::from_files(&self.affix, &self.dictionary) {
+            swapped.language = self.language.clone();
+        }
+        swapped.replay_runtime_words(&self.runtime_words)?;
+        self.copy_session_to(swapped);
+        Ok(())
+    }
+
+    /// Returns what is needed to open an equal `SpellChecker`.
+    pub(crate) fn setup(&self) -> Setup {
+        Setup {
+            affix: self.affix.clone(),
+            dictionary: self.dictionary.clone(),
+            additional_dictionaries: self.additional_dictionaries.clone(),
+            key: self.key.clone(),
+            language: self.language.clone(),
+            runtime_words: self.runtime_words.clone(),
+            memory_files: self.memory_files.clone(),
+        }
+    }
+
+    /// Applies changes to the runtime dictionary, for restoring them on a
+    /// new handle.
+    pub(crate) fn replay_runtime_words(&mut self, runtime_words: &[RuntimeWord]) -> Result<()> {
+        runtime_words.iter().try_for_each(|w| match w {
+            RuntimeWord::Word(word) => self.add(word),
+            RuntimeWord::WithAffix { word, example } => self.add_with_affix(word, example),
+            RuntimeWord::Removed(word) => self.remove(word),
+        })
+    }
+
+    /// Returns true if the word is spelled correctly, or ignored.
     pub fn check<S>(&self, word: S) -> Result<bool>
     where
         S: AsRef<str>,
     {
-        let word = CString::new(word.as_ref())?;
-        match unsafe { ffi::Hunspell_spell(self.handle, word.as_ptr()) } {
-            // match ret {
-            0 => Ok(false),
-            _ => Ok(true),
+        if self.is_ignored(word.as_ref()) {
+            return Ok(true);
         }
+        if self.is_rejected_input(word.as_ref()) {
+            return Ok(false);
+        }
+        self.with_c_word(word.as_ref(), |word| {
+            let call = FfiCall::start(
+                FfiOperation::Check,
+                word.count_bytes(),
+                self.stats.as_deref(),
+            );
+            let correct = unsafe { ffi::Hunspell_spell(self.handle.as_ptr(), word.as_ptr()) != 0 };
+            call.finish(usize::from(correct));
+            correct
+        })
+        .map_err(|e| e.during(Operation::Check, word.as_ref()))
     }
 
     /// Returns a list of suggested spellings.
@@ -203,12 +808,94 @@ impl SpellChecker {
     where
         S: AsRef<str>,
     {
-        let word = CString::new(word.as_ref())?;
-        let mut list = null_mut();
-        let n = unsafe { ffi::Hunspell_suggest(self.handle, &mut list, word.as_ptr()) };
-        let strings = list_to_vec(list, n)?;
-        // unsafe { ffi::Hunspell_free_list(self.handle, &mut list, n) };
-        Ok(strings)
+        let mut suggestions = Vec::new();
+        self.suggest_into(word, &mut suggestions)?;
+        Ok(suggestions)
+    }
+
+    /// Like `suggest()`, but writes the suggestions to `suggestions`,
+    /// reusing its strings. For callers that make many calls, to save
+    /// allocations. Returns the number of suggestions.
+    ///
+    /// ```
+    /// use hunspell_rs::SpellChecker;
+    ///
+    /// let spell = SpellChecker::new("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic").unwrap();
+    /// let mut suggestions = Vec::new();
+    /// for word in ["catz", "progam"] {
+    ///     spell.suggest_into(word, &mut suggestions).unwrap();
+    ///     println!("{word}: {}", suggestions.join(", "));
+    /// }
+    /// ```
+    pub fn suggest_into<S>(&self, word: S, suggestions: &mut Vec<String>) -> Result<usize>
+    where
+        S: AsRef<str>,
+    {
+        let word = word.as_ref();
+        if self.is_too_long_to_suggest(word) || self.is_rejected_input(word) {
+            suggestions.clear();
+            self.filter_suggestions(word, suggestions);
+            return Ok(suggestions.len());
+        }
+        let during = |e: Error| e.during(Operation::Suggest, word);
+        let mut iter = self.suggest_iter(word).map_err(during)?;
+        let n = iter.len();
+        for i in 0..n {
+            let s = match iter.next_cstr().map(|s| self.codec.decode(s.to_bytes())) {
+                Some(Ok(s)) => s,
+                Some(Err(e)) => {
+                    suggestions.clear();
+                    return Err(during(e));
+                }
+                None => {
+                    suggestions.clear();
+                    return Err(during(Error::NullPtr));
+                }
+            };
+            match suggestions.get_mut(i) {
+                Some(suggestion) => {
+                    suggestion.clear();
+                    suggestion.push_str(&s);
+                }
+                None => suggestions.push(s.into_owned()),
+            }
+        }
+        suggestions.truncate(n);
+        drop(iter);
+        self.prepend_replacements(word, suggestions)?;
+        self.filter_suggestions(word, suggestions);
+        Ok(suggestions.len())
+    }
+
+    /// Returns `word` as a C string in the encoding of the dictionary.
+    pub(crate) fn c_string(&self, word: &str) -> Result<CString> {
+        let word = self
+            .input_policy
+            .apply(word)
+            .ok_or_else(|| self.input_policy.rejection(word))?;
+        let word = self.normalization.apply(&word);
+        Ok(CString::new(self.codec.encode(&word).into_owned())?)
+    }
+
+    /// Calls `f` with `word` as a C string in the encoding of the
+    /// dictionary, kept in a buffer that is reused between calls.
+    pub(crate) fn with_c_word<T>(&self, word: &str, f: impl FnOnce(&CStr) -> T) -> Result<T> {
+        let word = self
+            .input_policy
+            .apply(word)
+            .ok_or_else(|| self.input_policy.rejection(word))?;
+        if word.as_bytes().contains(&0) {
+            return Err(CString::new(word.as_bytes())
+                .expect_err("word contains nul")
+                .into());
+        }
+        let word = self.normalization.apply(&word);
+        let mut scratch = self.scratch.borrow_mut();
+        scratch.clear();
+        scratch.extend_from_slice(&self.codec.encode(&word));
+        scratch.push(0);
+        let word = CStr::from_bytes_with_nul(&scratch).expect("one nul at the end");
+        Ok(f(word))
     }
 
     /// Morphological analysis
@@ -216,13 +903,22 @@ impl SpellChecker {
     where
         S: AsRef<str>,
     {
-        let word = CString::new(word.as_ref())?;
-        let mut list = null_mut();
-        let n = unsafe { ffi::Hunspell_analyze(self.handle, &mut list, word.as_ptr()) };
-        let strings = list_to_vec(list, n)?;
-        unsafe { ffi::Hunspell_free_list(self.handle, &mut list, n) };
-
-        Ok(strings)
+        if self.is_rejected_input(word.as_ref()) {
+            return Ok(Vec::new());
+        }
+        self.cached_morphology(Morphology::Analyses, word.as_ref(), || {
+            let word = self.c_string(word.as_ref())?;
+            let call = FfiCall::start(
+                FfiOperation::Analyze,
+                word.count_bytes(),
+                self.stats.as_deref(),
+            );
+            let list = HunspellList::new(&self.handle, |handle, list| unsafe {
+                ffi::Hunspell_analyze(handle, list, word.as_ptr())
+            })?;
+            call.finish(list.len());
+            list.to_vec(self.codec)
+        })
     }
 
     /// Returns a list of stems
@@ -230,12 +926,16 @@ impl SpellChecker {
     where
         S: AsRef<str>,
     {
-        let word = CString::new(word.as_ref())?;
-        let mut list = null_mut();
-        let n = unsafe { ffi::Hunspell_stem(self.handle, &mut list, word.as_ptr()) };
-        let strings = list_to_vec(list, n)?;
-        // unsafe { ffi::Hunspell_free_list(self.handle, &mut list, n) };
-        Ok(strings)
+        if self.is_rejected_input(word.as_ref()) {
+            return Ok(Vec::new());
+        }
+        self.cached_morphology(Morphology::Stems, word.as_ref(), || {
+            let word = self.c_string(word.as_ref())?;
+            HunspellList::new(&self.handle, |handle, list| unsafe {
+                ffi::Hunspell_stem(handle, list, word.as_ptr())
+            })?
+            .to_vec(self.codec)
+        })
     }
 
     /// Returns a list of stems based on morphological analysis.
@@ -243,18 +943,14 @@ impl SpellChecker {
     where
         S: AsRef<str>,
     {
-        let word = CString::new(word.as_ref())?;
-        let mut analyzed = null_mut();
-        let mut list = null_mut();
-        let n_analyzed =
-            unsafe { ffi::Hunspell_analyze(self.handle, &mut analyzed, word.as_ptr()) };
-        let n = unsafe { ffi::Hunspell_stem2(self.handle, &mut list, analyzed, n_analyzed) };
-        let strings = list_to_vec(list, n)?;
-        unsafe {
-            ffi::Hunspell_free_list(self.handle, &mut analyzed, n_analyzed);
-            // ffi::Hunspell_free_list(self.handle, &mut list, n);
-        }
-        Ok(strings)
+        let analyses = self.encoded_analyses(word.as_ref())?;
+        // hunspell takes `char**` but does not change the strings
+        let mut pointers: Vec<*mut c_char> =
+            analyses.iter().map(|a| a.as_ptr() as *mut c_char).collect();
+        HunspellList::new(&self.handle, |handle, list| unsafe {
+            ffi::Hunspell_stem2(handle, list, pointers.as_mut_ptr(), pointers.len() as c_int)
+        })?
+        .to_vec(self.codec)
     }
 
     /// The second word and its affixation will be the model of the
@@ -263,15 +959,12 @@ impl SpellChecker {
     where
         S: AsRef<str>,
     {
-        let word1 = CString::new(word1.as_ref())?;
-        let word2 = CString::new(word2.as_ref())?;
-        let mut list = null_mut();
-        let n = unsafe {
-            ffi::Hunspell_generate(self.handle, &mut list, word1.as_ptr(), word2.as_ptr())
-        };
-        let strings = list_to_vec(list, n)?;
-        // unsafe { ffi::Hunspell_free_list(self.handle, &mut list, n) };
-        Ok(strings)
+        let word1 = self.c_string(word1.as_ref())?;
+        let word2 = self.c_string(word2.as_ref())?;
+        HunspellList::new(&self.handle, |handle, list| unsafe {
+            ffi::Hunspell_generate(handle, list, word1.as_ptr(), word2.as_ptr())
+        })?
+        .to_vec(self.codec)
     }
 
     /// The second word and its affixation will be the model of the
@@ -281,54 +974,278 @@ impl SpellChecker {
     where
         S: AsRef<str>,
     {
-        let word1 = CString::new(word1.as_ref())?;
-        let word2 = CString::new(word2.as_ref())?;
-        let mut analyzed = null_mut();
-        let mut list = null_mut();
-        let n_analyzed =
-            unsafe { ffi::Hunspell_analyze(self.handle, &mut analyzed, word1.as_ptr()) };
-        let n = unsafe {
-            ffi::Hunspell_generate2(self.handle, &mut list, word2.as_ptr(), analyzed, n_analyzed)
-        };
-        let strings = list_to_vec(list, n)?;
-        unsafe {
-            ffi::Hunspell_free_list(self.handle, &mut analyzed, n_analyzed);
-            // ffi::Hunspell_free_list(self.handle, &mut list, n);
+        let word2 = self.c_string(word2.as_ref())?;
+        let analyses = self.encoded_analyses(word1.as_ref())?;
+        let mut pointers: Vec<*mut c_char> =
+            analyses.iter().map(|a| a.as_ptr() as *mut c_char).collect();
+        HunspellList::new(&self.handle, |handle, list| unsafe {
+            ffi::Hunspell_generate2(
+                handle,
+                list,
+                word2.as_ptr(),
+                pointers.as_mut_ptr(),
+                pointers.len() as c_int,
+            )
+        })?
+        .to_vec(self.codec)
+    }
+
+    /// The analyses of `word` from `analyze()`, in the encoding of the
+    /// dictionary again.
+    fn encoded_analyses(&self, word: &str) -> Result<Vec<CString>> {
+        self.analyze(word)?
+            .iter()
+            .map(|a| Ok(CString::new(self.codec.encode(a).into_owned())?))
+            .collect()
+    }
+
+    /// Generates the forms of `word` described by morphological fields,
+    /// i.e. `&["is:plur"]`, without a model word like `generate()` needs.
+    /// Each description is matched separately; a description can hold
+    /// several fields separated by spaces. Every form is returned once.
+    ///
+    /// ```
+    /// use hunspell_rs::SpellChecker;
+    ///
+    /// let spell = SpellChecker::new("tests/fixtures/stem/stem.aff", "tests/fixtures/stem/stem.dic").unwrap();
+    /// assert_eq!(vec!["roses"], spell.generate_from_morph("rose", &["is:plur"]).unwrap());
+    /// ```
+    pub fn generate_from_morph<S, D>(&self, word: S, descriptions: &[D]) -> Result<Vec<String>>
+    where
+        S: AsRef<str>,
+        D: AsRef<str>,
+    {
+        let word = self.c_string(word.as_ref())?;
+        let descriptions = descriptions
+            .iter()
+            .map(|d| self.c_string(d.as_ref()))
+            .collect::<Result<Vec<CString>>>()?;
+        // hunspell takes `char**` but does not change the strings
+        let mut pointers: Vec<*mut c_char> = descriptions
+            .iter()
+            .map(|d| d.as_ptr() as *mut c_char)
+            .collect();
+        let mut forms = HunspellList::new(&self.handle, |handle, list| unsafe {
+            ffi::Hunspell_generate2(
+                handle,
+                list,
+                word.as_ptr(),
+                pointers.as_mut_ptr(),
+                pointers.len() as c_int,
+            )
+        })?
+        .to_vec(self.codec)?;
+        // hunspell returns a form for every homonym of the word
+        let mut seen = HashSet::new();
+        forms.retain(|form| seen.insert(form.clone()));
+        Ok(forms)
+    }
+
+    /// Opens the files of the `SpellChecker` again, with the same added,
+    /// removed and ignored words, autocorrect pairs and frequencies.
+    /// Unlike `clone()` it fails instead of panicking when the files no
+    /// longer exist.
+    pub fn try_clone(&self) -> Result<Self> {
+        let mut clone = self.setup().open()?;
+        self.copy_session_to(&mut clone);
+        Ok(clone)
+    }
+
+    /// Gives `other` the ignored words, autocorrect pairs, frequencies and
+    /// options of this checker, everything but the dictionaries and the
+    /// runtime words.
+    fn copy_session_to(&self, other: &mut SpellChecker) {
+        other
+            .ignored
+            .borrow_mut()
+            .clone_from(&self.ignored.borrow());
+        other
+            .replacements
+            .borrow_mut()
+            .clone_from(&self.replacements.borrow());
+        other
+            .frequencies
+            .borrow_mut()
+            .clone_from(&self.frequencies.borrow());
+        other.normalization = self.normalization;
+        other.input_policy = self.input_policy;
+        other.max_suggest_length = self.max_suggest_length;
+        other.correction_table = self.correction_table.clone();
+        other.suggestion_filter = self.suggestion_filter.clone();
+        other.stats = self.stats.clone();
+        if let Some(capacity) = self.analysis_cache_capacity() {
+            other.enable_analysis_cache(capacity);
         }
-        Ok(strings)
     }
 }
 
 impl Clone for SpellChecker {
     /// **Panics** if the files that the `SpellChecker` was created from
-    /// no longer exist.
+    /// no longer exist, use `try_clone()` to get an error instead.
     fn clone(&self) -> Self {
-        let mut clone = match &self.key {
-            Some(key) => Self::new_with_key(&self.affix, &self.dictionary, key)
-                .expect(&format!("Affix file '{:?}' no longer exists", &self.affix)),
-            None => Self::new(&self.affix, &self.dictionary).expect(&format!(
-                "Dictionary file '{:?}' no longer exists",
-                &self.dictionary
-            )),
+        self.try_clone()
+            .unwrap_or_else(|e| panic!("Cannot clone SpellChecker: {e}"))
+    }
+}
+
+/// The modification time and size of a file of a `SpellChecker` when it
+/// was loaded, see [`SpellChecker::is_stale()`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FileStamp {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+    size: Option<u64>,
+}
+
+impl FileStamp {
+    fn of(path: &Path) -> Self {
+        let metadata = std::fs::metadata(path).ok();
+        FileStamp {
+            path: path.to_path_buf(),
+            modified: metadata.as_ref().and_then(|m| m.modified().ok()),
+            size: metadata.map(|m| m.len()),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// `None` when the platform does not record modification times.
+    pub fn modified(&self) -> Option<SystemTime> {
+        self.modified
+    }
+
+    /// The size in bytes.
+    pub fn size(&self) -> Option<u64> {
+        self.size
+    }
+
+    /// Returns true if the file still has the same modification time and
+    /// size.
+    pub fn is_current(&self) -> bool {
+        Self::of(&self.path) == *self
+    }
+}
+
+/// Everything a `SpellChecker` was made from. Unlike `SpellChecker` it can
+/// be sent to other threads to open an equal `SpellChecker` there.
+#[derive(Debug, Clone)]
+pub(crate) struct Setup {
+    pub(crate) affix: PathBuf,
+    pub(crate) dictionary: PathBuf,
+    pub(crate) additional_dictionaries: Vec<PathBuf>,
+    pub(crate) key: Option<String>,
+    pub(crate) language: Option<Language>,
+    pub(crate) runtime_words: Vec<RuntimeWord>,
+    pub(crate) memory_files: Vec<Arc<File>>,
+}
+
+impl Setup {
+    /// Opens a new `SpellChecker` with its own handle.
+    pub(crate) fn open(&self) -> Result<SpellChecker> {
+        let mut spell_checker = match &self.key {
+            Some(key) => SpellChecker::new_with_key(&self.affix, &self.dictionary, key)?,
+            None => SpellChecker::new(&self.affix, &self.dictionary)?,
         };
         for d in &self.additional_dictionaries {
-            clone.add_dictionary(d).expect(&format!(
-                "Additional dictionary file '{:?}' no longer exists",
-                d
-            ));
+            spell_checker.add_dictionary(d)?;
+        }
+        spell_checker.memory_files = self.memory_files.clone();
+        spell_checker.language = self.language.clone();
+        spell_checker.replay_runtime_words(&self.runtime_words)?;
+        Ok(spell_checker)
+    }
+}
+
+/// Two spell checkers are equal when they are opened from the same files
+/// with the same key. Runtime words and the language are not compared,
+/// so the hash stays the same while words are added.
+impl PartialEq for SpellChecker {
+    fn eq(&self, other: &Self) -> bool {
+        self.affix == other.affix
+            && self.dictionary == other.dictionary
+            && self.additional_dictionaries == other.additional_dictionaries
+            && self.key == other.key
+    }
+}
+
+impl Eq for SpellChecker {}
+
+impl core::hash::Hash for SpellChecker {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.affix.hash(state);
+        self.dictionary.hash(state);
+        self.additional_dictionaries.hash(state);
+        self.key.hash(state);
+    }
+}
+
+impl core::fmt::Debug for SpellChecker {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        fmt.debug_struct("SpellChecker")
+            .field("language", &self.language)
+            .field("affix", &self.affix)
+            .field("dictionary", &self.dictionary)
+            .field("additional_dictionaries", &self.additional_dictionaries)
+            .field("encrypted", &self.key.is_some())
+            .field("runtime_words", &self.runtime_words.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl core::fmt::Display for SpellChecker {
+    /// Shows the language, the dictionary file and the number of extra
+    /// dictionaries and runtime words, i.e.
+    /// `en_US (/usr/share/hunspell/en_US.dic, 1 extra dictionaries, 3 runtime words)`.
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match &self.language {
+            Some(language) => write!(fmt, "{language}")?,
+            None => fmt.write_str("unknown language")?,
         }
-        clone
+        write!(
+            fmt,
+            " ({}, {} extra dictionaries, {} runtime words)",
+            self.dictionary.display(),
+            self.additional_dictionaries.len(),
+            self.runtime_words.len()
+        )
     }
 }
 
 impl Drop for SpellChecker {
     fn drop(&mut self) {
         unsafe {
-            ffi::Hunspell_destroy(self.handle);
+            ffi::Hunspell_destroy(self.handle.as_ptr());
         }
     }
 }
 
+/// Returns the name of the encoding of the dictionary of `handle`.
+fn dic_encoding(handle: NonNull<ffi::Hunhandle>) -> String {
+    // SAFETY: the string belongs to the handle and lives as long as it
+    let encoding = unsafe { ffi::Hunspell_get_dic_encoding(handle.as_ptr()) };
+    if encoding.is_null() {
+        return crate::DEFAULT_ENCODING.to_string();
+    }
+    unsafe { CStr::from_ptr(encoding) }
+        .to_string_lossy()
+        .into_owned()
+}
+
+/// Returns the handle created for `affix` and `dictionary`, if the
+/// hunspell library managed to create one.
+fn checked_handle(
+    handle: *mut ffi::Hunhandle,
+    affix: &Path,
+    dictionary: &Path,
+) -> Result<NonNull<ffi::Hunhandle>> {
+    NonNull::new(handle).ok_or_else(|| Error::HandleCreationFailed {
+        affix: affix.to_path_buf(),
+        dictionary: dictionary.to_path_buf(),
+    })
+}
+
 pub(crate) fn check_paths<P: AsRef<Path>>(affix: P, dictionary: P) -> Result<(PathBuf, PathBuf)> {
     let affix = affix.as_ref().to_path_buf();
     let dictionary = dictionary.as_ref().to_path_buf();
@@ -344,33 +1261,3 @@ pub(crate) fn check_paths<P: AsRef<Path>>(affix: P, dictionary: P) -> Result<(Pa
     }
     Ok((affix, dictionary))
 }
-
-pub(crate) fn list_to_vec(ptr: *mut *mut u8, len: i32) -> Result<Vec<String>> {
-    if ptr.is_null() {
-        return Err(Error::NullPtr);
-    }
-    if len < 0 {
-        return Err(Error::NegativeListLength(len));
-    }
-    if len == 0 {
-        return Ok(Vec::new());
-    }
-
-    // SAFETY:
-    //    - checked for null ptr, other issues depend on the hunspell library
-    //    - len has been checked: safe cast
-    unsafe { Vec::from_raw_parts(ptr, len as usize, len as usize) }
-        .into_iter()
-        .map(|p| {
-            if p.is_null() {
-                Err(Error::NullPtr)
-            } else {
-                // SAFETY: checked for null ptr, other issues depend on the hunspell library
-                unsafe { CStr::from_ptr(p) }
-                    .to_str()
-                    .and_then(|s| Ok(s.to_string()))
-                    .map_err(|e| e.into())
-            }
-        })
-        .collect()
-}
@@ -0,0 +1,421 @@
+//! The core operations of a spell checker as a trait.
+
+use std::sync::Arc;
+
+use crate::{
+    combine::{And, NotIn, Or},
+    CachedSpellChecker, CheckerChain, Error, FallbackChecker, FrozenSpellChecker,
+    MultiLanguageChecker, NullSpellChecker, Result, SpellChecker, SuggestionArbiter,
+};
+
+/// The core operations of a spell checker, implemented by [`SpellChecker`]
+/// and its wrappers. Write code against it to swap in another checker,
+/// or a mock in unit tests:
+///
+/// ```
+/// use hunspell_rs::{Result, Speller};
+///
+/// /// Accepts words without a `z`.
+/// struct NoZ;
+///
+/// impl Speller for NoZ {
+///     fn check(&self, word: &str) -> Result<bool> {
+///         Ok(!word.contains('z'))
+///     }
+///     fn suggest(&self, word: &str) -> Result<Vec<String>> {
+///         Ok(vec![word.replace('z', "s")])
+///     }
+///     fn stem(&self, word: &str) -> Result<Vec<String>> {
+///         Ok(vec![word.to_string()])
+///     }
+///     fn analyze(&self, _word: &str) -> Result<Vec<String>> {
+///         Ok(Vec::new())
+///     }
+/// }
+///
+/// fn misspelled<S: Speller>(speller: &S, words: &[&str]) -> Result<usize> {
+///     let mut count = 0;
+///     for word in words {
+///         if !speller.check(word)? {
+///             count += 1;
+///         }
+///     }
+///     Ok(count)
+/// }
+///
+/// assert_eq!(Ok(1), misspelled(&NoZ, &["cats", "catz"]));
+/// ```
+pub trait Speller {
+    /// See [`SpellChecker::check()`].
+    fn check(&self, word: &str) -> Result<bool>;
+
+    /// See [`SpellChecker::suggest()`].
+    fn suggest(&self, word: &str) -> Result<Vec<String>>;
+
+    /// See [`SpellChecker::stem()`].
+    fn stem(&self, word: &str) -> Result<Vec<String>>;
+
+    /// See [`SpellChecker::analyze()`].
+    fn analyze(&self, word: &str) -> Result<Vec<String>>;
+
+    /// See [`SpellChecker::add()`]. Fails with `Error::ReadOnly` by
+    /// default, for spellers that cannot be changed.
+    fn add(&mut self, _word: &str) -> Result<()> {
+        Err(Error::ReadOnly)
+    }
+
+    /// See [`SpellChecker::remove()`]. Fails with `Error::ReadOnly` by
+    /// default.
+    fn remove(&mut self, _word: &str) -> Result<()> {
+        Err(Error::ReadOnly)
+    }
+
+    /// Returns a speller that accepts the words both `self` and `other`
+    /// accept, see [`And`].
+    ///
+    /// ```
+    /// use hunspell_rs::{SpellChecker, Speller};
+    ///
+    /// let reduced = SpellChecker::new("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic").unwrap();
+    /// let rank = SpellChecker::new("tests/fixtures/rank/rank.aff", "tests/fixtures/rank/rank.dic").unwrap();
+    /// let both = reduced.and(rank);
+    /// assert_eq!(Ok(true), both.check("cat"));
+    /// assert_eq!(Ok(false), both.check("cats"));
+    /// ```
+    fn and<B: Speller>(self, other: B) -> And<Self, B>
+    where
+        Self: Sized,
+    {
+        And::new(self, other)
+    }
+
+    /// Returns a speller that accepts the words `self` or `other`
+    /// accepts, see [`Or`].
+    fn or<B: Speller>(self, other: B) -> Or<Self, B>
+    where
+        Self: Sized,
+    {
+        Or::new(self, other)
+    }
+
+    /// Returns a speller that accepts the words `self` accepts, except
+    /// those `forbidden` accepts, i.e. a list of banned words, see
+    /// [`NotIn`].
+    fn not_in<B: Speller>(self, forbidden: B) -> NotIn<Self, B>
+    where
+        Self: Sized,
+    {
+        NotIn::new(self, forbidden)
+    }
+}
+
+impl Speller for SpellChecker {
+    fn check(&self, word: &str) -> Result<bool> {
+        SpellChecker::check(self, word)
+    }
+
+    fn suggest(&self, word: &str) -> Result<Vec<String>> {
+        SpellChecker::suggest(self, word)
+    }
+
+    fn stem(&self, word: &str) -> Result<Vec<String>> {
+        SpellChecker::stem(self, word)
+    }
+
+    fn analyze(&self, word: &str) -> Result<Vec<String>> {
+        SpellChecker::analyze(self, word)
+    }
+
+    fn add(&mut self, word: &str) -> Result<()> {
+        SpellChecker::add(self, word)
+    }
+
+    fn remove(&mut self, word: &str) -> Result<()> {
+        SpellChecker::remove(self, word)
+    }
+}
+
+/// Stems and analyses are not cached.
+impl Speller for CachedSpellChecker {
+    fn check(&self, word: &str) -> Result<bool> {
+        CachedSpellChecker::check(self, word)
+    }
+
+    fn suggest(&self, word: &str) -> Result<Vec<String>> {
+        CachedSpellChecker::suggest(self, word)
+    }
+
+    fn stem(&self, word: &str) -> Result<Vec<String>> {
+        self.spell_checker().stem(word)
+    }
+
+    fn analyze(&self, word: &str) -> Result<Vec<String>> {
+        self.spell_checker().analyze(word)
+    }
+
+    fn add(&mut self, word: &str) -> Result<()> {
+        CachedSpellChecker::add(self, word)
+    }
+
+    fn remove(&mut self, word: &str) -> Result<()> {
+        CachedSpellChecker::remove(self, word)
+    }
+}
+
+/// `add()` and `remove()` fail with `Error::ReadOnly`.
+impl Speller for FrozenSpellChecker {
+    fn check(&self, word: &str) -> Result<bool> {
+        FrozenSpellChecker::check(self, word)
+    }
+
+    fn suggest(&self, word: &str) -> Result<Vec<String>> {
+        FrozenSpellChecker::suggest(self, word)
+    }
+
+    fn stem(&self, word: &str) -> Result<Vec<String>> {
+        FrozenSpellChecker::stem(self, word)
+    }
+
+    fn analyze(&self, word: &str) -> Result<Vec<String>> {
+        FrozenSpellChecker::analyze(self, word)
+    }
+}
+
+/// Stems and analyses of all checkers are concatenated, leaving out
+/// duplicates. Words are added to and removed from all checkers.
+impl<A: SuggestionArbiter> Speller for MultiLanguageChecker<A> {
+    fn check(&self, word: &str) -> Result<bool> {
+        MultiLanguageChecker::check(self, word)
+    }
+
+    fn suggest(&self, word: &str) -> Result<Vec<String>> {
+        MultiLanguageChecker::suggest(self, word)
+    }
+
+    fn stem(&self, word: &str) -> Result<Vec<String>> {
+        concat(self.checkers(), |c| c.stem(word))
+    }
+
+    fn analyze(&self, word: &str) -> Result<Vec<String>> {
+        concat(self.checkers(), |c| c.analyze(word))
+    }
+
+    fn add(&mut self, word: &str) -> Result<()> {
+        self.checkers_mut().iter_mut().try_for_each(|c| c.add(word))
+    }
+
+    fn remove(&mut self, word: &str) -> Result<()> {
+        self.checkers_mut()
+            .iter_mut()
+            .try_for_each(|c| c.remove(word))
+    }
+}
+
+/// Stems and analyses of the primary checker, followed by those of the
+/// fallback. Words are added to and removed from both checkers.
+impl<A: SuggestionArbiter> Speller for FallbackChecker<A> {
+    fn check(&self, word: &str) -> Result<bool> {
+        FallbackChecker::check(self, word)
+    }
+
+    fn suggest(&self, word: &str) -> Result<Vec<String>> {
+        FallbackChecker::suggest(self, word)
+    }
+
+    fn stem(&self, word: &str) -> Result<Vec<String>> {
+        concat(&[self.primary(), self.fallback()], |c| c.stem(word))
+    }
+
+    fn analyze(&self, word: &str) -> Result<Vec<String>> {
+        concat(&[self.primary(), self.fallback()], |c| c.analyze(word))
+    }
+
+    fn add(&mut self, word: &str) -> Result<()> {
+        self.primary_mut().add(word)?;
+        self.fallback_mut().add(word)
+    }
+
+    fn remove(&mut self, word: &str) -> Result<()> {
+        self.primary_mut().remove(word)?;
+        self.fallback_mut().remove(word)
+    }
+}
+
+/// Stems and analyses of all layers, in order. Words are added to and
+/// removed from the first layer, use
+/// [`layer_mut()`](CheckerChain::layer_mut) for another one.
+impl Speller for CheckerChain {
+    fn check(&self, word: &str) -> Result<bool> {
+        CheckerChain::check(self, word)
+    }
+
+    fn suggest(&self, word: &str) -> Result<Vec<String>> {
+        CheckerChain::suggest(self, word)
+    }
+
+    fn stem(&self, word: &str) -> Result<Vec<String>> {
+        concat(&self.layers, |l| l.speller.stem(word))
+    }
+
+    fn analyze(&self, word: &str) -> Result<Vec<String>> {
+        concat(&self.layers, |l| l.speller.analyze(word))
+    }
+
+    fn add(&mut self, word: &str) -> Result<()> {
+        match self.layers.first_mut() {
+            Some(layer) => layer.speller.add(word),
+            None => Err(Error::ReadOnly),
+        }
+    }
+
+    fn remove(&mut self, word: &str) -> Result<()> {
+        match self.layers.first_mut() {
+            Some(layer) => layer.speller.remove(word),
+            None => Err(Error::ReadOnly),
+        }
+    }
+}
+
+impl Speller for NullSpellChecker {
+    fn check(&self, _word: &str) -> Result<bool> {
+        Ok(true)
+    }
+
+    fn suggest(&self, _word: &str) -> Result<Vec<String>> {
+        Ok(Vec::new())
+    }
+
+    fn stem(&self, _word: &str) -> Result<Vec<String>> {
+        Ok(Vec::new())
+    }
+
+    fn analyze(&self, _word: &str) -> Result<Vec<String>> {
+        Ok(Vec::new())
+    }
+
+    fn add(&mut self, _word: &str) -> Result<()> {
+        Ok(())
+    }
+
+    fn remove(&mut self, _word: &str) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// `add()` and `remove()` fail with `Error::ReadOnly`, a shared reference
+/// cannot change the speller.
+impl<T: Speller + ?Sized> Speller for &T {
+    fn check(&self, word: &str) -> Result<bool> {
+        (**self).check(word)
+    }
+
+    fn suggest(&self, word: &str) -> Result<Vec<String>> {
+        (**self).suggest(word)
+    }
+
+    fn stem(&self, word: &str) -> Result<Vec<String>> {
+        (**self).stem(word)
+    }
+
+    fn analyze(&self, word: &str) -> Result<Vec<String>> {
+        (**self).analyze(word)
+    }
+}
+
+impl<T: Speller + ?Sized> Speller for &mut T {
+    fn check(&self, word: &str) -> Result<bool> {
+        (**self).check(word)
+    }
+
+    fn suggest(&self, word: &str) -> Result<Vec<String>> {
+        (**self).suggest(word)
+    }
+
+    fn stem(&self, word: &str) -> Result<Vec<String>> {
+        (**self).stem(word)
+    }
+
+    fn analyze(&self, word: &str) -> Result<Vec<String>> {
+        (**self).analyze(word)
+    }
+
+    fn add(&mut self, word: &str) -> Result<()> {
+        (**self).add(word)
+    }
+
+    fn remove(&mut self, word: &str) -> Result<()> {
+        (**self).remove(word)
+    }
+}
+
+impl<T: Speller + ?Sized> Speller for Box<T> {
+    fn check(&self, word: &str) -> Result<bool> {
+        (**self).check(word)
+    }
+
+    fn suggest(&self, word: &str) -> Result<Vec<String>> {
+        (**self).suggest(word)
+    }
+
+    fn stem(&self, word: &str) -> Result<Vec<String>> {
+        (**self).stem(word)
+    }
+
+    fn analyze(&self, word: &str) -> Result<Vec<String>> {
+        (**self).analyze(word)
+    }
+
+    fn add(&mut self, word: &str) -> Result<()> {
+        (**self).add(word)
+    }
+
+    fn remove(&mut self, word: &str) -> Result<()> {
+        (**self).remove(word)
+    }
+}
+
+/// For the checkers of a [`CheckerRegistry`](crate::CheckerRegistry).
+/// `add()` and `remove()` fail with `Error::ReadOnly` while the speller
+/// is shared.
+impl<T: Speller + ?Sized> Speller for Arc<T> {
+    fn check(&self, word: &str) -> Result<bool> {
+        (**self).check(word)
+    }
+
+    fn suggest(&self, word: &str) -> Result<Vec<String>> {
+        (**self).suggest(word)
+    }
+
+    fn stem(&self, word: &str) -> Result<Vec<String>> {
+        (**self).stem(word)
+    }
+
+    fn analyze(&self, word: &str) -> Result<Vec<String>> {
+        (**self).analyze(word)
+    }
+
+    fn add(&mut self, word: &str) -> Result<()> {
+        Arc::get_mut(self).ok_or(Error::ReadOnly)?.add(word)
+    }
+
+    fn remove(&mut self, word: &str) -> Result<()> {
+        Arc::get_mut(self).ok_or(Error::ReadOnly)?.remove(word)
+    }
+}
+
+/// Concatenates the results of `f` for all `checkers`, leaving out
+/// duplicates.
+fn concat<C, F>(checkers: &[C], f: F) -> Result<Vec<String>>
+where
+    F: Fn(&C) -> Result<Vec<String>>,
+{
+    let mut all: Vec<String> = Vec::new();
+    for checker in checkers {
+        for s in f(checker)? {
+            if !all.contains(&s) {
+                all.push(s);
+            }
+        }
+    }
+    Ok(all)
+}
@@ -0,0 +1,99 @@
+//! Splitting run-together words into known words.
+
+use crate::{Result, SpellChecker};
+
+impl SpellChecker {
+    /// Words longer than this many characters are not split by
+    /// [`split_suggestions()`](SpellChecker::split_suggestions), which
+    /// checks every substring.
+    pub const MAX_SPLIT_LENGTH: usize = 64;
+
+    /// The maximal number of results of
+    /// [`split_suggestions()`](SpellChecker::split_suggestions).
+    pub const MAX_SPLIT_SUGGESTIONS: usize = 10;
+
+    /// Suggests ways to split `word` into two or more correct words, like
+    /// `no cats` for `nocats`, for text from OCR and chats where spaces
+    /// went missing. Unlike hunspell's own suggestions, this finds splits
+    /// into any number of words.
+    ///
+    /// Only the splits into the fewest words are returned, at most
+    /// [`MAX_SPLIT_SUGGESTIONS`](SpellChecker::MAX_SPLIT_SUGGESTIONS),
+    /// those with longer words first. Words that are correct themselves
+    /// are split too, check them first when that is not wanted.
+    ///
+    /// ```
+    /// use hunspell_rs::SpellChecker;
+    ///
+    /// let mut spell = SpellChecker::new("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic").unwrap();
+    /// spell.add("no").unwrap();
+    /// assert_eq!(vec!["no cats"], spell.split_suggestions("nocats").unwrap());
+    /// ```
+    pub fn split_suggestions<S>(&self, word: S) -> Result<Vec<String>>
+    where
+        S: AsRef<str>,
+    {
+        let word = word.as_ref();
+        let bounds: Vec<usize> = word
+            .char_indices()
+            .map(|(i, _)| i)
+            .chain([word.len()])
+            .collect();
+        let n = bounds.len() - 1;
+        if !(2..=Self::MAX_SPLIT_LENGTH).contains(&n) {
+            return Ok(Vec::new());
+        }
+        // ends[i] are the j for which word[bounds[i]..bounds[j]] is a
+        // correct word followed by a splittable rest, longest first, and
+        // parts[i] the fewest words word[bounds[i]..] splits into
+        let mut ends: Vec<Vec<usize>> = vec![Vec::new(); n];
+        let mut parts: Vec<Option<usize>> = vec![None; n + 1];
+        parts[n] = Some(0);
+        for i in (0..n).rev() {
+            for j in (i + 1..=n).rev() {
+                let Some(rest) = parts[j] else {
+                    continue;
+                };
+                if (i, j) == (0, n) || !self.check(&word[bounds[i]..bounds[j]])? {
+                    continue;
+                }
+                ends[i].push(j);
+                parts[i] = Some(parts[i].map_or(rest + 1, |p| p.min(rest + 1)));
+            }
+        }
+        let mut splits = Vec::new();
+        if let Some(fewest) = parts[0] {
+            let mut path = Vec::with_capacity(fewest);
+            collect_splits(word, &bounds, &ends, &parts, 0, &mut path, &mut splits);
+        }
+        Ok(splits)
+    }
+}
+
+/// Adds the splits of `word[bounds[i]..]` into the fewest words to
+/// `splits`, after the words in `path`.
+fn collect_splits<'a>(
+    word: &'a str,
+    bounds: &[usize],
+    ends: &[Vec<usize>],
+    parts: &[Option<usize>],
+    i: usize,
+    path: &mut Vec<&'a str>,
+    splits: &mut Vec<String>,
+) {
+    if i == bounds.len() - 1 {
+        splits.push(path.join(" "));
+        return;
+    }
+    for &j in &ends[i] {
+        if splits.len() == SpellChecker::MAX_SPLIT_SUGGESTIONS {
+            return;
+        }
+        if parts[j].map(|p| p + 1) != parts[i] {
+            continue;
+        }
+        path.push(&word[bounds[i]..bounds[j]]);
+        collect_splits(word, bounds, ends, parts, j, path, splits);
+        path.pop();
+    }
+}
@@ -0,0 +1,165 @@
+//! The state of a `SpellChecker` as plain data, without a hunspell handle.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs::File,
+    path::PathBuf,
+    sync::Arc,
+};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    spell_checker::{RuntimeWord, Setup},
+    Dictionary, InputPolicy, Language, Normalization, Result, SpellChecker,
+};
+
+/// Everything a [`SpellChecker`] is made of: its files, key and language,
+/// the runtime and ignored words, the autocorrect pairs and the options,
+/// made with [`state()`](SpellChecker::state).
+///
+/// A serialized `SpellChecker` deserializes into a `SpellCheckerState`
+/// without opening the files, so settings saved on another machine can be
+/// read, their paths repaired, and only then be opened with
+/// [`open()`](SpellCheckerState::open). Deserializing a `SpellChecker`
+/// itself fails when the files do not exist.
+///
+/// ```
+/// use hunspell_rs::{SpellChecker, SpellCheckerState};
+///
+/// let spell = SpellChecker::new("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic").unwrap();
+/// spell.ignore("rustc");
+/// let mut state = spell.state();
+/// state.affix = "/no/such/dir/reduced.aff".into();
+///
+/// assert!(state.open().is_err());
+/// state.affix = "tests/fixtures/reduced.aff".into();
+/// let spell = state.open().unwrap();
+/// assert_eq!(Ok(true), spell.check("rustc"));
+/// ```
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(rename = "SpellChecker")
+)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpellCheckerState {
+    // First, so that sequence formats like bincode reject other versions
+    // before reading fields they would misread. Serializations from before
+    // version 3 have it elsewhere or not at all; they are only read from
+    // self-describing formats, with the defaults for the missing fields.
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            default = "crate::serde::first_version",
+            deserialize_with = "crate::serde::supported_version"
+        )
+    )]
+    pub(crate) version: u32,
+    pub affix: PathBuf,
+    pub dictionary: PathBuf,
+    pub additional_dictionaries: Vec<PathBuf>,
+    pub key: Option<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub language: Option<Language>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub runtime_words: Vec<RuntimeWord>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub ignored: BTreeSet<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub replacements: BTreeMap<String, String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub normalization: Normalization,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub max_suggest_length: Option<usize>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub input_policy: InputPolicy,
+}
+
+impl SpellCheckerState {
+    /// The version of the serialization this state was read from, or
+    /// [`SpellChecker::SERDE_VERSION`] when it was not read.
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    /// The files and key, to validate or open without the rest of the
+    /// state.
+    pub fn dictionary(&self) -> Dictionary {
+        let dictionary = self.additional_dictionaries.iter().fold(
+            Dictionary::from_files(&self.affix, &self.dictionary),
+            |d, a| d.with_additional_dictionary(a),
+        );
+        match &self.key {
+            Some(key) => dictionary.with_key(key),
+            None => dictionary,
+        }
+    }
+
+    /// Opens a new `SpellChecker` with its own handle and this state.
+    pub fn open(&self) -> Result<SpellChecker> {
+        self.open_with_memory_files(Vec::new())
+    }
+
+    /// Like `open()`, for files in `memory_files`, which the checker keeps
+    /// open.
+    pub(crate) fn open_with_memory_files(
+        &self,
+        memory_files: Vec<Arc<File>>,
+    ) -> Result<SpellChecker> {
+        let setup = Setup {
+            affix: self.affix.clone(),
+            dictionary: self.dictionary.clone(),
+            additional_dictionaries: self.additional_dictionaries.clone(),
+            key: self.key.clone(),
+            language: self.language.clone(),
+            runtime_words: self.runtime_words.clone(),
+            memory_files,
+        };
+        let mut spell_checker = setup.open()?;
+        for word in &self.ignored {
+            spell_checker.ignore(word);
+        }
+        for (from, to) in &self.replacements {
+            spell_checker.add_replacement(from, to);
+        }
+        spell_checker.set_normalization(self.normalization);
+        spell_checker.set_max_suggest_length(self.max_suggest_length);
+        spell_checker.set_input_policy(self.input_policy);
+        Ok(spell_checker)
+    }
+}
+
+impl SpellChecker {
+    /// The version of the serialized form of a `SpellChecker`. Version 1
+    /// had only the files, the key, the language and the runtime words;
+    /// version 2 added the ignored words, the autocorrect pairs and the
+    /// options; version 3 moved the version to the front. Newer versions
+    /// are rejected. Older versions are deserialized with the defaults for
+    /// what they miss from self-describing formats like JSON, but not from
+    /// sequence formats like bincode, which store no field names: their
+    /// blobs of before version 3 cannot be read.
+    pub const SERDE_VERSION: u32 = 3;
+
+    /// Returns the state of this checker, to open an equal checker later
+    /// or elsewhere. It is what a serialized `SpellChecker` contains.
+    /// Dictionaries loaded from memory are referred to by paths that are
+    /// only valid while this checker exists.
+    pub fn state(&self) -> SpellCheckerState {
+        SpellCheckerState {
+            version: SpellChecker::SERDE_VERSION,
+            affix: self.affix.clone(),
+            dictionary: self.dictionary.clone(),
+            additional_dictionaries: self.additional_dictionaries.clone(),
+            key: self.key.clone(),
+            language: self.language.clone(),
+            runtime_words: self.runtime_words.clone(),
+            ignored: self.ignored.borrow().iter().cloned().collect(),
+            replacements: self.replacements.borrow().clone(),
+            normalization: self.normalization,
+            max_suggest_length: self.max_suggest_length,
+            input_policy: self.input_policy,
+        }
+    }
+}
@@ -0,0 +1,152 @@
+//! Counters of the calls to hunspell, for services that export metrics.
+
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use crate::{instrument::FfiOperation, SpellChecker};
+
+/// The calls a [`SpellChecker`] made to hunspell since its statistics
+/// were enabled or reset, see [`SpellChecker::enable_stats()`]. Words that are ignored or answered
+/// without hunspell, i.e. by a cache, are not counted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Stats {
+    /// The number of words checked.
+    pub checks: u64,
+    /// The number of checked words that were rejected.
+    pub misses: u64,
+    /// The number of words suggestions were made for.
+    pub suggests: u64,
+    /// The number of suggestions made, over all words.
+    pub suggestions: u64,
+    /// The number of words analyzed.
+    pub analyses: u64,
+    /// The time spent in hunspell for all these calls.
+    pub ffi_time: Duration,
+}
+
+impl Stats {
+    /// Returns the share of the checked words that were rejected, 0 when
+    /// none were checked.
+    pub fn miss_rate(&self) -> f64 {
+        match self.checks {
+            0 => 0.0,
+            checks => self.misses as f64 / checks as f64,
+        }
+    }
+
+    /// Returns the average number of suggestions per word, 0 when there
+    /// were no suggestion calls.
+    pub fn suggestions_per_word(&self) -> f64 {
+        match self.suggests {
+            0 => 0.0,
+            suggests => self.suggestions as f64 / suggests as f64,
+        }
+    }
+}
+
+/// The counters behind [`Stats`], shared by the clones of a checker.
+#[derive(Debug, Default)]
+pub(crate) struct StatsCounters {
+    checks: AtomicU64,
+    misses: AtomicU64,
+    suggests: AtomicU64,
+    suggestions: AtomicU64,
+    analyses: AtomicU64,
+    ffi_nanos: AtomicU64,
+}
+
+impl StatsCounters {
+    /// Counts a call of `operation` that returned `results` in `elapsed`.
+    pub(crate) fn record(&self, operation: FfiOperation, results: usize, elapsed: Duration) {
+        let add = |counter: &AtomicU64, n: u64| counter.fetch_add(n, Ordering::Relaxed);
+        match operation {
+            FfiOperation::Check => {
+                add(&self.checks, 1);
+                add(&self.misses, u64::from(results == 0));
+            }
+            FfiOperation::Suggest => {
+                add(&self.suggests, 1);
+                add(&self.suggestions, results as u64);
+            }
+            FfiOperation::Analyze => {
+                add(&self.analyses, 1);
+            }
+            FfiOperation::Create => {}
+        }
+        add(
+            &self.ffi_nanos,
+            u64::try_from(elapsed.as_nanos()).unwrap_or(u64::MAX),
+        );
+    }
+
+    fn snapshot(&self) -> Stats {
+        let get = |counter: &AtomicU64| counter.load(Ordering::Relaxed);
+        Stats {
+            checks: get(&self.checks),
+            misses: get(&self.misses),
+            suggests: get(&self.suggests),
+            suggestions: get(&self.suggestions),
+            analyses: get(&self.analyses),
+            ffi_time: Duration::from_nanos(get(&self.ffi_nanos)),
+        }
+    }
+
+    fn reset(&self) {
+        for counter in [
+            &self.checks,
+            &self.misses,
+            &self.suggests,
+            &self.suggestions,
+            &self.analyses,
+            &self.ffi_nanos,
+        ] {
+            counter.store(0, Ordering::Relaxed);
+        }
+    }
+}
+
+impl SpellChecker {
+    /// Starts counting the calls to hunspell, see [`Stats`]. Clones made
+    /// from then on share the counters, so the counts of all clones add
+    /// up. Does nothing when the statistics are enabled.
+    ///
+    /// ```
+    /// use hunspell_rs::SpellChecker;
+    ///
+    /// let mut spell = SpellChecker::new("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic").unwrap();
+    /// spell.enable_stats();
+    /// spell.check("catz").unwrap();
+    /// spell.suggest("catz").unwrap();
+    /// let stats = spell.stats().unwrap();
+    /// assert_eq!((1, 1, 1), (stats.checks, stats.misses, stats.suggests));
+    /// spell.reset_stats();
+    /// assert_eq!(0, spell.stats().unwrap().checks);
+    /// ```
+    pub fn enable_stats(&mut self) {
+        if self.stats.is_none() {
+            self.stats = Some(Arc::default());
+        }
+    }
+
+    /// Stops counting, and forgets the counts.
+    pub fn disable_stats(&mut self) {
+        self.stats = None;
+    }
+
+    /// Returns the statistics, `None` when they are not enabled.
+    pub fn stats(&self) -> Option<Stats> {
+        self.stats.as_ref().map(|s| s.snapshot())
+    }
+
+    /// Sets all counts to 0, also those of the clones that share them.
+    pub fn reset_stats(&self) {
+        if let Some(stats) = &self.stats {
+            stats.reset();
+        }
+    }
+}
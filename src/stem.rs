@@ -0,0 +1,79 @@
+//! Stems together with the analyses they come from.
+
+use crate::{ffi, ffi_util::HunspellList, Result, SpellChecker};
+
+/// A stem of a word and the analyses that lead to it, see
+/// [`SpellChecker::stem_detailed()`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct StemDetails {
+    pub stem: String,
+    /// The analyses of the word with this stem, in the order of
+    /// `analyze()`. Empty when the stem only came from `stem()`.
+    pub analyses: Vec<String>,
+}
+
+impl SpellChecker {
+    /// Returns every stem of `word` once, with the analyses it came from.
+    /// The stems that more analyses agree on come first, ties are sorted
+    /// alphabetically, so the order does not depend on the hunspell
+    /// version. Stems that `stem()` finds without an analysis, i.e. for
+    /// dictionaries without morphological fields, come last.
+    ///
+    /// ```
+    /// use hunspell_rs::SpellChecker;
+    ///
+    /// let spell = SpellChecker::new("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic").unwrap();
+    /// let stems = spell.stem_detailed("cats").unwrap();
+    /// assert_eq!(1, stems.len());
+    /// assert_eq!("cat", stems[0].stem);
+    /// ```
+    pub fn stem_detailed<S>(&self, word: S) -> Result<Vec<StemDetails>>
+    where
+        S: AsRef<str>,
+    {
+        let word = word.as_ref();
+        let c_word = self.c_string(word)?;
+        let analyses = HunspellList::new(&self.handle, |handle, list| unsafe {
+            ffi::Hunspell_analyze(handle, list, c_word.as_ptr())
+        })?;
+        let (analyzed, _) = analyses.as_raw();
+        let analysis_texts = analyses.to_vec(self.codec)?;
+        let mut details: Vec<StemDetails> = Vec::new();
+        for (i, analysis) in analysis_texts.into_iter().enumerate() {
+            // SAFETY: i is below the length of the list
+            let one = unsafe { analyzed.add(i) };
+            let stems = HunspellList::new(&self.handle, |handle, list| unsafe {
+                ffi::Hunspell_stem2(handle, list, one, 1)
+            })?
+            .to_vec(self.codec)?;
+            for stem in stems {
+                match details.iter_mut().find(|d| d.stem == stem) {
+                    Some(d) if !d.analyses.contains(&analysis) => d.analyses.push(analysis.clone()),
+                    Some(_) => {}
+                    None => details.push(StemDetails {
+                        stem,
+                        analyses: vec![analysis.clone()],
+                    }),
+                }
+            }
+        }
+        details.sort_by(|a, b| {
+            b.analyses
+                .len()
+                .cmp(&a.analyses.len())
+                .then_with(|| a.stem.cmp(&b.stem))
+        });
+        let mut plain = self.stem(word)?;
+        plain.sort();
+        plain.dedup();
+        for stem in plain {
+            if !details.iter().any(|d| d.stem == stem) {
+                details.push(StemDetails {
+                    stem,
+                    analyses: Vec::new(),
+                });
+            }
+        }
+        Ok(details)
+    }
+}
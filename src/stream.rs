@@ -0,0 +1,119 @@
+//! Checking text from a reader without loading all of it.
+
+use std::{collections::VecDeque, io::BufRead};
+
+use crate::{text::check_plain_text, Misspelling, Result, SpellChecker};
+
+/// Text is checked anyway when it grows beyond this without white space,
+/// to bound the memory.
+const MAX_PENDING: usize = 64 * 1024;
+
+/// The misspellings of a reader, see [`SpellChecker::check_stream()`].
+#[derive(Debug)]
+pub struct MisspellingStream<'a, R> {
+    spell: &'a SpellChecker,
+    reader: R,
+    // read, but not yet checked
+    pending: Vec<u8>,
+    // the offset of pending in the stream
+    offset: usize,
+    found: VecDeque<Misspelling>,
+    done: bool,
+}
+
+impl SpellChecker {
+    /// Returns the misspelled words of the text of `reader`, like
+    /// [`check_text()`](SpellChecker::check_text). The text is read as
+    /// the iterator is advanced, and only what follows the last white
+    /// space is kept between reads, so files of any size can be checked.
+    /// The ranges of the misspellings are byte offsets in the stream.
+    /// Bytes that are not UTF-8 are skipped.
+    ///
+    /// ```
+    /// use hunspell_rs::SpellChecker;
+    ///
+    /// let spell = SpellChecker::new("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic").unwrap();
+    /// let log = "cats catz\nprograms programz\n".as_bytes();
+    /// let misspelled: Vec<String> = spell
+    ///     .check_stream(log)
+    ///     .map(|m| m.unwrap().word)
+    ///     .collect();
+    /// assert_eq!(vec!["catz", "programz"], misspelled);
+    /// ```
+    pub fn check_stream<R: BufRead>(&self, reader: R) -> MisspellingStream<'_, R> {
+        MisspellingStream {
+            spell: self,
+            reader,
+            pending: Vec::new(),
+            offset: 0,
+            found: VecDeque::new(),
+            done: false,
+        }
+    }
+}
+
+impl<R: BufRead> MisspellingStream<'_, R> {
+    /// Reads the next part of the stream and checks what is complete.
+    fn read(&mut self) -> Result<()> {
+        let buf = self.reader.fill_buf()?;
+        if buf.is_empty() {
+            self.done = true;
+            return self.check(self.pending.len());
+        }
+        self.pending.extend_from_slice(buf);
+        let read = buf.len();
+        self.reader.consume(read);
+        let end = self
+            .pending
+            .iter()
+            .rposition(u8::is_ascii_whitespace)
+            .map(|i| i + 1);
+        match end {
+            Some(end) => self.check(end),
+            None if self.pending.len() > MAX_PENDING => self.check(self.pending.len()),
+            None => Ok(()),
+        }
+    }
+
+    /// Checks the first `end` bytes of the pending text.
+    fn check(&mut self, end: usize) -> Result<()> {
+        let mut start = 0;
+        while start < end {
+            let (valid, invalid) = match std::str::from_utf8(&self.pending[start..end]) {
+                Ok(text) => (text, 0),
+                Err(e) => (
+                    std::str::from_utf8(&self.pending[start..start + e.valid_up_to()])
+                        .unwrap_or_default(),
+                    e.error_len().unwrap_or(end - start - e.valid_up_to()),
+                ),
+            };
+            let offset = self.offset + start;
+            let (misspellings, _) = check_plain_text(|word| self.spell.check(word), valid)?;
+            self.found
+                .extend(misspellings.into_iter().map(|m| Misspelling {
+                    word: m.word,
+                    range: m.range.start + offset..m.range.end + offset,
+                }));
+            start += valid.len() + invalid;
+        }
+        self.pending.drain(..end);
+        self.offset += end;
+        Ok(())
+    }
+}
+
+impl<R: BufRead> Iterator for MisspellingStream<'_, R> {
+    type Item = Result<Misspelling>;
+
+    /// Returns the next misspelling, or the error of the reader after which
+    /// the iterator ends.
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.found.is_empty() && !self.done {
+            if let Err(e) = self.read() {
+                self.done = true;
+                return Some(Err(e));
+            }
+        }
+        self.found.pop_front().map(Ok)
+    }
+}
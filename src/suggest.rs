@@ -0,0 +1,531 @@
+use std::{
+    ffi::CStr,
+    sync::{
+        mpsc::{self, Receiver, RecvTimeoutError, Sender},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+use crate::{
+    encoding::Codec,
+    ffi,
+    ffi_util::HunspellList,
+    instrument::{FfiCall, FfiOperation},
+    spell_checker::Setup,
+    Casing, CorrectionTable, Error, InputPolicy, Language, Normalization, Operation, Result,
+    SpellChecker,
+};
+
+/// Options for [`SpellChecker::suggest_with_options()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SuggestOptions {
+    /// Maximal number of suggestions returned.
+    pub max: usize,
+    /// Maximal time to wait for hunspell, `Duration::MAX` waits as long
+    /// as it takes.
+    pub timeout: Duration,
+    /// Drops suggestions that need more character edits than this,
+    /// ignoring case and the differences within the groups of the `MAP`
+    /// table. Hunspell's n-gram suggestions can be unrelated to the word,
+    /// especially for short words.
+    pub max_distance: Option<usize>,
+    /// What to suggest for words of at most
+    /// [`SHORT_WORD_LENGTH`](ShortWordPolicy::SHORT_WORD_LENGTH)
+    /// characters.
+    pub short_words: ShortWordPolicy,
+}
+
+/// Suggestions for short words are often poor and slow to make, see
+/// [`SuggestOptions::short_words`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum ShortWordPolicy {
+    /// The same as for other words.
+    #[default]
+    Normal,
+    /// Only suggestions one character edit away.
+    NearOnly,
+    /// No suggestions, hunspell is not asked.
+    Skip,
+}
+
+impl ShortWordPolicy {
+    /// Words of at most this many characters are short.
+    pub const SHORT_WORD_LENGTH: usize = 3;
+}
+
+impl Default for SuggestOptions {
+    fn default() -> Self {
+        SuggestOptions {
+            max: usize::MAX,
+            timeout: Duration::MAX,
+            max_distance: None,
+            short_words: ShortWordPolicy::Normal,
+        }
+    }
+}
+
+/// A word, where to send its suggestions, and when the caller stops
+/// waiting for them.
+type Request = (String, Sender<Result<Vec<String>>>, Instant);
+
+/// A thread with its own handle that makes suggestions, so that slow
+/// calls can be abandoned.
+#[derive(Debug)]
+pub(crate) struct SuggestWorker {
+    sender: Sender<Request>,
+    setup: Setup,
+    session: WorkerSession,
+}
+
+/// The options of the `SpellChecker` that change what its handle is
+/// given, copied to the worker's checker.
+#[derive(Debug, Clone)]
+struct WorkerSession {
+    normalization: Normalization,
+    input_policy: InputPolicy,
+    correction_table: Option<Arc<dyn CorrectionTable>>,
+}
+
+impl WorkerSession {
+    fn of(spell_checker: &SpellChecker) -> Self {
+        WorkerSession {
+            normalization: spell_checker.normalization,
+            input_policy: spell_checker.input_policy,
+            correction_table: spell_checker.correction_table.clone(),
+        }
+    }
+
+    fn apply(self, spell_checker: &mut SpellChecker) {
+        spell_checker.normalization = self.normalization;
+        spell_checker.input_policy = self.input_policy;
+        spell_checker.correction_table = self.correction_table;
+    }
+
+    fn is_current(&self, spell_checker: &SpellChecker) -> bool {
+        self.normalization == spell_checker.normalization
+            && self.input_policy == spell_checker.input_policy
+            && match (&self.correction_table, &spell_checker.correction_table) {
+                (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+                (a, b) => a.is_none() && b.is_none(),
+            }
+    }
+}
+
+impl SuggestWorker {
+    fn spawn(spell_checker: &SpellChecker) -> Result<SuggestWorker> {
+        let (sender, receiver) = mpsc::channel::<Request>();
+        let setup = spell_checker.setup();
+        let session = WorkerSession::of(spell_checker);
+        let (thread_setup, thread_session) = (setup.clone(), session.clone());
+        std::thread::Builder::new()
+            .name("hunspell-rs-suggest".to_string())
+            .spawn(move || Self::run(thread_setup, thread_session, receiver))?;
+        Ok(SuggestWorker {
+            sender,
+            setup,
+            session,
+        })
+    }
+
+    fn run(setup: Setup, session: WorkerSession, receiver: Receiver<Request>) {
+        let spell_checker = setup.open().map(|mut spell_checker| {
+            session.apply(&mut spell_checker);
+            spell_checker
+        });
+        // ends when the worker is dropped
+        while let Ok((word, reply, deadline)) = receiver.recv() {
+            // queued behind a call that timed out, nobody waits for it
+            if Instant::now() >= deadline {
+                continue;
+            }
+            let result = match &spell_checker {
+                Ok(spell_checker) => spell_checker.suggest(word),
+                Err(e) => Err(e.clone()),
+            };
+            // the caller might have stopped waiting
+            let _ = reply.send(result);
+        }
+    }
+
+    /// Returns true if the worker's checker was opened like
+    /// `spell_checker`, including the runtime words and the options that
+    /// change what hunspell is given.
+    fn is_current(&self, spell_checker: &SpellChecker) -> bool {
+        self.setup.runtime_words == spell_checker.runtime_words
+            && self.setup.additional_dictionaries == spell_checker.additional_dictionaries
+            && self.setup.language == spell_checker.language
+            && self.session.is_current(spell_checker)
+    }
+}
+
+impl SpellChecker {
+    /// Returns at most `options.max` suggested spellings within
+    /// `options.max_distance`, giving up after `options.timeout` with
+    /// `Error::Timeout`. Short words follow `options.short_words`, and
+    /// words beyond the
+    /// [`max_suggest_length()`](SpellChecker::max_suggest_length) get none.
+    ///
+    /// A hunspell call cannot be interrupted, so with a timeout the
+    /// suggestions are made on a helper thread that has its own handle,
+    /// created on first use (and again after words or dictionaries were
+    /// added, or the normalization, input policy or correction table
+    /// changed). When a call times out, the helper finishes it in the
+    /// background; later calls wait behind it, and calls whose caller
+    /// stopped waiting are skipped. So there is at most one helper per
+    /// checker, its handle costs as much memory as the `SpellChecker`
+    /// itself.
+    pub fn suggest_with_options<S>(&self, word: S, options: &SuggestOptions) -> Result<Vec<String>>
+    where
+        S: AsRef<str>,
+    {
+        if self.is_too_long_to_suggest(word.as_ref()) || self.is_rejected_input(word.as_ref()) {
+            return Ok(Vec::new());
+        }
+        let short = word.as_ref().chars().count() <= ShortWordPolicy::SHORT_WORD_LENGTH;
+        let max_distance = match options.short_words {
+            ShortWordPolicy::Skip if short => return Ok(Vec::new()),
+            ShortWordPolicy::NearOnly if short => {
+                Some(options.max_distance.map_or(1, |d| d.min(1)))
+            }
+            _ => options.max_distance,
+        };
+        let mut suggestions = if options.timeout == Duration::MAX {
+            self.suggest(word.as_ref())?
+        } else {
+            let mut suggestions = self.suggest_on_worker(word.as_ref(), options.timeout)?;
+            self.prepend_replacements(word.as_ref(), &mut suggestions)?;
+            self.filter_suggestions(word.as_ref(), &mut suggestions);
+            suggestions
+        };
+        if let Some(max_distance) = max_distance {
+            let word = word.as_ref().to_lowercase();
+            let char_map = self.char_map();
+            suggestions.retain(|s| char_map.distance(&word, &s.to_lowercase()) <= max_distance);
+        }
+        suggestions.truncate(options.max);
+        Ok(suggestions)
+    }
+
+    fn suggest_on_worker(&self, word: &str, timeout: Duration) -> Result<Vec<String>> {
+        let mut worker = self.suggest_worker.borrow_mut();
+        if !worker.as_ref().is_some_and(|w| w.is_current(self)) {
+            *worker = Some(SuggestWorker::spawn(self)?);
+        }
+        let (reply, receiver) = mpsc::channel();
+        let deadline = Instant::now()
+            .checked_add(timeout)
+            .unwrap_or_else(|| Instant::now() + Duration::from_secs(u32::MAX.into()));
+        if let Some(worker) = worker.as_ref() {
+            // on failure the reply sender is dropped, handled below
+            let _ = worker.sender.send((word.to_string(), reply, deadline));
+        }
+        match receiver.recv_timeout(timeout) {
+            Ok(result) => result,
+            // the busy worker is kept, it skips the calls nobody waits for
+            Err(RecvTimeoutError::Timeout) => Err(Error::Timeout(timeout)),
+            Err(RecvTimeoutError::Disconnected) => {
+                *worker = None;
+                Err(Error::WorkerStopped)
+            }
+        }
+    }
+}
+
+/// The function of [`SpellChecker::set_suggestion_filter()`].
+pub(crate) type SuggestionFilter = dyn Fn(&str, Vec<String>) -> Vec<String> + Send + Sync;
+
+impl SpellChecker {
+    /// Passes every list of suggestions of `suggest()`, and so of the
+    /// methods built on it, to `filter` with the misspelled word, and
+    /// returns what it returns instead. The filter can drop offensive
+    /// words, replace spellings a style guide does not want or add
+    /// preferred terms, in one place. It also sees the empty list of a
+    /// word longer than the
+    /// [`max_suggest_length()`](SpellChecker::max_suggest_length). Clones
+    /// share the filter. `suggest_iter()` returns the suggestions of
+    /// hunspell as they are.
+    ///
+    /// ```
+    /// use hunspell_rs::SpellChecker;
+    ///
+    /// let mut spell = SpellChecker::new("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic").unwrap();
+    /// spell.set_suggestion_filter(|_, mut suggestions| {
+    ///     suggestions.retain(|s| s != "cat");
+    ///     suggestions
+    /// });
+    /// assert!(!spell.suggest("catz").unwrap().contains(&"cat".to_string()));
+    /// ```
+    pub fn set_suggestion_filter<F>(&mut self, filter: F)
+    where
+        F: Fn(&str, Vec<String>) -> Vec<String> + Send + Sync + 'static,
+    {
+        self.suggestion_filter = Some(Arc::new(filter));
+    }
+
+    /// Removes the filter of
+    /// [`set_suggestion_filter()`](SpellChecker::set_suggestion_filter).
+    pub fn clear_suggestion_filter(&mut self) {
+        self.suggestion_filter = None;
+    }
+
+    pub fn has_suggestion_filter(&self) -> bool {
+        self.suggestion_filter.is_some()
+    }
+
+    /// Replaces `suggestions` by what the suggestion filter makes of them.
+    pub(crate) fn filter_suggestions(&self, word: &str, suggestions: &mut Vec<String>) {
+        if let Some(filter) = &self.suggestion_filter {
+            *suggestions = filter(word, std::mem::take(suggestions));
+        }
+    }
+}
+
+/// A suggested spelling with how it relates to the misspelled word, see
+/// [`SpellChecker::suggest_classified()`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Suggestion {
+    pub word: String,
+    pub kind: SuggestionKind,
+    pub source: SuggestionSource,
+}
+
+/// Where a [`Suggestion`] comes from, so they can be grouped or labeled.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SuggestionSource {
+    /// Hunspell, with the language of the dictionary. With a
+    /// [`MultiLanguageChecker`](crate::MultiLanguageChecker) this tells
+    /// the languages apart.
+    Hunspell(Option<Language>),
+    /// Made by the application, with a name for the source.
+    Custom(String),
+}
+
+/// How a [`Suggestion`] differs from the misspelled word.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SuggestionKind {
+    /// Only the capitalization differs, i.e. `paris` -> `Paris`.
+    CaseFix,
+    /// The word is split in several words, i.e. `nocats` -> `no cats`.
+    Split,
+    /// The same stem with a different affix, i.e. `catz` -> `cats`.
+    AffixVariant,
+    /// Any other correction, with the number of character edits
+    /// (insertions, deletions, substitutions and transpositions). Swapping
+    /// characters of the same `MAP` group is not counted.
+    EditDistance(usize),
+}
+
+impl SpellChecker {
+    /// Returns the suggested spellings, each classified by how it differs
+    /// from `word`.
+    pub fn suggest_classified<S>(&self, word: S) -> Result<Vec<Suggestion>>
+    where
+        S: AsRef<str>,
+    {
+        let word = word.as_ref();
+        self.suggest(word)?
+            .into_iter()
+            .map(|suggestion| {
+                let kind = self.classify(word, &suggestion)?;
+                Ok(Suggestion {
+                    word: suggestion,
+                    kind,
+                    source: SuggestionSource::Hunspell(self.language.clone()),
+                })
+            })
+            .collect()
+    }
+
+    fn classify(&self, word: &str, suggestion: &str) -> Result<SuggestionKind> {
+        if suggestion.to_lowercase() == word.to_lowercase() {
+            return Ok(SuggestionKind::CaseFix);
+        }
+        if suggestion.contains(' ') {
+            return Ok(SuggestionKind::Split);
+        }
+        let word_stems = self.stem(word)?;
+        let shares_stem = self.stem(suggestion)?.iter().any(|s| {
+            word_stems.contains(s) || (s.chars().count() > 1 && word.starts_with(s.as_str()))
+        });
+        if shares_stem {
+            return Ok(SuggestionKind::AffixVariant);
+        }
+        Ok(SuggestionKind::EditDistance(
+            self.char_map().distance(word, suggestion),
+        ))
+    }
+}
+
+impl SpellChecker {
+    /// Returns the suggestions for `word` one by one, converting them to
+    /// `String`s only when asked. The list made by hunspell is freed when
+    /// the iterator is dropped.
+    ///
+    /// ```
+    /// use hunspell_rs::SpellChecker;
+    ///
+    /// let spell = SpellChecker::new("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic").unwrap();
+    /// let best = spell.suggest_iter("catz").unwrap().next();
+    /// assert_eq!(Some(Ok("cat".to_string())), best);
+    /// ```
+    pub fn suggest_iter<S>(&self, word: S) -> Result<SuggestIter<'_>>
+    where
+        S: AsRef<str>,
+    {
+        if self.is_rejected_input(word.as_ref()) {
+            return Ok(SuggestIter {
+                list: HunspellList::new(&self.handle, |_, _| 0)?,
+                codec: self.codec,
+                next: 0,
+            });
+        }
+        let list = self.with_c_word(word.as_ref(), |word| {
+            let call = FfiCall::start(
+                FfiOperation::Suggest,
+                word.count_bytes(),
+                self.stats.as_deref(),
+            );
+            let list = HunspellList::new(&self.handle, |handle, list| unsafe {
+                ffi::Hunspell_suggest(handle, list, word.as_ptr())
+            });
+            call.finish(list.as_ref().map_or(0, HunspellList::len));
+            list
+        })??;
+        Ok(SuggestIter {
+            list,
+            codec: self.codec,
+            next: 0,
+        })
+    }
+
+    /// Returns the first `n` suggestions of `suggest()`, for lists that
+    /// only show a few. Cheaper than `suggest()` and truncating: the rest
+    /// of hunspell's list is freed without being converted to `String`s.
+    /// When the [suggestion filter](SpellChecker::set_suggestion_filter)
+    /// drops some, more of the list is converted and filtered again until
+    /// `n` are left or the list runs out.
+    ///
+    /// ```
+    /// use hunspell_rs::SpellChecker;
+    ///
+    /// let spell = SpellChecker::new("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic").unwrap();
+    /// assert_eq!(vec!["cat"], spell.suggest_top("catz", 3).unwrap());
+    /// ```
+    pub fn suggest_top<S>(&self, word: S, n: usize) -> Result<Vec<String>>
+    where
+        S: AsRef<str>,
+    {
+        let word = word.as_ref();
+        let mut iter = if n == 0 || self.is_too_long_to_suggest(word) {
+            None
+        } else {
+            Some(
+                self.suggest_iter(word)
+                    .map_err(|e| e.during(Operation::Suggest, word))?,
+            )
+        };
+        let mut converted = Vec::new();
+        let mut wanted = n;
+        loop {
+            if let Some(iter) = &mut iter {
+                for suggestion in iter.take(wanted - converted.len()) {
+                    converted.push(suggestion.map_err(|e| e.during(Operation::Suggest, word))?);
+                }
+            }
+            let mut suggestions = converted.clone();
+            self.prepend_replacements(word, &mut suggestions)?;
+            self.filter_suggestions(word, &mut suggestions);
+            // the list ran out when it had fewer than wanted
+            if suggestions.len() >= n || converted.len() < wanted {
+                suggestions.truncate(n);
+                return Ok(suggestions);
+            }
+            wanted += n - suggestions.len();
+        }
+    }
+
+    /// Returns the lowercase, titlecase and uppercase forms of `word` that
+    /// are correct, in that order. Empty when `word` is correct itself,
+    /// or when the error is more than capitalization. Only `check()` is
+    /// called, which is far cheaper than `suggest()`, so capitalization
+    /// errors can be fixed without a search for suggestions.
+    ///
+    /// ```
+    /// use hunspell_rs::SpellChecker;
+    ///
+    /// let spell = SpellChecker::new("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic").unwrap();
+    /// let mut suggestions = spell.case_suggestions("cAT").unwrap();
+    /// if suggestions.is_empty() {
+    ///     suggestions = spell.suggest("cAT").unwrap();
+    /// }
+    /// assert_eq!(vec!["cat", "Cat", "CAT"], suggestions);
+    /// assert!(spell.case_suggestions("catz").unwrap().is_empty());
+    /// ```
+    pub fn case_suggestions<S>(&self, word: S) -> Result<Vec<String>>
+    where
+        S: AsRef<str>,
+    {
+        let word = word.as_ref();
+        if self.check(word)? {
+            return Ok(Vec::new());
+        }
+        let lower = word.to_lowercase();
+        let variants = [Casing::Title.apply(&lower), word.to_uppercase()];
+        let mut suggestions: Vec<String> = Vec::new();
+        for variant in std::iter::once(lower).chain(variants) {
+            if suggestions.contains(&variant) {
+                continue;
+            }
+            if self.check(&variant)? {
+                suggestions.push(variant);
+            }
+        }
+        Ok(suggestions)
+    }
+}
+
+/// The suggestions made by [`SpellChecker::suggest_iter()`].
+///
+/// [`next_cstr()`](SuggestIter::next_cstr) borrows the next suggestion
+/// from hunspell without any allocation.
+#[derive(Debug)]
+pub struct SuggestIter<'a> {
+    list: HunspellList<'a>,
+    codec: Codec,
+    next: usize,
+}
+
+impl SuggestIter<'_> {
+    /// Returns the next suggestion as hunspell made it, in the encoding of
+    /// the dictionary.
+    pub fn next_cstr(&mut self) -> Option<&CStr> {
+        if self.next >= self.list.len() {
+            return None;
+        }
+        self.next += 1;
+        self.list.get(self.next - 1)
+    }
+}
+
+impl Iterator for SuggestIter<'_> {
+    type Item = Result<String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next >= self.list.len() {
+            return None;
+        }
+        let codec = self.codec;
+        Some(match self.next_cstr() {
+            Some(s) => codec.decode(s.to_bytes()).map(|s| s.into_owned()),
+            None => Err(Error::NullPtr),
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.list.len() - self.next;
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for SuggestIter<'_> {}
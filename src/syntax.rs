@@ -0,0 +1,425 @@
+//! Skipping markup when checking documents.
+
+use std::ops::Range;
+
+/// Finds the prose in a document, so that [`TextChecker`](crate::TextChecker)
+/// only checks that and skips the markup. The ranges refer to the
+/// original text, so the ranges of the misspellings do as well.
+///
+/// Implement it for other formats:
+///
+/// ```
+/// use std::ops::Range;
+/// use hunspell_rs::{SpellChecker, SyntaxFilter, TextChecker};
+///
+/// /// Skips `{{ template tags }}`.
+/// #[derive(Debug)]
+/// struct Templates;
+///
+/// impl SyntaxFilter for Templates {
+///     fn prose_regions(&self, text: &str) -> Vec<Range<usize>> {
+///         let mut regions = Vec::new();
+///         let mut start = 0;
+///         while let Some(open) = text[start..].find("{{") {
+///             regions.push(start..start + open);
+///             start = text[start + open..].find("}}").map_or(text.len(), |c| start + open + c + 2);
+///         }
+///         regions.push(start..text.len());
+///         regions
+///     }
+/// }
+///
+/// let spell = SpellChecker::new("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic").unwrap();
+/// let checker = TextChecker::new(spell).with_syntax_filter(Templates);
+/// assert!(checker.check_text("cats {{ catz }} cats").unwrap().is_empty());
+/// ```
+pub trait SyntaxFilter: std::fmt::Debug {
+    /// Returns the byte ranges of `text` that are prose.
+    fn prose_regions(&self, text: &str) -> Vec<Range<usize>>;
+
+    /// Returns the byte ranges of `text` that are headings or titles.
+    /// Capitalized words in headings are accepted when the lowercase word
+    /// is correct, so `Title Case` needs no extra dictionary entries.
+    fn heading_regions(&self, _text: &str) -> Vec<Range<usize>> {
+        Vec::new()
+    }
+}
+
+/// Checks everything.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct PlainText;
+
+impl SyntaxFilter for PlainText {
+    fn prose_regions(&self, text: &str) -> Vec<Range<usize>> {
+        std::iter::once(0..text.len()).collect()
+    }
+}
+
+/// Skips TeX commands, math and comments, like `hunspell -t`. The
+/// arguments of commands like `\ref`, `\cite` and `\begin` are skipped
+/// as well.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Latex;
+
+impl SyntaxFilter for Latex {
+    fn prose_regions(&self, text: &str) -> Vec<Range<usize>> {
+        outside(text, latex_markup(text))
+    }
+}
+
+/// Skips code blocks, inline code, link targets and HTML tags. Headings
+/// are `#` and underlined (setext) headings.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Markdown;
+
+impl SyntaxFilter for Markdown {
+    fn prose_regions(&self, text: &str) -> Vec<Range<usize>> {
+        outside(text, markdown_markup(text))
+    }
+
+    fn heading_regions(&self, text: &str) -> Vec<Range<usize>> {
+        let mut headings = Vec::new();
+        let mut previous: Option<Range<usize>> = None;
+        let mut start = 0;
+        for line in text.split_inclusive('\n') {
+            let range = start..start + line.trim_end().len();
+            let trimmed = line.trim();
+            if trimmed.starts_with('#') {
+                headings.push(range.clone());
+            } else if !trimmed.is_empty()
+                && (trimmed.bytes().all(|b| b == b'=') || trimmed.bytes().all(|b| b == b'-'))
+            {
+                headings.extend(previous.take());
+            }
+            previous = (!trimmed.is_empty()).then_some(range);
+            start += line.len();
+        }
+        headings
+    }
+}
+
+/// Skips tags, comments, entities and the contents of `script`, `style`,
+/// `code` and `pre` elements. Headings are the `h1` to `h6` and `title`
+/// elements.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Html;
+
+impl SyntaxFilter for Html {
+    fn prose_regions(&self, text: &str) -> Vec<Range<usize>> {
+        outside(text, html_markup(text))
+    }
+
+    fn heading_regions(&self, text: &str) -> Vec<Range<usize>> {
+        let lowercase = text.to_ascii_lowercase();
+        let mut headings = Vec::new();
+        for name in ["h1", "h2", "h3", "h4", "h5", "h6", "title"] {
+            let open = format!("<{name}");
+            let close = format!("</{name}");
+            let mut from = 0;
+            while let Some(start) = find(&lowercase, from, &open) {
+                let end = find(&lowercase, start, &close).unwrap_or(text.len());
+                headings.push(start..end);
+                from = end;
+            }
+        }
+        headings
+    }
+}
+
+/// Returns the parts of `text` not covered by `markup`.
+fn outside(text: &str, mut markup: Vec<Range<usize>>) -> Vec<Range<usize>> {
+    markup.sort_by_key(|r| r.start);
+    let mut prose = Vec::new();
+    let mut start = 0;
+    for range in markup {
+        if range.start > start {
+            prose.push(start..range.start);
+        }
+        start = start.max(range.end);
+    }
+    if start < text.len() {
+        prose.push(start..text.len());
+    }
+    prose
+}
+
+/// Commands whose first argument is not prose.
+const LATEX_ARGUMENT_COMMANDS: &[&str] = &[
+    "begin",
+    "end",
+    "ref",
+    "eqref",
+    "pageref",
+    "autoref",
+    "cref",
+    "Cref",
+    "label",
+    "cite",
+    "citep",
+    "citet",
+    "nocite",
+    "url",
+    "href",
+    "input",
+    "include",
+    "includegraphics",
+    "usepackage",
+    "documentclass",
+    "bibliography",
+    "bibliographystyle",
+    "newcommand",
+    "renewcommand",
+    "setlength",
+];
+
+/// Environments with math.
+const LATEX_MATH_ENVIRONMENTS: &[&str] = &[
+    "math",
+    "displaymath",
+    "equation",
+    "equation*",
+    "align",
+    "align*",
+    "eqnarray",
+    "eqnarray*",
+    "gather",
+    "gather*",
+    "multline",
+    "multline*",
+];
+
+fn latex_markup(text: &str) -> Vec<Range<usize>> {
+    let bytes = text.as_bytes();
+    let mut markup = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'%' => {
+                let end = find(text, i, "\n").unwrap_or(text.len());
+                markup.push(i..end);
+                i = end;
+            }
+            b'$' => {
+                let delimiter = if text[i..].starts_with("$$") {
+                    "$$"
+                } else {
+                    "$"
+                };
+                let end = find_unescaped(text, i + delimiter.len(), delimiter)
+                    .map_or(text.len(), |e| e + delimiter.len());
+                markup.push(i..end);
+                i = end;
+            }
+            b'\\' => {
+                let end = latex_command_end(text, i);
+                markup.push(i..end);
+                i = end;
+            }
+            _ => i += 1,
+        }
+    }
+    markup
+}
+
+/// Returns the end of the command, math or environment starting with the
+/// backslash at `start`.
+fn latex_command_end(text: &str, start: usize) -> usize {
+    let bytes = text.as_bytes();
+    let name_start = start + 1;
+    let mut end = name_start;
+    while end < bytes.len() && bytes[end].is_ascii_alphabetic() {
+        end += 1;
+    }
+    if end == name_start {
+        // \( math \), \[ math \] or an escaped character
+        return match bytes.get(name_start) {
+            Some(b'(') => find(text, name_start, "\\)").map_or(text.len(), |e| e + 2),
+            Some(b'[') => find(text, name_start, "\\]").map_or(text.len(), |e| e + 2),
+            Some(c) if c.is_ascii() => name_start + 1,
+            _ => name_start,
+        };
+    }
+    let name = &text[name_start..end];
+    if bytes.get(end) == Some(&b'*') {
+        end += 1;
+    }
+    if !LATEX_ARGUMENT_COMMANDS.contains(&name) {
+        return end;
+    }
+    // optional arguments, then the first argument
+    let mut arg_start = skip_spaces(text, end);
+    while bytes.get(arg_start) == Some(&b'[') {
+        end = braced_end(text, arg_start, b'[', b']');
+        arg_start = skip_spaces(text, end);
+    }
+    if bytes.get(arg_start) != Some(&b'{') {
+        return end;
+    }
+    end = braced_end(text, arg_start, b'{', b'}');
+    if name == "begin" {
+        let environment = text[arg_start + 1..end].trim_end_matches('}');
+        if LATEX_MATH_ENVIRONMENTS.contains(&environment) {
+            let end_tag = format!("\\end{{{environment}}}");
+            return find(text, end, &end_tag).map_or(text.len(), |e| e + end_tag.len());
+        }
+    }
+    end
+}
+
+/// Returns the position after the bracket closing the one at `start`.
+fn braced_end(text: &str, start: usize, open: u8, close: u8) -> usize {
+    let bytes = text.as_bytes();
+    let mut depth = 0;
+    let mut i = start;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 1,
+            c if c == open => depth += 1,
+            c if c == close => {
+                depth -= 1;
+                if depth == 0 {
+                    return i + 1;
+                }
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    text.len()
+}
+
+fn skip_spaces(text: &str, start: usize) -> usize {
+    start
+        + text[start..]
+            .bytes()
+            .take_while(|b| b.is_ascii_whitespace())
+            .count()
+}
+
+fn find(text: &str, start: usize, pattern: &str) -> Option<usize> {
+    text[start..].find(pattern).map(|p| p + start)
+}
+
+/// Like `find()`, skipping occurrences preceded by a backslash.
+fn find_unescaped(text: &str, start: usize, pattern: &str) -> Option<usize> {
+    let mut from = start;
+    loop {
+        let found = find(text, from, pattern)?;
+        if found == 0 || text.as_bytes()[found - 1] != b'\\' {
+            return Some(found);
+        }
+        from = found + 1;
+    }
+}
+
+fn markdown_markup(text: &str) -> Vec<Range<usize>> {
+    let mut markup = Vec::new();
+    // fenced code blocks
+    let mut fence: Option<(usize, &str)> = None;
+    let mut line_start = 0;
+    for line in text.split_inclusive('\n') {
+        let trimmed = line.trim_start();
+        let marker = ["```", "~~~"].into_iter().find(|m| trimmed.starts_with(m));
+        match (fence, marker) {
+            (None, Some(marker)) => fence = Some((line_start, marker)),
+            (Some((start, open)), Some(marker)) if marker == open => {
+                markup.push(start..line_start + line.len());
+                fence = None;
+            }
+            _ => {}
+        }
+        line_start += line.len();
+    }
+    if let Some((start, _)) = fence {
+        markup.push(start..text.len());
+    }
+
+    let bytes = text.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        if markup.iter().any(|r| r.contains(&i)) {
+            i += 1;
+            continue;
+        }
+        let end = match bytes[i] {
+            b'`' => {
+                let ticks = text[i..].bytes().take_while(|&b| b == b'`').count();
+                let delimiter = &text[i..i + ticks];
+                find(text, i + ticks, delimiter).map(|e| e + ticks)
+            }
+            // link and image targets
+            b']' if bytes.get(i + 1) == Some(&b'(') => Some(braced_end(text, i + 1, b'(', b')')),
+            // reference definitions, [label]: target
+            b']' if bytes.get(i + 1) == Some(&b':') => {
+                Some(find(text, i, "\n").unwrap_or(text.len()))
+            }
+            // autolinks and HTML
+            b'<' if bytes
+                .get(i + 1)
+                .is_some_and(|b| b.is_ascii_alphabetic() || *b == b'/' || *b == b'!') =>
+            {
+                find(text, i, ">").map(|e| e + 1)
+            }
+            _ => None,
+        };
+        match end {
+            Some(end) => {
+                markup.push(i..end);
+                i = end;
+            }
+            None => i += 1,
+        }
+    }
+    markup
+}
+
+/// Elements whose contents are not prose.
+const HTML_CODE_ELEMENTS: &[&str] = &["script", "style", "code", "pre"];
+
+fn html_markup(text: &str) -> Vec<Range<usize>> {
+    let bytes = text.as_bytes();
+    let mut markup = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        let end = match bytes[i] {
+            b'<' if text[i..].starts_with("<!--") => {
+                find(text, i, "-->").map_or(text.len(), |e| e + 3)
+            }
+            b'<' => {
+                let tag_end = find(text, i, ">").map_or(text.len(), |e| e + 1);
+                let name: String = text[i + 1..tag_end]
+                    .chars()
+                    .take_while(|c| c.is_ascii_alphanumeric())
+                    .collect::<String>()
+                    .to_ascii_lowercase();
+                if HTML_CODE_ELEMENTS.contains(&name.as_str()) {
+                    let close = format!("</{name}");
+                    let lowercase = text.to_ascii_lowercase();
+                    find(&lowercase, tag_end, &close)
+                        .and_then(|c| find(text, c, ">"))
+                        .map_or(text.len(), |e| e + 1)
+                } else {
+                    tag_end
+                }
+            }
+            b'&' => {
+                let entity = text[i + 1..]
+                    .bytes()
+                    .take_while(|b| b.is_ascii_alphanumeric() || *b == b'#')
+                    .count();
+                if bytes.get(i + 1 + entity) == Some(&b';') {
+                    i + entity + 2
+                } else {
+                    i + 1
+                }
+            }
+            _ => {
+                i += 1;
+                continue;
+            }
+        };
+        markup.push(i..end);
+        i = end;
+    }
+    markup
+}
@@ -14,24 +14,45 @@
 //   See the License for the specific language governing permissions and
 //   limitations under the License.
 
-use crate::SpellChecker;
+use std::ffi::CString;
+
+use crate::{
+    aff::FlagType, apply_suggestion, available_dictionaries_in, build_info, catch_panic,
+    compare_spellers, ffi, validate_dictionary, BatchOptions, CacheStats, CachedSpellChecker,
+    CasePolicy, Casing, CheckDetails, CheckOptions, CheckerChain, CheckerRegistry, CompoundSegment,
+    Config, CorrectionAction, CorrectionSession, Diagnostic, Diagnostics, Dictionary,
+    DictionaryBuilder, Divergence, DocumentChecker, EditKind, Error, ExtraWordPolicy,
+    FallbackChecker, FileStorage, FirstWins, FrozenSpellChecker, GrammarIssue, GrammarOptions,
+    GrammarRule, HighlightWriter, Html, IgnoreRules, InputPolicy, Interleave, InvisibleChars,
+    KeyboardLayout, Language, LanguageCount, LanguageResolver, Latex, LintIssue, LintKind,
+    Markdown, MultiLanguageChecker, Normalization, NulHandling, NullSpellChecker, Operation,
+    PanicBoundary, PersonalDictionary, RankingOptions, RejectionKind, Report, ResultCache,
+    SampleOptions, SampleUnit, ScoreBased, ShortWordPolicy, SpellChecker, Speller, Stats,
+    StemDetails, SuggestOptions, Suggestion, SuggestionArbiter, SuggestionCase, SuggestionEdit,
+    SuggestionKind, SuggestionMismatch, SuggestionPage, SuggestionSource, TextChecker, UserProfile,
+    ValidationLimits, WordForms, WordSource,
+};
+
+/// Opens the dictionary most tests use.
+fn open_reduced() -> SpellChecker {
+    SpellChecker::new("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic").unwrap()
+}
 
 #[test]
 fn create_and_destroy() {
-    let _hs =
-        SpellChecker::new("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic").unwrap();
+    let _hs = open_reduced();
 }
 
 #[test]
 fn check() {
-    let hs = SpellChecker::new("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic").unwrap();
+    let hs = open_reduced();
     assert_eq!(Ok(true), hs.check("cats"));
     assert_eq!(Ok(false), hs.check("nocats"));
 }
 
 #[test]
 fn spell_with_add_and_remove() {
-    let hs = SpellChecker::new("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic").unwrap();
+    let mut hs = open_reduced();
     assert_eq!(Ok(true), hs.check("cats"));
     assert_eq!(Ok(false), hs.check("octonasaurius"));
     assert_eq!(Ok(()), hs.add("octonasaurius"));
@@ -42,7 +63,7 @@ fn spell_with_add_and_remove() {
 
 #[test]
 fn spell_with_add_with_affix() {
-    let hs = SpellChecker::new("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic").unwrap();
+    let mut hs = open_reduced();
     assert_eq!(Ok(true), hs.check("cats"));
     assert_eq!(Ok(false), hs.check("rusts"));
     assert_eq!(Ok(()), hs.add_with_affix("rust", "cat"));
@@ -51,8 +72,7 @@ fn spell_with_add_with_affix() {
 
 #[test]
 fn spell_with_extra_dic() {
-    let mut hs =
-        SpellChecker::new("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic").unwrap();
+    let mut hs = open_reduced();
     assert_eq!(Ok(true), hs.check("cats"));
     assert_eq!(Ok(false), hs.check("systemdunits"));
     assert_eq!(Ok(true), hs.add_dictionary("tests/fixtures/extra.dic"));
@@ -62,24 +82,4783 @@ fn spell_with_extra_dic() {
 
 #[test]
 fn suggest() {
-    let hs = SpellChecker::new("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic").unwrap();
-    assert!(hs.suggest("progra").unwrap().len() > 0);
+    let hs = open_reduced();
+    assert!(!hs.suggest("progra").unwrap().is_empty());
 }
 
 #[test]
 fn stem() {
-    let hs = SpellChecker::new("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic").unwrap();
+    let hs = open_reduced();
     let cat_stem = hs.stem("cats").unwrap();
     assert!(cat_stem[0] == "cat");
+    assert_eq!(Ok(Vec::new()), hs.stem("nocats"));
+}
+
+// Every list made by hunspell is freed exactly once. Miri cannot run
+// hunspell, check for leaks and double frees with
+// `valgrind --leak-check=full target/debug/deps/hunspell_rs-* list_ownership`.
+#[test]
+fn list_ownership() {
+    let hs = open_reduced();
+    for _ in 0..200 {
+        assert_eq!(Ok(vec!["cat".to_string()]), hs.stem("cats"));
+        assert_eq!(Ok(vec!["cat".to_string()]), hs.extended_stem("cats"));
+        assert_eq!(Ok(Vec::new()), hs.stem("qqqqqqqq"));
+        assert_eq!(Ok(Vec::new()), hs.extended_stem("qqqqqqqq"));
+        assert!(!hs.analyze("cats").unwrap().is_empty());
+        assert!(hs.generate("program", "cats").is_ok());
+        assert!(hs.extended_generate("program", "cats").is_ok());
+        assert_eq!(Ok(vec!["cat".to_string()]), hs.suggest("catz"));
+        assert_eq!(
+            Some(Ok("cat".to_string())),
+            hs.suggest_iter("catz").unwrap().next()
+        );
+    }
 }
 
 #[test]
 #[cfg(feature = "serde")]
 fn serde() {
-    let hs = SpellChecker::new("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic").unwrap();
+    let mut hs = open_reduced();
+    hs.set_language("en_GB".parse().unwrap());
+    hs.add("octonasaurius").unwrap();
+    hs.add_with_affix("rust", "cat").unwrap();
     let serialized: Vec<u8> = bincode::serialize(&hs).unwrap();
     let deserialized: SpellChecker = bincode::deserialize(&serialized[..]).unwrap();
-    assert_eq!(hs.affix(), deserialized.affix());
+    assert_eq!(hs, deserialized);
+    assert_eq!(hs.language(), deserialized.language());
     let cat_stem = deserialized.stem("cats").unwrap();
     assert!(cat_stem[0] == "cat");
+    assert_eq!(Ok(true), deserialized.check("octonasaurius"));
+    assert_eq!(Ok(true), deserialized.check("rusts"));
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn serde_session_state() {
+    let mut hs = open_reduced();
+    hs.add_dictionary("tests/fixtures/extra.dic").unwrap();
+    hs.ignore("rustc");
+    hs.add_replacement("teh", "the");
+    hs.set_normalization(Normalization::none());
+    hs.set_max_suggest_length(Some(20));
+    hs.set_input_policy(InputPolicy::untrusted());
+    let bytes: Vec<u8> = bincode::serialize(&hs).unwrap();
+    let json = serde_json::to_string(&hs).unwrap();
+    for restored in [
+        bincode::deserialize::<SpellChecker>(&bytes).unwrap(),
+        serde_json::from_str::<SpellChecker>(&json).unwrap(),
+    ] {
+        assert_eq!(hs, restored);
+        assert_eq!(Ok(true), restored.check("systemdunits"));
+        assert!(restored.is_ignored("rustc"));
+        assert_eq!(Some("the".to_string()), restored.replacement("teh"));
+        assert_eq!(Normalization::none(), restored.normalization());
+        assert_eq!(Some(20), restored.max_suggest_length());
+        assert_eq!(InputPolicy::untrusted(), restored.input_policy());
+    }
+
+    // version 1 had no version and no options
+    let old = r#"{"affix":"tests/fixtures/reduced.aff","dictionary":"tests/fixtures/reduced.dic","additional_dictionaries":[],"key":null}"#;
+    let restored: SpellChecker = serde_json::from_str(old).unwrap();
+    assert_eq!(Normalization::default(), restored.normalization());
+    assert!(!restored.is_ignored("rustc"));
+    let newer = json.replace(r#""version":3"#, r#""version":4"#);
+    assert!(serde_json::from_str::<SpellChecker>(&newer).is_err());
+
+    // sequence formats reject other versions before reading the rest
+    let mut state = hs.state();
+    state.version = SpellChecker::SERDE_VERSION + 1;
+    let newer = bincode::serialize(&state).unwrap();
+    let error = bincode::deserialize::<SpellChecker>(&newer).unwrap_err();
+    assert!(error
+        .to_string()
+        .contains("newer than the supported version"));
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn deserialize_state_without_files() {
+    let hs = open_reduced();
+    hs.ignore("rustc");
+    let json = serde_json::to_string(&hs)
+        .unwrap()
+        .replace("tests/fixtures", "/no/such/dir");
+    assert!(serde_json::from_str::<SpellChecker>(&json).is_err());
+
+    let mut state: crate::SpellCheckerState = serde_json::from_str(&json).unwrap();
+    assert_eq!(SpellChecker::SERDE_VERSION, state.version());
+    assert!(state.dictionary().validate(&Default::default()).is_err());
+    state.affix = "tests/fixtures/reduced.aff".into();
+    state.dictionary = "tests/fixtures/reduced.dic".into();
+    let restored = state.open().unwrap();
+    assert_eq!(hs, restored);
+    assert_eq!(Ok(true), restored.check("rustc"));
+}
+
+#[test]
+fn list_dictionaries() {
+    let found = available_dictionaries_in(["tests/fixtures", "tests/no-such-dir"]);
+    assert_eq!(1, found.len());
+    assert_eq!("reduced", found[0].language_tag);
+    assert_eq!("UTF-8", found[0].encoding);
+    assert_eq!(Ok(true), found[0].open().unwrap().check("cats"));
+}
+
+#[test]
+fn resolve_language_tags() {
+    let dir = std::env::temp_dir().join(format!("hunspell-rs-resolve-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    for name in ["pt", "pt_PT", "sr_RS", "de_CH", "de_AT"] {
+        std::fs::copy(
+            "tests/fixtures/reduced.aff",
+            dir.join(format!("{name}.aff")),
+        )
+        .unwrap();
+        std::fs::copy(
+            "tests/fixtures/reduced.dic",
+            dir.join(format!("{name}.dic")),
+        )
+        .unwrap();
+    }
+    let resolver = LanguageResolver::new().with_search_paths([&dir]);
+    let resolved = |tag| resolver.resolve(tag).map(|d| d.language_tag);
+    assert_eq!(
+        vec!["pt_BR", "pt_PT", "pt"],
+        resolver.candidates("pt-br").unwrap()
+    );
+    assert_eq!("pt_PT", resolved("pt-BR").unwrap());
+    assert_eq!("pt_PT", resolved("pt_PT").unwrap());
+    assert_eq!("sr_RS", resolved("sr-Latn-RS").unwrap());
+    // no de_DE or de, so the first other German dictionary
+    assert_eq!("de_AT", resolved("de").unwrap());
+    assert_eq!(
+        "pt",
+        LanguageResolver::new()
+            .with_search_paths([&dir])
+            .with_alias("pt-BR", "pt")
+            .resolve("pt-BR")
+            .unwrap()
+            .language_tag
+    );
+    match resolver.resolve("fr-CA") {
+        Err(Error::DictionaryNotFound(message)) => {
+            assert!(message.contains("fr_CA, fr_FR, fr"), "{message}");
+            assert!(message.contains(&dir.display().to_string()), "{message}");
+        }
+        other => panic!("{other:?}"),
+    }
+    assert!(matches!(
+        resolver.resolve("1234"),
+        Err(Error::InvalidLanguageTag(_))
+    ));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn from_memory() {
+    let affix = std::fs::read("tests/fixtures/reduced.aff").unwrap();
+    let dictionary = std::fs::read("tests/fixtures/reduced.dic").unwrap();
+    let extra = std::fs::read("tests/fixtures/extra.dic").unwrap();
+    let mut hs = SpellChecker::from_memory(&affix, &dictionary).unwrap();
+    assert_eq!(Ok(true), hs.check("cats"));
+    assert_eq!(Ok(true), hs.add_dictionary_from_memory(&extra));
+    assert_eq!(Ok(true), hs.check("systemdunits"));
+    let clone = hs.clone();
+    drop(hs);
+    assert_eq!(Ok(true), clone.check("systemdunits"));
+}
+
+#[test]
+fn personal_dictionary() {
+    let path =
+        std::env::temp_dir().join(format!("hunspell-rs-personal-{}.dic", std::process::id()));
+    let mut personal = PersonalDictionary::open(&path).unwrap();
+    assert!(personal.is_empty());
+    assert!(personal.add("octonasaurius"));
+    assert!(personal.add("and/or"));
+    assert!(!personal.add("octonasaurius"));
+    personal.save().unwrap();
+
+    let reopened = PersonalDictionary::open(&path).unwrap();
+    assert_eq!(personal, reopened);
+    let mut hs = open_reduced();
+    assert_eq!(Ok(false), hs.check("octonasaurius"));
+    hs.add_personal_dictionary(&reopened).unwrap();
+    assert_eq!(Ok(true), hs.check("octonasaurius"));
+
+    let mut hs = open_reduced();
+    assert_eq!(Ok(true), hs.add_dictionary(&path));
+    assert_eq!(Ok(true), hs.check("octonasaurius"));
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn validated() {
+    let limits = ValidationLimits::default();
+    let hs = SpellChecker::new_validated(
+        "tests/fixtures/reduced.aff",
+        "tests/fixtures/reduced.dic",
+        &limits,
+    )
+    .unwrap();
+    assert_eq!(Ok(true), hs.check("cats"));
+    if cfg!(any(target_os = "linux", target_os = "android")) {
+        // hunspell got the validated contents, not the files
+        assert!(hs.affix().starts_with("/proc/self/fd"));
+        let mut reloaded = hs.try_clone().unwrap();
+        reloaded.reload().unwrap();
+        assert_eq!(Ok(true), reloaded.check("cats"));
+    }
+
+    let limits = ValidationLimits {
+        max_entries: 1,
+        ..Default::default()
+    };
+    assert!(matches!(
+        validate_dictionary(
+            "tests/fixtures/reduced.aff",
+            "tests/fixtures/reduced.dic",
+            &limits
+        ),
+        Err(Error::DictionaryRejected(_))
+    ));
+    let limits = ValidationLimits {
+        max_affix_size: 10,
+        ..Default::default()
+    };
+    assert!(matches!(
+        validate_dictionary(
+            "tests/fixtures/reduced.aff",
+            "tests/fixtures/reduced.dic",
+            &limits
+        ),
+        Err(Error::DictionaryRejected(_))
+    ));
+}
+
+#[test]
+fn language() {
+    let mut hs = open_reduced();
+    assert_eq!(None, hs.language());
+    let language: Language = "en-GB".parse().unwrap();
+    assert_eq!("en_GB", language.as_str());
+    assert_eq!("en", language.primary());
+    assert_eq!(Some("GB"), language.region());
+    assert_eq!("en-GB", language.to_bcp47());
+    hs.set_language(language.clone());
+    assert_eq!(Some(&language), hs.clone().language());
+    assert!("reduced".parse::<Language>().is_err());
+}
+
+#[test]
+fn clone_keeps_runtime_words() {
+    let mut hs = open_reduced();
+    hs.add("octonasaurius").unwrap();
+    hs.add_with_affix("rust", "cat").unwrap();
+    hs.add("dinosaurius").unwrap();
+    hs.remove("dinosaurius").unwrap();
+    hs.remove("program").unwrap();
+    let clone = hs.clone();
+    assert_eq!(Ok(true), clone.check("octonasaurius"));
+    assert_eq!(Ok(true), clone.check("rusts"));
+    assert_eq!(Ok(false), clone.check("dinosaurius"));
+    assert_eq!(Ok(false), clone.check("program"));
+}
+
+#[test]
+fn display_and_debug() {
+    let mut hs = SpellChecker::new_with_key(
+        "tests/fixtures/reduced.aff",
+        "tests/fixtures/reduced.dic",
+        "secret",
+    )
+    .unwrap();
+    hs.set_language("en_GB".parse().unwrap());
+    hs.add("octonasaurius").unwrap();
+    assert_eq!(
+        "en_GB (tests/fixtures/reduced.dic, 0 extra dictionaries, 1 runtime words)",
+        hs.to_string()
+    );
+    let debug = format!("{hs:?}");
+    assert!(debug.contains("reduced.aff"));
+    assert!(!debug.contains("secret"));
+    assert!(!debug.contains("handle"));
+}
+
+#[test]
+// the hash does not include the runtime words
+#[allow(clippy::mutable_key_type)]
+fn equality() {
+    use std::collections::HashSet;
+
+    let mut hs = open_reduced();
+    let mut extra = open_reduced();
+    assert_eq!(hs, extra);
+    extra.add_dictionary("tests/fixtures/extra.dic").unwrap();
+    assert_ne!(hs, extra);
+    assert_eq!(extra, extra.clone());
+    let keyed = SpellChecker::new_with_key(
+        "tests/fixtures/reduced.aff",
+        "tests/fixtures/reduced.dic",
+        "k",
+    )
+    .unwrap();
+    assert_ne!(hs, keyed);
+
+    let mut set = HashSet::new();
+    set.insert(hs.clone());
+    hs.add("octonasaurius").unwrap();
+    assert!(set.contains(&hs));
+    assert!(!set.contains(&extra));
+}
+
+#[test]
+fn suggest_with_options() {
+    let mut hs = open_reduced();
+    let options = SuggestOptions {
+        max: 1,
+        ..Default::default()
+    };
+    assert_eq!(
+        Ok(vec!["cats".to_string()]),
+        hs.suggest_with_options("cts", &options)
+    );
+    let options = SuggestOptions {
+        max: 5,
+        timeout: std::time::Duration::from_secs(60),
+        ..Default::default()
+    };
+    assert_eq!(
+        hs.suggest("progra"),
+        hs.suggest_with_options("progra", &options)
+    );
+    hs.add("octonasaurius").unwrap();
+    assert_eq!(
+        Ok(vec!["octonasaurius".to_string()]),
+        hs.suggest_with_options("octonasaurus", &options)
+    );
+    // the helper thread gets the options that change what hunspell sees
+    hs.set_input_policy(InputPolicy {
+        nul: NulHandling::Strip,
+        ..Default::default()
+    });
+    assert_eq!(
+        hs.suggest("ca\0tz"),
+        hs.suggest_with_options("ca\0tz", &options)
+    );
+    hs.set_normalization(Normalization {
+        typographic: true,
+        ..Default::default()
+    });
+    hs.add("don't").unwrap();
+    assert_eq!(
+        Ok(vec!["don't".to_string()]),
+        hs.suggest_with_options("don’tt", &options)
+    );
+    let options = SuggestOptions {
+        max_distance: Some(1),
+        ..Default::default()
+    };
+    assert_eq!(
+        Ok(vec!["Program".to_string()]),
+        hs.suggest_with_options("Progam", &options)
+    );
+    assert_eq!(Ok(vec![]), hs.suggest_with_options("prgrm", &options));
+    let options = SuggestOptions {
+        short_words: ShortWordPolicy::Skip,
+        ..Default::default()
+    };
+    assert_eq!(Ok(vec![]), hs.suggest_with_options("cts", &options));
+    assert!(!hs
+        .suggest_with_options("catz", &options)
+        .unwrap()
+        .is_empty());
+    let options = SuggestOptions {
+        short_words: ShortWordPolicy::NearOnly,
+        ..Default::default()
+    };
+    assert_eq!(
+        Ok(vec!["cat".to_string()]),
+        hs.suggest_with_options("ca", &options)
+    );
+}
+
+#[test]
+fn conversions() {
+    use std::path::Path;
+
+    let hs = SpellChecker::try_from((
+        Path::new("tests/fixtures/reduced.aff"),
+        Path::new("tests/fixtures/reduced.dic"),
+    ))
+    .unwrap();
+    let parsed: SpellChecker = "tests/fixtures/reduced".parse().unwrap();
+    assert_eq!(hs, parsed);
+    assert!("tests/fixtures/missing".parse::<SpellChecker>().is_err());
+
+    let config = Config {
+        dictionary: "tests/fixtures/reduced".to_string(),
+        extra_dictionaries: vec!["tests/fixtures/extra.dic".into()],
+        ..Default::default()
+    };
+    let configured = SpellChecker::try_from(&config).unwrap();
+    assert_eq!(Ok(true), configured.check("systemdunits"));
+    let config = Config {
+        dictionary: "xx_NOT_INSTALLED".to_string(),
+        ..Default::default()
+    };
+    assert_eq!(
+        Err(Error::DictionaryNotFound("xx_NOT_INSTALLED".to_string())),
+        SpellChecker::try_from(&config)
+    );
+}
+
+#[test]
+fn suggest_classified() {
+    let mut hs = open_reduced();
+    hs.add("no").unwrap();
+    hs.add("Paris").unwrap();
+    let kind_of = |word: &str, suggestion: &str| {
+        hs.suggest_classified(word)
+            .unwrap()
+            .into_iter()
+            .find(|s| s.word == suggestion)
+            .map(|s| s.kind)
+    };
+    assert_eq!(Some(SuggestionKind::CaseFix), kind_of("paris", "Paris"));
+    assert_eq!(Some(SuggestionKind::Split), kind_of("nocat", "no cat"));
+    assert_eq!(Some(SuggestionKind::AffixVariant), kind_of("catz", "cat"));
+    assert_eq!(
+        Some(&Suggestion {
+            word: "program".to_string(),
+            kind: SuggestionKind::EditDistance(1),
+            source: SuggestionSource::Hunspell(None),
+        }),
+        hs.suggest_classified("porgram").unwrap().first()
+    );
+}
+
+#[test]
+fn edit_distance() {
+    use crate::distance::edit_distance;
+
+    assert_eq!(0, edit_distance("cat", "cat"));
+    assert_eq!(1, edit_distance("cat", "cats"));
+    assert_eq!(1, edit_distance("cat", "act"));
+    assert_eq!(3, edit_distance("kitten", "sitting"));
+    assert_eq!(3, edit_distance("", "abc"));
+    assert_eq!(1, edit_distance("naïve", "naive"));
+}
+
+#[test]
+fn casing() {
+    assert_eq!(Casing::Lower, Casing::of("cat"));
+    assert_eq!(Casing::Title, Casing::of("Cat"));
+    assert_eq!(Casing::Title, Casing::of("I"));
+    assert_eq!(Casing::Upper, Casing::of("CAT'S"));
+    assert_eq!(Casing::Mixed, Casing::of("iPhone"));
+    assert_eq!("Élan", apply_suggestion("Elen", "élan"));
+    assert_eq!("ÉLAN", apply_suggestion("ELEN", "élan"));
+    assert_eq!("iPhone", apply_suggestion("iphone", "iPhone"));
+    assert_eq!("cats", apply_suggestion("cAst", "cats"));
+}
+
+#[cfg(feature = "snapshot")]
+#[test]
+fn snapshot() {
+    let mut hs = open_reduced();
+    hs.add_dictionary("tests/fixtures/extra.dic").unwrap();
+    hs.add("octonasaurius").unwrap();
+    hs.remove("program").unwrap();
+
+    let mut bytes = Vec::new();
+    hs.save_snapshot(&mut bytes, false).unwrap();
+    let loaded = SpellChecker::load_snapshot(bytes.as_slice()).unwrap();
+    assert_eq!(hs, loaded);
+    assert_eq!(Ok(true), loaded.check("systemdunits"));
+    assert_eq!(Ok(true), loaded.check("octonasaurius"));
+    assert_eq!(Ok(false), loaded.check("program"));
+
+    let mut bytes = Vec::new();
+    hs.save_snapshot(&mut bytes, true).unwrap();
+    if cfg!(any(target_os = "linux", target_os = "android")) {
+        let loaded = SpellChecker::load_snapshot(bytes.as_slice()).unwrap();
+        assert_eq!(Ok(true), loaded.check("cats"));
+        assert_eq!(Ok(true), loaded.check("systemdunits"));
+        assert_eq!(Ok(true), loaded.check("octonasaurius"));
+    }
+
+    assert_eq!(
+        Err(Error::InvalidSnapshot("not a snapshot".to_string())),
+        SpellChecker::load_snapshot(&b"HSRSXXXX\x01\x00"[..])
+    );
+
+    // a compressed section that inflates past its stated length
+    let mut bomb = b"HSRSSNAP\x02\x00\x09\x01".to_vec();
+    let zeros = miniz_oxide::deflate::compress_to_vec(&[0; 1 << 20], 6);
+    bomb.extend_from_slice(&(zeros.len() as u64).to_le_bytes());
+    bomb.extend_from_slice(&64u64.to_le_bytes());
+    bomb.extend_from_slice(&zeros);
+    assert_eq!(
+        Err(Error::InvalidSnapshot(
+            "corrupt compressed section".to_string()
+        )),
+        SpellChecker::load_snapshot(bomb.as_slice())
+    );
+
+    // a language that is no language tag
+    let mut config = Vec::new();
+    for field in ["tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic"] {
+        config.extend_from_slice(&(field.len() as u32).to_le_bytes());
+        config.extend_from_slice(field.as_bytes());
+    }
+    config.extend_from_slice(&[0, 0, 0, 0, 0, 1, 4, 0, 0, 0]);
+    config.extend_from_slice(b"../x");
+    let mut snapshot = b"HSRSSNAP\x02\x00\x01\x00".to_vec();
+    snapshot.extend_from_slice(&(config.len() as u64).to_le_bytes());
+    snapshot.extend_from_slice(&config);
+    assert_eq!(
+        Err(Error::InvalidLanguageTag("../x".to_string())),
+        SpellChecker::load_snapshot(snapshot.as_slice())
+    );
+}
+
+#[cfg(feature = "snapshot")]
+#[test]
+fn snapshot_state() {
+    let mut spell = SpellChecker::new(
+        "tests/fixtures/stem/stem.aff",
+        "tests/fixtures/stem/stem.dic",
+    )
+    .unwrap();
+    spell.ignore("rustc");
+    spell.add_replacement("teh", "the");
+    spell.set_normalization(Normalization::none());
+    spell.set_max_suggest_length(Some(20));
+    spell.set_input_policy(InputPolicy {
+        nul: NulHandling::Replace('x'),
+        ..InputPolicy::untrusted()
+    });
+    spell.enable_analysis_cache(10);
+    let analyses = spell.analyze("roses").unwrap();
+
+    let mut bytes = Vec::new();
+    spell.save_snapshot(&mut bytes, false).unwrap();
+    let loaded = SpellChecker::load_snapshot(bytes.as_slice()).unwrap();
+    assert_eq!(spell.state(), loaded.state());
+    assert_eq!(Some(10), loaded.analysis_cache_capacity());
+    assert_eq!(analyses, loaded.analyze("roses").unwrap());
+    assert_eq!(1, loaded.analysis_cache_stats().unwrap().hits);
+
+    if cfg!(any(target_os = "linux", target_os = "android")) {
+        let mut bytes = Vec::new();
+        spell.save_snapshot(&mut bytes, true).unwrap();
+        let loaded = SpellChecker::load_snapshot(bytes.as_slice()).unwrap();
+        assert_eq!(Ok(true), loaded.check("rustc"));
+        assert_eq!(analyses, loaded.analyze("roses").unwrap());
+        assert_eq!(1, loaded.analysis_cache_stats().unwrap().hits);
+    }
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStrExt;
+
+        let dir = std::env::temp_dir().join(format!("hunspell-rs-snapshot-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let affix = dir.join(std::ffi::OsStr::from_bytes(b"reduced-\xff.aff"));
+        let dictionary = dir.join("reduced.dic");
+        std::fs::copy("tests/fixtures/reduced.aff", &affix).unwrap();
+        std::fs::copy("tests/fixtures/reduced.dic", &dictionary).unwrap();
+        let spell = SpellChecker::new(&affix, &dictionary).unwrap();
+        assert_eq!(
+            Err(Error::UnsupportedPath(affix)),
+            spell.save_snapshot(Vec::new(), false)
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    // files that changed since the snapshot was written
+    let dir = std::env::temp_dir().join(format!(
+        "hunspell-rs-snapshot-changed-{}",
+        std::process::id()
+    ));
+    std::fs::create_dir_all(&dir).unwrap();
+    let affix = dir.join("reduced.aff");
+    let dictionary = dir.join("reduced.dic");
+    std::fs::copy("tests/fixtures/reduced.aff", &affix).unwrap();
+    std::fs::copy("tests/fixtures/reduced.dic", &dictionary).unwrap();
+    let mut bytes = Vec::new();
+    SpellChecker::new(&affix, &dictionary)
+        .unwrap()
+        .save_snapshot(&mut bytes, false)
+        .unwrap();
+    let mut contents = std::fs::read_to_string(&dictionary).unwrap();
+    contents.push_str("dog\n");
+    std::fs::write(&dictionary, contents).unwrap();
+    assert!(matches!(
+        SpellChecker::load_snapshot(bytes.as_slice()),
+        Err(Error::FingerprintMismatch { .. })
+    ));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn suggestion_arbiters() {
+    let lists = || {
+        vec![
+            vec![],
+            vec!["cots".to_string(), "cat".to_string()],
+            vec!["cat".to_string(), "cut".to_string()],
+        ]
+    };
+    assert_eq!(vec!["cots", "cat"], FirstWins.arbitrate("cta", lists()));
+    assert_eq!(
+        vec!["cots", "cat", "cut"],
+        Interleave.arbitrate("cta", lists())
+    );
+    assert_eq!(
+        vec!["cat", "cots", "cut"],
+        ScoreBased.arbitrate("cta", lists())
+    );
+
+    let extra =
+        SpellChecker::new("tests/fixtures/reduced.aff", "tests/fixtures/extra.dic").unwrap();
+    let multi = MultiLanguageChecker::new(vec![open_reduced(), extra]);
+    assert_eq!(Ok(true), multi.check("cats"));
+    assert_eq!(Ok(true), multi.check("systemdunits"));
+    assert_eq!(Ok(false), multi.check("catz"));
+    assert_eq!(Ok(vec!["cat".to_string()]), multi.suggest("catz"));
+    let accepting = multi.accepted_by("systemdunits").unwrap();
+    assert_eq!(1, accepting.len());
+    assert!(accepting[0].dictionary().ends_with("extra.dic"));
+    let misspelled = multi.check_text("cats, systemdunits and catz").unwrap();
+    assert_eq!(
+        vec!["and", "catz"],
+        misspelled.iter().map(|m| &m.word).collect::<Vec<_>>()
+    );
+
+    let extra =
+        SpellChecker::new("tests/fixtures/reduced.aff", "tests/fixtures/extra.dic").unwrap();
+    let fallback = FallbackChecker::new(extra, open_reduced());
+    assert_eq!(Ok(true), fallback.check("program"));
+    assert_eq!(
+        Ok(vec!["program".to_string()]),
+        fallback.suggest("porgram").map(|mut s| {
+            s.truncate(1);
+            s
+        })
+    );
+}
+
+#[test]
+fn ignore() {
+    let hs = open_reduced();
+    assert_eq!(Ok(false), hs.check("catz"));
+    hs.ignore("catz");
+    assert!(hs.is_ignored("catz"));
+    assert_eq!(Ok(true), hs.check("catz"));
+    assert_eq!(Ok(false), hs.check("catzs"));
+    assert!(!hs.suggest("catzs").unwrap().contains(&"catz".to_string()));
+    assert_eq!(Ok(true), hs.clone().check("catz"));
+    hs.clear_ignored();
+    assert_eq!(Ok(false), hs.check("catz"));
+}
+
+#[test]
+fn check_text() {
+    let hs = open_reduced();
+    let text = "cats, catz at info@cats.org and https://catz.example/x 0xcafe #fff";
+    let misspelled = hs.check_text(text).unwrap();
+    let words: Vec<&str> = misspelled.iter().map(|m| m.word.as_str()).collect();
+    assert_eq!(vec!["catz", "at", "and"], words);
+    assert_eq!(6..10, misspelled[0].range);
+
+    let checker = TextChecker::new(hs).with_ignore_rules(IgnoreRules::none());
+    assert_eq!(11, checker.check_text(text).unwrap().len());
+    assert!(IgnoreRules::default().with_pattern("(").is_err());
+}
+
+#[test]
+fn text_checker_cache() {
+    let hs = open_reduced();
+    let session = hs.session_hash();
+    let mut checker = TextChecker::new(hs).with_cache(2);
+    assert_eq!(1, checker.check_text("cats catz").unwrap().len());
+    assert_eq!(1, checker.check_text("cats catz").unwrap().len());
+    checker.spell_checker_mut().add("catz").unwrap();
+    assert_ne!(session, checker.spell_checker().session_hash());
+    assert_eq!(0, checker.check_text("cats catz").unwrap().len());
+    checker.spell_checker_mut().remove("catz").unwrap();
+    assert_eq!(1, checker.check_text("cats catz").unwrap().len());
+}
+
+#[test]
+fn text_checker_cache_after_reload() {
+    let dir = std::env::temp_dir().join(format!("hunspell-rs-reload-cache-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let affix = dir.join("reduced.aff");
+    let dictionary = dir.join("reduced.dic");
+    std::fs::copy("tests/fixtures/reduced.aff", &affix).unwrap();
+    std::fs::copy("tests/fixtures/reduced.dic", &dictionary).unwrap();
+    let mut checker =
+        TextChecker::new(SpellChecker::new(&affix, &dictionary).unwrap()).with_cache(2);
+    assert_eq!(1, checker.check_text("cat dog").unwrap().len());
+
+    let mut contents = std::fs::read_to_string(&dictionary).unwrap();
+    contents.push_str("dog\n");
+    std::fs::write(&dictionary, contents).unwrap();
+    checker.spell_checker_mut().reload().unwrap();
+    assert_eq!(Ok(true), checker.spell_checker().check("dog"));
+    assert_eq!(0, checker.check_text("cat dog").unwrap().len());
+
+    let session = checker.spell_checker().session_hash();
+    checker.spell_checker_mut().set_input_policy(InputPolicy {
+        max_word_length: Some(2),
+        ..Default::default()
+    });
+    assert_ne!(session, checker.spell_checker().session_hash());
+    assert_eq!(2, checker.check_text("cat dog").unwrap().len());
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[cfg(feature = "watch")]
+#[test]
+fn watch_dir() {
+    use crate::WatchEvent;
+    use std::ops::ControlFlow;
+
+    let dir = std::env::temp_dir().join(format!("hunspell-rs-watch-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("a.txt"), "cats catz").unwrap();
+    let hs = open_reduced();
+    let checker = TextChecker::new(hs);
+    let mut reports = Vec::new();
+    checker
+        .watch_dir(&dir, |event| {
+            if let WatchEvent::Checked { misspellings, .. } = event {
+                reports.push(misspellings.len());
+            }
+            if reports.len() == 1 {
+                std::fs::write(dir.join("a.txt"), "cats catz catx").unwrap();
+                return ControlFlow::Continue(());
+            }
+            ControlFlow::Break(())
+        })
+        .unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert_eq!(vec![1, 2], reports);
+}
+
+#[cfg(feature = "watch")]
+#[test]
+fn reloading_spell_checker() {
+    use crate::ReloadingSpellChecker;
+
+    let dir = std::env::temp_dir().join(format!("hunspell-rs-reloading-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let affix = dir.join("reduced.aff");
+    let dictionary = dir.join("reduced.dic");
+    std::fs::copy("tests/fixtures/reduced.aff", &affix).unwrap();
+    std::fs::copy("tests/fixtures/reduced.dic", &dictionary).unwrap();
+    let mut hs = SpellChecker::new(&affix, &dictionary).unwrap();
+    hs.add("octonasaurius").unwrap();
+    let spell = ReloadingSpellChecker::new(hs).unwrap();
+    assert_eq!(Ok(false), Speller::check(&spell, "dogs"));
+    assert_eq!(Ok(false), spell.reload_if_changed());
+
+    std::fs::write(dir.join("notes.txt"), "not a dictionary").unwrap();
+    std::fs::write(&dictionary, "3\ncat/S\nprogram/S\ndog/S\n").unwrap();
+    let mut reloaded = false;
+    for _ in 0..100 {
+        if spell.spell_checker().check("dogs") == Ok(true) {
+            reloaded = true;
+            break;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(50));
+    }
+    assert!(reloaded);
+    assert_eq!(None, spell.reload_error());
+    assert_eq!(Ok(true), Speller::check(&spell, "octonasaurius"));
+    let hs = spell.into_inner();
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert_eq!(Ok(true), hs.check("cats"));
+}
+
+#[test]
+fn check_detailed() {
+    let mut hs = open_reduced();
+    hs.add_dictionary("tests/fixtures/extra.dic").unwrap();
+    hs.add("octonasaurius").unwrap();
+    hs.ignore("catz");
+    let sources = |word| hs.check_detailed(word).unwrap().sources;
+    assert_eq!(vec![WordSource::Dictionary], sources("cats"));
+    assert_eq!(
+        vec![WordSource::ExtraDictionary(
+            "tests/fixtures/extra.dic".into()
+        )],
+        sources("systemdunits")
+    );
+    assert_eq!(vec![WordSource::Runtime], sources("octonasaurius"));
+    assert_eq!(vec![WordSource::Ignored], sources("catz"));
+    assert_eq!(
+        CheckDetails {
+            correct: false,
+            sources: vec![]
+        },
+        hs.check_detailed("catx").unwrap()
+    );
+    assert!(hs.check_detailed("systemdunits").unwrap().only_extra());
+
+    let checker = TextChecker::new(hs).with_extra_word_policy(ExtraWordPolicy::Reject);
+    let misspelled = checker.check_text("cats systemdunits").unwrap();
+    assert_eq!(
+        vec!["systemdunits"],
+        misspelled.iter().map(|m| &m.word).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn latex_filter() {
+    let hs = open_reduced();
+    let text = r"\section{Cats} cats $x^catz$ \cite[p.~2]{catx} % catz
+\begin{equation*} catz \end{equation*} \textbf{catz} \% catx";
+    let checker = TextChecker::new(hs).with_syntax_filter(Latex);
+    let misspelled = checker.check_text(text).unwrap();
+    assert_eq!(
+        vec!["catz", "catx"],
+        misspelled.iter().map(|m| &m.word).collect::<Vec<_>>()
+    );
+    assert_eq!("catx", &text[misspelled[1].range.clone()]);
+}
+
+#[test]
+fn suggestion_source() {
+    let open = |dic: &str, lang: &str| {
+        let mut hs = SpellChecker::new("tests/fixtures/reduced.aff", dic).unwrap();
+        hs.set_language(lang.parse().unwrap());
+        hs
+    };
+    let multi = MultiLanguageChecker::new(vec![
+        open("tests/fixtures/extra.dic", "en_US"),
+        open("tests/fixtures/reduced.dic", "nl"),
+    ]);
+    let suggestions = multi.suggest_classified("catz").unwrap();
+    assert_eq!("cat", suggestions[0].word);
+    assert_eq!(
+        SuggestionSource::Hunspell(Some("nl".parse().unwrap())),
+        suggestions[0].source
+    );
+}
+
+#[test]
+fn markdown_and_html_filters() {
+    let open = || open_reduced();
+    let words = |misspelled: Vec<crate::Misspelling>| {
+        misspelled.into_iter().map(|m| m.word).collect::<Vec<_>>()
+    };
+
+    let markdown = "cats `catz` [cats](catz.md) <catz>\n```\ncatz\n```\ncatx";
+    let checker = TextChecker::new(open()).with_syntax_filter(Markdown);
+    assert_eq!(vec!["catx"], words(checker.check_text(markdown).unwrap()));
+
+    let html = "<p class=\"catz\">cats &amp; <code>catz</code><!-- catz --> catx</p>";
+    let checker = TextChecker::new(open()).with_syntax_filter(Html);
+    assert_eq!(vec!["catx"], words(checker.check_text(html).unwrap()));
+}
+
+#[test]
+fn pipe() {
+    let mut hs = open_reduced();
+    let input = "cats catz\n!\n*catz\n@catx\n^*cats catx qqqqqqqq\n+\n\\emph{cats} $catz$ catsz\n";
+    let mut output = Vec::new();
+    crate::pipe::run(&mut hs, input.as_bytes(), &mut output).unwrap();
+    let output = String::from_utf8(output).unwrap();
+    let mut lines = output.lines();
+    assert_eq!(Some(crate::pipe::BANNER), lines.next());
+    assert_eq!(
+        vec![
+            "*",
+            "& catz 1 5: cat",
+            "",
+            "# qqqqqqqq 12",
+            "",
+            "& catsz 2 19: cats, catz",
+            ""
+        ],
+        lines.collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn title_case_headings() {
+    let open = || {
+        SpellChecker::new(
+            "tests/fixtures/keepcase/keepcase.aff",
+            "tests/fixtures/keepcase/keepcase.dic",
+        )
+        .unwrap()
+    };
+    let words = |misspelled: Vec<crate::Misspelling>| {
+        misspelled.into_iter().map(|m| m.word).collect::<Vec<_>>()
+    };
+
+    let markdown = "# Cat\n\nCat and cat\n\nCat\n===\n";
+    let checker = TextChecker::new(open()).with_syntax_filter(Markdown);
+    assert_eq!(vec!["Cat"], words(checker.check_text(markdown).unwrap()));
+    assert_eq!(7..10, checker.check_text(markdown).unwrap()[0].range);
+
+    let html = "<h2>Cat</h2><p>Cat</p>";
+    let checker = TextChecker::new(open()).with_syntax_filter(Html);
+    assert_eq!(15..18, checker.check_text(html).unwrap()[0].range);
+    assert_eq!(1, checker.check_text(html).unwrap().len());
+}
+
+#[test]
+fn possessives_and_acronym_plurals() {
+    let hs = open_reduced();
+    let text = "the cat's CATs and CAT’s, not catz's or Cats";
+    let misspelled = hs.check_text(text).unwrap();
+    assert_eq!(
+        vec!["the", "and", "not", "catz's", "or"],
+        misspelled
+            .iter()
+            .map(|m| m.word.as_str())
+            .collect::<Vec<_>>()
+    );
+
+    let checker = TextChecker::new(hs).with_word_forms(WordForms {
+        acronym_plurals: false,
+        ..WordForms::default()
+    });
+    assert_eq!(6, checker.check_text(text).unwrap().len());
+    let checker =
+        TextChecker::new(checker.spell_checker().clone()).with_word_forms(WordForms::none());
+    assert_eq!(8, checker.check_text(text).unwrap().len());
+}
+
+#[test]
+fn accept_lists() {
+    let hs = open_reduced();
+    let text = "cats etc. cats, i.e. the 3rd catz";
+    assert_eq!(6, hs.check_text(text).unwrap().len());
+
+    let english: Language = "en_GB".parse().unwrap();
+    let rules = IgnoreRules::default().with_accept_list(&english);
+    let checker = TextChecker::new(hs).with_ignore_rules(rules);
+    let misspelled = checker.check_text(text).unwrap();
+    assert_eq!(
+        vec!["the", "catz"],
+        misspelled
+            .iter()
+            .map(|m| m.word.as_str())
+            .collect::<Vec<_>>()
+    );
+
+    let klingon: Language = "tlh".parse().unwrap();
+    assert!(crate::accept_list(&klingon).is_empty());
+    assert!(IgnoreRules::none()
+        .with_accept_list(&klingon)
+        .patterns
+        .is_empty());
+}
+
+#[test]
+fn abbreviations() {
+    let hs = open_reduced();
+    let text = "cats catz. Cats aprox. cats Drr. Cat";
+    let misspelled = hs.check_text(text).unwrap();
+    assert_eq!(
+        vec![("catz", 5..9), ("aprox.", 16..22), ("Drr", 28..31)],
+        misspelled
+            .iter()
+            .map(|m| (m.word.as_str(), m.range.clone()))
+            .collect::<Vec<_>>()
+    );
+
+    let checker = TextChecker::new(hs).with_abbreviations(["aprox.", "Drr"]);
+    let misspelled = checker.check_text(text).unwrap();
+    assert_eq!(
+        vec!["catz"],
+        misspelled.iter().map(|m| &m.word).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn suggest_into() {
+    let hs = open_reduced();
+    let mut suggestions = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+    assert_eq!(Ok(1), hs.suggest_into("catz", &mut suggestions));
+    assert_eq!(vec!["cat"], suggestions);
+    assert_eq!(Ok(0), hs.suggest_into("qqqqqqqq", &mut suggestions));
+    assert!(suggestions.is_empty());
+    let error = hs.suggest_into("ca\0t", &mut suggestions).unwrap_err();
+    assert_eq!(Some(Operation::Suggest), error.operation());
+    assert!(matches!(error.root_cause(), Error::NulError(_)));
+    assert!(matches!(
+        hs.check("ca\0t").map_err(|e| e.root_cause().clone()),
+        Err(Error::NulError(_))
+    ));
+}
+
+#[test]
+fn error_context() {
+    use std::error::Error as _;
+
+    let mut spell = open_reduced();
+    let error = spell.check("ca\0t").unwrap_err();
+    match &error {
+        Error::Failed {
+            operation,
+            subject,
+            cause,
+        } => {
+            assert_eq!(Operation::Check, *operation);
+            assert_eq!("ca\0t", subject);
+            assert!(matches!(**cause, Error::NulError(_)));
+        }
+        _ => panic!("no context: {error:?}"),
+    }
+    assert!(error
+        .to_string()
+        .starts_with("check \"ca\\0t\": nul byte found"));
+    // the cause, and the cause of that
+    let cause = error.source().unwrap();
+    assert!(cause.to_string().starts_with("nul byte found"));
+    assert!(cause.source().is_some());
+
+    let error = spell.remove("ca\0t").unwrap_err();
+    assert_eq!(Some(Operation::Remove), error.operation());
+    assert_eq!("add_dictionary", Operation::AddDictionary.to_string());
+
+    // file errors keep the path and the I/O error
+    let error = PersonalDictionary::new("tests/fixtures/no/such/dir/words.dic")
+        .save()
+        .unwrap_err();
+    assert_eq!(Some(Operation::Save), error.operation());
+    assert_eq!(
+        "save \"tests/fixtures/no/such/dir/words.dic\": No such file or directory (os error 2)",
+        error.to_string()
+    );
+    match error.root_cause() {
+        Error::IoError(e) => {
+            assert_eq!(
+                Some(std::path::Path::new("tests/fixtures/no/such/dir/words.dic")),
+                e.path()
+            );
+            assert_eq!(std::io::ErrorKind::NotFound, e.kind());
+        }
+        cause => panic!("not an I/O error: {cause:?}"),
+    }
+    let io = error.source().unwrap().source().unwrap();
+    assert!(io.downcast_ref::<std::io::Error>().is_some());
+
+    // errors that tell what went wrong are left as they are
+    assert!(matches!(
+        spell.add("two words"),
+        Err(Error::InvalidWord { .. })
+    ));
+    assert_eq!(None, spell.add("two words").unwrap_err().operation());
+}
+
+#[test]
+fn suggest_iter() {
+    let hs = open_reduced();
+    let mut iter = hs.suggest_iter("catsz").unwrap();
+    assert_eq!(2, iter.len());
+    assert_eq!(Some("cats"), iter.next_cstr().map(|s| s.to_str().unwrap()));
+    assert_eq!(Some(Ok("cat".to_string())), iter.next());
+    assert_eq!(None, iter.next());
+    drop(iter);
+    assert_eq!(0, hs.suggest_iter("qqqqqqqq").unwrap().count());
+}
+
+#[test]
+fn check_with_options() {
+    let mut hs = open_reduced();
+    hs.add("Paris").unwrap();
+    hs.add("don't").unwrap();
+    let insensitive = CheckOptions {
+        case: CasePolicy::Insensitive,
+        ..Default::default()
+    };
+    assert_eq!(Ok(false), hs.check_with("paris", &CheckOptions::default()));
+    assert_eq!(Ok(true), hs.check_with("paris", &insensitive));
+    assert_eq!(Ok(true), hs.check_with("cAtS", &insensitive));
+    assert_eq!(Ok(false), hs.check_with("catz", &insensitive));
+
+    let normalize = CheckOptions {
+        normalize: true,
+        ..Default::default()
+    };
+    assert_eq!(Ok(false), hs.check_with("don’t", &CheckOptions::default()));
+    assert_eq!(Ok(true), hs.check_with("don’t", &normalize));
+
+    let digits = CheckOptions {
+        digits: true,
+        ..Default::default()
+    };
+    assert_eq!(Ok(false), hs.check_with("mp3", &CheckOptions::default()));
+    for word in ["mp3", "v2", "10km", "1.2.3", "٣cats"] {
+        assert_eq!(Ok(true), hs.check_with(word, &digits), "{word}");
+    }
+    assert_eq!(Ok(false), hs.check_with("catz", &digits));
+
+    let compounds = |parts| CheckOptions {
+        compound_parts: parts,
+        case: CasePolicy::Insensitive,
+        ..Default::default()
+    };
+    assert_eq!(Ok(false), hs.check_with("catprogram", &compounds(0)));
+    assert_eq!(Ok(false), hs.check_with("catprogram", &compounds(1)));
+    assert_eq!(Ok(true), hs.check_with("catprogram", &compounds(2)));
+    assert_eq!(Ok(true), hs.check_with("CatProgramsCATS", &compounds(3)));
+    assert_eq!(Ok(false), hs.check_with("catprogramscats", &compounds(2)));
+    assert_eq!(Ok(false), hs.check_with("catprogramz", &compounds(3)));
+    // parts are at least three characters
+    hs.add("ab").unwrap();
+    assert_eq!(Ok(false), hs.check_with("abcat", &compounds(2)));
+
+    let hyphens = CheckOptions {
+        split_hyphens: true,
+        compound_parts: 2,
+        ..Default::default()
+    };
+    // each part is a compound of its own
+    assert_eq!(Ok(true), hs.check_with("catprogram-catprogram", &hyphens));
+    assert_eq!(
+        Ok(false),
+        hs.check_with("catprogram-catprogram", &compounds(2))
+    );
+    assert_eq!(Ok(false), hs.check_with("catprogram-programz", &hyphens));
+    assert_eq!(Ok(false), hs.check_with("--", &hyphens));
+}
+
+#[test]
+fn frozen_spell_checker() {
+    fn shareable<T: Send + Sync>() {}
+    shareable::<FrozenSpellChecker>();
+
+    let mut hs = open_reduced();
+    hs.add("systemdunits").unwrap();
+    hs.ignore("catz");
+    let session = hs.session_hash();
+    let frozen = std::sync::Arc::new(hs.freeze());
+    assert_eq!(session, frozen.session_hash());
+    let threads: Vec<_> = (0..4)
+        .map(|_| {
+            let frozen = frozen.clone();
+            std::thread::spawn(move || {
+                (0..50).all(|_| {
+                    frozen.check("systemdunits") == Ok(true)
+                        && frozen.check("catz") == Ok(true)
+                        && frozen.suggest("progam").is_ok_and(|s| !s.is_empty())
+                })
+            })
+        })
+        .collect();
+    assert!(threads.into_iter().all(|t| t.join().unwrap()));
+
+    let hs = std::sync::Arc::into_inner(frozen).unwrap().thaw();
+    assert_eq!(session, hs.session_hash());
+}
+
+#[test]
+fn latin1_dictionary() {
+    let mut hs = SpellChecker::new(
+        "tests/fixtures/latin1/latin1.aff",
+        "tests/fixtures/latin1/latin1.dic",
+    )
+    .unwrap();
+    assert_eq!("ISO8859-1", hs.encoding());
+    assert_eq!(Ok(true), hs.check("cafés"));
+    assert_eq!(Ok(true), hs.check("garçon"));
+    assert_eq!(Ok(false), hs.check("garcon"));
+    assert_eq!(Ok(false), hs.check("garçon€"));
+    assert_eq!(Ok(vec!["garçon".to_string()]), hs.suggest("garcon"));
+    assert_eq!(Ok(vec!["café".to_string()]), hs.stem("cafés"));
+    hs.add("déjà").unwrap();
+    assert_eq!(Ok(true), hs.check("déjà"));
+
+    let hs = open_reduced();
+    assert_eq!("UTF-8", hs.encoding());
+}
+
+#[test]
+fn checker_registry() {
+    let registry = CheckerRegistry::new();
+    let result = registry.preload(&[
+        "tests/fixtures/reduced",
+        "tests/fixtures/keepcase/keepcase",
+        "tests/fixtures/missing",
+        "tests/fixtures/reduced",
+    ]);
+    assert!(matches!(
+        result,
+        Err(Error::PreloadFailed(failed)) if failed.len() == 1 && failed[0].0 == "tests/fixtures/missing"
+    ));
+    assert_eq!(
+        vec!["tests/fixtures/keepcase/keepcase", "tests/fixtures/reduced"],
+        registry.dictionaries()
+    );
+    let reduced = registry.get("tests/fixtures/reduced").unwrap();
+    assert!(std::sync::Arc::ptr_eq(
+        &reduced,
+        &registry.get("tests/fixtures/reduced").unwrap()
+    ));
+    assert!(registry.get("tests/fixtures/missing").is_err());
+    assert!(!registry.contains("tests/fixtures/missing"));
+    assert_eq!(
+        Ok(true),
+        registry
+            .get("tests/fixtures/latin1/latin1")
+            .unwrap()
+            .check("naïve")
+    );
+}
+
+#[test]
+fn registry_swap_dictionary() {
+    let registry = CheckerRegistry::new();
+    let old = registry.get("tests/fixtures/reduced").unwrap();
+    let missing = Dictionary::from_files("/no/such/dir/reduced.aff", "/no/such/dir/reduced.dic");
+    assert!(registry
+        .swap_dictionary("tests/fixtures/reduced", missing)
+        .is_err());
+    assert!(std::sync::Arc::ptr_eq(
+        &old,
+        &registry.get("tests/fixtures/reduced").unwrap()
+    ));
+
+    let upgrade =
+        Dictionary::from_files("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic")
+            .with_additional_dictionary("tests/fixtures/extra.dic");
+    registry
+        .swap_dictionary("tests/fixtures/reduced", upgrade.clone())
+        .unwrap();
+    let new = registry.get("tests/fixtures/reduced").unwrap();
+    assert_eq!(Ok(true), new.check("systemdunits"));
+    assert_eq!(Ok(false), old.check("systemdunits"));
+
+    // a dictionary that is not open yet is opened under the name
+    registry.swap_dictionary("upgraded", upgrade).unwrap();
+    assert_eq!(
+        vec!["tests/fixtures/reduced", "upgraded"],
+        registry.dictionaries()
+    );
+}
+
+#[test]
+fn registry_idle_eviction() {
+    let registry = CheckerRegistry::new().with_idle_timeout(std::time::Duration::from_millis(300));
+    registry
+        .preload(&["tests/fixtures/reduced", "tests/fixtures/keepcase/keepcase"])
+        .unwrap();
+    std::thread::sleep(std::time::Duration::from_millis(200));
+    registry.get("tests/fixtures/reduced").unwrap();
+    std::thread::sleep(std::time::Duration::from_millis(200));
+    assert_eq!(1, registry.evict_idle());
+    assert_eq!(vec!["tests/fixtures/reduced"], registry.dictionaries());
+    assert_eq!(0, CheckerRegistry::new().evict_idle());
+
+    // evicted dictionaries are opened again
+    let keepcase = registry.get("tests/fixtures/keepcase/keepcase").unwrap();
+    assert_eq!(Ok(true), keepcase.check("cat"));
+}
+
+#[test]
+fn is_stale_and_reload() {
+    let dir = std::env::temp_dir().join(format!("hunspell-rs-stale-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let affix = dir.join("reduced.aff");
+    let dictionary = dir.join("reduced.dic");
+    std::fs::copy("tests/fixtures/reduced.aff", &affix).unwrap();
+    std::fs::copy("tests/fixtures/reduced.dic", &dictionary).unwrap();
+    let mut hs = SpellChecker::new(&affix, &dictionary).unwrap();
+    hs.ignore("catz");
+    assert_eq!(2, hs.file_stamps().len());
+    assert!(hs.file_stamps()[1].size().is_some());
+    assert!(!hs.is_stale());
+    assert_eq!(Ok(false), hs.check("dogz"));
+
+    let mut contents = std::fs::read_to_string(&dictionary).unwrap();
+    contents.push_str("dogz\n");
+    std::fs::write(&dictionary, contents).unwrap();
+    assert!(hs.is_stale());
+    hs.reload().unwrap();
+    assert!(!hs.is_stale());
+    assert_eq!(Ok(true), hs.check("dogz"));
+    assert_eq!(Ok(true), hs.check("catz"));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert!(hs.is_stale());
+    assert!(hs.reload().is_err());
+    assert_eq!(Ok(true), hs.check("dogz"));
+}
+
+#[cfg(feature = "langdetect")]
+#[test]
+fn language_detection() {
+    let reduced = open_reduced();
+    let extra =
+        SpellChecker::new("tests/fixtures/reduced.aff", "tests/fixtures/extra.dic").unwrap();
+    let text = "Cats, programs and systemdunits. Spectralmagnifier systemdunits cat!";
+    let multi = MultiLanguageChecker::new(vec![reduced, extra]);
+    let words = |misspelled: Vec<crate::Misspelling>| {
+        misspelled.into_iter().map(|m| m.word).collect::<Vec<_>>()
+    };
+    assert_eq!(vec!["and"], words(multi.check_text(text).unwrap()));
+
+    let multi = multi.with_language_detection();
+    assert!(multi.language_detection());
+    let detected = multi.detect_language("cats and programs").unwrap();
+    assert_eq!(vec![&multi.checkers()[0]], detected);
+    assert_eq!(2, multi.detect_language("and or").unwrap().len());
+    let misspelled = multi.check_text(text).unwrap();
+    assert_eq!(
+        vec!["and", "systemdunits", "cat"],
+        words(misspelled.clone())
+    );
+    assert_eq!("cat", &text[misspelled[2].range.clone()]);
+}
+
+#[test]
+fn speller() {
+    fn words_of<S: Speller>(mut speller: S) -> Result<(bool, bool, Vec<String>), Error> {
+        speller.add("octonasaurius")?;
+        let added = speller.check("octonasaurius")?;
+        speller.remove("octonasaurius")?;
+        Ok((
+            added,
+            speller.check("octonasaurius")?,
+            speller.stem("cats")?,
+        ))
+    }
+    let new = || SpellChecker::new("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic");
+    let expected = Ok((true, false, vec!["cat".to_string()]));
+    assert_eq!(expected, words_of(new().unwrap()));
+    assert_eq!(
+        expected,
+        words_of(CachedSpellChecker::new(new().unwrap(), 10))
+    );
+    let mut multi = MultiLanguageChecker::new(vec![new().unwrap(), new().unwrap()]);
+    assert_eq!(expected, words_of(&mut multi));
+    // a shared reference cannot change the speller
+    assert_eq!(Err(Error::ReadOnly), words_of(&multi));
+    assert_eq!(
+        expected,
+        words_of(FallbackChecker::new(new().unwrap(), new().unwrap()))
+    );
+    let boxed: Box<dyn Speller> = Box::new(new().unwrap());
+    assert_eq!(expected, words_of(boxed));
+
+    let frozen = std::sync::Arc::new(new().unwrap().freeze());
+    assert_eq!(Err(Error::ReadOnly), words_of(frozen.clone()));
+    assert_eq!(Ok(vec!["cat".to_string()]), Speller::stem(&frozen, "cats"));
+}
+
+#[test]
+fn analyze_compound() {
+    let hs = SpellChecker::new(
+        "tests/fixtures/compound/compound.aff",
+        "tests/fixtures/compound/compound.dic",
+    )
+    .unwrap();
+    let tag = |name: &str, value: &str| (name.to_string(), value.to_string());
+    let segment = |surface: &str, stem: &str, tags| CompoundSegment {
+        surface: surface.to_string(),
+        stem: stem.to_string(),
+        tags,
+    };
+    assert_eq!(
+        vec![vec![
+            segment("foot", "foot", vec![tag("po", "noun")]),
+            segment(
+                "balls",
+                "ball",
+                vec![tag("po", "noun"), tag("is", "plural")]
+            ),
+        ]],
+        hs.analyze_compound("footballs").unwrap()
+    );
+    assert_eq!(
+        vec![vec![segment(
+            "cats",
+            "cat",
+            vec![tag("po", "noun"), tag("is", "plural")]
+        )]],
+        hs.analyze_compound("cats").unwrap()
+    );
+    assert_eq!(3, hs.analyze_compound("ballfootball").unwrap()[0].len());
+    assert!(hs.analyze_compound("catz").unwrap().is_empty());
+    assert_eq!(
+        vec![segment("x", "y", vec![tag("", "z")])],
+        CompoundSegment::parse("x", "st:y z")
+    );
+}
+
+#[test]
+fn add_validates_words() {
+    let mut hs = open_reduced();
+    let invalid = |word: &str, character| {
+        Err(Error::InvalidWord {
+            word: word.to_string(),
+            character,
+        })
+    };
+    assert_eq!(invalid("", None), hs.add(""));
+    assert_eq!(invalid("two words", Some(' ')), hs.add("two words"));
+    assert_eq!(invalid("a\tb", Some('\t')), hs.add("a\tb"));
+    assert_eq!(invalid("at&t", Some('&')), hs.add_with_affix("at&t", "cat"));
+    assert_eq!(Ok(()), hs.add("o'clock"));
+    assert_eq!(Ok(()), hs.add("self-made"));
+    assert_eq!(Ok(()), hs.add("naïveté"));
+    assert_eq!(Ok(true), hs.check("o'clock"));
+
+    let mut hs = SpellChecker::new(
+        "tests/fixtures/compound/compound.aff",
+        "tests/fixtures/compound/compound.dic",
+    )
+    .unwrap();
+    assert_eq!(Ok(()), hs.add("at&t"));
+
+    let mut hs = SpellChecker::new(
+        "tests/fixtures/latin1/latin1.aff",
+        "tests/fixtures/latin1/latin1.dic",
+    )
+    .unwrap();
+    assert_eq!(Ok(()), hs.add("crème"));
+    assert_eq!(invalid("€uro", Some('€')), hs.add("€uro"));
+}
+
+#[test]
+fn dictionary() {
+    let dictionary =
+        Dictionary::from_files("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic")
+            .with_additional_dictionary("tests/fixtures/extra.dic")
+            .with_key("secret");
+    assert_eq!(Some("secret"), dictionary.key());
+    assert!(!format!("{dictionary:?}").contains("secret"));
+    let dictionary =
+        Dictionary::from_files("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic")
+            .with_additional_dictionary("tests/fixtures/extra.dic");
+    let clone = dictionary.clone();
+    assert_eq!(dictionary, clone);
+    dictionary.validate(&ValidationLimits::default()).unwrap();
+    let mut first = dictionary.open().unwrap();
+    let second = clone.open().unwrap();
+    first.add("octonasaurius").unwrap();
+    assert_eq!(Ok(true), first.check("systemdunits"));
+    assert_eq!(Ok(true), second.check("systemdunits"));
+    assert_eq!(Ok(false), second.check("octonasaurius"));
+
+    let affix = std::fs::read("tests/fixtures/reduced.aff").unwrap();
+    let dic = std::fs::read("tests/fixtures/reduced.dic").unwrap();
+    let in_memory = Dictionary::from_bytes(affix, dic);
+    let limits = ValidationLimits {
+        max_dictionary_size: 4,
+        ..Default::default()
+    };
+    assert!(in_memory.validate(&limits).is_err());
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    assert_eq!(Ok(true), in_memory.open().unwrap().check("cats"));
+
+    assert!(Dictionary::from_files("missing.aff", "missing.dic")
+        .open()
+        .is_err());
+    #[cfg(feature = "serde")]
+    {
+        let serialized = bincode::serialize(&dictionary).unwrap();
+        let deserialized: Dictionary = bincode::deserialize(&serialized).unwrap();
+        assert_eq!(dictionary, deserialized);
+        let serialized = bincode::serialize(&in_memory).unwrap();
+        let deserialized: Dictionary = bincode::deserialize(&serialized).unwrap();
+        assert_eq!(in_memory, deserialized);
+    }
+}
+
+#[test]
+fn affix_file() {
+    use crate::aff::{AffixFile, AffixRule, Directive, FlagDirective, FlagType};
+
+    let contents = "\
+# an affix file
+SET UTF-8
+FLAG long
+TRY aeiou
+LANG en_US
+COMPLEXPREFIXES
+COMPOUNDFLAG Xx
+
+REP 2
+REP f ph
+REP shun   tion
+ICONV 1
+ICONV ’ '
+MAP 1
+MAP aáà
+PFX Re Y 1
+PFX Re 0 re .
+SFX Pl N 2
+SFX Pl 0 s/Re [^sxzhy] is:plural
+SFX Pl y ies [^aeiou]y
+";
+    let aff = AffixFile::parse(contents).unwrap();
+    assert_eq!(Some("UTF-8"), aff.encoding());
+    assert_eq!(FlagType::Long, aff.flag_type());
+    assert_eq!(vec!["Re", "Pl"], aff.flag_type().split("RePl"));
+    assert_eq!(Some("aeiou"), aff.try_chars());
+    assert_eq!(Some("en_US"), aff.setting("LANG"));
+    assert_eq!(Some(""), aff.setting("COMPLEXPREFIXES"));
+    assert_eq!(Some("Xx"), aff.flag(FlagDirective::CompoundFlag));
+    assert_eq!(
+        vec![
+            &("f".to_string(), "ph".to_string()),
+            &("shun".to_string(), "tion".to_string())
+        ],
+        aff.replacements()
+    );
+    assert!(aff.directives.contains(&Directive::InputConversions(vec![(
+        "’".to_string(),
+        "'".to_string()
+    )])));
+    assert!(aff.directives.contains(&Directive::Table {
+        name: "MAP".to_string(),
+        rows: vec![vec!["aáà".to_string()]]
+    }));
+    assert_eq!(1, aff.prefixes().count());
+    let plural = aff.suffixes().next().unwrap();
+    assert!(!plural.cross_product);
+    assert_eq!(
+        AffixRule {
+            strip: String::new(),
+            add: "s".to_string(),
+            continuation: "Re".to_string(),
+            condition: "[^sxzhy]".to_string(),
+            morph_fields: vec!["is:plural".to_string()],
+        },
+        plural.rules[0]
+    );
+    assert_eq!(Directive::Other(String::new()), aff.directives[7]);
+
+    let written = aff.to_string();
+    assert!(written.starts_with("# an affix file\nSET UTF-8\nFLAG long\n"));
+    assert!(written.contains("REP shun tion\n"));
+    assert!(written.contains("SFX Pl 0 s/Re [^sxzhy] is:plural\n"));
+    assert_eq!(aff, AffixFile::parse(&written).unwrap());
+
+    assert_eq!(
+        Err(Error::InvalidAffixFile(
+            "line 1: SFX rule without header".to_string()
+        )),
+        AffixFile::parse("SFX S 0 s .")
+    );
+    assert!(AffixFile::parse("REP 1\nREP f").is_err());
+
+    let latin1 = AffixFile::read("tests/fixtures/latin1/latin1.aff").unwrap();
+    assert!(latin1.try_chars().unwrap().contains('ç'));
+    let path = std::env::temp_dir().join(format!("hunspell-rs-aff-{}.aff", std::process::id()));
+    latin1.write(&path).unwrap();
+    // ç in ISO8859-1
+    assert!(std::fs::read(&path).unwrap().contains(&0xe7));
+    assert_eq!(latin1, AffixFile::read(&path).unwrap());
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn affix_groups() {
+    use crate::aff::{AffixFile, AffixKind, AffixRule};
+
+    let aff = AffixFile::parse(
+        "PFX U Y 1\nPFX U 0 un .\nSFX U N 1\nSFX U 0 ed [^e]\nSFX S Y 2\nSFX S 0 s [^sxzhy]\nSFX S y ies [^aeiou]y\n",
+    )
+    .unwrap();
+    let kinds: Vec<(AffixKind, &str)> = aff
+        .affix_groups()
+        .map(|(kind, group)| (kind, group.flag.as_str()))
+        .collect();
+    assert_eq!(
+        vec![
+            (AffixKind::Prefix, "U"),
+            (AffixKind::Suffix, "U"),
+            (AffixKind::Suffix, "S")
+        ],
+        kinds
+    );
+    let forms: Vec<String> = aff
+        .affix_groups_of("U")
+        .flat_map(|(kind, group)| {
+            group
+                .rules
+                .iter()
+                .filter_map(move |r| r.apply(kind, "lock"))
+        })
+        .collect();
+    assert_eq!(vec!["unlock", "locked"], forms);
+    let plural = aff.affix_groups_of("S").next().unwrap().1;
+    let apply = |word| {
+        plural
+            .rules
+            .iter()
+            .find_map(|r| r.apply(AffixKind::Suffix, word))
+    };
+    assert_eq!(Some("cats".to_string()), apply("cat"));
+    assert_eq!(Some("flies".to_string()), apply("fly"));
+    assert_eq!(None, apply("box"));
+    assert_eq!(None, apply("y"));
+    assert_eq!(0, aff.affix_groups_of("X").count());
+    let broken = AffixRule {
+        condition: "[ab".to_string(),
+        ..Default::default()
+    };
+    assert!(!broken.matches(AffixKind::Suffix, "cab"));
+}
+
+#[test]
+fn add_is_idempotent() {
+    let mut hs = open_reduced();
+    assert!(!hs.contains_added("octonasaurius"));
+    hs.add("octonasaurius").unwrap();
+    let session = hs.session_hash();
+    hs.add("octonasaurius").unwrap();
+    assert_eq!(session, hs.session_hash());
+    assert!(hs.contains_added("octonasaurius"));
+    assert!(hs.clone().contains_added("octonasaurius"));
+    hs.add_with_affix("rust", "cat").unwrap();
+    hs.add_with_affix("rust", "cat").unwrap();
+    assert_eq!(2, hs.runtime_words().len());
+    assert!(hs.contains_added("rust"));
+
+    hs.remove("octonasaurius").unwrap();
+    assert!(!hs.contains_added("octonasaurius"));
+    assert_eq!(Ok(false), hs.check("octonasaurius"));
+    hs.add("octonasaurius").unwrap();
+    assert_eq!(Ok(true), hs.check("octonasaurius"));
+    assert!(!hs.contains_added("cats"));
+}
+
+#[test]
+fn dic_file() {
+    use crate::dic::{DicEntry, DicFile};
+
+    let dic = DicFile::parse("4\ncat/S po:noun\nand\\/or\nwalk/SD\tpo:verb is:base\nNew York\n\n")
+        .unwrap();
+    assert_eq!(
+        vec![
+            DicEntry::new("cat")
+                .with_flags("S")
+                .with_morph_field("po:noun"),
+            DicEntry::new("and/or"),
+            DicEntry::new("walk")
+                .with_flags("SD")
+                .with_morph_field("po:verb")
+                .with_morph_field("is:base"),
+            DicEntry::new("New York"),
+        ],
+        dic.entries
+    );
+    let written = dic.to_string();
+    assert_eq!(
+        "4\ncat/S po:noun\nand\\/or\nwalk/SD po:verb is:base\nNew York\n",
+        written
+    );
+    assert_eq!(dic, DicFile::parse(&written).unwrap());
+    assert!(DicFile::parse("cat/S\n").is_err());
+
+    let latin1 = DicFile::read("tests/fixtures/latin1/latin1.dic", "ISO8859-1").unwrap();
+    assert_eq!(DicEntry::new("café").with_flags("S"), latin1.entries[0]);
+    let dir = std::env::temp_dir().join(format!("hunspell-rs-dic-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::copy("tests/fixtures/latin1/latin1.aff", dir.join("latin1.aff")).unwrap();
+    let mut generated = latin1.clone();
+    generated
+        .entries
+        .push(DicEntry::new("crème").with_flags("S"));
+    generated
+        .write(dir.join("latin1.dic"), "ISO8859-1")
+        .unwrap();
+    let hs = SpellChecker::new(dir.join("latin1.aff"), dir.join("latin1.dic")).unwrap();
+    assert_eq!(Ok(true), hs.check("crèmes"));
+    assert_eq!(Ok(true), hs.check("garçons"));
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn user_profile() {
+    let mut hs = open_reduced();
+    hs.add("octonasaurius").unwrap();
+    hs.add_with_affix("rust", "cat").unwrap();
+    hs.ignore("catz");
+    hs.add_replacement("teh", "the");
+    hs.add_replacement("recieve", "receive");
+    hs.remove_replacement("recieve");
+    let profile = hs.extract_profile();
+    assert_eq!(
+        UserProfile {
+            words: ["octonasaurius", "rust"].map(String::from).into(),
+            ignored: ["catz".to_string()].into(),
+            replacements: [("teh".to_string(), "the".to_string())].into(),
+        },
+        profile
+    );
+    assert_eq!(Some("the".to_string()), hs.clone().replacement("teh"));
+
+    let mut other = open_reduced();
+    other.add("program").unwrap();
+    other.apply_profile(&profile).unwrap();
+    assert_eq!(Ok(true), other.check("octonasaurius"));
+    assert_eq!(Ok(true), other.check("catz"));
+    assert_eq!(None, other.replacement("recieve"));
+    assert_eq!(3, other.extract_profile().words.len());
+    #[cfg(feature = "serde")]
+    {
+        let serialized = bincode::serialize(&profile).unwrap();
+        assert_eq!(profile, bincode::deserialize(&serialized).unwrap());
+    }
+}
+
+#[test]
+fn dictionary_builder() {
+    let builder = DictionaryBuilder::from_words(["ibuprofen", " paracetamol ", "", "ibuprofen"]);
+    assert_eq!(
+        vec!["ibuprofen", "paracetamol"],
+        builder.words().collect::<Vec<_>>()
+    );
+    assert_eq!(
+        "SET UTF-8\nTRY aeoprbcfilmntu\n",
+        builder.affix_file().to_string()
+    );
+    assert_eq!(
+        "2\nibuprofen\nparacetamol\n",
+        builder.dic_file().to_string()
+    );
+
+    let dir = std::env::temp_dir().join(format!("hunspell-rs-builder-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let builder = builder.with_language("en_GB");
+    builder
+        .write(dir.join("terms.aff"), dir.join("terms.dic"))
+        .unwrap();
+    let hs = SpellChecker::new(dir.join("terms.aff"), dir.join("terms.dic")).unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert_eq!(Some(&"en_GB".parse().unwrap()), hs.language());
+    assert_eq!(Ok(true), hs.check("paracetamol"));
+    assert_eq!(Ok(false), hs.check("cats"));
+
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    {
+        let hs = builder.build().open().unwrap();
+        assert_eq!(Ok(true), hs.check("ibuprofen"));
+        assert_eq!(vec!["ibuprofen"], hs.suggest("ibuprofn").unwrap());
+    }
+}
+
+#[test]
+fn dictionary_builder_encoding() {
+    let builder = DictionaryBuilder::from_words(["кот", "собака"])
+        .with_encoding("KOI8-R")
+        .unwrap();
+    assert!(builder.affix_file().to_string().starts_with("SET KOI8-R\n"));
+    let dir = std::env::temp_dir().join(format!("hunspell-rs-builder-koi8-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    builder
+        .write(dir.join("ru.aff"), dir.join("ru.dic"))
+        .unwrap();
+    let dic = std::fs::read(dir.join("ru.dic")).unwrap();
+    let hs = SpellChecker::new(dir.join("ru.aff"), dir.join("ru.dic")).unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert_eq!(b"2\n\xcb\xcf\xd4\n", &dic[..6]);
+    assert_eq!(Ok(true), hs.check("кот"));
+    assert_eq!(Ok(false), hs.check("кит"));
+
+    assert_eq!(
+        Err(Error::UnsupportedEncoding("EBCDIC".to_string())),
+        DictionaryBuilder::from_words(["cat"]).with_encoding("EBCDIC")
+    );
+    assert_eq!(
+        Err(Error::InvalidWord {
+            word: "кот".to_string(),
+            character: Some('к')
+        }),
+        DictionaryBuilder::from_words(["cat", "кот"]).with_encoding("ISO8859-1")
+    );
+    // ISO8859-3 leaves bytes unassigned, U+FFFD is not one of them
+    assert_eq!(
+        Err(Error::InvalidWord {
+            word: "ca\u{fffd}".to_string(),
+            character: Some('\u{fffd}')
+        }),
+        DictionaryBuilder::from_words(["ca\u{fffd}"]).with_encoding("ISO8859-3")
+    );
+}
+
+#[test]
+fn test_suggestions() {
+    let hs = open_reduced();
+    let cases = SuggestionCase::read(
+        "tests/fixtures/suggestions/reduced.wrong",
+        "tests/fixtures/suggestions/reduced.sug",
+    )
+    .unwrap();
+    assert_eq!(SuggestionCase::new("catsz", &["cats", "cat"]), cases[1]);
+    assert_eq!(SuggestionCase::new("qqqqqq", &[]), cases[2]);
+    let report = hs.test_suggestions(&cases).unwrap();
+    assert!(report.is_ok(), "{report}");
+    assert_eq!(3, report.passed);
+
+    let report = hs
+        .test_suggestions(&[
+            SuggestionCase::new("catsz", &["cat", "cats"]),
+            SuggestionCase::new("progam", &["program"]),
+        ])
+        .unwrap();
+    assert!(!report.is_ok());
+    assert_eq!(
+        vec![SuggestionMismatch {
+            word: "catsz".to_string(),
+            expected: vec!["cat".to_string(), "cats".to_string()],
+            actual: vec!["cats".to_string(), "cat".to_string()],
+        }],
+        report.mismatches
+    );
+    assert_eq!(
+        "1 passed, 1 failed\ncatsz: expected cat, cats; got cats, cat",
+        report.to_string()
+    );
+}
+
+#[test]
+fn compare_spellers_reports_divergences() {
+    let reduced = open_reduced();
+    let mut same = open_reduced();
+    assert_eq!(
+        Ok(vec![]),
+        compare_spellers(&reduced, &same, ["cats", "progam"])
+    );
+    same.add("dog").unwrap();
+    let divergences = compare_spellers(&reduced, &same, ["cats", "dog", "dgo"]).unwrap();
+    assert_eq!(
+        Divergence::Check {
+            word: "dog".to_string(),
+            left: false,
+            right: true,
+        },
+        divergences[0]
+    );
+    assert!(divergences[1..]
+        .iter()
+        .all(|d| !matches!(d, Divergence::Check { .. })));
+}
+
+#[test]
+fn export_runtime_words() {
+    let mut spell = open_reduced();
+    spell.add("octonasaurius").unwrap();
+    spell.add_with_affix("dog", "cat").unwrap();
+    spell.add_with_affix("pup", "dog").unwrap();
+    spell.add("removed").unwrap();
+    spell.remove("removed").unwrap();
+    let path = std::env::temp_dir().join(format!("hunspell-rs-export-{}.dic", std::process::id()));
+    spell.export_runtime_words(&path).unwrap();
+    assert_eq!(
+        "3\noctonasaurius\ndog/S\npup/S\n",
+        std::fs::read_to_string(&path).unwrap()
+    );
+    let mut later = open_reduced();
+    later.add_dictionary(&path).unwrap();
+    assert_eq!(Ok(true), later.check("octonasaurius"));
+    assert_eq!(Ok(true), later.check("pups"));
+    assert_eq!(Ok(false), later.check("removed"));
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn build_info_reports_features() {
+    let info = build_info();
+    assert_eq!(cfg!(feature = "bundled"), info.bundled);
+    assert_eq!(cfg!(feature = "bundled"), info.hunspell_version.is_some());
+    assert_eq!(cfg!(feature = "serde"), info.features.contains(&"serde"));
+    let mut sorted = info.features.clone();
+    sorted.sort();
+    assert_eq!(sorted, info.features);
+    assert!(info.to_string().starts_with(&format!(
+        "hunspell-rs {}, hunspell",
+        env!("CARGO_PKG_VERSION")
+    )));
+}
+
+#[test]
+fn panics_become_errors() {
+    struct Panicking;
+
+    impl Speller for Panicking {
+        fn check(&self, word: &str) -> Result<bool, Error> {
+            panic!("cannot check {word}")
+        }
+        fn suggest(&self, _word: &str) -> Result<Vec<String>, Error> {
+            Ok(Vec::new())
+        }
+        fn stem(&self, _word: &str) -> Result<Vec<String>, Error> {
+            Ok(Vec::new())
+        }
+        fn analyze(&self, _word: &str) -> Result<Vec<String>, Error> {
+            Ok(Vec::new())
+        }
+    }
+
+    let mut speller = PanicBoundary::new(Panicking);
+    assert_eq!(
+        Err(Error::Panicked("cannot check cats".to_string())),
+        Speller::check(&speller, "cats")
+    );
+    assert_eq!(Ok(Vec::new()), Speller::suggest(&speller, "cats"));
+    assert_eq!(Err(Error::ReadOnly), Speller::add(&mut speller, "cats"));
+
+    let spell = open_reduced();
+    let result: Result<bool, Error> = catch_panic(|| {
+        let _ = spell.check("cats");
+        panic!("after check")
+    });
+    assert_eq!(Err(Error::Panicked("after check".to_string())), result);
+    assert_eq!(Ok(true), spell.check("cats"));
+}
+
+#[test]
+fn try_clone_fails_without_files() {
+    let dir = std::env::temp_dir().join(format!("hunspell-rs-try-clone-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let affix = dir.join("reduced.aff");
+    let dictionary = dir.join("reduced.dic");
+    std::fs::copy("tests/fixtures/reduced.aff", &affix).unwrap();
+    std::fs::copy("tests/fixtures/reduced.dic", &dictionary).unwrap();
+    let mut spell = SpellChecker::new(&affix, &dictionary).unwrap();
+    spell.add("octonasaurius").unwrap();
+    spell.ignore("catz");
+    let clone = spell.try_clone().unwrap();
+    assert_eq!(Ok(true), clone.check("octonasaurius"));
+    assert!(clone.is_ignored("catz"));
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert!(spell.try_clone().is_err());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn portable_spell_checker() {
+    let dir = std::env::temp_dir().join(format!("hunspell-rs-portable-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let affix = dir.join("reduced.aff");
+    let dictionary = dir.join("reduced.dic");
+    std::fs::copy("tests/fixtures/reduced.aff", &affix).unwrap();
+    std::fs::copy("tests/fixtures/reduced.dic", &dictionary).unwrap();
+    let mut spell = SpellChecker::new(&affix, &dictionary).unwrap();
+    spell.add_dictionary("tests/fixtures/extra.dic").unwrap();
+    spell.add_with_affix("dog", "cat").unwrap();
+    spell.remove("program").unwrap();
+    let serialized = bincode::serialize(&spell.to_portable().unwrap()).unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    let portable: crate::PortableSpellChecker = bincode::deserialize(&serialized).unwrap();
+    assert!(format!("{portable:?}").contains("encrypted: false"));
+    let spell = portable.open().unwrap();
+    assert_eq!(Ok(true), spell.check("dogs"));
+    assert_eq!(Ok(true), spell.check("systemdunits"));
+    assert_eq!(Ok(false), spell.check("program"));
+    assert_eq!(Ok(true), spell.check("cats"));
+}
+
+#[cfg(feature = "zip")]
+#[test]
+fn dictionary_from_extension() {
+    let spell = Dictionary::from_oxt("tests/fixtures/extension/reduced.oxt")
+        .unwrap()
+        .open()
+        .unwrap();
+    assert_eq!(Ok(true), spell.check("programs"));
+    assert_eq!(Ok(false), spell.check("systemdunits"));
+    let spell = Dictionary::from_oxt("tests/fixtures/extension/extra.xpi")
+        .unwrap()
+        .open()
+        .unwrap();
+    assert_eq!(Ok(true), spell.check("systemdunits"));
+    assert_eq!(
+        Err(Error::InvalidExtension("not a zip file".to_string())),
+        Dictionary::from_oxt("tests/fixtures/reduced.aff")
+    );
+}
+
+#[test]
+fn invisible_chars_in_text() {
+    let hs = open_reduced();
+    let text = "pro\u{ad}grams, cat, c\u{200b}ats\u{2060}";
+    assert_eq!(Ok(vec![]), hs.check_text(text));
+    let checker = TextChecker::new(hs).with_invisible_chars(InvisibleChars::Split);
+    assert_eq!(InvisibleChars::Split, checker.invisible_chars());
+    let words: Vec<String> = checker
+        .check_text(text)
+        .unwrap()
+        .into_iter()
+        .map(|m| m.word)
+        .collect();
+    assert_eq!(vec!["pro", "grams", "c", "ats"], words);
+    let checker = checker.with_invisible_chars(InvisibleChars::Strip);
+    let misspellings = checker.check_text("pro\u{ad}gramz").unwrap();
+    assert_eq!(0..10, misspellings[0].range);
+}
+
+#[cfg(feature = "fetch")]
+#[test]
+fn dictionary_fetcher() {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    let cache = std::env::temp_dir().join(format!("hunspell-rs-fetch-{}", std::process::id()));
+    let downloads = Arc::new(AtomicUsize::new(0));
+    let counter = downloads.clone();
+    let fetcher = crate::DictionaryFetcher::new(move |url: &str| {
+        counter.fetch_add(1, Ordering::SeqCst);
+        match url {
+            "https://example.org/dicts/en-US/index.aff" => {
+                Ok(std::fs::read("tests/fixtures/reduced.aff")?)
+            }
+            "https://example.org/dicts/en-US/index.dic" => {
+                Ok(std::fs::read("tests/fixtures/reduced.dic")?)
+            }
+            _ => Err(Error::FetchFailed(format!("{url}: 404"))),
+        }
+    })
+    .with_base_url("https://example.org/dicts/")
+    .with_cache_dir(&cache)
+    .with_checksums(
+        "en-US",
+        "210e12b73c1b485abc16fa55affbfb169ec90dbbeb73f233a1684802f1a347d2",
+        "C1781D5248125593BF4128DAF17634CC3129F37869F16456816BA60ECC63469F",
+    )
+    .with_checksums("nl", "00", "00");
+
+    assert!(!fetcher.is_cached("en-US"));
+    let spell = fetcher.get("en-US").unwrap().open().unwrap();
+    assert_eq!(Ok(true), spell.check("cats"));
+    assert!(fetcher.is_cached("en-US"));
+    fetcher.get("en-US").unwrap();
+    assert_eq!(2, downloads.load(Ordering::SeqCst));
+
+    std::fs::write(cache.join("en-US/index.dic"), "1\ndog\n").unwrap();
+    fetcher.get("en-US").unwrap();
+    assert_eq!(4, downloads.load(Ordering::SeqCst));
+
+    assert_eq!(
+        Err(Error::FetchFailed(
+            "https://example.org/dicts/nl/index.aff: 404".to_string()
+        )),
+        fetcher.get("nl")
+    );
+    assert_eq!(
+        Err(Error::InvalidLanguageTag("../en".to_string())),
+        fetcher.get("../en")
+    );
+    std::fs::remove_dir_all(cache).unwrap();
+}
+
+#[test]
+fn roman_numerals_and_ordinals() {
+    let hs = open_reduced();
+    let text = "cats of Louis XIV, chapter xvii, Xiv, 21st and XIXe";
+    let rules = IgnoreRules {
+        roman_numerals: true,
+        ..Default::default()
+    };
+    let checker = TextChecker::new(hs).with_ignore_rules(rules.clone());
+    let words = |checker: &TextChecker| -> Vec<String> {
+        checker
+            .check_text(text)
+            .unwrap()
+            .into_iter()
+            .map(|m| m.word)
+            .collect()
+    };
+    assert_eq!(
+        vec!["of", "Louis", "chapter", "Xiv", "21st", "and", "XIXe"],
+        words(&checker)
+    );
+    let checker = checker.with_ignore_rules(
+        rules
+            .with_ordinals(&"en".parse().unwrap())
+            .with_ordinals(&"fr".parse().unwrap()),
+    );
+    assert_eq!(
+        vec!["of", "Louis", "chapter", "Xiv", "and"],
+        words(&checker)
+    );
+}
+
+#[test]
+fn suggest_page() {
+    let spell = open_reduced();
+    let all = spell.suggest("catsz").unwrap();
+    let cached = CachedSpellChecker::new(spell, 10);
+    let first = cached.suggest_page("catsz", 0, 1).unwrap();
+    assert_eq!(
+        SuggestionPage {
+            suggestions: all[..1].to_vec(),
+            offset: 0,
+            total: all.len(),
+        },
+        first
+    );
+    assert!(first.has_more());
+    let rest = cached.suggest_page("catsz", 1, 10).unwrap();
+    assert_eq!(all[1..], rest.suggestions);
+    assert!(!rest.has_more());
+    assert!(cached
+        .suggest_page("catsz", 10, 10)
+        .unwrap()
+        .suggestions
+        .is_empty());
+    assert_eq!(Ok(all), cached.suggest("catsz"));
+    assert_eq!(CacheStats { hits: 3, misses: 1 }, cached.stats());
+}
+
+#[test]
+fn config_file() {
+    let config = Config::read("tests/fixtures/spellcheck.toml").unwrap();
+    assert_eq!(
+        Config {
+            dictionary: "tests/fixtures/reduced".to_string(),
+            extra_dictionaries: vec!["tests/fixtures/extra.dic".into()],
+            key: None,
+            ignore_words: vec!["rustc".to_string(), "hunspell".to_string()],
+            syntax: Some("markdown".to_string()),
+        },
+        config
+    );
+    let checker = TextChecker::try_from(&config).unwrap();
+    let words: Vec<String> = checker
+        .check_text("`catz` and rustc cats")
+        .unwrap()
+        .into_iter()
+        .map(|m| m.word)
+        .collect();
+    assert_eq!(vec!["and"], words);
+
+    let config =
+        Config::parse("language = \"en_US\" # comment\nkey = \"a \\\"#\\\" b\"\n").unwrap();
+    assert_eq!("en_US", config.dictionary);
+    assert_eq!(Some("a \"#\" b"), config.key.as_deref());
+    assert_eq!(
+        Err(Error::InvalidConfig("line 2: unknown key lang".to_string())),
+        Config::parse("dictionary = 'en'\nlang = 'en'")
+    );
+    assert_eq!(
+        Err(Error::InvalidConfig(
+            "line 2: expected an array".to_string()
+        )),
+        Config::parse("dictionary = 'en'\nignore_words = 'rustc'")
+    );
+    assert_eq!(
+        Err(Error::InvalidConfig(
+            "no dictionary or language".to_string()
+        )),
+        Config::parse("syntax = 'html'")
+    );
+    let config = Config {
+        dictionary: "tests/fixtures/reduced".to_string(),
+        syntax: Some("rst".to_string()),
+        ..config
+    };
+    assert_eq!(
+        Err(Error::InvalidConfig("unknown syntax rst".to_string())),
+        TextChecker::try_from(&config).map(|_| ())
+    );
+}
+
+#[test]
+fn config_problems() {
+    let problems = match Config::parse("dictionary = en\nlang = 'en'\nsyntax = 'html'") {
+        Err(Error::Multiple(problems)) => problems,
+        other => panic!("expected all problems, got {other:?}"),
+    };
+    assert_eq!(
+        vec![
+            (
+                "line 1".to_string(),
+                Error::InvalidConfig("line 1: expected a string".to_string())
+            ),
+            (
+                "line 2".to_string(),
+                Error::InvalidConfig("line 2: unknown key lang".to_string())
+            ),
+            (
+                "dictionary".to_string(),
+                Error::InvalidConfig("no dictionary or language".to_string())
+            ),
+        ],
+        problems
+    );
+
+    let config = Config {
+        dictionary: "tests/fixtures/reduced".to_string(),
+        extra_dictionaries: vec![
+            "tests/fixtures/missing.dic".into(),
+            "tests/fixtures/extra.dic".into(),
+            "tests/fixtures/gone.dic".into(),
+        ],
+        syntax: Some("rst".to_string()),
+        ..Config::default()
+    };
+    let items: Vec<String> = match TextChecker::try_from(&config) {
+        Err(Error::Multiple(problems)) => problems.into_iter().map(|(item, _)| item).collect(),
+        other => panic!("expected all problems, got {:?}", other.map(|_| ())),
+    };
+    assert_eq!(
+        vec![
+            "syntax",
+            "tests/fixtures/missing.dic",
+            "tests/fixtures/gone.dic"
+        ],
+        items
+    );
+    // without a dictionary, the extra dictionaries are still looked for
+    let config = Config {
+        dictionary: "tests/fixtures/none".to_string(),
+        syntax: None,
+        ..config
+    };
+    let items: Vec<String> = match SpellChecker::try_from(&config) {
+        Err(Error::Multiple(problems)) => problems.into_iter().map(|(item, _)| item).collect(),
+        other => panic!("expected all problems, got {:?}", other.map(|_| ())),
+    };
+    assert_eq!(
+        vec![
+            "tests/fixtures/none",
+            "tests/fixtures/missing.dic",
+            "tests/fixtures/gone.dic"
+        ],
+        items
+    );
+}
+
+#[test]
+fn rejection_kind() {
+    let spell = SpellChecker::new(
+        "tests/fixtures/forbidden/forbidden.aff",
+        "tests/fixtures/forbidden/forbidden.dic",
+    )
+    .unwrap();
+    assert_eq!(Ok(None), spell.rejection_kind("regardless"));
+    assert_eq!(
+        Ok(Some(RejectionKind::Forbidden)),
+        spell.rejection_kind("irregardless")
+    );
+    assert_eq!(
+        Ok(Some(RejectionKind::Case {
+            accepted: "cats".to_string()
+        })),
+        spell.rejection_kind("cATS")
+    );
+    assert_eq!(
+        Ok(Some(RejectionKind::Compound {
+            parts: vec!["cats".to_string(), "regardless".to_string()]
+        })),
+        spell.rejection_kind("catsregardless")
+    );
+    assert_eq!(Ok(Some(RejectionKind::Unknown)), spell.rejection_kind("ca"));
+    assert_eq!(Ok(None), spell.rejection_kind("thrus"));
+}
+
+#[test]
+fn forbidden_and_warn_words() {
+    let mut spell = SpellChecker::new(
+        "tests/fixtures/forbidden/forbidden.aff",
+        "tests/fixtures/forbidden/forbidden.dic",
+    )
+    .unwrap();
+    assert_eq!(Ok(true), spell.is_forbidden("irregardless"));
+    assert_eq!(Ok(false), spell.is_forbidden("regardless"));
+    assert_eq!(Ok(false), spell.is_forbidden("thru"));
+    assert_eq!(Ok(false), spell.is_forbidden("unknown"));
+    assert_eq!(Ok(true), spell.is_warn("thru"));
+    assert_eq!(Ok(true), spell.is_warn("thrus"));
+    assert_eq!(Ok(false), spell.is_warn("cats"));
+    assert_eq!(Ok(false), spell.is_warn("irregardless"));
+    spell.add("irregardless").unwrap();
+    assert_eq!(Ok(true), spell.is_forbidden("irregardless"));
+
+    let plain = open_reduced();
+    assert_eq!(Ok(false), plain.is_forbidden("cat"));
+    assert_eq!(Ok(false), plain.is_warn("cat"));
+}
+
+#[test]
+fn file_report() {
+    let spell = open_reduced();
+    let checker = TextChecker::new(spell);
+    let empty = checker.report("empty.txt", "").unwrap();
+    assert_eq!(0, empty.words);
+    assert_eq!(0.0, empty.per_thousand());
+    let file = checker
+        .report("a.txt", "cats see https://example.org programs")
+        .unwrap();
+    assert_eq!(3, file.words);
+    assert_eq!(
+        vec!["see"],
+        file.misspellings
+            .iter()
+            .map(|m| &m.word)
+            .collect::<Vec<_>>()
+    );
+    let mut report = Report::new();
+    report.push(empty);
+    report.push(file);
+    assert_eq!(3, report.words());
+    assert_eq!(1, report.misspellings());
+    assert!((report.per_thousand() - 333.33).abs() < 0.01);
+    assert!(checker.report_file("tests/fixtures/missing.txt").is_err());
+}
+
+#[test]
+fn stem_detailed() {
+    let spell = SpellChecker::new(
+        "tests/fixtures/stem/stem.aff",
+        "tests/fixtures/stem/stem.dic",
+    )
+    .unwrap();
+    let stems = spell.stem_detailed("rose").unwrap();
+    assert_eq!(
+        vec!["rose", "rise"],
+        stems.iter().map(|s| s.stem.as_str()).collect::<Vec<_>>()
+    );
+    assert_eq!(2, stems[0].analyses.len());
+    assert_eq!(1, stems[1].analyses.len());
+    assert!(stems[1].analyses[0].contains("po:verb"));
+    assert_eq!(
+        vec![StemDetails {
+            stem: "rose".to_string(),
+            analyses: spell.analyze("roses").unwrap(),
+        }],
+        spell.stem_detailed("roses").unwrap()
+    );
+    assert!(spell.stem_detailed("xyz").unwrap().is_empty());
+}
+
+#[test]
+fn stem_and_analyze_all() {
+    let spell = SpellChecker::new(
+        "tests/fixtures/stem/stem.aff",
+        "tests/fixtures/stem/stem.dic",
+    )
+    .unwrap();
+    let words = ["roses", "rose", "xyz", "roses"];
+    let stems = spell.stem_all(words).unwrap();
+    let expected: Vec<Vec<String>> = words
+        .iter()
+        .map(|w| spell.stem(w))
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(expected, stems);
+    let options = BatchOptions { deduplicate: true };
+    assert_eq!(expected, spell.stem_all_with(words, &options).unwrap());
+    let analyses = spell
+        .analyze_all_with(words.iter().copied(), &options)
+        .unwrap();
+    assert_eq!(4, analyses.len());
+    assert_eq!(spell.analyze("rose").unwrap(), analyses[1]);
+    assert!(analyses[2].is_empty());
+    assert_eq!(analyses[0], analyses[3]);
+    assert!(spell.stem_all(["rose", "a\0b"]).is_err());
+}
+
+#[test]
+fn report_compare() {
+    let spell = open_reduced();
+    let checker = TextChecker::new(spell);
+    let mut older = Report::new();
+    older.push(checker.report("a.txt", "catz catz programz").unwrap());
+    older.push(checker.report("b.txt", "dogz").unwrap());
+    let mut newer = Report::new();
+    newer.push(checker.report("a.txt", "cats catz catz catz").unwrap());
+    newer.push(checker.report("b.txt", "catz dogz").unwrap());
+    let diff = newer.compare(&older);
+    let words = |list: &[(std::path::PathBuf, crate::Misspelling)]| {
+        list.iter()
+            .map(|(path, m)| format!("{}:{}", path.display(), m.word))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(vec!["a.txt:catz", "b.txt:catz"], words(&diff.new));
+    assert_eq!(vec!["a.txt:programz"], words(&diff.fixed));
+    assert_eq!(
+        vec!["a.txt:catz", "a.txt:catz", "b.txt:dogz"],
+        words(&diff.persisting)
+    );
+    assert_eq!(15..19, diff.new[0].1.range);
+    assert_eq!(
+        "2 new, 1 fixed, 3 persisting misspellings\n\
+         new: a.txt: catz\n\
+         new: b.txt: catz\n\
+         fixed: a.txt: programz\n",
+        diff.to_string()
+    );
+    let same = older.compare(&older);
+    assert!(same.new.is_empty() && same.fixed.is_empty());
+    assert_eq!(4, same.persisting.len());
+}
+
+#[test]
+fn generate_from_morph() {
+    let spell = SpellChecker::new(
+        "tests/fixtures/stem/stem.aff",
+        "tests/fixtures/stem/stem.dic",
+    )
+    .unwrap();
+    assert_eq!(
+        vec!["roses"],
+        spell.generate_from_morph("rose", &["is:plur"]).unwrap()
+    );
+    assert_eq!(
+        vec!["roses"],
+        spell
+            .generate_from_morph("rose", &spell.analyze("roses").unwrap())
+            .unwrap()
+    );
+    let none: [&str; 0] = [];
+    assert!(spell.generate_from_morph("rose", &none).unwrap().is_empty());
+    assert!(spell.generate_from_morph("rose", &["is:\0"]).is_err());
+}
+
+#[test]
+fn replacements_lead_suggestions() {
+    let spell = open_reduced();
+    let before = spell.session_hash();
+    let plain = spell.suggest("catz").unwrap();
+    spell.add_replacement("catz", "cat");
+    spell.add_replacement("teh", "the");
+    spell.add_replacement("progarm", "program");
+    assert_ne!(before, spell.session_hash());
+    let suggestions = spell.suggest("catz").unwrap();
+    assert_eq!("cat", suggestions[0]);
+    assert_eq!(1, suggestions.iter().filter(|s| *s == "cat").count());
+    assert_eq!(
+        plain.len() + usize::from(!plain.contains(&"cat".to_string())),
+        suggestions.len()
+    );
+    // whole words are trusted, parts of words only when the result is correct
+    assert_eq!("THE", spell.suggest("TEH").unwrap()[0]);
+    assert_eq!("programs", spell.suggest("progarms").unwrap()[0]);
+    assert!(!spell.suggest("tehs").unwrap().contains(&"thes".to_string()));
+    let options = SuggestOptions {
+        timeout: std::time::Duration::from_secs(10),
+        ..Default::default()
+    };
+    assert_eq!(
+        "the",
+        spell.suggest_with_options("teh", &options).unwrap()[0]
+    );
+    let mut reused = vec!["x".to_string(); 20];
+    let n = spell.suggest_into("teh", &mut reused).unwrap();
+    assert_eq!(n, reused.len());
+    assert_eq!("the", reused[0]);
+}
+
+#[cfg(feature = "autocorrect")]
+#[test]
+fn quick_suggest() {
+    use crate::common_correction;
+
+    assert_eq!(Some("a lot".to_string()), common_correction("alot"));
+    assert_eq!(Some("SEPARATE".to_string()), common_correction("SEPERATE"));
+    assert_eq!(None, common_correction("separate"));
+    assert_eq!(None, common_correction(""));
+
+    let spell = open_reduced();
+    assert_eq!(vec!["Receive"], spell.quick_suggest("Recieve").unwrap());
+    spell.add_replacement("recieve", "reprieve");
+    assert_eq!(vec!["reprieve"], spell.quick_suggest("recieve").unwrap());
+    assert_eq!(
+        spell.suggest("progam").unwrap(),
+        spell.quick_suggest("progam").unwrap()
+    );
+
+    let mut german = open_reduced();
+    german.set_language("de".parse().unwrap());
+    assert_eq!(
+        german.suggest("teh").unwrap(),
+        german.quick_suggest("teh").unwrap()
+    );
+}
+
+#[test]
+fn char_map() {
+    let spell =
+        SpellChecker::new("tests/fixtures/map/map.aff", "tests/fixtures/map/map.dic").unwrap();
+    let map = spell.char_map();
+    assert_eq!(2, map.groups.len());
+    assert!(map.are_similar("ç", "c"));
+    assert!(!map.are_similar("é", "c"));
+    assert_eq!(0, map.distance("facade", "façade"));
+    assert_eq!(1, map.distance("facad", "façade"));
+    assert_eq!(
+        vec![Suggestion {
+            word: "façade".to_string(),
+            kind: SuggestionKind::EditDistance(0),
+            source: SuggestionSource::Hunspell(spell.language().cloned()),
+        }],
+        spell.suggest_classified("facade").unwrap()
+    );
+    let options = SuggestOptions {
+        max_distance: Some(0),
+        ..Default::default()
+    };
+    assert_eq!(
+        vec!["café"],
+        spell.suggest_with_options("cafe", &options).unwrap()
+    );
+
+    let reduced = open_reduced();
+    assert!(reduced.char_map().groups.is_empty());
+    assert_eq!(
+        vec!["ss", "ß", "s"],
+        crate::aff::CharMap::parse_row("(ss)(ß)s")
+    );
+}
+
+#[test]
+fn forbidden_words() {
+    use crate::{aff::AffixFile, dic::DicFile};
+
+    let builder = DictionaryBuilder::from_words(["regardless", "irregardless", "cats"])
+        .with_forbidden_words(["irregardless", " "]);
+    assert_eq!(
+        vec!["irregardless"],
+        builder.forbidden_words().collect::<Vec<_>>()
+    );
+    let affix = builder.affix_file();
+    assert!(affix.to_string().contains("FORBIDDENWORD !\n"));
+    let dic = builder.dic_file();
+    assert_eq!(vec!["irregardless"], dic.forbidden_words(&affix));
+
+    // a word forbidden in one dictionary is forbidden in the merged one
+    let mut other = DicFile::parse("3\nirregardless\ndogs\ncats\n").unwrap();
+    other.merge(&dic, &affix);
+    assert_eq!(
+        "4\ndogs\ncats\nirregardless/!\nregardless\n",
+        other.to_string()
+    );
+    // without FORBIDDENWORD the flag means nothing
+    let mut plain = DicFile::parse("1\nirregardless\n").unwrap();
+    plain.merge(&dic, &AffixFile::default());
+    assert_eq!(4, plain.entries.len());
+
+    let dir = std::env::temp_dir().join(format!("hunspell-rs-forbidden-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    builder
+        .write(dir.join("terms.aff"), dir.join("terms.dic"))
+        .unwrap();
+    std::fs::write(dir.join("more.dic"), "1\nirregardless\n").unwrap();
+    let mut spell = SpellChecker::new(dir.join("terms.aff"), dir.join("terms.dic")).unwrap();
+    spell.add_dictionary(dir.join("more.dic")).unwrap();
+    let correct = (spell.check("regardless"), spell.check("irregardless"));
+    let kind = spell.rejection_kind("irregardless");
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert_eq!((Ok(true), Ok(false)), correct);
+    assert_eq!(Ok(Some(RejectionKind::Forbidden)), kind);
+}
+
+#[test]
+fn suggest_ranked() {
+    let spell = SpellChecker::new(
+        "tests/fixtures/rank/rank.aff",
+        "tests/fixtures/rank/rank.dic",
+    )
+    .unwrap();
+    let ranked = |word, layout| {
+        let options = RankingOptions {
+            layout,
+            ..Default::default()
+        };
+        spell.suggest_ranked(word, &options)
+    };
+    // qwerty has no key next to e that is in a suggestion
+    assert_eq!(
+        spell.suggest("cet").unwrap(),
+        ranked("cet", KeyboardLayout::Qwerty).unwrap()
+    );
+    assert_eq!(
+        vec!["cat", "cot", "cut"],
+        ranked("cst", KeyboardLayout::Qwerty).unwrap()
+    );
+    assert_eq!(
+        vec!["cat", "cot", "cut"],
+        ranked("cqt", KeyboardLayout::Azerty).unwrap()
+    );
+    assert_eq!(
+        vec!["Cut", "Cat", "Cot"],
+        ranked("Cyt", KeyboardLayout::Qwerty).unwrap()
+    );
+    let options = RankingOptions {
+        max: 1,
+        ..Default::default()
+    };
+    assert_eq!(vec!["cut"], spell.suggest_ranked("cyt", &options).unwrap());
+
+    assert!(KeyboardLayout::Qwerty.are_neighbours('g', 'B'));
+    assert!(!KeyboardLayout::Qwerty.are_neighbours('g', 'g'));
+    assert!(!KeyboardLayout::Qwerty.are_neighbours('q', 'p'));
+    assert!(KeyboardLayout::Azerty.are_neighbours('a', 'q'));
+    assert!(!KeyboardLayout::Dvorak.are_neighbours('a', 'ж'));
+}
+
+#[test]
+fn combined_spellers() {
+    let rank = || {
+        SpellChecker::new(
+            "tests/fixtures/rank/rank.aff",
+            "tests/fixtures/rank/rank.dic",
+        )
+    };
+
+    let both = open_reduced().and(rank().unwrap());
+    assert!(both.check("cat").unwrap());
+    assert!(!both.check("cats").unwrap());
+    assert!(!both.check("cot").unwrap());
+    assert_eq!(vec!["cat"], both.suggest("catz").unwrap());
+
+    let mut either = open_reduced().or(rank().unwrap());
+    assert!(either.check("cats").unwrap());
+    assert!(either.check("cot").unwrap());
+    assert!(!either.check("cots").unwrap());
+    either.add("cots").unwrap();
+    assert!(either.check("cots").unwrap());
+
+    let banned = rank().unwrap();
+    let mut allowed = open_reduced().not_in(&banned);
+    assert!(!allowed.check("cat").unwrap());
+    assert!(allowed.check("cats").unwrap());
+    assert_eq!(vec!["cat"], open_reduced().suggest("catz").unwrap());
+    assert!(allowed.suggest("catz").unwrap().is_empty());
+    allowed.add("cat").unwrap();
+    assert!(!allowed.check("cat").unwrap());
+
+    // composites compose
+    let nested = open_reduced().or(rank().unwrap()).not_in(&banned);
+    assert!(nested.check("program").unwrap());
+    assert!(!nested.check("cot").unwrap());
+    let boxed: Box<dyn Speller> = Box::new(open_reduced().and(rank().unwrap()));
+    assert!(boxed.check("cat").unwrap());
+}
+
+#[test]
+fn suggest_by_frequency() {
+    let spell = SpellChecker::new(
+        "tests/fixtures/frequency/frequency.aff",
+        "tests/fixtures/frequency/frequency.dic",
+    )
+    .unwrap();
+    spell
+        .read_frequencies("tests/fixtures/frequency/frequency.freq")
+        .unwrap();
+    assert_eq!(1000, spell.frequency("the"));
+    assert_eq!(1000, spell.frequency("The"));
+    assert_eq!(0, spell.frequency("hate"));
+    assert_eq!(
+        vec!["the", "he", "hue", "hoe", "hate"],
+        spell.suggest_by_frequency("hte").unwrap()
+    );
+    assert_eq!(
+        vec!["The", "He", "Hue", "Hoe", "Hate"],
+        spell.suggest_by_frequency("Hte").unwrap()
+    );
+
+    spell.add_frequencies([("hate", 600)]);
+    assert_eq!(
+        vec!["the", "hate", "he", "hue", "hoe"],
+        spell.suggest_by_frequency("hte").unwrap()
+    );
+    assert_eq!(600, spell.try_clone().unwrap().frequency("hate"));
+    spell.clear_frequencies();
+    assert_eq!(
+        spell.suggest("hte").unwrap(),
+        spell.suggest_by_frequency("hte").unwrap()
+    );
+
+    let dir = std::env::temp_dir().join(format!("hunspell-rs-frequency-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let invalid = dir.join("invalid.freq");
+    std::fs::write(&invalid, "the 1000\nhe many\n").unwrap();
+    assert_eq!(
+        Err(Error::InvalidFrequencyList("line 2: he many".to_string())),
+        spell.read_frequencies(&invalid)
+    );
+    assert_eq!(0, spell.frequency("the"));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn budgeted_text_check() {
+    let spell = open_reduced();
+    let checker = TextChecker::new(spell);
+    let text = "cats catz https://catz.org program catz";
+    // without time, one word is checked per call
+    let check = checker
+        .check_text_within(text, std::time::Duration::ZERO)
+        .unwrap();
+    assert_eq!(Some(5), check.resume_at);
+    assert!(check.misspellings.is_empty());
+    let check = checker
+        .resume_check(text, check, std::time::Duration::ZERO)
+        .unwrap();
+    assert_eq!(Some(27), check.resume_at);
+    assert_eq!(1, check.misspellings.len());
+    let check = checker
+        .resume_check(text, check, std::time::Duration::ZERO)
+        .unwrap();
+    let check = checker
+        .resume_check(text, check, std::time::Duration::ZERO)
+        .unwrap();
+    assert!(check.is_complete());
+    assert_eq!(checker.check_text(text).unwrap(), check.misspellings);
+    assert_eq!(
+        check.clone(),
+        checker
+            .resume_check(text, check, std::time::Duration::ZERO)
+            .unwrap()
+    );
+
+    let check = checker
+        .check_text_within(text, std::time::Duration::from_secs(60))
+        .unwrap();
+    assert!(check.is_complete());
+    assert_eq!(checker.check_text(text).unwrap(), check.misspellings);
+}
+
+#[test]
+fn spell_report() {
+    let reduced = open_reduced();
+    let rank = SpellChecker::new(
+        "tests/fixtures/rank/rank.aff",
+        "tests/fixtures/rank/rank.dic",
+    )
+    .unwrap();
+    let languages = (reduced.language().cloned(), rank.language().cloned());
+    let text = "cats cot cat catz https://catz.org cotz catz";
+
+    let report = TextChecker::new(reduced.clone())
+        .spell_report(text)
+        .unwrap();
+    assert_eq!(6, report.words);
+    assert_eq!(
+        vec![("cot", 1), ("catz", 2), ("cotz", 1)],
+        report
+            .misspellings
+            .iter()
+            .map(|m| (m.word.as_str(), m.ranges.len()))
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(4, report.occurrences());
+    assert_eq!(
+        vec![LanguageCount {
+            language: languages.0.clone(),
+            words: 2
+        }],
+        report.languages
+    );
+
+    let multi = MultiLanguageChecker::new(vec![reduced, rank]);
+    let report = multi.spell_report(text).unwrap();
+    assert_eq!(6, report.words);
+    assert_eq!(
+        vec!["catz", "cotz"],
+        report
+            .misspellings
+            .iter()
+            .map(|m| &m.word)
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        vec![
+            LanguageCount {
+                language: languages.0,
+                words: 2
+            },
+            LanguageCount {
+                language: languages.1,
+                words: 1
+            }
+        ],
+        report.languages
+    );
+    assert!(!report.is_clean());
+    assert!(multi.spell_report("cats cot").unwrap().is_clean());
+    #[cfg(feature = "serde")]
+    {
+        let serialized = bincode::serialize(&report).unwrap();
+        assert_eq!(report, bincode::deserialize(&serialized).unwrap());
+    }
+}
+
+#[test]
+fn prioritized_text_check() {
+    let spell = open_reduced();
+    let checker = TextChecker::new(spell);
+    let text = "catz cats cotz program catz";
+    // the viewport starts in the middle of cotz and ends in program
+    let check = checker
+        .check_text_prioritized(text, 12..17, std::time::Duration::ZERO)
+        .unwrap();
+    assert_eq!(
+        vec!["catz", "cotz"],
+        check
+            .misspellings
+            .iter()
+            .map(|m| &m.word)
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(Some(5), check.resume_at);
+    let mut check = checker
+        .resume_check(text, check, std::time::Duration::ZERO)
+        .unwrap();
+    // cotz and program are not checked again
+    assert_eq!(Some(23), check.resume_at);
+    while !check.is_complete() {
+        check = checker
+            .resume_check(text, check, std::time::Duration::ZERO)
+            .unwrap();
+    }
+    assert_eq!(checker.check_text(text).unwrap(), check.misspellings);
+
+    let check = checker
+        .check_text_prioritized(text, 0..0, std::time::Duration::from_secs(60))
+        .unwrap();
+    assert!(check.is_complete());
+    assert_eq!(checker.check_text(text).unwrap(), check.misspellings);
+}
+
+#[test]
+fn diagnostics() {
+    let spell = open_reduced();
+    let checker = TextChecker::new(spell);
+    let mut diagnostics = Diagnostics::new();
+    assert_eq!("[]", diagnostics.to_json());
+    diagnostics.extend(
+        checker
+            .diagnostics("docs/a \"b\".txt", "cats\n  çatz programz")
+            .unwrap(),
+    );
+    assert_eq!(2, diagnostics.diagnostics.len());
+    let first = &diagnostics.diagnostics[0];
+    assert_eq!((2, 3), (first.line, first.column));
+    assert_eq!(7..12, first.range);
+    assert_eq!(
+        "unknown word `programz`, did you mean `program` or `programs`?",
+        Diagnostic {
+            word: "programz".to_string(),
+            suggestions: vec!["program".to_string(), "programs".to_string()],
+            ..first.clone()
+        }
+        .message()
+    );
+    assert_eq!(
+        "unknown word `x`",
+        Diagnostic {
+            word: "x".to_string(),
+            suggestions: Vec::new(),
+            ..first.clone()
+        }
+        .message()
+    );
+
+    let json = diagnostics.to_json();
+    assert!(json.starts_with(r#"[{"file":"docs/a \"b\".txt","range":[7,12],"line":2,"column":3,"#));
+    let sarif = diagnostics.to_sarif();
+    assert!(sarif.contains(r#""uri":"docs/a%20%22b%22.txt""#));
+    assert!(sarif.contains(
+        r#""region":{"startLine":2,"startColumn":3,"endLine":2,"endColumn":7,"byteOffset":7,"byteLength":5}"#
+    ));
+    assert_eq!(sarif.matches(r#""ruleId":"misspelling""#).count(), 2);
+    assert_eq!(sarif.matches('{').count(), sarif.matches('}').count());
+}
+
+#[test]
+fn highlight_writer() {
+    use std::io::Write;
+
+    let spell = open_reduced();
+    let mut writer = HighlightWriter::new(&spell, Vec::new()).with_style("<");
+    writer.write_all(b"cats catz\nprog").unwrap();
+    // the unfinished word is held back
+    assert_eq!(b"cats <catz\x1b[0m\n", &writer.get_ref()[..]);
+    writer
+        .write_all("rams programz https://catz.org \u{e9}".as_bytes())
+        .unwrap();
+    writer.write_all(&[0xff, b' ', 0xc3]).unwrap();
+    writer.write_all(&[0xa9, b'\n']).unwrap();
+    writer.flush().unwrap();
+    let mut expected =
+        b"cats <catz\x1b[0m\nprograms <programz\x1b[0m https://catz.org <\xc3\xa9\x1b[0m".to_vec();
+    expected.extend_from_slice(&[0xff, b' ', b'<', 0xc3, 0xa9]);
+    expected.extend_from_slice(b"\x1b[0m\n");
+    assert_eq!(expected, writer.get_ref()[..]);
+
+    // the rest is written when the writer is dropped
+    let mut output = Vec::new();
+    {
+        let mut writer = HighlightWriter::new(&spell, &mut output);
+        writer.write_all(b"cats catz").unwrap();
+    }
+    assert_eq!(b"cats \x1b[4;31mcatz\x1b[0m", &output[..]);
+}
+
+#[test]
+fn check_stream() {
+    let spell = open_reduced();
+    let mut text = "cats catz programs\nprogramz çatz "
+        .repeat(500)
+        .into_bytes();
+    text.extend_from_slice(&[0xff, b' ']);
+    text.extend_from_slice("catz".as_bytes());
+    let expected = spell.check_text(&String::from_utf8_lossy(&text)).unwrap();
+    // a tiny buffer splits words and characters between reads
+    let reader = std::io::BufReader::with_capacity(3, &text[..]);
+    let streamed = spell
+        .check_stream(reader)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(1501, streamed.len());
+    assert_eq!(
+        expected.iter().map(|m| &m.word).collect::<Vec<_>>(),
+        streamed.iter().map(|m| &m.word).collect::<Vec<_>>()
+    );
+    let last = streamed.last().unwrap();
+    assert_eq!(&text[last.range.clone()], b"catz");
+    assert_eq!(&text[streamed[1].range.clone()], b"programz");
+
+    struct Failing;
+    impl std::io::Read for Failing {
+        fn read(&mut self, _: &mut [u8]) -> std::io::Result<usize> {
+            Err(std::io::ErrorKind::BrokenPipe.into())
+        }
+    }
+    let mut stream = spell.check_stream(std::io::BufReader::new(Failing));
+    assert!(matches!(
+        stream.next(),
+        Some(Err(Error::IoError(e))) if e.kind() == std::io::ErrorKind::BrokenPipe
+    ));
+    assert_eq!(None, stream.next());
+}
+
+#[test]
+fn document_checker() {
+    let spell = open_reduced();
+    let checker = || TextChecker::new(spell.clone());
+    let mut document =
+        DocumentChecker::new(checker(), "cats catz\nprograms\nprogramz cats").unwrap();
+    let words = |misspellings: &[crate::Misspelling]| {
+        misspellings
+            .iter()
+            .map(|m| (m.word.clone(), m.range.clone()))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        vec![("catz".to_string(), 5..9), ("programz".to_string(), 19..27)],
+        words(&document.misspellings())
+    );
+
+    // a new misspelling in the middle line, the last line moves along
+    let changes = document.edit(10..10, "catz ").unwrap();
+    assert_eq!(vec![("catz".to_string(), 10..14)], words(&changes.added));
+    assert!(changes.removed.is_empty());
+    assert_eq!("cats catz\ncatz programs\nprogramz cats", document.text());
+
+    // typing in a line keeps the misspellings of the line that moved
+    let changes = document.edit(0..0, "x").unwrap();
+    assert_eq!(vec![("xcats".to_string(), 0..5)], words(&changes.added));
+    assert!(changes.removed.is_empty());
+    let changes = document.edit(0..1, "").unwrap();
+    assert_eq!(vec![("xcats".to_string(), 0..5)], words(&changes.removed));
+    assert!(changes.added.is_empty());
+
+    // joining lines
+    let changes = document.edit(9..15, "").unwrap();
+    assert_eq!(
+        vec![("catz".to_string(), 5..9), ("catz".to_string(), 10..14)],
+        words(&changes.removed)
+    );
+    assert_eq!(
+        vec![("catzprograms".to_string(), 5..17)],
+        words(&changes.added)
+    );
+    assert_eq!("cats catzprograms\nprogramz cats", document.text());
+
+    // splitting a line, and unicode
+    let changes = document.edit(9..9, "\nçatz ").unwrap();
+    assert_eq!(
+        vec![("catz".to_string(), 5..9), ("çatz".to_string(), 10..15)],
+        words(&changes.added)
+    );
+    assert_eq!(
+        vec![("catzprograms".to_string(), 5..17)],
+        words(&changes.removed)
+    );
+
+    let full = DocumentChecker::new(checker(), document.text()).unwrap();
+    assert_eq!(full.misspellings(), document.misspellings());
+    assert_eq!(
+        checker().check_text(document.text()).unwrap(),
+        document.misspellings()
+    );
+
+    assert_eq!(Err(Error::InvalidEdit(11..12)), document.edit(11..12, ""));
+    assert_eq!(Err(Error::InvalidEdit(0..100)), document.edit(0..100, ""));
+    let end = document.text().len();
+    assert!(document.edit(end..end, "\n").unwrap().is_empty());
+}
+
+#[test]
+fn classify_tokens() {
+    use crate::{Token, TokenKind};
+
+    let text = "see https://me@example.org/a.html, 0xFF… and ½\u{ad}ly 2nd ☺";
+    let tokens: Vec<(TokenKind, &str)> = crate::classify_tokens(text)
+        .into_iter()
+        .map(|t| (t.kind, &text[t.range]))
+        .collect();
+    assert_eq!(
+        vec![
+            (TokenKind::Word, "see"),
+            (TokenKind::Url, "https://me@example.org/a.html"),
+            (TokenKind::Punctuation, ","),
+            (TokenKind::Code, "0xFF"),
+            (TokenKind::Punctuation, "…"),
+            (TokenKind::Word, "and"),
+            (TokenKind::Word, "½\u{ad}ly"),
+            (TokenKind::Word, "2nd"),
+            (TokenKind::Punctuation, "☺"),
+        ],
+        tokens
+    );
+    assert_eq!(
+        vec![Token {
+            kind: TokenKind::Number,
+            range: 0..3
+        }],
+        crate::classify_tokens("123")
+    );
+    assert!(crate::classify_tokens(" \n").is_empty());
+}
+
+#[cfg(feature = "lsp")]
+#[test]
+fn spelling_server() {
+    use crate::{PersonalDictionary, SpellingServer, ADD_TO_DICTIONARY};
+    use lsp_types::{
+        CodeActionContext, CodeActionOrCommand, CodeActionParams, DidChangeTextDocumentParams,
+        DidCloseTextDocumentParams, DidOpenTextDocumentParams, ExecuteCommandParams, Position,
+        Range, TextDocumentContentChangeEvent, TextDocumentIdentifier, TextDocumentItem, Uri,
+        VersionedTextDocumentIdentifier,
+    };
+
+    let spell = open_reduced();
+    let path = std::env::temp_dir().join(format!("hunspell-rs-lsp-{}.dic", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let mut server = SpellingServer::new(TextChecker::new(spell))
+        .with_personal_dictionary(PersonalDictionary::new(&path))
+        .unwrap();
+    let uri: Uri = "file:///notes.txt".parse().unwrap();
+    let ranges = |diagnostics: &[lsp_types::Diagnostic]| {
+        diagnostics
+            .iter()
+            .map(|d| {
+                (
+                    d.range.start.line,
+                    d.range.start.character,
+                    d.range.end.character,
+                )
+            })
+            .collect::<Vec<_>>()
+    };
+
+    // positions count UTF-16 code units, 𝄞 has two
+    let published = server
+        .did_open(DidOpenTextDocumentParams {
+            text_document: TextDocumentItem::new(
+                uri.clone(),
+                "plaintext".to_string(),
+                1,
+                "cats catz\n𝄞 catz programz".to_string(),
+            ),
+        })
+        .unwrap();
+    assert_eq!(Some(1), published.version);
+    assert_eq!(
+        vec![(0, 5, 9), (1, 3, 7), (1, 8, 16)],
+        ranges(&published.diagnostics)
+    );
+    assert_eq!("unknown word `catz`", published.diagnostics[0].message);
+
+    let published = server
+        .did_change(DidChangeTextDocumentParams {
+            text_document: VersionedTextDocumentIdentifier::new(uri.clone(), 2),
+            content_changes: vec![TextDocumentContentChangeEvent {
+                range: Some(Range::new(Position::new(1, 6), Position::new(1, 7))),
+                range_length: None,
+                text: "s".to_string(),
+            }],
+        })
+        .unwrap();
+    assert_eq!(Some(2), published.version);
+    assert_eq!(vec![(0, 5, 9), (1, 8, 16)], ranges(&published.diagnostics));
+    assert_eq!(Some("cats catz\n𝄞 cats programz"), server.text(&uri));
+
+    // the cursor at the end of a word
+    let params = CodeActionParams {
+        text_document: TextDocumentIdentifier::new(uri.clone()),
+        range: Range::new(Position::new(0, 9), Position::new(0, 9)),
+        context: CodeActionContext::default(),
+        work_done_progress_params: Default::default(),
+        partial_result_params: Default::default(),
+    };
+    let actions: Vec<_> = server
+        .code_actions(&params)
+        .unwrap()
+        .into_iter()
+        .map(|a| match a {
+            CodeActionOrCommand::CodeAction(a) => a,
+            CodeActionOrCommand::Command(_) => panic!("a command"),
+        })
+        .collect();
+    let titles: Vec<&str> = actions.iter().map(|a| a.title.as_str()).collect();
+    assert_eq!(
+        vec!["Replace with `cat`", "Add `catz` to the dictionary"],
+        titles
+    );
+    let edit = &actions[0].edit.as_ref().unwrap().changes.as_ref().unwrap()[&uri][0];
+    assert_eq!("cat", edit.new_text);
+    assert_eq!(
+        Range::new(Position::new(0, 5), Position::new(0, 9)),
+        edit.range
+    );
+    assert_eq!(Some(true), actions[0].is_preferred);
+
+    let command = actions[1].command.clone().unwrap();
+    assert_eq!(ADD_TO_DICTIONARY, command.command);
+    let published = server
+        .execute_command(ExecuteCommandParams {
+            command: command.command,
+            arguments: command.arguments.unwrap(),
+            work_done_progress_params: Default::default(),
+        })
+        .unwrap();
+    assert_eq!(1, published.len());
+    assert_eq!(vec![(1, 8, 16)], ranges(&published[0].diagnostics));
+    assert_eq!(
+        vec!["catz"],
+        PersonalDictionary::open(&path)
+            .unwrap()
+            .words()
+            .collect::<Vec<_>>()
+    );
+    std::fs::remove_file(&path).unwrap();
+
+    // a word that cannot be added leaves the documents open
+    assert!(server
+        .execute_command(ExecuteCommandParams {
+            command: ADD_TO_DICTIONARY.to_string(),
+            arguments: vec![serde_json::Value::String("cat\0z".to_string())],
+            work_done_progress_params: Default::default(),
+        })
+        .is_err());
+    assert_eq!(Some("cats catz\n𝄞 cats programz"), server.text(&uri));
+    assert_eq!(vec![(1, 8, 16)], ranges(&server.diagnostics(&uri).unwrap()));
+
+    assert_eq!(
+        Err(Error::UnknownCommand("rm".to_string())),
+        server.execute_command(ExecuteCommandParams {
+            command: "rm".to_string(),
+            arguments: Vec::new(),
+            work_done_progress_params: Default::default(),
+        })
+    );
+    let closed = server.did_close(DidCloseTextDocumentParams {
+        text_document: TextDocumentIdentifier::new(uri.clone()),
+    });
+    assert!(closed.diagnostics.is_empty());
+    assert_eq!(None, server.diagnostics(&uri));
+    assert_eq!(
+        Err(Error::UnknownDocument("file:///notes.txt".to_string())),
+        server.code_actions(&params)
+    );
+}
+
+#[test]
+fn tokenizer_profiles() {
+    use crate::TokenizerProfile;
+
+    let mut spell = open_reduced();
+    let words = |misspellings: Vec<crate::Misspelling>| {
+        misspellings.into_iter().map(|m| m.word).collect::<Vec<_>>()
+    };
+    let text = "Dr. cats'll see L’programs";
+    assert_eq!(
+        vec!["Dr.", "cats'll", "see", "L’programs"],
+        words(TextChecker::new(spell.clone()).check_text(text).unwrap())
+    );
+
+    // selected by the language of the dictionary
+    spell.set_language("en_GB".parse().unwrap());
+    let english = TextChecker::new(spell.clone());
+    assert!(english
+        .tokenizer_profile()
+        .abbreviations
+        .contains(&"Dr".to_string()));
+    assert_eq!(
+        vec!["see", "L’programs"],
+        words(english.check_text(text).unwrap())
+    );
+    let french = english
+        .with_tokenizer_profile(TokenizerProfile::for_language(&"fr".parse().unwrap()).unwrap());
+    assert_eq!(
+        vec!["cats'll", "see"],
+        words(french.check_text(text).unwrap())
+    );
+
+    // custom profiles
+    let profile = TokenizerProfile {
+        word_chars: ":".to_string(),
+        clitic_suffixes: vec![":n".to_string(), String::new()],
+        ..TokenizerProfile::default()
+    };
+    let custom = TextChecker::new(spell).with_tokenizer_profile(profile);
+    assert_eq!(
+        vec!["cats:programs", "don", "t"],
+        words(custom.check_text("cats:programs cat:n don't").unwrap())
+    );
+    assert_eq!(None, TokenizerProfile::for_language(&"xx".parse().unwrap()));
+}
+
+#[test]
+fn max_suggest_length() {
+    let mut spell = open_reduced();
+    assert_eq!(None, spell.max_suggest_length());
+    spell.add_replacement("catzzz", "cats");
+    spell.set_max_suggest_length(Some(5));
+    assert_eq!(vec!["cat"], spell.suggest("catz").unwrap());
+    // characters count, not bytes
+    spell.add_replacement("çatzz", "cats");
+    assert_eq!("cats", spell.suggest("çatzz").unwrap()[0]);
+    assert!(spell.suggest("catzzz").unwrap().is_empty());
+    let mut suggestions = vec!["old".to_string()];
+    assert_eq!(0, spell.suggest_into("catzzz", &mut suggestions).unwrap());
+    assert!(suggestions.is_empty());
+    let options = SuggestOptions {
+        timeout: std::time::Duration::from_secs(10),
+        ..SuggestOptions::default()
+    };
+    assert!(spell
+        .suggest_with_options("programz", &options)
+        .unwrap()
+        .is_empty());
+    assert_eq!(Some(5), spell.try_clone().unwrap().max_suggest_length());
+    spell.set_max_suggest_length(None);
+    assert_eq!(vec!["cats"], spell.suggest("catzzz").unwrap());
+}
+
+#[test]
+fn spell_checker_pool() {
+    use crate::SpellCheckerPool;
+    use std::{sync::Arc, time::Duration};
+
+    let dictionary =
+        Dictionary::from_files("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic");
+    assert_eq!(1, SpellCheckerPool::new(&dictionary, 0).unwrap().size());
+    let pool = Arc::new(SpellCheckerPool::new(&dictionary, 2).unwrap());
+    assert_eq!(2, pool.available());
+
+    let first = pool.get();
+    let second = pool.try_get().unwrap();
+    assert!(pool.try_get().is_none());
+    assert_eq!(
+        Err(Error::Timeout(Duration::from_millis(20))),
+        pool.get_timeout(Duration::from_millis(20)).map(|_| ())
+    );
+    assert_eq!(Ok(true), second.check("programs"));
+
+    // a waiting thread gets the checker that comes back
+    let waiting = {
+        let pool = pool.clone();
+        std::thread::spawn(move || pool.get().check("catz"))
+    };
+    std::thread::sleep(Duration::from_millis(50));
+    drop(first);
+    assert_eq!(Ok(false), waiting.join().unwrap());
+    drop(second);
+    assert_eq!(2, pool.available());
+
+    assert!(SpellCheckerPool::new(
+        &Dictionary::from_files("tests/fixtures/missing.aff", "tests/fixtures/missing.dic"),
+        2
+    )
+    .is_err());
+
+    // the contents of a dictionary from memory are loaded once
+    let memory = Dictionary::from_bytes(
+        std::fs::read("tests/fixtures/reduced.aff").unwrap(),
+        std::fs::read("tests/fixtures/reduced.dic").unwrap(),
+    );
+    let pool = SpellCheckerPool::new(&memory, 3).unwrap();
+    let (a, b) = (pool.get(), pool.get());
+    assert_eq!(2, a.memory_files.len());
+    assert!(a
+        .memory_files
+        .iter()
+        .zip(&b.memory_files)
+        .all(|(a, b)| Arc::ptr_eq(a, b)));
+    assert_eq!(Ok(true), b.check("cats"));
+}
+
+#[test]
+fn correction_tables() {
+    use crate::{CompactCorrections, CorrectionTable};
+    use std::collections::HashMap;
+
+    let pairs: Vec<(String, String)> = (0..20_000)
+        .map(|i| (format!("typo{i}"), format!("fix{i}")))
+        .collect();
+    let table = CompactCorrections::new(pairs.iter().map(|(a, b)| (a, b)));
+    assert_eq!(20_000, table.len());
+    for (from, to) in &pairs {
+        assert_eq!(Some(to.as_str()), table.correction(from));
+    }
+    assert_eq!(None, table.correction("typo20000"));
+    assert_eq!(None, table.correction("fix1"));
+
+    let table = CompactCorrections::new([("teh", "ten"), ("ädress", "address"), ("teh", "the")]);
+    assert_eq!(2, table.len());
+    assert_eq!(Some("the"), table.correction("teh"));
+    assert_eq!(Some("address"), table.correction("ädress"));
+    assert!(CompactCorrections::new(Vec::<(&str, &str)>::new()).is_empty());
+    assert_eq!(
+        None,
+        CompactCorrections::new(Vec::<(&str, &str)>::new()).correction("")
+    );
+
+    let mut spell = open_reduced();
+    assert!(spell.correction_table().is_none());
+    let suggestions = spell.suggest("catz").unwrap();
+    spell.set_correction_table(HashMap::from([("catz".to_string(), "cats".to_string())]));
+    assert_eq!(
+        Some("cats"),
+        spell.correction_table().unwrap().correction("catz")
+    );
+    assert_eq!("cats", spell.suggest("catz").unwrap()[0]);
+    assert_eq!("Cats", spell.suggest("Catz").unwrap()[0]);
+    assert_eq!(
+        "cats",
+        spell.try_clone().unwrap().suggest("catz").unwrap()[0]
+    );
+    // replacements come first
+    spell.add_replacement("catz", "cat");
+    assert_eq!("cat", spell.suggest("catz").unwrap()[0]);
+    spell.remove_replacement("catz");
+    spell.clear_correction_table();
+    assert_eq!(suggestions, spell.suggest("catz").unwrap());
+}
+
+#[test]
+fn raw_handle() {
+    let affix = CString::new("tests/fixtures/reduced.aff").unwrap();
+    let dictionary = CString::new("tests/fixtures/reduced.dic").unwrap();
+    let handle = unsafe { ffi::Hunspell_create(affix.as_ptr(), dictionary.as_ptr()) };
+    let spell = unsafe {
+        SpellChecker::from_raw(
+            handle,
+            "tests/fixtures/reduced.aff",
+            "tests/fixtures/reduced.dic",
+        )
+        .unwrap()
+    };
+    assert_eq!(handle, unsafe { spell.as_raw() });
+    assert!(spell.check("cats").unwrap());
+    let word = CString::new("catz").unwrap();
+    assert_eq!(0, unsafe {
+        ffi::Hunspell_add(spell.as_raw(), word.as_ptr())
+    });
+    assert!(spell.check("catz").unwrap());
+    // clones have a handle of their own
+    let clone = spell.try_clone().unwrap();
+    assert_ne!(unsafe { spell.as_raw() }, unsafe { clone.as_raw() });
+    assert!(clone.check("cats").unwrap());
+    assert!(matches!(
+        unsafe { SpellChecker::from_raw(std::ptr::null_mut(), "a.aff", "a.dic") },
+        Err(Error::HandleCreationFailed { .. })
+    ));
+}
+
+#[cfg(feature = "dylib")]
+#[test]
+fn missing_hunspell_library() {
+    assert!(matches!(
+        crate::load_hunspell_library("tests/fixtures/libhunspell-missing.so"),
+        Err(Error::LibraryLoading(_))
+    ));
+    // the library that is in use keeps working
+    let spell = open_reduced();
+    assert_eq!(Ok(true), spell.check("cats"));
+}
+
+#[test]
+fn strict_validation() {
+    let strict = ValidationLimits {
+        strict: true,
+        ..Default::default()
+    };
+    for name in ["compound", "forbidden", "keepcase", "latin1", "map", "stem"] {
+        validate_dictionary(
+            format!("tests/fixtures/{name}/{name}.aff"),
+            format!("tests/fixtures/{name}/{name}.dic"),
+            &strict,
+        )
+        .unwrap();
+    }
+
+    let dir = std::env::temp_dir().join(format!("hunspell-rs-strict-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let affix = dir.join("strict.aff");
+    let dictionary = dir.join("strict.dic");
+    std::fs::write(
+        &affix,
+        "# comment\nSET ISO8859-1\nTRY ea\nSFXX S Y 1\nWORDCHAR -\n",
+    )
+    .unwrap();
+    std::fs::write(&dictionary, "1\ncafé\n").unwrap();
+    SpellChecker::new_validated(&affix, &dictionary, &Default::default()).unwrap();
+    let reasons: Vec<String> = match SpellChecker::new_validated(&affix, &dictionary, &strict) {
+        Err(Error::Multiple(warnings)) => warnings
+            .into_iter()
+            .map(|(_, e)| match e {
+                Error::DictionaryRejected(reason) => reason,
+                e => panic!("unexpected error {e:?}"),
+            })
+            .collect(),
+        other => panic!("expected warnings, got {:?}", other.map(|_| ())),
+    };
+    assert_eq!(3, reasons.len());
+    assert!(reasons[0].ends_with("strict.aff:4: unknown directive SFXX"));
+    assert!(reasons[1].ends_with("strict.aff:5: unknown directive WORDCHAR"));
+    assert!(reasons[2].ends_with("strict.dic:1: UTF-8, but the affix file sets an 8-bit encoding"));
+
+    std::fs::write(&affix, "SET UTF-8\n").unwrap();
+    std::fs::write(&dictionary, b"1\ncaf\xe9\n").unwrap();
+    assert!(matches!(
+        validate_dictionary(&affix, &dictionary, &strict),
+        Err(Error::DictionaryRejected(reason)) if reason.ends_with("not UTF-8, the encoding of the affix file")
+    ));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn affix_options() {
+    use crate::aff::{AffixFile, AffixOption};
+
+    assert_eq!(
+        Some(AffixOption::CompoundMin),
+        AffixOption::from_name("COMPOUNDMIN")
+    );
+    assert_eq!("LEMMA_PRESENT", AffixOption::LemmaPresent.name());
+    assert_eq!(None, AffixOption::from_name("COMPOUNDFLAG"));
+    let aff = AffixFile::read("tests/fixtures/compound/compound.aff").unwrap();
+    assert_eq!(Some("2"), aff.setting(AffixOption::CompoundMin));
+    assert_eq!(Some("0123456789&"), aff.setting(AffixOption::WordChars));
+}
+
+#[test]
+fn sampled_estimate() {
+    let checker = TextChecker::new(open_reduced());
+    let dir = std::env::temp_dir().join(format!("hunspell-rs-sample-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let paths: Vec<_> = (0..4).map(|i| dir.join(format!("{i}.txt"))).collect();
+    for path in &paths {
+        std::fs::write(path, "cats catz programs cat ".repeat(25)).unwrap();
+    }
+
+    let all = SampleOptions {
+        every: 1,
+        ..Default::default()
+    };
+    let estimate = checker.estimate_files(&paths, &all).unwrap();
+    assert_eq!(
+        (4, 400, 100),
+        (estimate.files, estimate.words, estimate.misspellings)
+    );
+    assert_eq!(250.0, estimate.per_thousand);
+    assert!(estimate.lower < 250.0 && estimate.upper > 250.0);
+
+    let files = SampleOptions {
+        every: 2,
+        seed: 3,
+        unit: SampleUnit::Files,
+    };
+    let estimate = checker.estimate_files(&paths, &files).unwrap();
+    assert_eq!((2, 200), (estimate.files, estimate.words));
+    let words = SampleOptions {
+        every: 3,
+        seed: 3,
+        unit: SampleUnit::Words,
+    };
+    let estimate = checker.estimate_files(&paths, &words).unwrap();
+    assert_eq!(4, estimate.files);
+    assert!((133..=134).contains(&estimate.words));
+    assert_eq!(estimate, checker.estimate_files(&paths, &words).unwrap());
+
+    let nothing = checker.estimate_files(Vec::<&str>::new(), &words).unwrap();
+    assert_eq!((0.0, 1000.0), (nothing.lower, nothing.upper));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn non_ascii_paths() {
+    let dir = std::env::temp_dir().join(format!("hunspell-rs-Пользователь-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let affix = dir.join("словарь.aff");
+    let dictionary = dir.join("словарь.dic");
+    let extra = dir.join("ordbok-é.dic");
+    std::fs::copy("tests/fixtures/reduced.aff", &affix).unwrap();
+    std::fs::copy("tests/fixtures/reduced.dic", &dictionary).unwrap();
+    std::fs::write(&extra, "1\ncatz\n").unwrap();
+    let mut spell = SpellChecker::new(&affix, &dictionary).unwrap();
+    assert!(spell.check("cats").unwrap());
+    assert!(spell.add_dictionary(&extra).unwrap());
+    assert!(spell.check("catz").unwrap());
+    drop(spell);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn deterministic_pool_reports() {
+    use crate::SpellCheckerPool;
+
+    let dir = std::env::temp_dir().join(format!("hunspell-rs-pool-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let mut paths = Vec::new();
+    for i in 0..12 {
+        let path = dir.join(format!("{i}.txt"));
+        std::fs::write(&path, "cats catz ".repeat(i * 50) + "programz").unwrap();
+        paths.push(path);
+    }
+    let dictionary =
+        Dictionary::from_files("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic");
+    let pool = SpellCheckerPool::new(&dictionary, 4).unwrap();
+    assert!(pool.is_deterministic());
+    let first = pool.report_files(&paths).unwrap();
+    let found: Vec<_> = first.files.iter().map(|f| &f.path).collect();
+    assert_eq!(paths.iter().collect::<Vec<_>>(), found);
+    let checker = TextChecker::new(dictionary.open().unwrap());
+    for (file, path) in first.files.iter().zip(&paths) {
+        assert_eq!(&checker.report_file(path).unwrap(), file);
+    }
+    for _ in 0..3 {
+        assert_eq!(
+            first.to_string(),
+            pool.report_files(&paths).unwrap().to_string()
+        );
+    }
+
+    // without it the same files are checked, in any order
+    let pool = pool.with_deterministic(false);
+    let report = pool.report_files(&paths).unwrap();
+    assert_eq!(first.words(), report.words());
+    assert_eq!(first.misspellings(), report.misspellings());
+
+    paths.push(dir.join("missing.txt"));
+    assert!(pool.report_files(&paths).is_err());
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn identifier_splitting() {
+    use crate::text::identifier_parts;
+
+    let parts = |word: &'static str| -> Vec<&str> {
+        identifier_parts(word)
+            .into_iter()
+            .map(|r| &word[r])
+            .collect()
+    };
+    assert_eq!(vec!["camel", "Case"], parts("camelCase"));
+    assert_eq!(vec!["Pascal", "Case", "Word"], parts("PascalCaseWord"));
+    assert_eq!(vec!["snake", "case"], parts("__snake__case_"));
+    assert_eq!(vec!["HTTP", "Server"], parts("HTTPServer"));
+    assert_eq!(vec!["utf8", "Decode"], parts("utf8Decode"));
+    assert_eq!(vec!["CONSTANT", "NAME"], parts("CONSTANT_NAME"));
+    assert_eq!(vec!["Über", "Größe"], parts("ÜberGröße"));
+    assert_eq!(vec!["word"], parts("word"));
+
+    let spell = open_reduced();
+    let text = "catPrograms cat_programz CATS_PROGRAM";
+    let checker = TextChecker::new(spell.clone());
+    // underscores separate words anyway
+    let found: Vec<_> = checker
+        .check_text(text)
+        .unwrap()
+        .into_iter()
+        .map(|m| m.word)
+        .collect();
+    assert_eq!(vec!["catPrograms", "programz"], found);
+    let checker = checker.with_identifier_splitting(true);
+    assert!(checker.identifier_splitting());
+    let misspellings = checker.check_text(text).unwrap();
+    assert_eq!(1, misspellings.len());
+    assert_eq!("programz", misspellings[0].word);
+    assert_eq!(16..24, misspellings[0].range);
+
+    let options = CheckOptions {
+        identifiers: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        Ok(false),
+        spell.check_with("catPrograms", &CheckOptions::default())
+    );
+    assert_eq!(Ok(true), spell.check_with("catPrograms", &options));
+    assert_eq!(Ok(true), spell.check_with("cat_programs", &options));
+    assert_eq!(Ok(false), spell.check_with("catProgramz", &options));
+}
+
+#[test]
+fn normalization() {
+    let mut spell = open_reduced();
+    spell.add("café").unwrap();
+    spell.add("don't").unwrap();
+    spell.add("cat-like").unwrap();
+    assert_eq!(Normalization::default(), spell.normalization());
+    // e and a combining acute accent
+    let decomposed = "cafe\u{301}";
+    assert_eq!(Ok(true), spell.check(decomposed));
+    assert_eq!(Ok(false), spell.check("don’t"));
+    assert_eq!(Ok(false), spell.check("cat\u{2011}like"));
+
+    spell.set_normalization(Normalization {
+        typographic: true,
+        ..Default::default()
+    });
+    for word in ["don’t", "don‘t", "donʼt", "cat\u{2011}like", "ca\u{ad}ts"] {
+        assert_eq!(Ok(true), spell.check(word), "{word}");
+    }
+    spell.set_normalization(Normalization::none());
+    assert_eq!(Ok(false), spell.check(decomposed));
+    assert_eq!("e\u{301}", Normalization::none().apply("e\u{301}").as_ref());
+    assert_eq!("é", Normalization::default().apply("e\u{301}").as_ref());
+}
+
+#[cfg(feature = "hyphenation")]
+#[test]
+fn hyphenation() {
+    use crate::Hyphenator;
+    let hyphenator = Hyphenator::from_file("tests/fixtures/hyphen/hyph_en.dic").unwrap();
+    assert_eq!((2, 2), (hyphenator.left_min(), hyphenator.right_min()));
+    assert_eq!(
+        vec!["in", "for", "ma", "tion"],
+        hyphenator.hyphenate("information")
+    );
+    assert_eq!(
+        vec!["In", "for", "ma", "tion"],
+        hyphenator.hyphenate("Information")
+    );
+    assert_eq!(
+        vec!["hy", "phen", "a", "tion"],
+        hyphenator.hyphenate("hyphenation")
+    );
+    // too short to hyphenate, and no hyphen within the minimums
+    assert_eq!(vec!["on"], hyphenator.hyphenate("on"));
+    assert_eq!(vec![""], hyphenator.hyphenate(""));
+    assert_eq!(
+        vec![2, 5],
+        hyphenator
+            .clone()
+            .with_min(2, 5)
+            .hyphenation_points("information")
+    );
+
+    let latin1 = Hyphenator::from_bytes(b"ISO8859-1\nLEFTHYPHENMIN 1\n\xe91\n").unwrap();
+    assert_eq!(vec!["\u{e9}", "t\u{e9}"], latin1.hyphenate("\u{e9}t\u{e9}"));
+
+    assert!(matches!(
+        Hyphenator::from_bytes(b"UTF-8\nRIGHTHYPHENMIN two\n"),
+        Err(Error::InvalidHyphenationPatterns(line)) if line == "line 2: RIGHTHYPHENMIN two"
+    ));
+    assert!(matches!(
+        Hyphenator::from_bytes(b"KLINGON\n"),
+        Err(Error::InvalidHyphenationPatterns(_))
+    ));
+}
+
+#[cfg(feature = "thesaurus")]
+#[test]
+fn thesaurus() {
+    use crate::{SynonymEntry, Thesaurus};
+    let thesaurus = Thesaurus::from_files(
+        "tests/fixtures/thesaurus/th_en.idx",
+        "tests/fixtures/thesaurus/th_en.dat",
+    )
+    .unwrap();
+    assert_eq!(3, thesaurus.len());
+    let happy = thesaurus.synonyms("happy");
+    assert_eq!(2, happy.len());
+    assert_eq!(
+        SynonymEntry {
+            part_of_speech: "(adj)".to_string(),
+            synonyms: vec![
+                "glad".to_string(),
+                "pleased (similar term)".to_string(),
+                "unhappy (antonym)".to_string()
+            ],
+        },
+        happy[1]
+    );
+    // looked up in lowercase when not found as written
+    assert_eq!(
+        thesaurus.synonyms("information"),
+        thesaurus.synonyms("Information")
+    );
+    assert!(thesaurus.synonyms("nocats").is_empty());
+
+    let latin1 = Thesaurus::from_bytes(
+        b"ISO8859-1\n1\ncaf\xe9|10\n",
+        b"ISO8859-1\ncaf\xe9|1\n(noun)|bistro\n".to_vec(),
+    )
+    .unwrap();
+    assert_eq!(vec!["bistro"], latin1.synonyms("caf\u{e9}")[0].synonyms);
+
+    assert!(matches!(
+        Thesaurus::from_bytes(b"UTF-8\n1\nword|1000\n", b"UTF-8\n".to_vec()),
+        Err(Error::InvalidThesaurus(line)) if line == "line 3: word|1000"
+    ));
+}
+
+#[test]
+fn grammar_checks() {
+    let rules = |issues: &[GrammarIssue]| {
+        issues
+            .iter()
+            .map(|i| (i.rule, i.range.clone(), i.replacement.clone()))
+            .collect::<Vec<_>>()
+    };
+    let all = GrammarOptions::default();
+    assert_eq!(
+        vec![
+            (GrammarRule::DoubledWord, 3..7, String::new()),
+            (GrammarRule::PunctuationSpacing, 11..13, " ".to_string()),
+        ],
+        rules(&all.check("The The cat  sat"))
+    );
+    // numbers, abbreviations and ellipses
+    assert!(all
+        .check("It is 1 1 way, e.g. that. Mr. J. smith said... so 1,000 is fine.")
+        .is_empty());
+    assert_eq!(
+        vec![
+            (GrammarRule::LowercaseSentenceStart, 6..8, "É".to_string()),
+            (GrammarRule::PunctuationSpacing, 8..9, "; ".to_string()),
+            (GrammarRule::LowercaseSentenceStart, 15..16, "O".to_string()),
+        ],
+        rules(&all.check("Done! é;yes.\" ok"))
+    );
+    let spacing_only = GrammarOptions {
+        doubled_words: false,
+        sentence_start: false,
+        ..GrammarOptions::default()
+    };
+    assert!(spacing_only.check("the the. end").is_empty());
+    assert!(GrammarOptions::none().check("the the .x").is_empty());
+
+    let spell = open_reduced();
+    let text = "cats cats\nprograms ,cats";
+    let document = DocumentChecker::new(TextChecker::new(spell.clone()), text).unwrap();
+    assert!(document.grammar_issues().is_empty());
+    let mut document = document.with_grammar(all);
+    assert_eq!(
+        vec![
+            (GrammarRule::DoubledWord, 4..9, String::new()),
+            (GrammarRule::PunctuationSpacing, 18..19, String::new()),
+            (GrammarRule::PunctuationSpacing, 19..20, ", ".to_string()),
+        ],
+        rules(&document.grammar_issues())
+    );
+    assert_eq!(
+        "doubled word `cats`",
+        document.grammar_issues()[0].message()
+    );
+    document.edit(18..19, "").unwrap();
+    assert_eq!(
+        vec![(GrammarRule::DoubledWord, 4..9, String::new())],
+        rules(&document.grammar_issues())[..1]
+    );
+    assert_eq!(2, document.grammar_issues().len());
+    document.edit(0..5, "").unwrap();
+    assert_eq!(
+        vec![(GrammarRule::PunctuationSpacing, 13..14, ", ".to_string())],
+        rules(&document.grammar_issues())
+    );
+    assert!(document.misspellings().is_empty());
+}
+
+#[test]
+fn suggestion_filter() {
+    let mut spell = open_reduced();
+    let unfiltered = spell.suggest("catz").unwrap();
+    assert!(unfiltered.contains(&"cat".to_string()));
+    assert!(!spell.has_suggestion_filter());
+
+    spell.set_suggestion_filter(|word, mut suggestions| {
+        suggestions.retain(|s| s != "cat");
+        if word == "programz" {
+            suggestions.insert(0, "program".to_string());
+        }
+        suggestions.dedup();
+        suggestions
+    });
+    assert!(spell.has_suggestion_filter());
+    let filtered = spell.suggest("catz").unwrap();
+    assert!(!filtered.contains(&"cat".to_string()));
+    assert_eq!(unfiltered.len() - 1, filtered.len());
+    assert_eq!("program", spell.suggest("programz").unwrap()[0]);
+    // the methods built on suggest(), and clones
+    let mut suggestions = vec!["x".to_string(); 10];
+    assert_eq!(
+        filtered.len(),
+        spell.suggest_into("catz", &mut suggestions).unwrap()
+    );
+    assert_eq!(filtered, suggestions);
+    let options = SuggestOptions {
+        timeout: std::time::Duration::from_secs(60),
+        ..SuggestOptions::default()
+    };
+    assert_eq!(
+        filtered,
+        spell.suggest_with_options("catz", &options).unwrap()
+    );
+    assert!(spell
+        .suggest_classified("catz")
+        .unwrap()
+        .iter()
+        .all(|s| s.word != "cat"));
+    assert_eq!(
+        filtered,
+        spell.try_clone().unwrap().suggest("catz").unwrap()
+    );
+
+    spell.clear_suggestion_filter();
+    assert_eq!(unfiltered, spell.suggest("catz").unwrap());
+}
+
+#[test]
+fn persistent_result_cache() {
+    let dir = std::env::temp_dir().join(format!("hunspell-rs-cache-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let (a, b) = (dir.join("a.txt"), dir.join("b.txt"));
+    std::fs::write(&a, "cats catz").unwrap();
+    std::fs::write(&b, "programz").unwrap();
+    let storage = || FileStorage::new(dir.join("cache/results"));
+    let spell = open_reduced();
+    let checker = TextChecker::new(spell.clone());
+
+    let mut cache = ResultCache::open(storage(), &checker).unwrap();
+    assert!(cache.is_empty());
+    let report_a = checker.report_file_cached(&a, &mut cache).unwrap();
+    assert_eq!(checker.report_file(&a).unwrap(), report_a);
+    checker.report_file_cached(&b, &mut cache).unwrap();
+    assert_eq!((0, 2), (cache.hits(), cache.misses()));
+    cache.save().unwrap();
+
+    // unchanged files come from the cache, changed ones are checked
+    std::fs::write(&b, "programs").unwrap();
+    let mut cache = ResultCache::open(storage(), &checker).unwrap();
+    assert_eq!(2, cache.len());
+    assert_eq!(
+        report_a,
+        checker.report_file_cached(&a, &mut cache).unwrap()
+    );
+    assert!(checker
+        .report_file_cached(&b, &mut cache)
+        .unwrap()
+        .misspellings
+        .is_empty());
+    assert_eq!((1, 1), (cache.hits(), cache.misses()));
+    cache.save().unwrap();
+
+    // other dictionary data starts over
+    let mut spell = spell;
+    spell.add("catz").unwrap();
+    let added = TextChecker::new(spell);
+    let mut cache = ResultCache::open(storage(), &added).unwrap();
+    assert!(cache.is_empty());
+    assert_ne!(
+        cache.fingerprint(),
+        ResultCache::open(storage(), &checker)
+            .unwrap()
+            .fingerprint()
+    );
+    let fingerprint = |spell: SpellChecker| -> Result<u64, Error> {
+        Ok(ResultCache::open(storage(), &TextChecker::new(spell))?.fingerprint())
+    };
+    let (mut words, mut with_affix, ignored) = (
+        checker.spell_checker().clone(),
+        checker.spell_checker().clone(),
+        checker.spell_checker().clone(),
+    );
+    words.add("rust").unwrap();
+    words.add("cat").unwrap();
+    with_affix.add_with_affix("rust", "cat").unwrap();
+    ignored.ignore("rust");
+    ignored.ignore("cat");
+    let (words, with_affix, ignored) = (
+        fingerprint(words).unwrap(),
+        fingerprint(with_affix).unwrap(),
+        fingerprint(ignored).unwrap(),
+    );
+    assert_ne!(words, with_affix);
+    assert_ne!(words, ignored);
+    assert!(added
+        .report_file_cached(&a, &mut cache)
+        .unwrap()
+        .misspellings
+        .is_empty());
+
+    // as does a cache that cannot be read
+    std::fs::write(storage().path(), "not a cache").unwrap();
+    assert!(ResultCache::open(storage(), &checker).unwrap().is_empty());
+
+    let mut cache = ResultCache::open(storage(), &checker).unwrap();
+    checker.report_file_cached(&a, &mut cache).unwrap();
+    cache.save().unwrap();
+
+    // other settings of the text or spell checker miss the cache
+    let spell = || checker.spell_checker().clone();
+    let mut normalized = spell();
+    normalized.set_normalization(Normalization {
+        typographic: true,
+        ..Default::default()
+    });
+    let mut policed = spell();
+    policed.set_input_policy(InputPolicy::untrusted());
+    for other in [
+        TextChecker::new(spell()).with_ignore_rules(IgnoreRules::none()),
+        TextChecker::new(spell()).with_syntax_filter(Markdown),
+        TextChecker::new(spell()).with_extra_word_policy(ExtraWordPolicy::Reject),
+        TextChecker::new(spell()).with_abbreviations(["catz"]),
+        TextChecker::new(spell()).with_word_forms(WordForms {
+            possessives: false,
+            ..Default::default()
+        }),
+        TextChecker::new(normalized.clone()),
+        TextChecker::new(policed.clone()),
+    ] {
+        let mut cache = ResultCache::open(storage(), &other).unwrap();
+        assert!(cache.is_empty(), "{other:?}");
+        other.report_file_cached(&a, &mut cache).unwrap();
+        assert_eq!((0, 1), (cache.hits(), cache.misses()));
+    }
+    let mut cache = ResultCache::open(storage(), &checker).unwrap();
+    checker.report_file_cached(&a, &mut cache).unwrap();
+    assert_eq!((1, 0), (cache.hits(), cache.misses()));
+    std::fs::remove_file(&a).unwrap();
+    cache.prune();
+    assert!(cache.is_empty());
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn dictionary_fingerprint() {
+    let files = Dictionary::from_files("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic");
+    let fingerprint = files.fingerprint().unwrap();
+    // the same on every run, build and platform
+    let small = Dictionary::from_bytes(&b"SET UTF-8\n"[..], &b"1\ncat\n"[..]);
+    assert_eq!(0x4f6a_a8d9_eb19_db7e, small.fingerprint().unwrap());
+    files.check_fingerprint(fingerprint).unwrap();
+    assert_eq!(
+        Err(Error::FingerprintMismatch {
+            expected: fingerprint,
+            found: small.fingerprint().unwrap()
+        }),
+        small.check_fingerprint(fingerprint)
+    );
+    let extra = files
+        .clone()
+        .with_additional_dictionary("tests/fixtures/extra.dic");
+    assert_ne!(fingerprint, extra.fingerprint().unwrap());
+    assert_ne!(
+        fingerprint,
+        files.clone().with_key("secret").fingerprint().unwrap()
+    );
+    assert!(matches!(
+        Dictionary::from_files("tests/fixtures/none.aff", "tests/fixtures/none.dic")
+            .fingerprint()
+            .unwrap_err()
+            .root_cause(),
+        Error::IoError(_)
+    ));
+
+    assert_eq!(None, files.version().unwrap());
+    let versioned = Dictionary::from_bytes(
+        &b"SET UTF-8\n# VERSION in a comment\nVERSIONS 1\nVERSION 2024.05 (beta)\n"[..],
+        &b"1\ncat\n"[..],
+    );
+    assert_eq!(
+        Some("2024.05 (beta)".to_string()),
+        versioned.version().unwrap()
+    );
+}
+
+#[cfg(feature = "tracing")]
+#[test]
+fn tracing_spans() {
+    use std::{
+        fmt::Debug,
+        sync::{Arc, Mutex},
+    };
+    use tracing::{
+        field::{Field, Visit},
+        span::{Attributes, Id, Record},
+        Event, Metadata, Subscriber,
+    };
+
+    // the fields of every span, by span
+    type SpanFields = Vec<(String, String)>;
+    #[derive(Clone, Default)]
+    struct Spans(Arc<Mutex<Vec<SpanFields>>>);
+    struct Fields<'a>(&'a mut Vec<(String, String)>);
+    impl Visit for Fields<'_> {
+        fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+            self.0
+                .push((field.name().to_string(), format!("{value:?}")));
+        }
+    }
+    impl Subscriber for Spans {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+        fn new_span(&self, span: &Attributes<'_>) -> Id {
+            let mut spans = self.0.lock().unwrap();
+            let mut fields = vec![("name".to_string(), span.metadata().name().to_string())];
+            span.record(&mut Fields(&mut fields));
+            spans.push(fields);
+            Id::from_u64(spans.len() as u64)
+        }
+        fn record(&self, span: &Id, values: &Record<'_>) {
+            let mut spans = self.0.lock().unwrap();
+            values.record(&mut Fields(&mut spans[span.into_u64() as usize - 1]));
+        }
+        fn record_follows_from(&self, _: &Id, _: &Id) {}
+        fn event(&self, _: &Event<'_>) {}
+        fn enter(&self, _: &Id) {}
+        fn exit(&self, _: &Id) {}
+    }
+
+    let spans = Spans::default();
+    tracing::subscriber::with_default(spans.clone(), || -> Result<(), Error> {
+        let spell = open_reduced();
+        spell.check("cats")?;
+        spell.suggest("catz")?;
+        spell.analyze("cats")?;
+        Ok(())
+    })
+    .unwrap();
+    let spans = spans.0.lock().unwrap();
+    let field = |span: &SpanFields, name: &str| {
+        span.iter().find(|(n, _)| n == name).map(|(_, v)| v.clone())
+    };
+    let operations: Vec<_> = spans
+        .iter()
+        .filter_map(|span| field(span, "operation"))
+        .collect();
+    assert_eq!(
+        vec!["\"create\"", "\"check\"", "\"suggest\"", "\"analyze\""],
+        operations
+    );
+    assert!(spans
+        .iter()
+        .all(|span| field(span, "name").as_deref() == Some("hunspell")
+            && field(span, "duration_us").is_some()));
+    assert_eq!(Some("4".to_string()), field(&spans[1], "word_len"));
+    assert_eq!(Some("1".to_string()), field(&spans[1], "results"));
+    assert!(field(&spans[0], "affix").unwrap().ends_with("reduced.aff"));
+}
+
+#[test]
+fn call_stats() {
+    let mut spell = open_reduced();
+    assert_eq!(None, spell.stats());
+    spell.check("cats").unwrap();
+    spell.enable_stats();
+    assert_eq!(Some(Stats::default()), spell.stats());
+
+    for word in ["cats", "catz", "programz"] {
+        spell.check(word).unwrap();
+    }
+    let suggestions =
+        spell.suggest("catz").unwrap().len() + spell.suggest("programz").unwrap().len();
+    spell.analyze("cats").unwrap();
+    // ignored words do not reach hunspell
+    spell.ignore("catz");
+    spell.check("catz").unwrap();
+    let stats = spell.stats().unwrap();
+    assert_eq!(
+        (3, 2, 2, suggestions as u64, 1),
+        (
+            stats.checks,
+            stats.misses,
+            stats.suggests,
+            stats.suggestions,
+            stats.analyses
+        )
+    );
+    assert!(stats.ffi_time > std::time::Duration::ZERO);
+    assert_eq!(2.0 / 3.0, stats.miss_rate());
+    assert_eq!(suggestions as f64 / 2.0, stats.suggestions_per_word());
+
+    // clones share the counters
+    let clone = spell.try_clone().unwrap();
+    clone.check("cats").unwrap();
+    assert_eq!(4, spell.stats().unwrap().checks);
+    clone.reset_stats();
+    assert_eq!(Some(Stats::default()), spell.stats());
+    assert_eq!(0.0, Stats::default().miss_rate());
+
+    spell.disable_stats();
+    spell.check("cats").unwrap();
+    assert_eq!(None, spell.stats());
+    assert_eq!(0, clone.stats().unwrap().checks);
+}
+
+#[cfg(feature = "test-util")]
+#[test]
+fn fixture_builder() {
+    use crate::FixtureBuilder;
+
+    let fixture = FixtureBuilder::new()
+        .with_prefix("U", [("0", "un", ".")])
+        .with_suffix("S", [("0", "s", ".")])
+        .with_words(["do/US", "café"])
+        .with_setting("FULLSTRIP", "")
+        .with_encoding("ISO8859-1")
+        .unwrap()
+        .write()
+        .unwrap();
+    let affix = std::fs::read(fixture.affix()).unwrap();
+    assert!(affix.starts_with(b"SET ISO8859-1\n"));
+    assert!(std::fs::read(fixture.dictionary())
+        .unwrap()
+        .ends_with(b"caf\xe9\n"));
+    let spell = fixture.open().unwrap();
+    for word in ["undos", "café"] {
+        assert_eq!(Ok(true), spell.check(word));
+    }
+    let dictionary = fixture.to_dictionary();
+    assert_eq!(Ok(true), dictionary.open().unwrap().check("undo"));
+    drop(spell);
+    let dir = fixture.dir().to_path_buf();
+    drop(fixture);
+    assert!(!dir.exists());
+
+    // the same words for the same seed
+    let random = FixtureBuilder::new().with_random_words(50, 7);
+    assert_eq!(
+        random.dic_file(),
+        FixtureBuilder::new().with_random_words(50, 7).dic_file()
+    );
+    assert_ne!(
+        random.dic_file(),
+        FixtureBuilder::new().with_random_words(50, 8).dic_file()
+    );
+    let fixture = random.write().unwrap();
+    let spell = fixture.open().unwrap();
+    for entry in &random.dic_file().entries {
+        assert!((2..=10).contains(&entry.word.len()));
+        assert_eq!(Ok(true), spell.check(&entry.word));
+    }
+
+    assert!(matches!(
+        FixtureBuilder::new().with_encoding("KLINGON"),
+        Err(Error::UnsupportedEncoding(_))
+    ));
+    assert!(matches!(
+        FixtureBuilder::new()
+            .with_word("кот")
+            .with_encoding("ISO8859-1")
+            .unwrap()
+            .write(),
+        Err(Error::InvalidWord { .. })
+    ));
+}
+
+#[test]
+fn input_policy() {
+    let mut spell = open_reduced();
+    assert_eq!(InputPolicy::default(), spell.input_policy());
+    spell.set_input_policy(InputPolicy {
+        nul: NulHandling::Replace('a'),
+        ..Default::default()
+    });
+    assert_eq!(Ok(true), spell.check("c\0t"));
+    spell.set_input_policy(InputPolicy::untrusted());
+    assert_eq!(Ok(true), spell.check("ca\0t"));
+    assert_eq!(vec!["cat"], spell.suggest("c\0atz").unwrap());
+    let long = "a".repeat(InputPolicy::UNTRUSTED_MAX_WORD_LENGTH + 1);
+    assert_eq!(Ok(false), spell.check(&long));
+    assert!(spell.suggest(&long).unwrap().is_empty());
+    assert_eq!(0, spell.suggest_iter(&long).unwrap().len());
+    assert!(spell.analyze("cat\u{1b}").unwrap().is_empty());
+    assert!(spell.stem("cat\u{1b}").unwrap().is_empty());
+    assert!(matches!(
+        spell.add("dog\u{7}").map_err(|e| e.root_cause().clone()),
+        Err(Error::InvalidWord {
+            character: Some('\u{7}'),
+            ..
+        })
+    ));
+    assert_eq!(Ok(false), spell.check("dog"));
+    let clone = spell.try_clone().unwrap();
+    assert_eq!(InputPolicy::untrusted(), clone.input_policy());
+}
+
+#[test]
+fn suggest_top() {
+    let mut spell = open_reduced();
+    spell.add("cot").unwrap();
+    let all = spell.suggest("cst").unwrap();
+    assert_eq!(vec!["cat", "cot"], all);
+    assert_eq!(all[..1], spell.suggest_top("cst", 1).unwrap());
+    assert_eq!(all, spell.suggest_top("cst", all.len() + 5).unwrap());
+    assert!(spell.suggest_top("cst", 0).unwrap().is_empty());
+    spell.add_replacement("cst", "cot");
+    assert_eq!(vec!["cot"], spell.suggest_top("cst", 1).unwrap());
+
+    // suggestions dropped by the filter are made up from the rest
+    spell.remove_replacement("cst");
+    spell.set_suggestion_filter(|_, suggestions: Vec<String>| {
+        suggestions.into_iter().filter(|s| s != "cat").collect()
+    });
+    assert_eq!(vec!["cot"], spell.suggest_top("cst", 1).unwrap());
+    assert_eq!(vec!["cot"], spell.suggest_top("cst", 5).unwrap());
+}
+
+#[test]
+fn split_suggestions() {
+    let mut spell = open_reduced();
+    for word in ["no", "spell", "check", "this", "spellcheck"] {
+        spell.add(word).unwrap();
+    }
+    assert_eq!(vec!["no cats"], spell.split_suggestions("nocats").unwrap());
+    assert_eq!(
+        vec!["spellcheck this"],
+        spell.split_suggestions("spellcheckthis").unwrap()
+    );
+    assert_eq!(
+        vec!["this check spell"],
+        spell.split_suggestions("thischeckspell").unwrap()
+    );
+    spell.add("cats").unwrap();
+    spell.add("scat").unwrap();
+    assert_eq!(
+        vec!["no cats cat", "no cat scat"],
+        spell.split_suggestions("nocatscat").unwrap()
+    );
+    assert!(spell.split_suggestions("cat").unwrap().is_empty());
+    assert!(spell.split_suggestions("catqq").unwrap().is_empty());
+    assert!(spell.split_suggestions("").unwrap().is_empty());
+}
+
+#[test]
+fn expand_entries() {
+    use crate::{aff::AffixFile, dic::DicEntry};
+
+    let aff = AffixFile::parse(
+        "NEEDAFFIX _\nFORBIDDENWORD !\nONLYINCOMPOUND C\n\
+         PFX U Y 1\nPFX U 0 un .\n\
+         PFX R N 1\nPFX R 0 re .\n\
+         SFX A Y 1\nSFX A 0 able/S .\n\
+         SFX S Y 2\nSFX S 0 s [^y]\nSFX S y ies [^aeiou]y\n\
+         SFX N Y 1\nSFX N 0 ness/_ .\n",
+    )
+    .unwrap();
+    let expand = |line| aff.expand(&DicEntry::parse(line));
+    assert_eq!(vec!["fly", "flies"], expand("fly/S"));
+    assert_eq!(
+        vec![
+            "lock",
+            "lockable",
+            "lockables",
+            "unlock",
+            "unlockable",
+            "unlockables"
+        ],
+        expand("lock/AU")
+    );
+    assert_eq!(vec!["do", "dos", "redo"], expand("do/RS"));
+    assert_eq!(vec!["kind"], expand("kind/N"));
+    assert_eq!(vec!["cats"], expand("cat/S_"));
+    assert!(expand("irregardless/!").is_empty());
+    assert!(expand("foot/C").is_empty());
+
+    let mut spell = open_reduced();
+    assert_eq!(
+        vec!["program", "programs"],
+        spell.all_forms("program").unwrap()
+    );
+    assert!(spell.all_forms("dog").unwrap().is_empty());
+    spell.add_dictionary("tests/fixtures/extra.dic").unwrap();
+    assert_eq!(
+        vec!["spectralmagnifier", "spectralmagnifiers"],
+        spell.all_forms("spectralmagnifier").unwrap()
+    );
+}
+
+#[test]
+fn dictionary_lint() {
+    let dir = std::env::temp_dir().join(format!("hunspell-rs-lint-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let extra = dir.join("extra.dic");
+    std::fs::write(&extra, "1\nsystemdunits/SS\n").unwrap();
+    let dictionary = Dictionary::from_bytes(
+        b"SET UTF-8\nFLAG long\nFORBIDDENWORD !!\nCOMPOUNDRULE 1\nCOMPOUNDRULE (Aa)*(Bb)\nSFX SS Y 1\nSFX SS 0 s .\n"
+            .to_vec(),
+        "5\ncat/SS\n/SS\nd\u{f8}g/XXAa\nfoot/Bb\ncat/SS\nbad/SSS\n"
+            .as_bytes()
+            .to_vec(),
+    )
+    .with_additional_dictionary(&extra);
+    let stats = dictionary.stats().unwrap();
+    assert_eq!(5, stats.entries);
+    assert_eq!("UTF-8", stats.encoding);
+    assert_eq!(FlagType::Long, stats.flag_type);
+    assert_eq!(Some(&3), stats.flag_usage.get("SS"));
+    assert_eq!(Some(&1), stats.flag_usage.get("XX"));
+    assert_eq!(
+        vec!["systemdunits", "foot", "cat", "d\u{f8}g", "cat"],
+        stats.longest_entries
+    );
+    let issues = dictionary.lint().unwrap();
+    let messages: Vec<String> = issues.iter().map(LintIssue::message).collect();
+    assert_eq!(
+        vec![
+            "line 3: the word is missing".to_string(),
+            "line 4: `d\u{f8}g` has flag `XX`, which is not defined".to_string(),
+            "line 6: duplicate of `cat` on line 2".to_string(),
+            "line 7: `SSS` has an odd number of characters for long flags".to_string(),
+        ],
+        messages
+    );
+    assert_eq!(
+        LintKind::Duplicate {
+            word: "cat".to_string(),
+            first_line: 2
+        },
+        issues[2].kind
+    );
+    std::fs::write(&extra, "x\nsystemdunits/S1\n").unwrap();
+    let issues = dictionary.lint().unwrap();
+    assert_eq!(
+        Some(extra.as_path()),
+        issues[4].additional_dictionary.as_deref()
+    );
+    assert_eq!(1, issues[4].line);
+    assert!(matches!(issues[5].kind, LintKind::UndefinedFlag { .. }));
+
+    let aliased = Dictionary::from_bytes(
+        b"AF 1\nAF S\nSFX S Y 1\nSFX S 0 s .\n".to_vec(),
+        b"2\ncat/1\ndog/2\n".to_vec(),
+    );
+    assert_eq!(Some(&1), aliased.stats().unwrap().flag_usage.get("S"));
+    assert_eq!(
+        vec!["line 3: flag alias `2` is not in the AF table"],
+        aliased
+            .lint()
+            .unwrap()
+            .iter()
+            .map(LintIssue::message)
+            .collect::<Vec<_>>()
+    );
+    assert!(
+        Dictionary::from_files("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic")
+            .with_key("secret")
+            .lint()
+            .is_err()
+    );
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn input_and_output_conversions() {
+    let mut spell = SpellChecker::from_memory(
+        "SET UTF-8\nFORBIDDENWORD !\nICONV 1\nICONV ’ '\nOCONV 1\nOCONV ' ’\nSFX S Y 1\nSFX S 0 s .\n"
+            .as_bytes(),
+        "2\ndon't/S\nain't/!\n".as_bytes(),
+    ).unwrap();
+    assert_eq!(
+        vec![("’".to_string(), "'".to_string())],
+        spell.input_conversions().pairs
+    );
+    assert_eq!("don’t", spell.output_conversions().apply("don't"));
+    assert_eq!(Ok(true), spell.check("don’t"));
+    assert_eq!(vec!["don’t", "don’ts"], spell.all_forms("don’t").unwrap());
+    assert_eq!(
+        Some(RejectionKind::Forbidden),
+        spell.rejection_kind("ain’t").unwrap()
+    );
+    spell.add("can’t").unwrap();
+    assert_eq!(Ok(true), spell.check("can't"));
+    assert_eq!(Ok(true), spell.check("can’t"));
+    spell.remove("can’t").unwrap();
+    assert_eq!(Ok(false), spell.check("can't"));
+
+    let plain = open_reduced();
+    assert!(plain.input_conversions().is_empty());
+    assert!(plain.output_conversions().is_empty());
+}
+
+#[test]
+fn check_cased() {
+    let mut spell = open_reduced();
+    spell.add("Paris").unwrap();
+    spell.add("NASA").unwrap();
+    for (word, dictionary, strict, insensitive, all_caps) in [
+        ("cat", true, true, true, true),
+        ("Cat", true, false, true, true),
+        ("CAT", true, false, true, true),
+        ("cAt", false, false, true, false),
+        ("Paris", true, true, true, true),
+        ("PARIS", true, false, true, true),
+        ("paris", false, false, true, false),
+        ("NASA", true, true, true, true),
+        ("catz", false, false, false, false),
+    ] {
+        assert_eq!(
+            Ok(dictionary),
+            spell.check_cased(word, CasePolicy::Dictionary),
+            "{word}"
+        );
+        assert_eq!(
+            Ok(strict),
+            spell.check_cased(word, CasePolicy::Strict),
+            "{word}"
+        );
+        assert_eq!(
+            Ok(insensitive),
+            spell.check_cased(word, CasePolicy::Insensitive),
+            "{word}"
+        );
+        assert_eq!(
+            Ok(all_caps),
+            spell.check_cased(word, CasePolicy::AllowAllCaps),
+            "{word}"
+        );
+    }
+    let keepcase = SpellChecker::new(
+        "tests/fixtures/keepcase/keepcase.aff",
+        "tests/fixtures/keepcase/keepcase.dic",
+    )
+    .unwrap();
+    assert_eq!(
+        Ok(false),
+        keepcase.check_cased("CAT", CasePolicy::Dictionary)
+    );
+    assert_eq!(
+        Ok(true),
+        keepcase.check_cased("CAT", CasePolicy::AllowAllCaps)
+    );
+    assert_eq!(
+        Ok(false),
+        keepcase.check_cased("Cat", CasePolicy::AllowAllCaps)
+    );
+}
+
+#[test]
+fn checker_chain() {
+    let language = open_reduced();
+    let mut project =
+        SpellChecker::new("tests/fixtures/reduced.aff", "tests/fixtures/extra.dic").unwrap();
+    project.add("cot").unwrap();
+    let mut chain = CheckerChain::new()
+        .with_layer("project", project, 3.0)
+        .with_layer("language", language, 1.0);
+    assert_eq!(
+        vec!["project", "language"],
+        chain.layers().collect::<Vec<_>>()
+    );
+    assert_eq!(Some("project"), chain.accepted_by("systemdunits").unwrap());
+    assert_eq!(Some("language"), chain.accepted_by("program").unwrap());
+    assert_eq!(Ok(false), chain.check("cst"));
+
+    let scored = chain.suggest_scored("cst").unwrap();
+    assert_eq!("cot", scored[0].word);
+    assert_eq!(vec!["project"], scored[0].layers);
+    assert_eq!(3.0, scored[0].score);
+    assert_eq!("cat", scored[1].word);
+    assert_eq!(vec!["cot", "cat"], chain.suggest("cst").unwrap());
+
+    chain.layer_mut("language").unwrap().add("cst").unwrap();
+    assert_eq!(Some("language"), chain.accepted_by("cst").unwrap());
+    Speller::add(&mut chain, "dog").unwrap();
+    assert_eq!(Some("project"), chain.accepted_by("dog").unwrap());
+    assert!(chain.layer_mut("personal").is_none());
+    assert_eq!(
+        Err(Error::ReadOnly),
+        Speller::add(&mut CheckerChain::new(), "dog")
+    );
+}
+
+#[test]
+fn correction_session() {
+    let mut hs = open_reduced();
+    let text = "ĉatz progam catz progam rustc catz rustc";
+    let mut session = CorrectionSession::new(&mut hs, text).unwrap();
+    assert_eq!("ĉatz", session.current().unwrap().word);
+    session.apply(CorrectionAction::Ignore).unwrap();
+    assert_eq!("progam", session.current().unwrap().word);
+    assert!(matches!(
+        session.apply(CorrectionAction::Replace(99)),
+        Err(Error::InvalidCorrection(_))
+    ));
+    assert_eq!("progam", session.current().unwrap().word);
+    session.apply(CorrectionAction::Replace(0)).unwrap();
+    assert_eq!("catz", session.current().unwrap().word);
+    assert_eq!(14..18, session.current().unwrap().range);
+    assert_eq!("cat", session.suggestions()[0]);
+    session.apply(CorrectionAction::ReplaceAll(0)).unwrap();
+    // the second progam was only replaced once
+    assert_eq!("progam", session.current().unwrap().word);
+    session.apply(CorrectionAction::Ignore).unwrap();
+    assert_eq!("rustc", session.current().unwrap().word);
+    assert_eq!(25..30, session.current().unwrap().range);
+    session.apply(CorrectionAction::IgnoreAll).unwrap();
+    assert!(session.is_done());
+    assert!(matches!(
+        session.apply(CorrectionAction::Ignore),
+        Err(Error::InvalidCorrection(_))
+    ));
+    assert_eq!(
+        "ĉatz program cat progam rustc cat rustc",
+        session.into_text()
+    );
+    assert!(hs.is_ignored("rustc"));
+}
+
+#[test]
+fn suggestion_edits() {
+    let edits = SuggestionEdit::between("ctas", "cats");
+    assert_eq!(
+        vec![SuggestionEdit {
+            kind: EditKind::Transpose,
+            range: 1..3,
+            replacement: "at".to_string(),
+        }],
+        edits
+    );
+    let edits = SuggestionEdit::between("çatts", "cats");
+    let kinds: Vec<EditKind> = edits.iter().map(|e| e.kind).collect();
+    assert_eq!(vec![EditKind::Substitute, EditKind::Delete], kinds);
+    assert_eq!(0..2, edits[0].range);
+    assert_eq!(3..4, edits[1].range);
+    assert!(SuggestionEdit::between("cat", "cat").is_empty());
+
+    // applying the edits from the back gives the suggestion
+    for (word, suggestion) in [
+        ("progam", "program"),
+        ("Ëxtra", "extras"),
+        ("abc", ""),
+        ("", "ab"),
+    ] {
+        let mut edited = word.to_string();
+        for edit in SuggestionEdit::between(word, suggestion).iter().rev() {
+            edited.replace_range(edit.range.clone(), &edit.replacement);
+        }
+        assert_eq!(suggestion, edited);
+    }
+
+    let hs = open_reduced();
+    let (suggestion, edits) = &hs.suggest_with_edits("catz").unwrap()[0];
+    assert_eq!("cat", suggestion);
+    assert_eq!(EditKind::Delete, edits[0].kind);
+    assert_eq!(3..4, edits[0].range);
+}
+
+#[test]
+fn analysis_cache() {
+    let mut hs = SpellChecker::new(
+        "tests/fixtures/stem/stem.aff",
+        "tests/fixtures/stem/stem.dic",
+    )
+    .unwrap();
+    let uncached = (
+        hs.analyze("roses").unwrap(),
+        hs.stem("roses").unwrap(),
+        hs.extended_stem("roses").unwrap(),
+        hs.extended_generate("rose", "roses").unwrap(),
+    );
+    assert_eq!(None, hs.analysis_cache_stats());
+    hs.enable_analysis_cache(10);
+    hs.enable_stats();
+    let cached = (
+        hs.analyze("roses").unwrap(),
+        hs.stem("roses").unwrap(),
+        hs.extended_stem("roses").unwrap(),
+        hs.analyze("roses").unwrap(),
+    );
+    assert_eq!(uncached.0, cached.0);
+    assert_eq!(uncached.1, cached.1);
+    assert_eq!(uncached.2, cached.2);
+    assert_eq!(uncached.0, cached.3);
+    assert_eq!(uncached.3, hs.extended_generate("rose", "roses").unwrap());
+    assert_eq!(uncached.3, hs.extended_generate("rose", "roses").unwrap());
+    // every word is analyzed and stemmed once, the rest is from the cache
+    assert_eq!(
+        CacheStats { hits: 3, misses: 3 },
+        hs.analysis_cache_stats().unwrap()
+    );
+    assert_eq!(2, hs.stats().unwrap().analyses);
+
+    // a clone has an empty cache of the same size
+    assert_eq!(Some(10), hs.try_clone().unwrap().analysis_cache_capacity());
+    hs.add("roseses").unwrap();
+    hs.analyze("roses").unwrap();
+    assert_eq!(4, hs.analysis_cache_stats().unwrap().misses);
+    hs.disable_analysis_cache();
+    assert_eq!(None, hs.analysis_cache_capacity());
+}
+
+#[test]
+fn null_spell_checker() {
+    let mut null = NullSpellChecker;
+    assert_eq!(Ok(true), Speller::check(&null, "qqqq"));
+    assert!(null.suggest("qqqq").unwrap().is_empty());
+    assert!(null.stem("cats").unwrap().is_empty());
+    assert!(null.analyze("cats").unwrap().is_empty());
+    assert_eq!(Ok(()), Speller::add(&mut null, "qqqq"));
+
+    let spell = SpellChecker::for_language_or_null("xx_NOT_INSTALLED");
+    assert_eq!(Ok(true), spell.check("qqqq"));
+    assert!(matches!(
+        SpellChecker::for_language("xx_NOT_INSTALLED"),
+        Err(Error::DictionaryNotFound(_))
+    ));
+}
+
+#[test]
+fn case_suggestions() {
+    let spell = open_reduced();
+    assert_eq!(
+        vec!["program", "Program", "PROGRAM"],
+        spell.case_suggestions("pROGRAM").unwrap()
+    );
+    assert_eq!(
+        vec!["cats", "Cats", "CATS"],
+        spell.case_suggestions("cATS").unwrap()
+    );
+    assert!(spell.case_suggestions("Cat").unwrap().is_empty());
+    assert!(spell.case_suggestions("progam").unwrap().is_empty());
+    assert!(spell.case_suggestions("").unwrap().is_empty());
+
+    spell.ignore("rustc");
+    assert_eq!(vec!["rustc"], spell.case_suggestions("RustC").unwrap());
+}
+
+#[test]
+fn swap_dictionary() {
+    let mut spell = open_reduced();
+    spell.add("progam").unwrap();
+    spell.remove("program").unwrap();
+    spell.ignore("rustc");
+    spell.add_replacement("teh", "the");
+    spell.set_max_suggest_length(Some(20));
+    assert_eq!(Ok(false), spell.check("systemdunits"));
+
+    let missing = Dictionary::from_files("/no/such/dir/reduced.aff", "/no/such/dir/reduced.dic");
+    assert!(spell.swap_dictionary(missing).is_err());
+    assert_eq!(Ok(true), spell.check("progam"));
+
+    let upgrade =
+        Dictionary::from_files("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic")
+            .with_additional_dictionary("tests/fixtures/extra.dic");
+    spell.swap_dictionary(upgrade).unwrap();
+    assert_eq!(Ok(true), spell.check("systemdunits"));
+    assert_eq!(Ok(true), spell.check("progam"));
+    assert_eq!(Ok(false), spell.check("program"));
+    assert_eq!(Ok(true), spell.check("rustc"));
+    assert_eq!(Some("the".to_string()), spell.replacement("teh"));
+    assert_eq!(Some(20), spell.max_suggest_length());
+    assert_eq!(2, spell.runtime_words().len());
+
+    let dir = std::env::temp_dir().join(format!("hunspell-rs-swap-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::copy("tests/fixtures/reduced.aff", dir.join("nl_NL.aff")).unwrap();
+    std::fs::copy("tests/fixtures/reduced.dic", dir.join("nl_NL.dic")).unwrap();
+    let dutch = Dictionary::from_files(dir.join("nl_NL.aff"), dir.join("nl_NL.dic"));
+    assert_eq!(None, spell.language());
+    spell.swap_dictionary(dutch.clone()).unwrap();
+    assert_eq!(Some(&"nl_NL".parse().unwrap()), spell.language());
+    spell.set_language("en_GB".parse().unwrap());
+    spell.swap_dictionary(dutch).unwrap();
+    assert_eq!(Some(&"en_GB".parse().unwrap()), spell.language());
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn cached_spell_checker() {
+    let hs = open_reduced();
+    let mut cached = CachedSpellChecker::new(hs, 2);
+    assert_eq!(Ok(false), cached.check("catz"));
+    assert_eq!(Ok(false), cached.check("catz"));
+    assert_eq!(Ok(vec!["cat".to_string()]), cached.suggest("catz"));
+    assert_eq!(Ok(vec!["cat".to_string()]), cached.suggest("catz"));
+    assert_eq!(CacheStats { hits: 2, misses: 2 }, cached.stats());
+
+    // "cats" and "program" push out "catz"
+    cached.check("cats").unwrap();
+    cached.check("program").unwrap();
+    cached.check("catz").unwrap();
+    assert_eq!(CacheStats { hits: 2, misses: 5 }, cached.stats());
+
+    cached.add("catz").unwrap();
+    assert_eq!(Ok(true), cached.check("catz"));
+    assert_eq!(6, cached.stats().misses);
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn async_spell_checker() {
+    use std::{
+        future::Future,
+        sync::Arc,
+        task::{Context, Poll, Wake},
+    };
+
+    struct Unpark(std::thread::Thread);
+
+    impl Wake for Unpark {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let waker = Arc::new(Unpark(std::thread::current())).into();
+        let mut context = Context::from_waker(&waker);
+        let mut future = std::pin::pin!(future);
+        loop {
+            match future.as_mut().poll(&mut context) {
+                Poll::Ready(output) => return output,
+                Poll::Pending => std::thread::park(),
+            }
+        }
+    }
+
+    let mut hs = open_reduced();
+    hs.add("octonasaurius").unwrap();
+    let spell = crate::AsyncSpellChecker::from_spell_checker(&hs).unwrap();
+    assert_eq!(Ok(true), block_on(spell.check("octonasaurius")));
+    assert_eq!(Ok(vec!["cat".to_string()]), block_on(spell.suggest("catz")));
+    assert_eq!(
+        Ok(vec!["cat".to_string()]),
+        block_on(spell.run(|hs| hs.stem("cats")))
+    );
 }
@@ -0,0 +1,944 @@
+//! Checking running text instead of single words.
+//!
+//! The text is split in words, and the words that are rejected are
+//! returned with their byte range in the original text, so they can be
+//! highlighted or replaced in place.
+
+use std::{
+    borrow::Cow,
+    cell::RefCell,
+    collections::{hash_map::DefaultHasher, HashMap, HashSet, VecDeque},
+    hash::{Hash, Hasher},
+    ops::Range,
+    sync::OnceLock,
+    time::{Duration, Instant},
+};
+
+use regex::Regex;
+
+use crate::{
+    accept_list::{accept_regex, ordinal_regex},
+    Casing, Error, Language, PlainText, Result, SpellChecker, SyntaxFilter, TokenizerProfile,
+};
+
+/// A misspelled word in a text.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Misspelling {
+    pub word: String,
+    /// The byte range of the word in the text.
+    pub range: Range<usize>,
+}
+
+/// The result of [`TextChecker::check_text_within()`]: the misspellings
+/// found before the time ran out, and where to go on.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BudgetedCheck {
+    /// The misspellings up to `resume_at` and in the priority range of
+    /// [`check_text_prioritized()`](TextChecker::check_text_prioritized),
+    /// in order.
+    pub misspellings: Vec<Misspelling>,
+    /// The byte offset of the first word that was not checked, `None`
+    /// when the whole text was checked.
+    pub resume_at: Option<usize>,
+    // The words checked first, skipped when resuming.
+    priority: Range<usize>,
+}
+
+impl BudgetedCheck {
+    pub fn is_complete(&self) -> bool {
+        self.resume_at.is_none()
+    }
+}
+
+/// Parts of a text that are not spell checked. Matches are skipped in
+/// place, so the ranges of the misspellings still refer to the original
+/// text.
+///
+/// By default URLs, email addresses and hex literals are ignored.
+///
+/// ```
+/// use hunspell_rs::{IgnoreRules, SpellChecker, TextChecker};
+///
+/// let spell = SpellChecker::new("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic").unwrap();
+/// let rules = IgnoreRules::default().with_pattern(r"\$[A-Z]+").unwrap();
+/// let checker = TextChecker::new(spell).with_ignore_rules(rules);
+/// let misspelled = checker.check_text("cats see $HOME and https://example.org").unwrap();
+/// assert_eq!(vec!["see", "and"], misspelled.iter().map(|m| &m.word).collect::<Vec<_>>());
+/// ```
+#[derive(Debug, Clone)]
+pub struct IgnoreRules {
+    /// Ignore URLs like `https://example.org/page` and `www.example.org`.
+    pub urls: bool,
+    /// Ignore email addresses.
+    pub emails: bool,
+    /// Ignore hex literals like `0xdeadbeef` and colors like `#c0ffee`.
+    pub hex_literals: bool,
+    /// Ignore Roman numerals like `XIV` and `xvii`, in upper or lower
+    /// case. Off by default, as it also accepts words like `mix` and a
+    /// lowercase `i`.
+    pub roman_numerals: bool,
+    /// Ignore everything these match.
+    pub patterns: Vec<Regex>,
+}
+
+impl Default for IgnoreRules {
+    fn default() -> Self {
+        IgnoreRules {
+            urls: true,
+            emails: true,
+            hex_literals: true,
+            roman_numerals: false,
+            patterns: Vec::new(),
+        }
+    }
+}
+
+impl IgnoreRules {
+    /// Rules that ignore nothing.
+    pub fn none() -> Self {
+        IgnoreRules {
+            urls: false,
+            emails: false,
+            hex_literals: false,
+            roman_numerals: false,
+            patterns: Vec::new(),
+        }
+    }
+
+    /// Adds a regular expression, see the [regex](https://docs.rs/regex)
+    /// crate for the syntax.
+    pub fn with_pattern(mut self, pattern: &str) -> Result<Self> {
+        let regex = Regex::new(pattern).map_err(|e| Error::InvalidPattern(e.to_string()))?;
+        self.patterns.push(regex);
+        Ok(self)
+    }
+
+    /// Adds the common abbreviations and ordinal numbers of `language`,
+    /// see [`accept_list()`](crate::accept_list). Languages without a list
+    /// add nothing.
+    pub fn with_accept_list(mut self, language: &Language) -> Self {
+        self.patterns.extend(accept_regex(language));
+        self
+    }
+
+    /// Adds the ordinal numbers of `language` only, like `21st` or `XIXe`
+    /// in French. Languages without ordinals add nothing.
+    pub fn with_ordinals(mut self, language: &Language) -> Self {
+        self.patterns.extend(ordinal_regex(language));
+        self
+    }
+
+    /// Returns the byte ranges of `text` to skip.
+    pub(crate) fn ignored_ranges(&self, text: &str) -> Vec<Range<usize>> {
+        static ROMAN: OnceLock<[Regex; 2]> = OnceLock::new();
+        let mut regexes: Vec<&Regex> = Vec::new();
+        if self.urls {
+            regexes.push(url_regex());
+        }
+        if self.emails {
+            regexes.push(email_regex());
+        }
+        if self.hex_literals {
+            regexes.push(hex_regex());
+        }
+        if self.roman_numerals {
+            // one per case, so `XIV` and `xiv` match but `Xiv` does not
+            regexes.extend(ROMAN.get_or_init(|| {
+                [
+                    r"\bM{0,4}(?:CM|CD|D?C{0,3})(?:XC|XL|L?X{0,3})(?:IX|IV|V?I{0,3})\b",
+                    r"\bm{0,4}(?:cm|cd|d?c{0,3})(?:xc|xl|l?x{0,3})(?:ix|iv|v?i{0,3})\b",
+                ]
+                .map(|pattern| Regex::new(pattern).expect("valid regex"))
+            }));
+        }
+        regexes.extend(self.patterns.iter());
+        regexes
+            .into_iter()
+            .flat_map(|r| r.find_iter(text).map(|m| m.range()))
+            .filter(|range| !range.is_empty())
+            .collect()
+    }
+}
+
+/// What [`TextChecker`] does with words that only extra dictionaries
+/// accept, see
+/// [`CheckDetails::only_extra()`](crate::CheckDetails::only_extra).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum ExtraWordPolicy {
+    /// They are correct.
+    #[default]
+    Accept,
+    /// They are reported as misspellings, i.e. to keep project jargon out
+    /// of user facing texts.
+    Reject,
+}
+
+/// What [`TextChecker`] does with soft hyphens, zero width spaces and
+/// joiners and word joiners between letters, which are common in text
+/// copied from PDFs and web pages.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum InvisibleChars {
+    /// They are part of the word and removed before it is checked, so
+    /// `hy\u{ad}phen` is checked as `hyphen`.
+    #[default]
+    Strip,
+    /// They separate words.
+    Split,
+}
+
+/// Word forms that [`TextChecker`] accepts when the word they are built
+/// on is correct, for dictionaries that do not know them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WordForms {
+    /// Possessives with a trailing `'s` or `’s`, i.e. `Linux's`, `CPU's`.
+    pub possessives: bool,
+    /// Plurals of acronyms, i.e. `APIs`.
+    pub acronym_plurals: bool,
+}
+
+impl Default for WordForms {
+    fn default() -> Self {
+        WordForms {
+            possessives: true,
+            acronym_plurals: true,
+        }
+    }
+}
+
+impl WordForms {
+    /// Accepts no other forms, every word is checked as it is.
+    pub fn none() -> Self {
+        WordForms {
+            possessives: false,
+            acronym_plurals: false,
+        }
+    }
+
+    /// Returns the word that `word` is built on, if it is one of the
+    /// enabled forms.
+    fn stem(self, word: &str) -> Option<&str> {
+        if self.possessives {
+            let stem = word
+                .strip_suffix("'s")
+                .or_else(|| word.strip_suffix("’s"))
+                .filter(|stem| !stem.is_empty());
+            if stem.is_some() {
+                return stem;
+            }
+        }
+        if self.acronym_plurals {
+            return word
+                .strip_suffix('s')
+                .filter(|stem| stem.chars().count() > 1 && Casing::of(stem) == Casing::Upper);
+        }
+        None
+    }
+}
+
+/// Spell checks the prose found by its [`SyntaxFilter`], skipping the
+/// parts matched by its [`IgnoreRules`].
+///
+/// With [`with_cache()`](TextChecker::with_cache) the results are kept by
+/// the hash of the text, so checking an unchanged text again is free.
+/// The cache is dropped when the [`SpellChecker::session_hash()`] changes,
+/// i.e. after words were added, options were set or the files were
+/// reloaded, and when the ignore rules are replaced.
+#[derive(Debug)]
+pub struct TextChecker {
+    spell_checker: SpellChecker,
+    ignore_rules: IgnoreRules,
+    extra_word_policy: ExtraWordPolicy,
+    word_forms: WordForms,
+    invisible_chars: InvisibleChars,
+    // known abbreviations, without their period
+    abbreviations: HashSet<String>,
+    tokenizer_profile: TokenizerProfile,
+    syntax_filter: Box<dyn SyntaxFilter>,
+    identifier_splitting: bool,
+    cache: RefCell<ReportCache>,
+}
+
+/// Misspellings by text hash, oldest first out.
+#[derive(Debug, Default)]
+struct ReportCache {
+    capacity: usize,
+    session: u64,
+    reports: HashMap<(u64, usize), Vec<Misspelling>>,
+    order: VecDeque<(u64, usize)>,
+}
+
+impl ReportCache {
+    fn clear(&mut self) {
+        self.reports.clear();
+        self.order.clear();
+    }
+
+    fn get(&mut self, key: (u64, usize), session: u64) -> Option<Vec<Misspelling>> {
+        if self.session != session {
+            self.clear();
+            self.session = session;
+        }
+        self.reports.get(&key).cloned()
+    }
+
+    fn insert(&mut self, key: (u64, usize), report: Vec<Misspelling>) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.reports.len() >= self.capacity {
+            if let Some(oldest) = self.order.pop_front() {
+                self.reports.remove(&oldest);
+            }
+        }
+        self.order.push_back(key);
+        self.reports.insert(key, report);
+    }
+}
+
+impl TextChecker {
+    /// Creates a checker with the [`TokenizerProfile`] of the language of
+    /// the dictionary, or the default profile.
+    pub fn new(spell_checker: SpellChecker) -> Self {
+        let tokenizer_profile = spell_checker
+            .language()
+            .and_then(TokenizerProfile::for_language)
+            .unwrap_or_default();
+        TextChecker {
+            spell_checker,
+            ignore_rules: IgnoreRules::default(),
+            extra_word_policy: ExtraWordPolicy::Accept,
+            word_forms: WordForms::default(),
+            invisible_chars: InvisibleChars::default(),
+            abbreviations: HashSet::new(),
+            tokenizer_profile,
+            syntax_filter: Box::new(PlainText),
+            identifier_splitting: false,
+            cache: RefCell::new(ReportCache::default()),
+        }
+    }
+
+    /// Keeps the results of at most `capacity` texts, 0 disables caching
+    /// (the default).
+    pub fn with_cache(self, capacity: usize) -> Self {
+        {
+            let mut cache = self.cache.borrow_mut();
+            cache.capacity = capacity;
+            cache.clear();
+        }
+        self
+    }
+
+    /// Forgets all cached results.
+    pub fn clear_cache(&self) {
+        self.cache.borrow_mut().clear();
+    }
+
+    /// Writes the settings that decide what is reported, in a form that
+    /// is the same on every run, for the key of a
+    /// [`ResultCache`](crate::ResultCache). The syntax filter is written
+    /// as its `Debug` output, which should show its options.
+    pub(crate) fn write_settings(&self, field: &mut impl FnMut(&[u8])) {
+        let mut abbreviations: Vec<&String> = self.abbreviations.iter().collect();
+        abbreviations.sort();
+        for setting in [
+            format!("{:?}", self.ignore_rules),
+            format!("{:?}", self.extra_word_policy),
+            format!("{:?}", self.word_forms),
+            format!("{:?}", self.invisible_chars),
+            format!("{abbreviations:?}"),
+            format!("{:?}", self.tokenizer_profile),
+            format!("{:?}", self.syntax_filter),
+            format!("{:?}", self.identifier_splitting),
+        ] {
+            field(setting.as_bytes());
+        }
+    }
+
+    pub fn with_ignore_rules(mut self, ignore_rules: IgnoreRules) -> Self {
+        self.set_ignore_rules(ignore_rules);
+        self
+    }
+
+    pub fn with_extra_word_policy(mut self, policy: ExtraWordPolicy) -> Self {
+        self.extra_word_policy = policy;
+        self.clear_cache();
+        self
+    }
+
+    pub fn extra_word_policy(&self) -> ExtraWordPolicy {
+        self.extra_word_policy
+    }
+
+    pub fn with_word_forms(mut self, word_forms: WordForms) -> Self {
+        self.word_forms = word_forms;
+        self.clear_cache();
+        self
+    }
+
+    pub fn word_forms(&self) -> WordForms {
+        self.word_forms
+    }
+
+    pub fn with_invisible_chars(mut self, invisible_chars: InvisibleChars) -> Self {
+        self.invisible_chars = invisible_chars;
+        self.clear_cache();
+        self
+    }
+
+    pub fn invisible_chars(&self) -> InvisibleChars {
+        self.invisible_chars
+    }
+
+    /// Accepts these abbreviations when they are followed by a period,
+    /// i.e. `Dr.` and `approx.`, also before a capitalized word.
+    ///
+    /// Other words followed by a period and a lowercase word or number are
+    /// taken as abbreviations as well: when they are misspelled, the
+    /// period is part of the reported word and range. Before a capitalized
+    /// word the period ends the sentence and is left out.
+    pub fn with_abbreviations<I, S>(mut self, abbreviations: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.abbreviations.extend(
+            abbreviations
+                .into_iter()
+                .map(|a| a.as_ref().trim_end_matches('.').to_string()),
+        );
+        self.clear_cache();
+        self
+    }
+
+    /// Returns the abbreviations, without their period.
+    pub fn abbreviations(&self) -> impl Iterator<Item = &str> {
+        self.abbreviations.iter().map(String::as_str)
+    }
+
+    /// Splits the text in words with `profile` instead of the profile of
+    /// the language of the dictionary.
+    pub fn with_tokenizer_profile(mut self, profile: TokenizerProfile) -> Self {
+        self.tokenizer_profile = profile;
+        self.clear_cache();
+        self
+    }
+
+    pub fn tokenizer_profile(&self) -> &TokenizerProfile {
+        &self.tokenizer_profile
+    }
+
+    /// Only checks the prose found by `syntax_filter`.
+    pub fn with_syntax_filter<F>(mut self, syntax_filter: F) -> Self
+    where
+        F: SyntaxFilter + 'static,
+    {
+        self.syntax_filter = Box::new(syntax_filter);
+        self.clear_cache();
+        self
+    }
+
+    pub fn syntax_filter(&self) -> &dyn SyntaxFilter {
+        self.syntax_filter.as_ref()
+    }
+
+    /// Checks the parts of rejected identifiers on their own: `camelCase`,
+    /// `PascalCase` and `snake_case` words are split where the case
+    /// changes and at underscores, and only the parts that are misspelled
+    /// are reported, for checking source code.
+    ///
+    /// ```
+    /// use hunspell_rs::{SpellChecker, TextChecker};
+    ///
+    /// let spell = SpellChecker::new("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic").unwrap();
+    /// let checker = TextChecker::new(spell).with_identifier_splitting(true);
+    /// let misspellings = checker.check_text("let catPrograms = catzProgram;").unwrap();
+    /// assert_eq!(vec!["let", "catz"], misspellings.iter().map(|m| &m.word).collect::<Vec<_>>());
+    /// assert_eq!(18..22, misspellings[1].range);
+    /// ```
+    pub fn with_identifier_splitting(mut self, identifier_splitting: bool) -> Self {
+        self.identifier_splitting = identifier_splitting;
+        self.clear_cache();
+        self
+    }
+
+    pub fn identifier_splitting(&self) -> bool {
+        self.identifier_splitting
+    }
+
+    pub fn spell_checker(&self) -> &SpellChecker {
+        &self.spell_checker
+    }
+
+    /// For adding or removing words, the cache is dropped when the words
+    /// change.
+    pub fn spell_checker_mut(&mut self) -> &mut SpellChecker {
+        &mut self.spell_checker
+    }
+
+    pub fn ignore_rules(&self) -> &IgnoreRules {
+        &self.ignore_rules
+    }
+
+    pub fn set_ignore_rules(&mut self, ignore_rules: IgnoreRules) {
+        self.ignore_rules = ignore_rules;
+        self.clear_cache();
+    }
+
+    /// Returns the misspelled words of `text`, in order.
+    pub fn check_text(&self, text: &str) -> Result<Vec<Misspelling>> {
+        if self.cache.borrow().capacity == 0 {
+            return self
+                .check_uncached(text)
+                .map(|(misspellings, _)| misspellings);
+        }
+        let mut hasher = DefaultHasher::new();
+        text.hash(&mut hasher);
+        let key = (hasher.finish(), text.len());
+        let session = self.spell_checker.session_hash();
+        if let Some(report) = self.cache.borrow_mut().get(key, session) {
+            return Ok(report);
+        }
+        let (report, _) = self.check_uncached(text)?;
+        self.cache.borrow_mut().insert(key, report.clone());
+        Ok(report)
+    }
+
+    /// Checks `text` for at most about `budget`, for checking as the user
+    /// types in large documents. Stops before the first word after the
+    /// budget is spent, but checks at least one word, so resuming always
+    /// gets further. The cache of [`check_text()`](TextChecker::check_text)
+    /// is not used.
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use hunspell_rs::{SpellChecker, TextChecker};
+    ///
+    /// let spell = SpellChecker::new("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic").unwrap();
+    /// let checker = TextChecker::new(spell);
+    /// let text = "cats catz ".repeat(1000);
+    /// let mut check = checker.check_text_within(&text, Duration::from_millis(5)).unwrap();
+    /// while !check.is_complete() {
+    ///     // let the user type, then go on
+    ///     check = checker.resume_check(&text, check, Duration::from_millis(5)).unwrap();
+    /// }
+    /// assert_eq!(1000, check.misspellings.len());
+    /// ```
+    pub fn check_text_within(&self, text: &str, budget: Duration) -> Result<BudgetedCheck> {
+        let start = BudgetedCheck {
+            misspellings: Vec::new(),
+            resume_at: Some(0),
+            priority: 0..0,
+        };
+        self.resume_check(text, start, budget)
+    }
+
+    /// Checks the words of `text` that overlap `priority`, i.e. the part
+    /// that is visible in an editor, and then the rest of the text like
+    /// [`check_text_within()`](TextChecker::check_text_within). The
+    /// priority range is always checked completely, the rest for what is
+    /// left of `budget`. Resume with [`resume_check()`](TextChecker::resume_check).
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use hunspell_rs::{SpellChecker, TextChecker};
+    ///
+    /// let spell = SpellChecker::new("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic").unwrap();
+    /// let checker = TextChecker::new(spell);
+    /// let text = "cats catz ".repeat(1000);
+    /// let viewport = 5000..5100;
+    /// let check = checker.check_text_prioritized(&text, viewport.clone(), Duration::ZERO).unwrap();
+    /// assert!(check.misspellings.iter().filter(|m| m.range.start >= viewport.start).count() >= 10);
+    /// ```
+    pub fn check_text_prioritized(
+        &self,
+        text: &str,
+        priority: Range<usize>,
+        budget: Duration,
+    ) -> Result<BudgetedCheck> {
+        let deadline = Instant::now() + budget;
+        let checked = self.check_part(text, None, |word| overlaps(word, &priority))?;
+        let start = BudgetedCheck {
+            misspellings: checked.misspellings,
+            resume_at: Some(0),
+            priority,
+        };
+        self.resume_check(
+            text,
+            start,
+            deadline.saturating_duration_since(Instant::now()),
+        )
+    }
+
+    /// Goes on with a check of [`check_text_within()`](TextChecker::check_text_within)
+    /// for at most about `budget`, adding to its misspellings. `text` must
+    /// be the same text, returns `previous` when it is complete.
+    pub fn resume_check(
+        &self,
+        text: &str,
+        previous: BudgetedCheck,
+        budget: Duration,
+    ) -> Result<BudgetedCheck> {
+        let Some(from) = previous.resume_at else {
+            return Ok(previous);
+        };
+        let priority = previous.priority;
+        let checked = self.check_part(text, Some(Instant::now() + budget), |word| {
+            word.start >= from && !overlaps(word, &priority)
+        })?;
+        let mut misspellings = previous.misspellings;
+        misspellings.extend(checked.misspellings);
+        misspellings.sort_by_key(|m| m.range.start);
+        Ok(BudgetedCheck {
+            misspellings,
+            resume_at: checked.stopped_at,
+            priority,
+        })
+    }
+
+    /// Returns the misspellings and the number of words that were checked.
+    pub(crate) fn check_uncached(&self, text: &str) -> Result<(Vec<Misspelling>, usize)> {
+        let checked = self.check_part(text, None, |_| true)?;
+        Ok((checked.misspellings, checked.words))
+    }
+
+    /// Like `check_uncached()`, for the words for which `select` returns
+    /// true only.
+    pub(crate) fn check_selected<F>(
+        &self,
+        text: &str,
+        select: F,
+    ) -> Result<(Vec<Misspelling>, usize)>
+    where
+        F: Fn(&Range<usize>) -> bool,
+    {
+        let checked = self.check_part(text, None, select)?;
+        Ok((checked.misspellings, checked.words))
+    }
+
+    /// Checks the words of `text` for which `select` returns true, until
+    /// the `deadline`.
+    fn check_part<F>(&self, text: &str, deadline: Option<Instant>, select: F) -> Result<Checked>
+    where
+        F: Fn(&Range<usize>) -> bool,
+    {
+        let regions = Regions {
+            prose: self.syntax_filter.prose_regions(text),
+            headings: self.syntax_filter.heading_regions(text),
+            ignored: self.ignore_rules.ignored_ranges(text),
+        };
+        let check = |word: &str| match self.extra_word_policy {
+            ExtraWordPolicy::Accept => self.spell_checker.check(word),
+            ExtraWordPolicy::Reject => self
+                .spell_checker
+                .check_detailed(word)
+                .map(|details| details.correct && !details.only_extra()),
+        };
+        check_text(
+            check,
+            text,
+            &regions,
+            words_with(
+                text,
+                self.invisible_chars,
+                &self.tokenizer_profile.word_chars,
+            )
+            .into_iter()
+            .filter(select),
+            &WordRules {
+                forms: self.word_forms,
+                abbreviations: &self.abbreviations,
+                profile: &self.tokenizer_profile,
+                identifiers: self.identifier_splitting,
+            },
+            deadline,
+        )
+    }
+}
+
+impl SpellChecker {
+    /// Returns the misspelled words of `text`, in order, skipping URLs,
+    /// email addresses and hex literals. Use a [`TextChecker`] for other
+    /// [`IgnoreRules`] or [`WordForms`].
+    pub fn check_text(&self, text: &str) -> Result<Vec<Misspelling>> {
+        check_plain_text(|word| self.check(word), text).map(|(misspellings, _)| misspellings)
+    }
+}
+
+/// The parts of a text found by the syntax filter and ignore rules.
+struct Regions {
+    prose: Vec<Range<usize>>,
+    headings: Vec<Range<usize>>,
+    ignored: Vec<Range<usize>>,
+}
+
+/// Returns the words of `text` that `check` rejects, with the defaults of
+/// [`SpellChecker::check_text()`], and the number of words that were
+/// checked.
+pub(crate) fn check_plain_text<F>(check: F, text: &str) -> Result<(Vec<Misspelling>, usize)>
+where
+    F: Fn(&str) -> Result<bool>,
+{
+    let regions = Regions {
+        prose: PlainText.prose_regions(text),
+        headings: Vec::new(),
+        ignored: IgnoreRules::default().ignored_ranges(text),
+    };
+    let profile = TokenizerProfile::default();
+    let checked = check_text(
+        check,
+        text,
+        &regions,
+        words_with(text, InvisibleChars::default(), &profile.word_chars),
+        &WordRules {
+            forms: WordForms::default(),
+            abbreviations: &HashSet::new(),
+            profile: &profile,
+            identifiers: false,
+        },
+        None,
+    )?;
+    Ok((checked.misspellings, checked.words))
+}
+
+/// How [`check_text()`] accepts words that the speller rejects.
+struct WordRules<'a> {
+    forms: WordForms,
+    abbreviations: &'a HashSet<String>,
+    profile: &'a TokenizerProfile,
+    /// Whether the parts of identifiers are checked on their own.
+    identifiers: bool,
+}
+
+/// The result of [`check_text()`].
+struct Checked {
+    misspellings: Vec<Misspelling>,
+    /// The number of words that were checked.
+    words: usize,
+    /// Where the deadline stopped the check.
+    stopped_at: Option<usize>,
+}
+
+fn check_text<F, W>(
+    check: F,
+    text: &str,
+    regions: &Regions,
+    words: W,
+    rules: &WordRules,
+    deadline: Option<Instant>,
+) -> Result<Checked>
+where
+    F: Fn(&str) -> Result<bool>,
+    W: IntoIterator<Item = Range<usize>>,
+{
+    let within = |regions: &[Range<usize>], range: &Range<usize>| {
+        regions
+            .iter()
+            .any(|r| r.start <= range.start && range.end <= r.end)
+    };
+    let mut misspellings = Vec::new();
+    let mut checked = 0;
+    for range in words {
+        if !within(&regions.prose, &range)
+            || regions
+                .ignored
+                .iter()
+                .any(|i| i.start < range.end && range.start < i.end)
+        {
+            continue;
+        }
+        if checked > 0 && deadline.is_some_and(|d| Instant::now() >= d) {
+            return Ok(Checked {
+                misspellings,
+                words: checked,
+                stopped_at: Some(range.start),
+            });
+        }
+        checked += 1;
+        let word = strip_invisible(&text[range.clone()]);
+        let word = word.as_ref();
+        let mut correct = check(word)?;
+        if !correct && Casing::of(word) == Casing::Title && within(&regions.headings, &range) {
+            correct = check(&word.to_lowercase())?;
+        }
+        if !correct {
+            if let Some(stem) = rules.forms.stem(word) {
+                correct = check(stem)?;
+            }
+        }
+        for stem in rules.profile.clitic_stems(word) {
+            if correct {
+                break;
+            }
+            correct = check(stem)?;
+        }
+        let period = text[range.end..].starts_with('.');
+        if !correct && period {
+            // hunspell also tries the word with the period, for
+            // dictionaries that list abbreviations
+            correct = rules.abbreviations.contains(word)
+                || rules.profile.abbreviations.iter().any(|a| a == word)
+                || check(&format!("{word}."))?;
+        }
+        if !correct && rules.identifiers && word.len() == range.len() {
+            let parts = identifier_parts(word);
+            if parts.len() > 1 {
+                for part in parts {
+                    if !check(&word[part.clone()])? {
+                        let part = range.start + part.start..range.start + part.end;
+                        misspellings.push(Misspelling {
+                            word: text[part.clone()].to_string(),
+                            range: part,
+                        });
+                    }
+                }
+                continue;
+            }
+        }
+        if !correct {
+            let range = if period && !ends_sentence(&text[range.end + 1..]) {
+                range.start..range.end + 1
+            } else {
+                range
+            };
+            misspellings.push(Misspelling {
+                word: text[range.clone()].to_string(),
+                range,
+            });
+        }
+    }
+    Ok(Checked {
+        misspellings,
+        words: checked,
+        stopped_at: None,
+    })
+}
+
+/// Returns true if the ranges have a byte in common.
+fn overlaps(a: &Range<usize>, b: &Range<usize>) -> bool {
+    a.start < b.end && b.start < a.end
+}
+
+/// Matches URLs like `https://example.org/page` and `www.example.org`.
+pub(crate) fn url_regex() -> &'static Regex {
+    static URL: OnceLock<Regex> = OnceLock::new();
+    URL.get_or_init(|| {
+        Regex::new(r"\b(?:[a-zA-Z][a-zA-Z0-9+.-]*://|www\.)[^\s<>]*[^\s<>.,;:!?)\]]")
+            .expect("valid regex")
+    })
+}
+
+pub(crate) fn email_regex() -> &'static Regex {
+    static EMAIL: OnceLock<Regex> = OnceLock::new();
+    EMAIL.get_or_init(|| Regex::new(r"\b[\w.%+-]+@[\w-]+(?:\.[\w-]+)+").expect("valid regex"))
+}
+
+/// Matches hex literals like `0xdeadbeef` and colors like `#c0ffee`.
+pub(crate) fn hex_regex() -> &'static Regex {
+    static HEX: OnceLock<Regex> = OnceLock::new();
+    HEX.get_or_init(|| {
+        Regex::new(r"\b0[xX][0-9a-fA-F]+\b|#[0-9a-fA-F]{3,8}\b").expect("valid regex")
+    })
+}
+
+/// Returns true when a period followed by `rest` ends a sentence: at the
+/// end of the text and before a capitalized word.
+fn ends_sentence(rest: &str) -> bool {
+    !rest
+        .trim_start()
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_alphanumeric() && !c.is_uppercase())
+}
+
+/// Returns the byte ranges of the words in `text`: runs of alphanumeric
+/// characters, including apostrophes between letters (`don't`) and
+/// invisible characters between letters or digits.
+pub(crate) fn words(text: &str) -> Vec<Range<usize>> {
+    words_with(text, InvisibleChars::Strip, "'’")
+}
+
+/// Like [`words()`], with `word_chars` instead of the apostrophes.
+fn words_with(text: &str, invisible_chars: InvisibleChars, word_chars: &str) -> Vec<Range<usize>> {
+    let mut words = Vec::new();
+    let mut start = None;
+    let mut chars = text.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        let in_word = c.is_alphanumeric()
+            || (start.is_some()
+                && word_chars.contains(c)
+                && chars.peek().is_some_and(|(_, next)| next.is_alphabetic()))
+            || (start.is_some()
+                && invisible_chars == InvisibleChars::Strip
+                && is_invisible(c)
+                && chars
+                    .clone()
+                    .map(|(_, next)| next)
+                    .find(|&next| !is_invisible(next))
+                    .is_some_and(char::is_alphanumeric));
+        match (in_word, start) {
+            (true, None) => start = Some(i),
+            (false, Some(s)) => {
+                words.push(s..i);
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(s) = start {
+        words.push(s..text.len());
+    }
+    words
+}
+
+/// Returns the byte ranges of the parts of an identifier: split at
+/// underscores, before an uppercase letter after a lowercase letter or a
+/// digit (`camel|Case`), and before the last uppercase letter of a run
+/// followed by a lowercase one (`HTTP|Server`).
+pub(crate) fn identifier_parts(word: &str) -> Vec<Range<usize>> {
+    let mut parts = Vec::new();
+    let mut start = 0;
+    let mut previous: Option<char> = None;
+    let mut chars = word.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        if c == '_' {
+            if start < i {
+                parts.push(start..i);
+            }
+            start = i + 1;
+            previous = None;
+            continue;
+        }
+        let next_lower = chars.peek().is_some_and(|(_, next)| next.is_lowercase());
+        let boundary = c.is_uppercase()
+            && previous.is_some_and(|p| {
+                p.is_lowercase() || p.is_numeric() || (p.is_uppercase() && next_lower)
+            });
+        if boundary && start < i {
+            parts.push(start..i);
+            start = i;
+        }
+        previous = Some(c);
+    }
+    if start < word.len() {
+        parts.push(start..word.len());
+    }
+    parts
+}
+
+/// Returns true for soft hyphens, zero width spaces, (non-)joiners, word
+/// joiners and zero width no-break spaces.
+pub(crate) fn is_invisible(c: char) -> bool {
+    matches!(
+        c,
+        '\u{ad}' | '\u{200b}' | '\u{200c}' | '\u{200d}' | '\u{2060}' | '\u{feff}'
+    )
+}
+
+/// Returns `word` without invisible characters, see `is_invisible()`.
+pub(crate) fn strip_invisible(word: &str) -> Cow<'_, str> {
+    if word.contains(is_invisible) {
+        Cow::Owned(word.chars().filter(|&c| !is_invisible(c)).collect())
+    } else {
+        Cow::Borrowed(word)
+    }
+}
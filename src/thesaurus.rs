@@ -0,0 +1,127 @@
+//! Synonyms from the MyThes thesaurus files that come with the
+//! LibreOffice dictionaries.
+
+use std::{collections::HashMap, path::Path};
+
+use crate::{encoding::Codec, Error, Operation, Result};
+
+/// A meaning of a word in a thesaurus, with the words that have it.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SynonymEntry {
+    /// The first field of the meaning, the part of speech like `(noun)`.
+    pub part_of_speech: String,
+    /// The synonyms, as they are in the file: some have a note like
+    /// `(antonym)` or `(similar term)`.
+    pub synonyms: Vec<String>,
+}
+
+/// A MyThes thesaurus, read from a `th_*.idx` index and its `th_*.dat`
+/// data file.
+///
+/// Both files start with their encoding. The index then has the number of
+/// words, followed by a `word|offset` line for every word, the offset being
+/// where the word starts in the data file. There a `word|count` line is
+/// followed by `count` meanings like `(noun)|term|phrase`. The entries are
+/// read when they are looked up.
+///
+/// ```
+/// use hunspell_rs::Thesaurus;
+///
+/// let thesaurus = Thesaurus::from_files(
+///     "tests/fixtures/thesaurus/th_en.idx",
+///     "tests/fixtures/thesaurus/th_en.dat",
+/// )
+/// .unwrap();
+/// let meanings = thesaurus.synonyms("word");
+/// assert_eq!("(verb)", meanings[1].part_of_speech);
+/// assert_eq!(vec!["express", "formulate"], meanings[1].synonyms);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Thesaurus {
+    codec: Codec,
+    index: HashMap<String, usize>,
+    data: Vec<u8>,
+}
+
+impl Thesaurus {
+    /// Reads a thesaurus from its index and data files.
+    pub fn from_files<P: AsRef<Path>, Q: AsRef<Path>>(idx: P, dat: Q) -> Result<Self> {
+        Self::from_bytes(
+            &std::fs::read(&idx).map_err(Error::io(Operation::Load, &idx))?,
+            std::fs::read(&dat).map_err(Error::io(Operation::Load, &dat))?,
+        )
+    }
+
+    /// Reads a thesaurus from the contents of its index and data files.
+    /// Fails with `Error::InvalidThesaurus` when the encoding is unknown,
+    /// or a line of the index is not a word and an offset in the data.
+    pub fn from_bytes(idx: &[u8], dat: Vec<u8>) -> Result<Self> {
+        let mut lines = idx.split(|&b| b == b'\n');
+        let encoding = String::from_utf8_lossy(lines.next().unwrap_or_default())
+            .trim()
+            .to_string();
+        let codec = Codec::lookup(&encoding)
+            .ok_or_else(|| Error::InvalidThesaurus(format!("unknown encoding {encoding}")))?;
+        let mut index = HashMap::new();
+        // the second line is the number of words
+        for (n, line) in lines.enumerate().skip(1) {
+            let line = codec.decode(line)?;
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            let invalid = || Error::InvalidThesaurus(format!("line {}: {line}", n + 2));
+            let (word, offset) = line.rsplit_once('|').ok_or_else(invalid)?;
+            let offset: usize = offset.parse().map_err(|_| invalid())?;
+            if offset >= dat.len() {
+                return Err(invalid());
+            }
+            index.insert(word.to_string(), offset);
+        }
+        Ok(Thesaurus {
+            codec,
+            index,
+            data: dat,
+        })
+    }
+
+    /// Returns the number of words in the thesaurus.
+    pub fn len(&self) -> usize {
+        self.index.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.index.is_empty()
+    }
+
+    /// Returns the meanings of `word` with their synonyms, looking up the
+    /// word in lowercase when it is not in the thesaurus as it is written.
+    /// Returns no meanings for unknown words, and leaves out the lines of
+    /// the entry that are not a meaning.
+    pub fn synonyms(&self, word: &str) -> Vec<SynonymEntry> {
+        let Some(&offset) = self
+            .index
+            .get(word)
+            .or_else(|| self.index.get(&word.to_lowercase()))
+        else {
+            return Vec::new();
+        };
+        let mut lines = self.data[offset..]
+            .split(|&b| b == b'\n')
+            .filter_map(|line| self.codec.decode(line).ok());
+        let count = lines
+            .next()
+            .and_then(|header| header.trim().rsplit_once('|')?.1.parse().ok())
+            .unwrap_or(0);
+        lines
+            .take(count)
+            .filter_map(|line| {
+                let mut fields = line.trim().split('|').map(str::to_string);
+                Some(SynonymEntry {
+                    part_of_speech: fields.next().filter(|f| !f.is_empty())?,
+                    synonyms: fields.filter(|f| !f.is_empty()).collect(),
+                })
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,125 @@
+//! The segmentation of text used for spell checking, on its own.
+
+use std::ops::Range;
+
+use crate::text::{email_regex, hex_regex, url_regex, words};
+
+/// What a [`Token`] is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TokenKind {
+    /// Letters and digits, possibly with apostrophes, that are checked.
+    Word,
+    /// Digits only.
+    Number,
+    Url,
+    Email,
+    /// Hex literals like `0xff` and colors like `#c0ffee`.
+    Code,
+    /// A run of other characters that are not white space.
+    Punctuation,
+}
+
+/// A part of a text, see [`classify_tokens()`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Token {
+    pub kind: TokenKind,
+    /// The byte range of the token in the text.
+    pub range: Range<usize>,
+}
+
+/// Splits `text` in tokens the way
+/// [`SpellChecker::check_text()`](crate::SpellChecker::check_text) does,
+/// in order. White space is left out.
+///
+/// ```
+/// use hunspell_rs::{classify_tokens, TokenKind};
+///
+/// let text = "Mail me@example.org, don't pay 42 for #fff!";
+/// let kinds: Vec<(TokenKind, &str)> = classify_tokens(text)
+///     .into_iter()
+///     .map(|t| (t.kind, &text[t.range]))
+///     .collect();
+/// assert_eq!(
+///     vec![
+///         (TokenKind::Word, "Mail"),
+///         (TokenKind::Email, "me@example.org"),
+///         (TokenKind::Punctuation, ","),
+///         (TokenKind::Word, "don't"),
+///         (TokenKind::Word, "pay"),
+///         (TokenKind::Number, "42"),
+///         (TokenKind::Word, "for"),
+///         (TokenKind::Code, "#fff"),
+///         (TokenKind::Punctuation, "!"),
+///     ],
+///     kinds
+/// );
+/// ```
+pub fn classify_tokens(text: &str) -> Vec<Token> {
+    // URLs first, an email address can be part of one
+    let mut tokens: Vec<Token> = Vec::new();
+    for (kind, regex) in [
+        (TokenKind::Url, url_regex()),
+        (TokenKind::Email, email_regex()),
+        (TokenKind::Code, hex_regex()),
+    ] {
+        for m in regex.find_iter(text) {
+            let range = m.range();
+            if !tokens.iter().any(|t| overlaps(&t.range, &range)) {
+                tokens.push(Token { kind, range });
+            }
+        }
+    }
+    let special = tokens.len();
+    for range in words(text) {
+        if tokens[..special].iter().any(|t| overlaps(&t.range, &range)) {
+            continue;
+        }
+        let kind = if text[range.clone()].chars().all(char::is_numeric) {
+            TokenKind::Number
+        } else {
+            TokenKind::Word
+        };
+        tokens.push(Token { kind, range });
+    }
+    tokens.sort_by_key(|t| t.range.start);
+
+    // the rest is white space or punctuation
+    let mut all = Vec::with_capacity(tokens.len());
+    let mut end = 0;
+    for token in tokens {
+        push_punctuation(text, end..token.range.start, &mut all);
+        end = token.range.end;
+        all.push(token);
+    }
+    push_punctuation(text, end..text.len(), &mut all);
+    all
+}
+
+/// Adds the runs of characters of `range` that are not white space.
+fn push_punctuation(text: &str, range: Range<usize>, tokens: &mut Vec<Token>) {
+    let mut start = None;
+    for (i, c) in text[range.clone()].char_indices() {
+        let i = range.start + i;
+        match (c.is_whitespace(), start) {
+            (false, None) => start = Some(i),
+            (true, Some(s)) => {
+                tokens.push(Token {
+                    kind: TokenKind::Punctuation,
+                    range: s..i,
+                });
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(s) = start {
+        tokens.push(Token {
+            kind: TokenKind::Punctuation,
+            range: s..range.end,
+        });
+    }
+}
+
+fn overlaps(a: &Range<usize>, b: &Range<usize>) -> bool {
+    a.start < b.end && b.start < a.end
+}
@@ -0,0 +1,271 @@
+//! How the words of a language are found in text: the characters that
+//! join letters, clitics and abbreviations.
+
+use crate::Language;
+
+/// The rules of a language for splitting text in words, used by
+/// [`TextChecker`](crate::TextChecker).
+///
+/// Profiles for about twenty languages come with the crate, see
+/// [`for_language()`](TokenizerProfile::for_language). A `TextChecker`
+/// takes the one of the language of its dictionary, another one can be
+/// set with
+/// [`with_tokenizer_profile()`](crate::TextChecker::with_tokenizer_profile).
+///
+/// ```
+/// use hunspell_rs::{SpellChecker, TextChecker, TokenizerProfile};
+///
+/// let spell = SpellChecker::new("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic").unwrap();
+/// let profile = TokenizerProfile::for_language(&"fr_FR".parse().unwrap()).unwrap();
+/// let checker = TextChecker::new(spell).with_tokenizer_profile(profile);
+/// // the elided article is checked apart
+/// assert!(checker.check_text("l'program d’cats").unwrap().is_empty());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TokenizerProfile {
+    /// Characters that are part of a word when a letter follows them,
+    /// besides letters and digits. By default the apostrophes `'` and `’`,
+    /// as in `don't`.
+    pub word_chars: String,
+    /// Elided words in front of a word, like `l'` in French. A word that
+    /// is rejected is checked again without them. `'` also matches `’`.
+    pub clitic_prefixes: Vec<String>,
+    /// Like `clitic_prefixes`, at the end of a word, like `'n` in Catalan.
+    pub clitic_suffixes: Vec<String>,
+    /// Abbreviations that are correct when followed by a period, without
+    /// the period.
+    pub abbreviations: Vec<String>,
+}
+
+impl Default for TokenizerProfile {
+    fn default() -> Self {
+        TokenizerProfile {
+            word_chars: "'’".to_string(),
+            clitic_prefixes: Vec::new(),
+            clitic_suffixes: Vec::new(),
+            abbreviations: Vec::new(),
+        }
+    }
+}
+
+impl TokenizerProfile {
+    /// Returns the profile that comes with the crate for `language`, by
+    /// its primary subtag, or `None` if there is none.
+    pub fn for_language(language: &Language) -> Option<TokenizerProfile> {
+        let (word_chars, prefixes, suffixes, abbreviations): Profile = match language.primary() {
+            "ca" => (
+                "'’·",
+                &["l'", "d'", "m'", "n'", "s'", "t'"],
+                &["'l", "'ls", "'m", "'n", "'ns", "'s", "'t"],
+                &["Sr", "Sra", "Dr", "Dra", "pàg", "etc", "núm", "av"],
+            ),
+            "cs" => (
+                "'’",
+                &[],
+                &[],
+                &["atd", "tzv", "např", "resp", "str", "Dr", "Ing", "Mgr"],
+            ),
+            "da" => (
+                "'’",
+                &[],
+                &[],
+                &[
+                    "bl", "ca", "dvs", "fx", "hhv", "jf", "kl", "mht", "nr", "osv", "pga",
+                ],
+            ),
+            "de" => (
+                "'’",
+                &[],
+                &["'s"],
+                &[
+                    "bzw", "ca", "evtl", "ggf", "inkl", "usw", "vgl", "Nr", "Dr", "Hr", "Fr",
+                    "Str", "Tel", "Abs",
+                ],
+            ),
+            "el" => (
+                "'’",
+                &["σ'", "απ'", "μ'", "τ'", "γι'"],
+                &[],
+                &["κ", "σελ", "αρ", "βλ"],
+            ),
+            "en" => (
+                "'’",
+                &[],
+                &["'ll", "'re", "'ve", "'d", "'m", "n't"],
+                &[
+                    "Mr", "Mrs", "Ms", "Dr", "Prof", "St", "Jr", "Sr", "No", "vs", "etc", "approx",
+                    "cf", "Inc", "Ltd", "Co", "Jan", "Feb", "Mar", "Apr", "Jun", "Jul", "Aug",
+                    "Sep", "Sept", "Oct", "Nov", "Dec",
+                ],
+            ),
+            "es" => (
+                "'’",
+                &[],
+                &[],
+                &[
+                    "Sr", "Sra", "Srta", "Dr", "Dra", "Ud", "Uds", "pág", "aprox", "etc", "núm",
+                    "vol", "av",
+                ],
+            ),
+            // EU:n, 5:ssä
+            "fi" => (
+                "'’:",
+                &[],
+                &[],
+                &["esim", "ks", "ns", "yms", "jne", "mm", "tms", "klo", "puh"],
+            ),
+            "fr" => (
+                "'’",
+                &[
+                    "l'", "d'", "j'", "m'", "n'", "s'", "t'", "c'", "qu'", "jusqu'", "lorsqu'",
+                    "puisqu'", "quoiqu'",
+                ],
+                &[],
+                &[
+                    "M", "Mme", "Mlle", "Dr", "Pr", "env", "etc", "cf", "av", "bd", "chap", "éd",
+                    "vol",
+                ],
+            ),
+            "ga" => ("'’", &["d'", "m'", "b'"], &[], &["Dr", "lch"]),
+            // acronyms with gershayim and geresh, צה״ל
+            "he" => ("'’״׳", &[], &[], &[]),
+            "hu" => (
+                "'’",
+                &[],
+                &[],
+                &["pl", "stb", "ill", "kb", "ld", "vö", "dr", "ún"],
+            ),
+            "it" => (
+                "'’",
+                &[
+                    "l'", "un'", "dell'", "all'", "dall'", "nell'", "sull'", "coll'", "d'", "c'",
+                    "quest'", "quell'",
+                ],
+                &[],
+                &[
+                    "ecc", "es", "pag", "Sig", "Dott", "Prof", "ca", "sec", "vol",
+                ],
+            ),
+            "nb" | "nn" | "no" => (
+                "'’",
+                &[],
+                &[],
+                &[
+                    "bl", "ca", "dvs", "fx", "kl", "mht", "nr", "osv", "pga", "tlf",
+                ],
+            ),
+            "nl" => (
+                "'’",
+                &[],
+                &["'s"],
+                &["bijv", "enz", "ca", "blz", "dhr", "mevr", "nr", "jl", "vnl"],
+            ),
+            "pl" => (
+                "'’",
+                &[],
+                &[],
+                &[
+                    "np", "itd", "itp", "tzn", "tj", "ul", "str", "dr", "prof", "godz",
+                ],
+            ),
+            "pt" => (
+                "'’",
+                &["d'"],
+                &[],
+                &["Sr", "Sra", "Dr", "Dra", "pág", "aprox", "etc", "av", "núm"],
+            ),
+            "ro" => ("'’", &[], &[], &["dl", "dna", "str", "nr", "etc", "pag"]),
+            "ru" => (
+                "'’",
+                &[],
+                &[],
+                &["г", "гг", "ул", "стр", "им", "др", "см", "тыс", "млн"],
+            ),
+            "sv" => (
+                "'’",
+                &[],
+                &[],
+                &["bl", "ca", "dvs", "etc", "fr", "kl", "nr", "osv", "tel"],
+            ),
+            // proper nouns take their case endings after an apostrophe,
+            // İstanbul'da
+            "tr" => (
+                "'’",
+                &[],
+                &[
+                    "'a", "'e", "'ya", "'ye", "'ı", "'i", "'u", "'ü", "'yı", "'yi", "'yu", "'yü",
+                    "'da", "'de", "'ta", "'te", "'dan", "'den", "'tan", "'ten", "'ın", "'in",
+                    "'un", "'ün", "'nın", "'nin", "'nun", "'nün", "'la", "'le", "'yla", "'yle",
+                ],
+                &["vb", "vs", "örn", "Dr", "Prof", "sn"],
+            ),
+            // the apostrophe is part of words like м'ята
+            "uk" => (
+                "'’ʼ",
+                &[],
+                &[],
+                &["ст", "р", "рр", "вул", "див", "ім", "тис", "млн"],
+            ),
+            _ => return None,
+        };
+        let owned = |words: &[&str]| words.iter().map(|w| w.to_string()).collect();
+        Some(TokenizerProfile {
+            word_chars: word_chars.to_string(),
+            clitic_prefixes: owned(prefixes),
+            clitic_suffixes: owned(suffixes),
+            abbreviations: owned(abbreviations),
+        })
+    }
+
+    /// Returns `word` without each of the clitic prefixes and suffixes it
+    /// has, when something is left.
+    pub(crate) fn clitic_stems<'a>(&'a self, word: &'a str) -> impl Iterator<Item = &'a str> {
+        let prefixed = self
+            .clitic_prefixes
+            .iter()
+            .filter_map(move |p| strip_clitic(word, p, false));
+        let suffixed = self
+            .clitic_suffixes
+            .iter()
+            .filter_map(move |s| strip_clitic(word, s, true));
+        prefixed.chain(suffixed).filter(|stem| !stem.is_empty())
+    }
+}
+
+/// The word characters, clitic prefixes and suffixes and abbreviations of
+/// a language.
+type Profile = (
+    &'static str,
+    &'static [&'static str],
+    &'static [&'static str],
+    &'static [&'static str],
+);
+
+/// Strips `clitic` from the start, or the end when `suffix` is set, of
+/// `word`, ignoring case and the kind of apostrophe.
+fn strip_clitic<'a>(word: &'a str, clitic: &str, suffix: bool) -> Option<&'a str> {
+    let same = |a: char, b: char| {
+        let apostrophe = |c| matches!(c, '\'' | '’');
+        (apostrophe(a) && apostrophe(b)) || a.to_lowercase().eq(b.to_lowercase())
+    };
+    let n = clitic.chars().count();
+    if n == 0 {
+        return None;
+    }
+    if suffix {
+        let (start, _) = word.char_indices().rev().nth(n - 1)?;
+        word[start..]
+            .chars()
+            .zip(clitic.chars())
+            .all(|(a, b)| same(a, b))
+            .then(|| &word[..start])
+    } else {
+        let end = word.char_indices().nth(n).map_or(word.len(), |(i, _)| i);
+        (word.chars().count() >= n
+            && word[..end]
+                .chars()
+                .zip(clitic.chars())
+                .all(|(a, b)| same(a, b)))
+        .then(|| &word[end..])
+    }
+}
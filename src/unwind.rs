@@ -0,0 +1,86 @@
+//! Turning panics into errors at the boundary of a host application.
+
+use std::panic::{self, AssertUnwindSafe};
+
+use crate::{Error, Result, Speller};
+
+/// Runs `f` and returns a panic in it as `Error::Panicked` with the panic
+/// message, so one bad input does not take down a server thread. The
+/// panic hook still runs, so the panic is logged as usual.
+///
+/// ```
+/// use hunspell_rs::{catch_panic, Error, SpellChecker};
+///
+/// let spell = SpellChecker::new("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic").unwrap();
+/// assert_eq!(Ok(true), catch_panic(|| spell.check("cats")));
+/// # std::panic::set_hook(Box::new(|_| ()));
+/// let result: Result<bool, Error> = catch_panic(|| panic!("malformed input"));
+/// assert_eq!(Err(Error::Panicked("malformed input".to_string())), result);
+/// ```
+pub fn catch_panic<T>(f: impl FnOnce() -> Result<T>) -> Result<T> {
+    // The RefCells of a SpellChecker are released while unwinding, so it
+    // can still be used after a panic.
+    panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|payload| {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic".to_string());
+        Err(Error::Panicked(message))
+    })
+}
+
+/// A [`Speller`] that returns panics of the speller it wraps as
+/// `Error::Panicked`, see [`catch_panic()`].
+///
+/// ```
+/// use hunspell_rs::{PanicBoundary, SpellChecker, Speller};
+///
+/// let spell = SpellChecker::new("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic").unwrap();
+/// let spell = PanicBoundary::new(spell);
+/// assert_eq!(Ok(true), Speller::check(&spell, "cats"));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct PanicBoundary<S> {
+    speller: S,
+}
+
+impl<S: Speller> PanicBoundary<S> {
+    pub fn new(speller: S) -> Self {
+        PanicBoundary { speller }
+    }
+
+    pub fn speller(&self) -> &S {
+        &self.speller
+    }
+
+    pub fn into_inner(self) -> S {
+        self.speller
+    }
+}
+
+impl<S: Speller> Speller for PanicBoundary<S> {
+    fn check(&self, word: &str) -> Result<bool> {
+        catch_panic(|| self.speller.check(word))
+    }
+
+    fn suggest(&self, word: &str) -> Result<Vec<String>> {
+        catch_panic(|| self.speller.suggest(word))
+    }
+
+    fn stem(&self, word: &str) -> Result<Vec<String>> {
+        catch_panic(|| self.speller.stem(word))
+    }
+
+    fn analyze(&self, word: &str) -> Result<Vec<String>> {
+        catch_panic(|| self.speller.analyze(word))
+    }
+
+    fn add(&mut self, word: &str) -> Result<()> {
+        catch_panic(|| self.speller.add(word))
+    }
+
+    fn remove(&mut self, word: &str) -> Result<()> {
+        catch_panic(|| self.speller.remove(word))
+    }
+}
@@ -0,0 +1,290 @@
+//! Validation of untrusted dictionaries.
+//!
+//! The hunspell library trusts its input: huge counts in the affix file or
+//! the dictionary header make it allocate accordingly and malformed files
+//! can make it misbehave. Before opening a dictionary from an untrusted
+//! source it can be checked against [`ValidationLimits`] by a pure Rust
+//! parser. The parser runs on a thread of this process, so its memory is
+//! only bounded by the size limits, and its timeout is best effort: a
+//! thread cannot be stopped, it finishes in the background.
+
+use std::{fs::File, io::Read, path::Path, sync::mpsc, time::Duration};
+
+use crate::{
+    aff::{is_directive, ENCODING_DIRECTIVE},
+    Error, Language, Operation, Result, SpellChecker,
+};
+
+/// Limits a dictionary has to stay within to be accepted by
+/// [`validate_dictionary()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationLimits {
+    /// Maximal size of the affix file in bytes.
+    pub max_affix_size: u64,
+    /// Maximal size of the dictionary file in bytes, which together with
+    /// `max_affix_size` bounds the memory used for validation.
+    pub max_dictionary_size: u64,
+    /// Maximal length of a line in bytes.
+    pub max_line_length: usize,
+    /// Maximal number of words in the dictionary, also applies to the count
+    /// on the first line.
+    pub max_entries: usize,
+    /// Maximal number of prefix and suffix rules in the affix file.
+    pub max_affix_rules: usize,
+    /// Maximal time to wait for validation. Best effort: after the
+    /// timeout the dictionary is rejected, but the validating thread runs
+    /// to its end in the background.
+    pub timeout: Duration,
+    /// Also reject dictionaries hunspell would open, but that are likely
+    /// wrong: affix files with directives hunspell does not know, and
+    /// dictionary files that are not in the encoding of the affix file.
+    /// When there is more than one such warning, the error is
+    /// `Error::Multiple` with all of them.
+    pub strict: bool,
+}
+
+impl Default for ValidationLimits {
+    fn default() -> Self {
+        ValidationLimits {
+            max_affix_size: 16 * 1024 * 1024,
+            max_dictionary_size: 64 * 1024 * 1024,
+            max_line_length: 4096,
+            max_entries: 2_000_000,
+            max_affix_rules: 100_000,
+            timeout: Duration::from_secs(10),
+            strict: false,
+        }
+    }
+}
+
+/// Checks that the affix and dictionary files are well formed and within
+/// `limits`, returns `Error::DictionaryRejected` with the reason if not.
+///
+/// Nothing is handed to the hunspell library.
+pub fn validate_dictionary<P>(affix: P, dictionary: P, limits: &ValidationLimits) -> Result<()>
+where
+    P: AsRef<Path>,
+{
+    read_validated(affix.as_ref(), dictionary.as_ref(), limits).map(|_| ())
+}
+
+/// Reads and validates the affix and dictionary files, returns the
+/// contents that were validated.
+fn read_validated(
+    affix: &Path,
+    dictionary: &Path,
+    limits: &ValidationLimits,
+) -> Result<(Vec<u8>, Vec<u8>)> {
+    let affix = affix.to_path_buf();
+    let dictionary = dictionary.to_path_buf();
+    let thread_limits = limits.clone();
+    with_best_effort_timeout(limits.timeout, move || {
+        validate(&affix, &dictionary, &thread_limits)
+    })
+}
+
+/// Runs `job` on a thread and waits at most `timeout` for it. After a
+/// timeout the thread is left to finish, it cannot be stopped.
+fn with_best_effort_timeout<T, F>(timeout: Duration, job: F) -> Result<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T> + Send + 'static,
+{
+    let (sender, receiver) = mpsc::channel();
+    std::thread::Builder::new()
+        .name("hunspell-rs-validate".to_string())
+        .spawn(move || {
+            // the receiver is gone after a timeout
+            let _ = sender.send(job());
+        })?;
+    match receiver.recv_timeout(timeout) {
+        Ok(result) => result,
+        Err(_) => Err(Error::DictionaryRejected(format!(
+            "validation took more than {timeout:?}"
+        ))),
+    }
+}
+
+impl SpellChecker {
+    /// Like `new()`, but the dictionary is first checked with
+    /// [`validate_dictionary()`]. Use this for dictionaries from untrusted
+    /// sources, or with [`strict`](ValidationLimits::strict) limits to
+    /// catch mistakes in dictionaries of your own.
+    ///
+    /// On Linux and Android hunspell is given the contents that were
+    /// validated, like with [`from_memory()`](SpellChecker::from_memory),
+    /// so the files cannot be replaced in between; `affix()` and
+    /// `dictionary()` return the paths of the memory files. On other
+    /// platforms the files are opened again after validation.
+    pub fn new_validated<P>(
+        affix: P,
+        dictionary: P,
+        limits: &ValidationLimits,
+    ) -> Result<SpellChecker>
+    where
+        P: AsRef<Path>,
+    {
+        let (affix_contents, dictionary_contents) =
+            read_validated(affix.as_ref(), dictionary.as_ref(), limits)?;
+        match SpellChecker::from_memory(&affix_contents, &dictionary_contents) {
+            Ok(mut spell_checker) => {
+                // the language can come from the name of the dictionary
+                spell_checker.language = Language::from_files(
+                    spell_checker.affix.clone(),
+                    dictionary.as_ref().to_path_buf(),
+                );
+                Ok(spell_checker)
+            }
+            Err(Error::InMemoryUnsupported) => SpellChecker::new(affix, dictionary),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+/// Validates the files, returns their contents.
+fn validate(
+    affix: &Path,
+    dictionary: &Path,
+    limits: &ValidationLimits,
+) -> Result<(Vec<u8>, Vec<u8>)> {
+    let (affix, dictionary) = crate::spell_checker::check_paths(affix, dictionary)?;
+    let mut warnings = Vec::new();
+    let affix_contents = read_limited(&affix, limits.max_affix_size)?;
+    let utf8 = validate_affix(&affix, &affix_contents, limits, &mut warnings)?;
+    let dictionary_contents = read_limited(&dictionary, limits.max_dictionary_size)?;
+    validate_dic(&dictionary, &dictionary_contents, limits)?;
+    if let Some(reason) = encoding_mismatch(&dictionary_contents, utf8) {
+        warnings.push((
+            dictionary.display().to_string(),
+            rejected(&dictionary, 1, reason),
+        ));
+    }
+    if !limits.strict || warnings.is_empty() {
+        return Ok((affix_contents, dictionary_contents));
+    }
+    match warnings.len() {
+        1 => Err(warnings.remove(0).1),
+        _ => Err(Error::Multiple(warnings)),
+    }
+}
+
+/// Returns why `contents` are not in the encoding of the affix file, as
+/// far as can be told: text that is UTF-8 in an 8-bit dictionary is
+/// likely in the wrong encoding, as 8-bit text is rarely valid UTF-8.
+fn encoding_mismatch(contents: &[u8], utf8: bool) -> Option<&'static str> {
+    let is_utf8 = std::str::from_utf8(contents).is_ok();
+    if utf8 && !is_utf8 {
+        Some("not UTF-8, the encoding of the affix file")
+    } else if !utf8 && is_utf8 && !contents.is_ascii() {
+        Some("UTF-8, but the affix file sets an 8-bit encoding")
+    } else {
+        None
+    }
+}
+
+fn rejected(path: &Path, line: usize, reason: &str) -> Error {
+    Error::DictionaryRejected(format!("{}:{}: {}", path.display(), line, reason))
+}
+
+fn read_limited(path: &Path, max_size: u64) -> Result<Vec<u8>> {
+    let mut contents = Vec::new();
+    File::open(path)
+        .and_then(|f| f.take(max_size + 1).read_to_end(&mut contents))
+        .map_err(Error::io(Operation::Open, path))?;
+    if contents.len() as u64 > max_size {
+        return Err(Error::DictionaryRejected(format!(
+            "{} is larger than {} bytes",
+            path.display(),
+            max_size
+        )));
+    }
+    Ok(contents)
+}
+
+fn check_line(path: &Path, number: usize, line: &[u8], limits: &ValidationLimits) -> Result<()> {
+    if line.len() > limits.max_line_length {
+        return Err(rejected(path, number, "line too long"));
+    }
+    if line.contains(&0) {
+        return Err(rejected(path, number, "contains a NUL byte"));
+    }
+    Ok(())
+}
+
+/// Returns whether the affix file sets UTF-8, and adds the unknown
+/// directives to `warnings`.
+fn validate_affix(
+    path: &Path,
+    contents: &[u8],
+    limits: &ValidationLimits,
+    warnings: &mut Vec<(String, Error)>,
+) -> Result<bool> {
+    let mut rules = 0usize;
+    let mut utf8 = false;
+    for (i, line) in contents.split(|b| *b == b'\n').enumerate() {
+        let number = i + 1;
+        check_line(path, number, line, limits)?;
+        let line = String::from_utf8_lossy(line);
+        let mut fields = line.split_whitespace();
+        match fields.next() {
+            Some(ENCODING_DIRECTIVE) => {
+                utf8 = fields
+                    .next()
+                    .is_some_and(|e| e.eq_ignore_ascii_case("UTF-8"))
+            }
+            Some(kind @ ("PFX" | "SFX")) => {
+                let fields: Vec<&str> = fields.collect();
+                if fields.len() < 3 {
+                    return Err(rejected(path, number, &format!("incomplete {kind} rule")));
+                }
+                // header lines are `PFX flag Y|N count`
+                if fields.len() == 3 && matches!(fields[1], "Y" | "N") {
+                    let count: usize = fields[2]
+                        .parse()
+                        .map_err(|_| rejected(path, number, "invalid rule count"))?;
+                    rules += count;
+                    if rules > limits.max_affix_rules {
+                        return Err(rejected(path, number, "too many affix rules"));
+                    }
+                }
+            }
+            Some(directive) if !directive.starts_with('#') && !is_directive(directive) => {
+                warnings.push((
+                    path.display().to_string(),
+                    rejected(path, number, &format!("unknown directive {directive}")),
+                ));
+            }
+            _ => {}
+        }
+    }
+    if utf8 {
+        std::str::from_utf8(contents)
+            .map_err(|_| Error::DictionaryRejected(format!("{} is not UTF-8", path.display())))?;
+    }
+    Ok(utf8)
+}
+
+fn validate_dic(path: &Path, contents: &[u8], limits: &ValidationLimits) -> Result<()> {
+    let mut lines = contents.split(|b| *b == b'\n');
+    let header = String::from_utf8_lossy(lines.next().unwrap_or_default());
+    let count: usize = header
+        .trim_start_matches('\u{feff}')
+        .trim()
+        .parse()
+        .map_err(|_| rejected(path, 1, "first line is not the number of words"))?;
+    if count > limits.max_entries {
+        return Err(rejected(path, 1, "too many words"));
+    }
+    let mut entries = 0usize;
+    for (i, line) in lines.enumerate() {
+        check_line(path, i + 2, line, limits)?;
+        if line.iter().all(u8::is_ascii_whitespace) {
+            continue;
+        }
+        entries += 1;
+        if entries > limits.max_entries {
+            return Err(rejected(path, i + 2, "too many words"));
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,282 @@
+//! Re-checking the files of a directory when they change, and reloading
+//! dictionaries when their files change.
+
+use std::{
+    cell::{Ref, RefCell},
+    collections::{hash_map::DefaultHasher, HashMap, HashSet},
+    hash::{Hash, Hasher},
+    ops::ControlFlow,
+    path::{Component, Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc, Arc,
+    },
+};
+
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+
+use crate::{Error, Misspelling, Result, SpellChecker, Speller, TextChecker};
+
+/// What [`TextChecker::watch_dir()`] reports.
+#[derive(Debug, Clone, PartialEq)]
+pub enum WatchEvent {
+    /// A file was checked, when watching started or after its contents
+    /// changed.
+    Checked {
+        path: PathBuf,
+        misspellings: Vec<Misspelling>,
+    },
+    /// A checked file was removed.
+    Removed(PathBuf),
+    /// A file could not be checked.
+    Failed { path: PathBuf, error: Error },
+}
+
+impl TextChecker {
+    /// Checks the text files in `dir` and its subdirectories, then watches
+    /// them and checks every file again when its contents change. Each
+    /// result is passed to `callback`, until it returns `Break`.
+    ///
+    /// Files that are not UTF-8 and hidden files and directories (starting
+    /// with `.`) are skipped. Blocks the current thread.
+    pub fn watch_dir<P, F>(&self, dir: P, mut callback: F) -> Result<()>
+    where
+        P: AsRef<Path>,
+        F: FnMut(WatchEvent) -> ControlFlow<()>,
+    {
+        let dir = dir.as_ref().canonicalize()?;
+        let (sender, receiver) = mpsc::channel();
+        let mut watcher =
+            notify::recommended_watcher(sender).map_err(|e| Error::WatchFailed(e.to_string()))?;
+        watcher
+            .watch(&dir, RecursiveMode::Recursive)
+            .map_err(|e| Error::WatchFailed(e.to_string()))?;
+
+        let mut checked: HashMap<PathBuf, u64> = HashMap::new();
+        let mut files = Vec::new();
+        collect_files(&dir, &mut files)?;
+        files.sort();
+        for path in files {
+            if let Some(event) = self.check_file(path, &mut checked) {
+                if callback(event).is_break() {
+                    return Ok(());
+                }
+            }
+        }
+
+        for event in receiver {
+            let event = event.map_err(|e| Error::WatchFailed(e.to_string()))?;
+            if matches!(event.kind, EventKind::Access(_)) {
+                continue;
+            }
+            for path in event.paths {
+                if is_hidden(&dir, &path) || path.is_dir() {
+                    continue;
+                }
+                if let Some(event) = self.check_file(path, &mut checked) {
+                    if callback(event).is_break() {
+                        return Ok(());
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks `path` if its contents changed since the last check.
+    fn check_file(&self, path: PathBuf, checked: &mut HashMap<PathBuf, u64>) -> Option<WatchEvent> {
+        let text = match std::fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                return checked.remove(&path).map(|_| WatchEvent::Removed(path));
+            }
+            // not a text file
+            Err(e) if e.kind() == std::io::ErrorKind::InvalidData => return None,
+            Err(e) => {
+                return Some(WatchEvent::Failed {
+                    path,
+                    error: e.into(),
+                })
+            }
+        };
+        let mut hasher = DefaultHasher::new();
+        text.hash(&mut hasher);
+        let hash = hasher.finish();
+        if checked.insert(path.clone(), hash) == Some(hash) {
+            return None;
+        }
+        Some(match self.check_text(&text) {
+            Ok(misspellings) => WatchEvent::Checked { path, misspellings },
+            Err(error) => WatchEvent::Failed { path, error },
+        })
+    }
+}
+
+/// Returns true if `path` or one of its parents below `dir` starts
+/// with `.`.
+fn is_hidden(dir: &Path, path: &Path) -> bool {
+    path.strip_prefix(dir)
+        .unwrap_or(path)
+        .components()
+        .any(|c| matches!(c, Component::Normal(name) if name.to_string_lossy().starts_with('.')))
+}
+
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    for entry in dir.read_dir()? {
+        let path = entry?.path();
+        if is_hidden(dir, &path) {
+            continue;
+        }
+        if path.is_dir() {
+            collect_files(&path, files)?;
+        } else if path.is_file() {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// A [`SpellChecker`] that loads its files again when they change, i.e.
+/// the word list of a project in a long running server.
+///
+/// The directories of the affix file, the dictionary and the extra
+/// dictionaries are watched, so files replaced by an editor are noticed
+/// as well. The reload happens on the next call after a change, keeping
+/// the runtime words; when it fails the old dictionary stays in use and
+/// the error is kept for [`reload_error()`](Self::reload_error).
+///
+/// ```
+/// use hunspell_rs::{ReloadingSpellChecker, SpellChecker};
+///
+/// let spell = SpellChecker::new("tests/fixtures/reduced.aff", "tests/fixtures/reduced.dic").unwrap();
+/// let spell = ReloadingSpellChecker::new(spell).unwrap();
+/// assert_eq!(Ok(true), spell.spell_checker().check("cats"));
+/// ```
+pub struct ReloadingSpellChecker {
+    spell_checker: RefCell<SpellChecker>,
+    changed: Arc<AtomicBool>,
+    reload_error: RefCell<Option<Error>>,
+    _watcher: RecommendedWatcher,
+}
+
+impl ReloadingSpellChecker {
+    /// Starts watching the files of `spell_checker`. Fails with
+    /// `Error::WatchFailed` when a directory cannot be watched.
+    pub fn new(spell_checker: SpellChecker) -> Result<Self> {
+        let mut files = HashSet::new();
+        let mut dirs = HashSet::new();
+        for stamp in spell_checker.file_stamps() {
+            let path = stamp.path();
+            let (Some(dir), Some(name)) = (path.parent(), path.file_name()) else {
+                continue;
+            };
+            let dir = if dir.as_os_str().is_empty() {
+                Path::new(".")
+            } else {
+                dir
+            };
+            let dir = dir.canonicalize()?;
+            files.insert(dir.join(name));
+            dirs.insert(dir);
+        }
+        let changed = Arc::new(AtomicBool::new(false));
+        let flag = changed.clone();
+        let mut watcher =
+            notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+                if let Ok(event) = event {
+                    if !matches!(event.kind, EventKind::Access(_))
+                        && event.paths.iter().any(|p| files.contains(p))
+                    {
+                        flag.store(true, Ordering::SeqCst);
+                    }
+                }
+            })
+            .map_err(|e| Error::WatchFailed(e.to_string()))?;
+        for dir in dirs {
+            watcher
+                .watch(&dir, RecursiveMode::NonRecursive)
+                .map_err(|e| Error::WatchFailed(e.to_string()))?;
+        }
+        Ok(ReloadingSpellChecker {
+            spell_checker: RefCell::new(spell_checker),
+            changed,
+            reload_error: RefCell::new(None),
+            _watcher: watcher,
+        })
+    }
+
+    /// Returns the spell checker, after reloading it if its files
+    /// changed.
+    pub fn spell_checker(&self) -> Ref<'_, SpellChecker> {
+        if let Err(e) = self.reload_if_changed() {
+            *self.reload_error.borrow_mut() = Some(e);
+        }
+        self.spell_checker.borrow()
+    }
+
+    /// Like [`spell_checker()`](ReloadingSpellChecker::spell_checker), to
+    /// add or remove words. They are kept when the files are reloaded.
+    pub fn spell_checker_mut(&mut self) -> &mut SpellChecker {
+        if let Err(e) = self.reload_if_changed() {
+            *self.reload_error.get_mut() = Some(e);
+        }
+        self.spell_checker.get_mut()
+    }
+
+    /// Reloads the spell checker if its files changed since the last
+    /// reload, returns true if it did.
+    pub fn reload_if_changed(&self) -> Result<bool> {
+        if !self.changed.swap(false, Ordering::SeqCst) {
+            return Ok(false);
+        }
+        self.spell_checker.borrow_mut().reload()?;
+        self.reload_error.borrow_mut().take();
+        Ok(true)
+    }
+
+    /// Returns the error of the last reload, if it failed.
+    pub fn reload_error(&self) -> Option<Error> {
+        self.reload_error.borrow().clone()
+    }
+
+    /// Stops watching and returns the spell checker.
+    pub fn into_inner(self) -> SpellChecker {
+        self.spell_checker.into_inner()
+    }
+}
+
+impl core::fmt::Debug for ReloadingSpellChecker {
+    fn fmt(&self, fmt: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        fmt.debug_struct("ReloadingSpellChecker")
+            .field("spell_checker", &self.spell_checker)
+            .field("changed", &self.changed)
+            .field("reload_error", &self.reload_error)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Speller for ReloadingSpellChecker {
+    fn check(&self, word: &str) -> Result<bool> {
+        self.spell_checker().check(word)
+    }
+
+    fn suggest(&self, word: &str) -> Result<Vec<String>> {
+        self.spell_checker().suggest(word)
+    }
+
+    fn stem(&self, word: &str) -> Result<Vec<String>> {
+        self.spell_checker().stem(word)
+    }
+
+    fn analyze(&self, word: &str) -> Result<Vec<String>> {
+        self.spell_checker().analyze(word)
+    }
+
+    fn add(&mut self, word: &str) -> Result<()> {
+        self.spell_checker_mut().add(word)
+    }
+
+    fn remove(&mut self, word: &str) -> Result<()> {
+        self.spell_checker_mut().remove(word)
+    }
+}
@@ -0,0 +1,27 @@
+#![cfg(feature = "cli")]
+
+use std::process::Command;
+
+fn hunspell_rs(args: &[&str]) -> (i32, String) {
+    let output = Command::new(env!("CARGO_BIN_EXE_hunspell-rs"))
+        .args(["-d", "tests/fixtures/reduced"])
+        .args(args)
+        .output()
+        .unwrap();
+    (
+        output.status.code().unwrap(),
+        String::from_utf8(output.stdout).unwrap(),
+    )
+}
+
+#[test]
+fn check_and_suggest() {
+    let file = std::env::temp_dir().join(format!("hunspell-rs-cli-{}.md", std::process::id()));
+    std::fs::write(&file, "cats\n`catx` cats catz\n").unwrap();
+    let (code, output) = hunspell_rs(&["check", file.to_str().unwrap()]);
+    std::fs::remove_file(&file).unwrap();
+    assert_eq!(1, code);
+    assert!(output.ends_with(".md:2:13: catz\n"));
+    let (code, output) = hunspell_rs(&["suggest", "catz"]);
+    assert_eq!((0, "catz: cat\n"), (code, output.as_str()));
+}